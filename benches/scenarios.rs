@@ -0,0 +1,19 @@
+//! Criterion benchmarks for the named scenarios in `zttt_rs::bench`
+//!
+//! Run with `cargo bench`, or `cargo bench -- simulator/` for one group.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use zttt_rs::bench;
+
+fn scenarios(c: &mut Criterion) {
+    for mut scenario in bench::scenarios() {
+        let (group, name) = scenario.name().split_once('/').unwrap_or(("misc", scenario.name()));
+        let mut group = c.benchmark_group(group);
+        group.throughput(Throughput::Elements(scenario.units_per_iteration()));
+        group.bench_function(name, |b| b.iter(|| scenario.run_once()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, scenarios);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+// Generates the gRPC service for the `zttt-server` binary.
+//
+// The service is described in Rust rather than compiled from
+// proto/zttt.proto so that building does not require `protoc`.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    generate_simulation_service();
+}
+
+#[cfg(feature = "grpc")]
+fn generate_simulation_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("super::{}", input))
+            .output_type(format!("super::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+            .build()
+    };
+
+    let service = Service::builder()
+        .name("SimulationService")
+        .package("zttt")
+        .method(method("submit", "Submit", "SubmitRequest", "SubmitResponse"))
+        .method(method("progress", "Progress", "JobRequest", "ProgressResponse"))
+        .method(method("result", "Result", "JobRequest", "ResultResponse"))
+        .build();
+
+    Builder::new().compile(&[service]);
+}
@@ -1,4 +1,4 @@
-use zttt_rs::backend::{Board, Player, GameResult, Engine};
+use zttt_rs::backend::{Board, Player, GameResult, Engine, Game, Replay};
 
 /// A perfect play engine using minimax algorithm with alpha-beta pruning
 /// (Included here for demonstration purposes)
@@ -84,30 +84,25 @@ impl Engine for PerfectEngine {
 
 fn main() {
     println!("=== Engine vs Engine TicTacToe Game ===\n");
-    
-    let mut board = Board::new();
-    let mut current_player = Player::X;
-    let mut move_count = 0;
+
+    let mut game = Game::new();
     let engine = PerfectEngine::new();
-    
+    while game.result() == GameResult::InProgress {
+        let (row, col) = engine
+            .choose_move(game.board(), game.current_player())
+            .expect("engine has a move while the game is in progress");
+        game.play(row, col).unwrap();
+    }
+
+    let replay = Replay::from_game(&game);
     println!("Starting position:");
-    println!("{}\n", board);
-    
-    while board.game_result() == GameResult::InProgress {
-        move_count += 1;
-        
-        // Get the best move for current player
-        if let Some((row, col)) = engine.choose_move(&board, current_player) {
-            board.make_move(row, col, current_player).unwrap();
-            println!("Move {}: {} plays at ({}, {})", move_count, current_player, row, col);
-            println!("{}\n", board);
-            
-            current_player = current_player.opponent();
-        }
+    println!("{}\n", replay.initial_board().formatter().grid(true).coordinates(true));
+    for frame in &replay {
+        println!("{}\n", frame);
     }
-    
+
     println!("Game over!");
-    match board.game_result() {
+    match game.result() {
         GameResult::Win(player) => println!("{} wins!", player),
         GameResult::Draw => println!("It's a draw!"),
         GameResult::InProgress => println!("Game in progress (should not happen)"),
@@ -1,8 +1,8 @@
-/// Example demonstrating how to implement custom engines with clear abstractions
-///
-/// This shows the clean separation between game logic (Board) and move selection (Engine)
+//! Example demonstrating how to implement custom engines with clear abstractions
+//!
+//! This shows the clean separation between game logic (Board) and move selection (Engine)
 
-use zttt_rs::backend::{Board, Player, GameResult, Engine};
+use zttt_rs::backend::{play_game, Board, Player, GameResult, Engine, EngineMetadata, StatefulEngine};
 
 /// A custom engine that picks moves based on priority positions
 ///
@@ -30,30 +30,24 @@ impl PriorityEngine {
 impl Engine for PriorityEngine {
     fn choose_move(&self, board: &Board, _player: Player) -> Option<(usize, usize)> {
         // Check positions in priority order
-        for &pos in &self.priorities {
-            if board.is_valid_move(pos.0, pos.1) {
-                return Some(pos);
-            }
+        self.priorities
+            .iter()
+            .find(|&&(row, col)| board.is_valid_move(row, col))
+            .copied()
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("priority"),
+            description: Some("Center, then corners, then edges"),
+            ..EngineMetadata::default()
         }
-        None
     }
 }
 
 /// Simulate a complete game with the given engine
-fn simulate_game<E: Engine>(engine: &E) -> GameResult {
-    let mut board = Board::new();
-    let mut current_player = Player::X;
-    
-    while board.game_result() == GameResult::InProgress {
-        if let Some((row, col)) = engine.choose_move(&board, current_player) {
-            board.make_move(row, col, current_player).unwrap();
-            current_player = current_player.opponent();
-        } else {
-            break;
-        }
-    }
-    
-    board.game_result()
+fn simulate_game<E: Engine + Clone>(engine: &E) -> GameResult {
+    play_game(&mut engine.clone(), &mut engine.clone(), Player::X).result
 }
 
 fn main() {
@@ -66,8 +60,8 @@ fn main() {
     // Create custom engine
     let priority_engine = PriorityEngine::new();
     
-    println!("Testing PriorityEngine strategy:");
-    println!("Priority order: center → corners → edges\n");
+    println!("Testing the {} engine:", priority_engine.name());
+    println!("Priority order: {}\n", priority_engine.description().unwrap_or_default());
     
     // Simulate a few games
     let num_games = 10;
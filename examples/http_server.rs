@@ -0,0 +1,15 @@
+//! REST API server for playing and simulating
+//!
+//! Run with: cargo run --example http_server --features http
+//!
+//! Then, for example:
+//!   curl -X POST localhost:8080/games
+//!   curl -X POST localhost:8080/games/0/moves -H 'content-type: application/json' -d '{"row":1,"col":1}'
+//!   curl 'localhost:8080/games/0/suggestion?engine=perfect'
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let addr = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    println!("Listening on http://{}", addr);
+    zttt_rs::http::serve(&addr).await
+}
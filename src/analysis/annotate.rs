@@ -0,0 +1,146 @@
+//! Move-quality annotation of played games
+//!
+//! Every move of a game is compared with perfect play from the
+//! [`SolvedDatabase`]. A move that keeps the best outcome with the best
+//! timing is [`MoveQuality::Best`]; one that keeps the outcome but wins more
+//! slowly or loses sooner is an [`Inaccuracy`](MoveQuality::Inaccuracy); one
+//! that throws away a win or a draw is a [`Blunder`](MoveQuality::Blunder).
+
+use std::fmt;
+use std::time::Duration;
+use crate::backend::format::square_name;
+use crate::backend::{Eval, Game, GameRecord, GameValue, Player, Solution, SolvedDatabase};
+
+/// How a move compares with perfect play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveQuality {
+    /// Keeps the best outcome as fast (or as slow) as possible
+    Best,
+    /// Keeps the outcome, but wins more slowly or loses sooner
+    Inaccuracy,
+    /// Turns a win into a draw or loss, or a draw into a loss
+    Blunder,
+}
+
+impl fmt::Display for MoveQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveQuality::Best => write!(f, "best"),
+            MoveQuality::Inaccuracy => write!(f, "inaccuracy"),
+            MoveQuality::Blunder => write!(f, "blunder"),
+        }
+    }
+}
+
+/// Verdict on one move of a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveAnnotation {
+    /// Index of the move in the record, starting at zero
+    pub ply: usize,
+    /// Player who made the move
+    pub player: Player,
+    /// The move as (row, col)
+    pub played: (usize, usize),
+    pub quality: MoveQuality,
+    /// Value of the position for the mover before the move, under perfect play
+    pub before: Solution,
+    /// Value for the mover after the move
+    pub after: Solution,
+    /// Every move that would have been [`MoveQuality::Best`]
+    pub best_moves: Vec<(usize, usize)>,
+    /// Time the engine took to choose the move, if the game was timed
+    pub duration: Option<Duration>,
+}
+
+impl MoveAnnotation {
+    /// Returns the game-theoretic points the move gave away: `0.0`, `0.5`
+    /// for a win turned into a draw or a draw into a loss, or `1.0` for a win
+    /// turned into a loss
+    pub fn swing(&self) -> f64 {
+        points(self.before.value) - points(self.after.value)
+    }
+}
+
+impl fmt::Display for MoveAnnotation {
+    /// Writes e.g. `4. O c3: blunder (draw -> loss in 3), best b1`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (row, col) = self.played;
+        write!(f, "{}. {} {}: {}", self.ply + 1, self.player, square_name(row, col), self.quality)?;
+        if self.quality != MoveQuality::Best {
+            write!(f, " ({} -> {})", Eval::from(self.before), Eval::from(self.after))?;
+            let best: Vec<String> = self.best_moves.iter().map(|&(row, col)| square_name(row, col)).collect();
+            write!(f, ", best {}", best.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the points a game value is worth to the player to move
+fn points(value: GameValue) -> f64 {
+    match value {
+        GameValue::Win => 1.0,
+        GameValue::Draw => 0.5,
+        GameValue::Loss => 0.0,
+    }
+}
+
+/// Labels every move of `record` by comparing it with perfect play
+///
+/// Games where O moves first are analyzed with the colors swapped, which
+/// does not change any move's value. Returns an error if the record contains
+/// an invalid move.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::analysis::{annotate_game, MoveQuality};
+/// use zttt_rs::backend::{GameRecord, Player, Rules};
+///
+/// // O answers the corner opening on an edge, which loses
+/// let record = GameRecord::new(Rules::Standard, Player::X, &[(0, 0), (0, 1), (1, 1), (2, 2), (1, 0)]).unwrap();
+/// let annotations = annotate_game(&record).unwrap();
+///
+/// assert_eq!(annotations[0].quality, MoveQuality::Best);
+/// assert_eq!(annotations[1].quality, MoveQuality::Blunder);
+/// assert_eq!(annotations[1].swing(), 0.5);
+/// assert_eq!(annotations[1].best_moves, vec![(1, 1)]);
+/// ```
+pub fn annotate_game(record: &GameRecord) -> Result<Vec<MoveAnnotation>, &'static str> {
+    let db = SolvedDatabase::shared(record.rules);
+    let mut game = Game::with_rules(record.rules);
+    let mut player = record.starting_player;
+    let mut annotations = Vec::with_capacity(record.moves.len());
+
+    for (ply, &played) in record.moves.iter().enumerate() {
+        let values = db.move_values(game.board());
+        let after = values
+            .iter()
+            .find(|&&(mv, _)| mv == played)
+            .map(|&(_, solution)| solution)
+            .ok_or("Record contains an invalid move")?;
+        let before = db.get(game.board()).ok_or("Record contains an invalid move")?;
+        let best = before.score();
+
+        let quality = if after.score() == best {
+            MoveQuality::Best
+        } else if after.value == before.value {
+            MoveQuality::Inaccuracy
+        } else {
+            MoveQuality::Blunder
+        };
+        annotations.push(MoveAnnotation {
+            ply,
+            player,
+            played,
+            quality,
+            before,
+            after,
+            best_moves: values.into_iter().filter(|(_, s)| s.score() == best).map(|(mv, _)| mv).collect(),
+            duration: record.move_durations.get(ply).copied(),
+        });
+
+        game.play(played.0, played.1)?;
+        player = player.opponent();
+    }
+    Ok(annotations)
+}
@@ -0,0 +1,188 @@
+//! Exploitability of an engine
+//!
+//! A best response knows an engine's policy and always plays the move that
+//! scores the most against it. Its expected score, compared with the value
+//! of the game under perfect play, measures how much the engine gives away to
+//! an opponent that has studied it: zero for a perfect engine, up to a full
+//! point for one that loses every game. Unlike a head-to-head win rate it
+//! does not depend on how good or how lucky the opponent is.
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::backend::{Board, GameResult, GameValue, Player, Rules, SolvedDatabase, StatefulEngine};
+
+/// Expected scores of a best response against an engine
+///
+/// Scores count one point per win and half a point per draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exploitability {
+    /// Expected score of a best response playing X against the engine
+    pub as_x: f64,
+    /// Expected score of a best response playing O against the engine
+    pub as_o: f64,
+    /// Score perfect play guarantees X; O is guaranteed the rest
+    pub value_x: f64,
+    /// Number of positions where the engine's moves were sampled
+    pub positions: usize,
+}
+
+impl Exploitability {
+    /// Returns the best response's average score above the game value,
+    /// from `0.0` for a perfect engine up to `1.0`
+    pub fn gain(&self) -> f64 {
+        ((self.as_x - self.value_x) + (self.as_o - (1.0 - self.value_x))) / 2.0
+    }
+}
+
+impl fmt::Display for Exploitability {
+    /// Writes e.g. `best response scores 1.00 as X, 1.00 as O (perfect play 0.50 / 0.50), exploitability 0.50`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "best response scores {:.2} as X, {:.2} as O (perfect play {:.2} / {:.2}), exploitability {:.2}",
+            self.as_x,
+            self.as_o,
+            self.value_x,
+            1.0 - self.value_x,
+            self.gain()
+        )
+    }
+}
+
+/// Computes a best response to `engine` under standard rules
+///
+/// The engine's policy at each position it can reach is estimated from
+/// `samples` calls (at least one); a deterministic engine needs only one. The
+/// best response is then found by searching every line, maximizing over its
+/// own moves and averaging over the engine's, and compared with the value of
+/// the game from the [`SolvedDatabase`]. The engine is assumed to choose its
+/// move from the position alone, not from the moves that led to it.
+///
+/// # Panics
+///
+/// Panics if the engine returns an invalid move or no move while the game is
+/// still in progress.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::analysis::exploitability;
+/// use zttt_rs::backend::{FastEngine, PerfectLookupEngine};
+///
+/// // Perfect play concedes nothing
+/// assert_eq!(exploitability(&mut PerfectLookupEngine, 1).gain(), 0.0);
+///
+/// // Always playing the first free square loses to a prepared opponent
+/// let fast = exploitability(&mut FastEngine, 1);
+/// assert_eq!(fast.as_x, 1.0);
+/// assert!(fast.gain() > 0.0);
+/// ```
+pub fn exploitability<E: StatefulEngine + ?Sized>(engine: &mut E, samples: usize) -> Exploitability {
+    exploitability_with_rules(engine, Rules::Standard, samples)
+}
+
+/// Computes a best response to `engine` under the given rules, like [`exploitability`]
+pub fn exploitability_with_rules<E: StatefulEngine + ?Sized>(engine: &mut E, rules: Rules, samples: usize) -> Exploitability {
+    let start = Board::with_rules(rules);
+    let value_x = match SolvedDatabase::shared(rules).get(&start).map(|solution| solution.value) {
+        Some(GameValue::Win) => 1.0,
+        Some(GameValue::Loss) => 0.0,
+        Some(GameValue::Draw) | None => 0.5,
+    };
+
+    let mut search = BestResponse {
+        engine,
+        samples: samples.max(1),
+        exploiter: Player::X,
+        values: HashMap::new(),
+        policies: HashMap::new(),
+    };
+    let as_x = search.value(&start);
+    search.exploiter = Player::O;
+    search.values.clear();
+    let as_o = search.value(&start);
+
+    Exploitability {
+        as_x,
+        as_o,
+        value_x,
+        positions: search.policies.len(),
+    }
+}
+
+/// Moves an engine plays at one position, with their probabilities
+type Policy = Vec<((usize, usize), f64)>;
+
+/// Expectimax search for the best response of one color
+struct BestResponse<'a, E: ?Sized> {
+    engine: &'a mut E,
+    samples: usize,
+    exploiter: Player,
+    /// Best-response score by board encoding
+    values: HashMap<u16, f64>,
+    /// Sampled move probabilities of the engine by board encoding
+    policies: HashMap<u16, Policy>,
+}
+
+impl<E: StatefulEngine + ?Sized> BestResponse<'_, E> {
+    /// Returns the exploiter's expected score from `board`
+    fn value(&mut self, board: &Board) -> f64 {
+        match board.game_result() {
+            GameResult::Win(winner) => return if winner == self.exploiter { 1.0 } else { 0.0 },
+            GameResult::Draw => return 0.5,
+            GameResult::InProgress => {}
+        }
+        let code = board.encode();
+        if let Some(&value) = self.values.get(&code) {
+            return value;
+        }
+
+        let player = board.current_player().expect("positions are reached from the empty board");
+        let value = if player == self.exploiter {
+            board
+                .valid_moves()
+                .into_iter()
+                .map(|mv| self.child_value(board, player, mv))
+                .fold(0.0, f64::max)
+        } else {
+            self.policy(board, player)
+                .into_iter()
+                .map(|(mv, probability)| probability * self.child_value(board, player, mv))
+                .sum()
+        };
+        self.values.insert(code, value);
+        value
+    }
+
+    fn child_value(&mut self, board: &Board, player: Player, (row, col): (usize, usize)) -> f64 {
+        let mut child = board.clone();
+        child.make_move(row, col, player).unwrap();
+        self.value(&child)
+    }
+
+    /// Returns the engine's move probabilities at `board`, sampling them once
+    fn policy(&mut self, board: &Board, player: Player) -> Policy {
+        let code = board.encode();
+        if let Some(policy) = self.policies.get(&code) {
+            return policy.clone();
+        }
+
+        let mut counts = [0usize; 9];
+        for _ in 0..self.samples {
+            let (row, col) = self
+                .engine
+                .choose_move_mut(board, player)
+                .expect("engine returned no move for a game in progress");
+            assert!(board.is_valid_move(row, col), "engine returned an invalid move");
+            counts[row * 3 + col] += 1;
+        }
+        let policy: Policy = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(cell, &count)| ((cell / 3, cell % 3), count as f64 / self.samples as f64))
+            .collect();
+        self.policies.insert(code, policy.clone());
+        policy
+    }
+}
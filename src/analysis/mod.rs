@@ -0,0 +1,15 @@
+//! # Analysis Module
+//!
+//! Tools for judging engines and games against perfect play, built on the
+//! [`SolvedDatabase`](crate::backend::SolvedDatabase):
+//! - **Exploitability**: How much a best response wins against an engine
+//! - **Annotation**: Best, inaccurate, and blundered moves of a played game
+//! - **Position analysis**: Value, optimal moves, and every move's value
+
+pub mod exploit;
+pub mod annotate;
+pub mod position;
+
+pub use exploit::{exploitability, exploitability_with_rules, Exploitability};
+pub use annotate::{annotate_game, MoveAnnotation, MoveQuality};
+pub use position::{analyze, PositionAnalysis};
@@ -0,0 +1,79 @@
+//! Perfect-play analysis of a single position
+
+use std::fmt;
+use crate::backend::format::square_name;
+use crate::backend::solver::swap_colors;
+use crate::backend::{Board, Eval, Player, SolvedDatabase};
+
+/// Value of a position and of every legal move, under perfect play
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionAnalysis {
+    /// Player the values are given for
+    pub player: Player,
+    /// Value of the position for `player`
+    pub value: Eval,
+    /// Every move that achieves `value`, in row-major order
+    pub best_moves: Vec<(usize, usize)>,
+    /// Every legal move with its value for `player`, best first
+    pub moves: Vec<((usize, usize), Eval)>,
+}
+
+/// Analyzes `board` with `player` to move
+///
+/// Works for games started by either player. Finished positions have no
+/// moves and are valued `Draw` or `LossIn(0)`. Returns an error if `board`
+/// cannot arise in a game with `player` to move.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::analysis::analyze;
+/// use zttt_rs::backend::{Board, Eval, Player};
+///
+/// // After a corner opening only the center holds the draw
+/// let board = Board::from_moves(&[(0, 0)]).unwrap();
+/// let analysis = analyze(&board, Player::O).unwrap();
+/// assert_eq!(analysis.value, Eval::Draw);
+/// assert_eq!(analysis.best_moves, vec![(1, 1)]);
+/// assert_eq!(analysis.moves.len(), 8);
+/// assert!(matches!(analysis.moves[1].1, Eval::LossIn(_)));
+///
+/// assert!(analyze(&board, Player::X).is_err());
+/// ```
+pub fn analyze(board: &Board, player: Player) -> Result<PositionAnalysis, &'static str> {
+    let db = SolvedDatabase::shared(board.rules());
+    let swapped;
+    let board = if board.current_player() == Some(player) {
+        board
+    } else {
+        swapped = Board::decode(swap_colors(board.encode()), board.rules());
+        &swapped
+    };
+    let solution = db.get(board).ok_or("Position cannot arise with this player to move")?;
+
+    let mut moves: Vec<((usize, usize), Eval)> = db
+        .move_values(board)
+        .into_iter()
+        .map(|(mv, solution)| (mv, Eval::from(solution)))
+        .collect();
+    moves.sort_by_key(|&(_, eval)| std::cmp::Reverse(eval.score()));
+    let value = Eval::from(solution);
+
+    Ok(PositionAnalysis {
+        player,
+        value,
+        best_moves: moves.iter().filter(|(_, eval)| *eval == value).map(|&(mv, _)| mv).collect(),
+        moves,
+    })
+}
+
+impl fmt::Display for PositionAnalysis {
+    /// Writes the value followed by one line per move, e.g. `b2 draw`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to move: {}", self.player, self.value)?;
+        for &((row, col), eval) in &self.moves {
+            write!(f, "\n  {} {}", square_name(row, col), eval)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Asynchronous engines (requires the `tokio` feature)
+//!
+//! Engines backed by network services or inference servers spend most of
+//! their time waiting. [`AsyncEngine`] lets them yield while they wait, and
+//! [`Simulator::run_async`](crate::simulation::Simulator::run_async) drives
+//! them without blocking a thread per game.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use crate::backend::board::Board;
+use crate::backend::engine::StatefulEngine;
+use crate::backend::player::Player;
+
+/// An engine whose moves are computed asynchronously
+///
+/// Implementations can use `async fn` directly:
+///
+/// ```
+/// use zttt_rs::backend::{AsyncEngine, Board, Player};
+///
+/// struct FirstMove;
+///
+/// impl AsyncEngine for FirstMove {
+///     async fn choose_move(&mut self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+///         board.valid_moves().first().copied()
+///     }
+/// }
+/// ```
+pub trait AsyncEngine {
+    /// Chooses the next move for `player`, or `None` if there is none
+    fn choose_move(&mut self, board: &Board, player: Player) -> impl Future<Output = Option<(usize, usize)>> + Send;
+}
+
+/// Runs a synchronous engine on tokio's blocking thread pool
+///
+/// This lets CPU-heavy engines take part in async simulations without stalling
+/// the runtime's worker threads.
+#[derive(Debug)]
+pub struct BlockingEngine<E> {
+    inner: Arc<Mutex<E>>,
+}
+
+impl<E> BlockingEngine<E>
+where
+    E: StatefulEngine + Send + 'static,
+{
+    /// Wraps `inner` for use as an [`AsyncEngine`]
+    pub fn new(inner: E) -> Self {
+        BlockingEngine {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+}
+
+impl<E> AsyncEngine for BlockingEngine<E>
+where
+    E: StatefulEngine + Send + 'static,
+{
+    async fn choose_move(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        let inner = Arc::clone(&self.inner);
+        let board = board.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut engine = inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            engine.choose_move_mut(&board, player)
+        })
+        .await
+        .expect("blocking engine task panicked")
+    }
+}
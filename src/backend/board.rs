@@ -1,25 +1,117 @@
 //! Board representation and game logic
 
 use std::fmt;
+use std::ops::Index;
+use std::str::FromStr;
 use crate::backend::player::{Player, Cell};
-use crate::backend::game::GameResult;
+use crate::backend::game::{GameResult, Outcome, Rules};
 use crate::backend::engine::Engine;
+use crate::backend::symmetry::Symmetry;
+use crate::backend::format::BoardFormatter;
+use crate::backend::pos::{Move, Pos};
+
+/// All eight winning lines: rows, columns, then diagonals
+pub const WIN_LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
 
 /// The TicTacToe board
+///
+/// The game result is cached and updated incrementally by `make_move`, so
+/// `game_result()` is a field read rather than a full board scan.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board {
     pub(crate) cells: [[Cell; 3]; 3],
+    filled: u8,
+    result: GameResult,
+    rules: Rules,
 }
 
 impl Board {
-    /// Creates a new empty board
+    /// Creates a new empty board with standard rules
     pub fn new() -> Self {
+        Self::with_rules(Rules::Standard)
+    }
+
+    /// Creates a new empty board with the given rules
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player, GameResult, Rules};
+    ///
+    /// let mut board = Board::with_rules(Rules::Misere);
+    /// board.make_move(0, 0, Player::X).unwrap();
+    /// board.make_move(1, 0, Player::O).unwrap();
+    /// board.make_move(0, 1, Player::X).unwrap();
+    /// board.make_move(1, 1, Player::O).unwrap();
+    /// board.make_move(0, 2, Player::X).unwrap();
+    /// // X completed a line, so X loses
+    /// assert_eq!(board.game_result(), GameResult::Win(Player::O));
+    /// ```
+    pub fn with_rules(rules: Rules) -> Self {
         Board {
             cells: [[Cell::Empty; 3]; 3],
+            filled: 0,
+            result: GameResult::InProgress,
+            rules,
+        }
+    }
+
+    /// Builds a board by playing `moves` in order, alternating players from X
+    ///
+    /// Returns an [`IllegalMove`] naming the first move that could not be
+    /// played.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, GameResult, Player};
+    ///
+    /// let board = Board::from_moves(&[(0, 0), (1, 1), (0, 1), (2, 2), (0, 2)]).unwrap();
+    /// assert_eq!(board.game_result(), GameResult::Win(Player::X));
+    ///
+    /// let error = Board::from_moves(&[(0, 0), (0, 0)]).unwrap_err();
+    /// assert_eq!((error.index, error.reason), (1, "Cell already occupied"));
+    /// ```
+    pub fn from_moves(moves: &[(usize, usize)]) -> Result<Self, IllegalMove> {
+        let mut board = Board::new();
+        let mut player = Player::X;
+        for (index, &(row, col)) in moves.iter().enumerate() {
+            board.make_move(row, col, player).map_err(|reason| IllegalMove { index, reason })?;
+            player = player.opponent();
         }
+        Ok(board)
+    }
+
+    /// Builds a board by playing `moves` in order, each by its own player
+    ///
+    /// Players need not alternate, which allows handicap and puzzle setups.
+    /// Returns an [`IllegalMove`] naming the first move that could not be
+    /// played.
+    pub fn from_player_moves(moves: &[Move]) -> Result<Self, IllegalMove> {
+        let mut board = Board::new();
+        for (index, &mv) in moves.iter().enumerate() {
+            board.apply_move(mv).map_err(|reason| IllegalMove { index, reason })?;
+        }
+        Ok(board)
+    }
+
+    /// Returns the rules this board is played under
+    pub fn rules(&self) -> Rules {
+        self.rules
     }
 
     /// Gets the cell at the given position
+    ///
+    /// Returns `None` out of range; `board[(row, col)]` panics instead.
     pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
         if row < 3 && col < 3 {
             Some(self.cells[row][col])
@@ -28,6 +120,37 @@ impl Board {
         }
     }
 
+    /// Returns an iterator over every cell in row-major order, with its position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Cell, Player};
+    ///
+    /// let mut board = Board::new();
+    /// board.make_move(2, 1, Player::O).unwrap();
+    /// let occupied: Vec<_> = board.cells().filter(|&(_, cell)| cell != Cell::Empty).collect();
+    /// assert_eq!(occupied, vec![((2, 1), Cell::Occupied(Player::O))]);
+    /// ```
+    pub fn cells(&self) -> Cells<'_> {
+        Cells { board: self, index: 0 }
+    }
+
+    /// Applies a [`Move`]; the typed form of [`make_move`](Self::make_move)
+    pub fn apply_move(&mut self, mv: Move) -> Result<(), &'static str> {
+        self.make_move(mv.pos.row(), mv.pos.col(), mv.player)
+    }
+
+    /// Returns the empty positions while the game is in progress
+    ///
+    /// The typed form of [`valid_moves`](Self::valid_moves).
+    pub fn valid_positions(&self) -> Vec<Pos> {
+        if self.result != GameResult::InProgress {
+            return Vec::new();
+        }
+        Pos::ALL.into_iter().filter(|&pos| self[pos] == Cell::Empty).collect()
+    }
+
     /// Makes a move on the board
     pub fn make_move(&mut self, row: usize, col: usize, player: Player) -> Result<(), &'static str> {
         if row >= 3 || col >= 3 {
@@ -38,23 +161,63 @@ impl Board {
             return Err("Cell already occupied");
         }
 
-        if self.game_result() != GameResult::InProgress {
+        if self.result != GameResult::InProgress {
             return Err("Game is already over");
         }
 
         self.cells[row][col] = Cell::Occupied(player);
+        self.filled += 1;
+        self.result = if self.completes_line(row, col, player) {
+            GameResult::Win(self.rules.winner(player))
+        } else if self.filled == 9 {
+            GameResult::Draw
+        } else {
+            GameResult::InProgress
+        };
         Ok(())
     }
 
+    /// Checks whether the piece at (row, col) completes a line through it
+    #[inline]
+    fn completes_line(&self, row: usize, col: usize, player: Player) -> bool {
+        let p = Cell::Occupied(player);
+        let c = &self.cells;
+        (c[row][0] == p && c[row][1] == p && c[row][2] == p)
+            || (c[0][col] == p && c[1][col] == p && c[2][col] == p)
+            || (row == col && c[0][0] == p && c[1][1] == p && c[2][2] == p)
+            || (row + col == 2 && c[0][2] == p && c[1][1] == p && c[2][0] == p)
+    }
+
+    /// Clears a cell and recomputes the cached result
+    ///
+    /// Used to take back moves; the cell contents are not validated.
+    pub(crate) fn clear_cell(&mut self, row: usize, col: usize) {
+        if self.cells[row][col] != Cell::Empty {
+            self.cells[row][col] = Cell::Empty;
+            self.refresh();
+        }
+    }
+
+    /// Recomputes the cached fill count and result from the cells
+    ///
+    /// Must be called after writing `cells` directly.
+    pub(crate) fn refresh(&mut self) {
+        self.filled = self.cells.iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&cell| cell != Cell::Empty)
+            .count() as u8;
+        self.result = self.compute_result();
+    }
+
     /// Checks if a move is valid
     pub fn is_valid_move(&self, row: usize, col: usize) -> bool {
-        row < 3 && col < 3 && self.cells[row][col] == Cell::Empty && self.game_result() == GameResult::InProgress
+        row < 3 && col < 3 && self.cells[row][col] == Cell::Empty && self.result == GameResult::InProgress
     }
 
     /// Gets all valid moves
     pub fn valid_moves(&self) -> Vec<(usize, usize)> {
         let mut moves = Vec::new();
-        if self.game_result() != GameResult::InProgress {
+        if self.result != GameResult::InProgress {
             return moves;
         }
         
@@ -68,40 +231,19 @@ impl Board {
         moves
     }
 
-    /// Checks the current game result
+    /// Returns the current game result
+    ///
+    /// The result is cached, so this is a constant-time read.
+    #[inline]
     pub fn game_result(&self) -> GameResult {
-        // Check rows
-        for row in 0..3 {
-            if let Cell::Occupied(player) = self.cells[row][0] {
-                if self.cells[row][1] == Cell::Occupied(player) 
-                    && self.cells[row][2] == Cell::Occupied(player) {
-                    return GameResult::Win(player);
-                }
-            }
-        }
-
-        // Check columns
-        for col in 0..3 {
-            if let Cell::Occupied(player) = self.cells[0][col] {
-                if self.cells[1][col] == Cell::Occupied(player) 
-                    && self.cells[2][col] == Cell::Occupied(player) {
-                    return GameResult::Win(player);
-                }
-            }
-        }
-
-        // Check diagonals
-        if let Cell::Occupied(player) = self.cells[0][0] {
-            if self.cells[1][1] == Cell::Occupied(player) 
-                && self.cells[2][2] == Cell::Occupied(player) {
-                return GameResult::Win(player);
-            }
-        }
+        self.result
+    }
 
-        if let Cell::Occupied(player) = self.cells[0][2] {
-            if self.cells[1][1] == Cell::Occupied(player) 
-                && self.cells[2][0] == Cell::Occupied(player) {
-                return GameResult::Win(player);
+    /// Computes the game result with a full board scan
+    fn compute_result(&self) -> GameResult {
+        if let Some([(row, col), _, _]) = self.winning_line() {
+            if let Cell::Occupied(player) = self.cells[row][col] {
+                return GameResult::Win(self.rules.winner(player));
             }
         }
 
@@ -117,6 +259,210 @@ impl Board {
         }
     }
 
+    /// Returns the coordinates of the completed line, if any
+    ///
+    /// Under misère rules this is the line that lost the game.
+    /// Lines are checked in the order rows, columns, diagonals; the first
+    /// completed line is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player};
+    ///
+    /// let mut board = Board::new();
+    /// board.make_move(0, 0, Player::X).unwrap();
+    /// board.make_move(1, 0, Player::O).unwrap();
+    /// board.make_move(1, 1, Player::X).unwrap();
+    /// board.make_move(2, 0, Player::O).unwrap();
+    /// board.make_move(2, 2, Player::X).unwrap();
+    /// assert_eq!(board.winning_line(), Some([(0, 0), (1, 1), (2, 2)]));
+    /// ```
+    pub fn winning_line(&self) -> Option<[(usize, usize); 3]> {
+        WIN_LINES.iter().copied().find(|&[(r0, c0), (r1, c1), (r2, c2)]| {
+            self.cells[r0][c0] != Cell::Empty
+                && self.cells[r0][c0] == self.cells[r1][c1]
+                && self.cells[r0][c0] == self.cells[r2][c2]
+        })
+    }
+
+    /// Returns the moves that win the game for `player` immediately
+    ///
+    /// These are the empty squares completing a line of two of the player's
+    /// pieces, in row-major order. Under misère rules completing a line loses,
+    /// so there are none. Empty if the game is over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player};
+    ///
+    /// // X has a1 and b1; O has a2 and b2
+    /// let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1)]).unwrap();
+    /// assert_eq!(board.winning_moves(Player::X), vec![(0, 2)]);
+    /// assert_eq!(board.blocking_moves(Player::X), vec![(1, 2)]);
+    /// ```
+    pub fn winning_moves(&self, player: Player) -> Vec<(usize, usize)> {
+        match self.rules {
+            Rules::Standard => self.completing_moves(player),
+            Rules::Misere => Vec::new(),
+        }
+    }
+
+    /// Returns the moves that stop the opponent of `player` from winning immediately
+    ///
+    /// These are the opponent's [`winning_moves`](Self::winning_moves); with
+    /// more than one, the opponent cannot be stopped.
+    pub fn blocking_moves(&self, player: Player) -> Vec<(usize, usize)> {
+        self.winning_moves(player.opponent())
+    }
+
+    /// Returns the empty squares that would complete a line for `player`,
+    /// whatever the rules, in row-major order
+    pub(crate) fn completing_moves(&self, player: Player) -> Vec<(usize, usize)> {
+        if self.result != GameResult::InProgress {
+            return Vec::new();
+        }
+        let mut moves: Vec<(usize, usize)> = WIN_LINES
+            .iter()
+            .filter_map(|line| {
+                let mut empty = None;
+                for &(row, col) in line {
+                    match self.cells[row][col] {
+                        Cell::Empty if empty.is_none() => empty = Some((row, col)),
+                        Cell::Occupied(owner) if owner == player => {}
+                        _ => return None,
+                    }
+                }
+                empty
+            })
+            .collect();
+        moves.sort_unstable();
+        moves.dedup();
+        moves
+    }
+
+    /// Checks that the position could arise from legal play
+    ///
+    /// Either player may have moved first, so the piece counts may differ by
+    /// at most one. Only one player may have completed a line, and that
+    /// player must have made the last move: a position with moves played
+    /// after the game ended is rejected. Boards built through
+    /// [`make_move`](Self::make_move) always pass.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Cell, Player};
+    ///
+    /// let x = Cell::Occupied(Player::X);
+    /// let o = Cell::Occupied(Player::O);
+    /// let e = Cell::Empty;
+    ///
+    /// // X completed the top row, then O moved anyway
+    /// let board = Board::try_from([[x, x, x], [o, o, e], [o, o, e]]);
+    /// assert_eq!(board, Err("Moves were played after the game ended"));
+    /// ```
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let x = self.count_pieces(Player::X);
+        let o = self.count_pieces(Player::O);
+        if x.abs_diff(o) > 1 {
+            return Err("Inconsistent piece counts");
+        }
+
+        let mut owners = WIN_LINES.iter().filter_map(|&[(r0, c0), (r1, c1), (r2, c2)]| {
+            let cell = self.cells[r0][c0];
+            (cell == self.cells[r1][c1] && cell == self.cells[r2][c2]).then_some(cell)
+        });
+        let Some(Cell::Occupied(owner)) = owners.find(|&cell| cell != Cell::Empty) else {
+            return Ok(());
+        };
+        if owners.any(|cell| cell == Cell::Occupied(owner.opponent())) {
+            return Err("Both players completed a line");
+        }
+        if self.count_pieces(owner) < self.count_pieces(owner.opponent()) {
+            return Err("Moves were played after the game ended");
+        }
+        Ok(())
+    }
+
+    /// Returns true if the position can arise from the empty board by legal
+    /// alternating play with X moving first
+    ///
+    /// Stricter than [`validate`](Self::validate), which allows either player
+    /// to have started: X must have the same number of pieces as O or one
+    /// more, and if a line is complete, removing one of the last mover's
+    /// pieces must undo every completed line. Exactly the positions returned
+    /// by [`enumerate_positions`](crate::backend::enumerate_positions) pass.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Cell, Player};
+    ///
+    /// let board = Board::from_moves(&[(0, 0), (1, 1), (0, 1)]).unwrap();
+    /// assert!(board.is_reachable());
+    ///
+    /// // O moved first: consistent piece counts, but not reachable with X first
+    /// let o = Cell::Occupied(Player::O);
+    /// let e = Cell::Empty;
+    /// let board = Board::try_from([[o, e, e], [e, e, e], [e, e, e]]).unwrap();
+    /// assert!(board.validate().is_ok());
+    /// assert!(!board.is_reachable());
+    /// ```
+    pub fn is_reachable(&self) -> bool {
+        let x = self.count_pieces(Player::X);
+        let o = self.count_pieces(Player::O);
+        let last = if x == o + 1 {
+            Player::X
+        } else if x == o {
+            Player::O
+        } else {
+            return false;
+        };
+        if x == 0 {
+            return true;
+        }
+
+        let lines: Vec<[(usize, usize); 3]> = WIN_LINES
+            .iter()
+            .copied()
+            .filter(|&[(r0, c0), (r1, c1), (r2, c2)]| {
+                let cell = self.cells[r0][c0];
+                cell != Cell::Empty && cell == self.cells[r1][c1] && cell == self.cells[r2][c2]
+            })
+            .collect();
+        if lines.is_empty() {
+            return true;
+        }
+        // The last move must lie on every completed line, and all of them
+        // must belong to the player who made it
+        lines.iter().all(|&[(row, col), _, _]| self.cells[row][col] == Cell::Occupied(last))
+            && Pos::ALL.into_iter().any(|pos| lines.iter().all(|line| line.contains(&pos.into())))
+    }
+
+    /// Returns the game result together with the winning line
+    pub fn outcome(&self) -> Outcome {
+        Outcome {
+            result: self.game_result(),
+            line: self.winning_line(),
+        }
+    }
+
+    /// Returns a formatter for rendering the board in other text styles
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Board;
+    ///
+    /// let board = Board::new();
+    /// println!("{}", board.formatter().grid(true).unicode(true).wide(true));
+    /// ```
+    pub fn formatter(&self) -> BoardFormatter<'_> {
+        BoardFormatter::new(self)
+    }
+
     /// Convenience method to select a move using an engine
     ///
     /// This is a helper method that accepts any engine implementing the `Engine` trait.
@@ -134,9 +480,257 @@ impl Board {
         engine.choose_move(self, player)
     }
 
+    /// Infers whose turn it is from the piece counts
+    ///
+    /// Assumes X moves first: X is to move when both players have the same
+    /// number of pieces, O when X has exactly one more. Any other count is
+    /// inconsistent with alternating play and yields `None`. The game result
+    /// is not considered, so a finished position still reports the side that
+    /// would move next.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player};
+    ///
+    /// let mut board = Board::new();
+    /// assert_eq!(board.current_player(), Some(Player::X));
+    /// board.make_move(1, 1, Player::X).unwrap();
+    /// assert_eq!(board.current_player(), Some(Player::O));
+    /// board.make_move(0, 0, Player::X).unwrap();
+    /// assert_eq!(board.current_player(), None);
+    /// ```
+    pub fn current_player(&self) -> Option<Player> {
+        let x = self.count_pieces(Player::X);
+        let o = self.count_pieces(Player::O);
+        if x == o {
+            Some(Player::X)
+        } else if x == o + 1 {
+            Some(Player::O)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a copy of the cells, indexed `[row][col]`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Cell, Player};
+    ///
+    /// let board = Board::from_moves(&[(0, 0), (2, 1)]).unwrap();
+    /// let cells = board.to_array();
+    /// assert_eq!(cells[2][1], Cell::Occupied(Player::O));
+    /// assert_eq!(Board::try_from(cells), Ok(board));
+    /// ```
+    pub fn to_array(&self) -> [[Cell; 3]; 3] {
+        self.cells
+    }
+
+    /// Encodes the cells as a base-3 number in `0..19683`
+    ///
+    /// Each cell contributes `0` (empty), `1` (X), or `2` (O) times
+    /// `3^(row * 3 + col)`. The encoding is compact enough to index lookup
+    /// tables directly. Rules are not encoded.
+    pub fn encode(&self) -> u16 {
+        self.cells
+            .iter()
+            .flat_map(|row| row.iter())
+            .rev()
+            .fold(0u16, |code, &cell| {
+                code * 3 + match cell {
+                    Cell::Empty => 0,
+                    Cell::Occupied(Player::X) => 1,
+                    Cell::Occupied(Player::O) => 2,
+                }
+            })
+    }
+
+    /// Returns the cells in row-major order as `X`, `O`, or `.`
+    pub(crate) fn cell_string(&self) -> String {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|cell| match cell {
+                Cell::Empty => '.',
+                Cell::Occupied(Player::X) => 'X',
+                Cell::Occupied(Player::O) => 'O',
+            })
+            .collect()
+    }
+
+    /// Decodes a board produced by [`encode`](Board::encode)
+    ///
+    /// The cells are not validated; the cached result is recomputed.
+    pub(crate) fn decode(mut code: u16, rules: Rules) -> Board {
+        let mut board = Board::with_rules(rules);
+        for index in 0..9 {
+            board.cells[index / 3][index % 3] = match code % 3 {
+                0 => Cell::Empty,
+                1 => Cell::Occupied(Player::X),
+                _ => Cell::Occupied(Player::O),
+            };
+            code /= 3;
+        }
+        board.refresh();
+        board
+    }
+
+    /// Returns the canonical representative of this board's symmetry class
+    ///
+    /// The canonical board is the image with the smallest [`encode`](Board::encode)
+    /// value. The returned symmetry maps this board onto the canonical one, so
+    /// a move `m` on this board corresponds to `symmetry.apply(m)` on the
+    /// canonical board.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player};
+    ///
+    /// let mut a = Board::new();
+    /// a.make_move(0, 0, Player::X).unwrap();
+    /// let mut b = Board::new();
+    /// b.make_move(2, 2, Player::X).unwrap();
+    /// assert_eq!(a.canonical().0, b.canonical().0);
+    /// ```
+    pub fn canonical(&self) -> (Board, Symmetry) {
+        let mut best = (self.clone(), Symmetry::Identity);
+        let mut best_code = self.encode();
+        for &symmetry in &Symmetry::ALL[1..] {
+            let image = self.transform(symmetry);
+            let code = image.encode();
+            if code < best_code {
+                best_code = code;
+                best = (image, symmetry);
+            }
+        }
+        best
+    }
+
+    /// Returns the distinct images of this board under the eight symmetries
+    ///
+    /// Each image comes with the symmetry that maps it back to this board, so
+    /// a move `m` found on an image corresponds to `back.apply(m)` here. The
+    /// board itself comes first, with [`Symmetry::Identity`]; symmetric boards
+    /// have fewer than eight images.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Board;
+    ///
+    /// // A corner opening has four distinct images, the center opening one
+    /// let corner = Board::from_moves(&[(0, 0)]).unwrap();
+    /// assert_eq!(corner.symmetry_class().len(), 4);
+    /// assert_eq!(Board::from_moves(&[(1, 1)]).unwrap().symmetry_class().len(), 1);
+    ///
+    /// for (image, back) in corner.symmetry_class() {
+    ///     assert_eq!(image.transform(back), corner);
+    /// }
+    /// ```
+    pub fn symmetry_class(&self) -> Vec<(Board, Symmetry)> {
+        let mut class: Vec<(Board, Symmetry)> = Vec::with_capacity(8);
+        for symmetry in Symmetry::ALL {
+            let image = self.transform(symmetry);
+            if class.iter().all(|(seen, _)| *seen != image) {
+                class.push((image, symmetry.inverse()));
+            }
+        }
+        class
+    }
+
+    /// Returns the valid moves that are distinct up to the board's symmetries
+    ///
+    /// Moves that are images of each other under a rotation or reflection
+    /// leaving the board unchanged lead to equivalent positions, so only the
+    /// first of each group (in row-major order) is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Board;
+    ///
+    /// // Corner, edge, and center are the only distinct first moves
+    /// assert_eq!(Board::new().distinct_moves(), vec![(0, 0), (0, 1), (1, 1)]);
+    /// ```
+    pub fn distinct_moves(&self) -> Vec<(usize, usize)> {
+        let code = self.encode();
+        let stabilizer: Vec<Symmetry> = Symmetry::ALL[1..]
+            .iter()
+            .copied()
+            .filter(|&symmetry| self.transform(symmetry).encode() == code)
+            .collect();
+        self.valid_moves()
+            .into_iter()
+            .filter(|&mv| stabilizer.iter().all(|s| s.apply(mv) >= mv))
+            .collect()
+    }
+
+    /// Returns the image of this board under a symmetry
+    ///
+    /// The piece on `(row, col)` moves to `symmetry.apply((row, col))`. The
+    /// result and rules are unchanged, since symmetries preserve lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Cell, Player, Symmetry};
+    ///
+    /// let board = Board::from_moves(&[(0, 0)]).unwrap();
+    /// let image = board.transform(Symmetry::Rotate90);
+    /// assert_eq!(image[(0, 2)], Cell::Occupied(Player::X));
+    /// assert_eq!(image, board.rotate90());
+    /// assert_eq!(image.transform(Symmetry::Rotate90.inverse()), board);
+    /// ```
+    pub fn transform(&self, symmetry: Symmetry) -> Board {
+        let mut image = self.clone();
+        for row in 0..3 {
+            for col in 0..3 {
+                let (r, c) = symmetry.apply((row, col));
+                image.cells[r][c] = self.cells[row][col];
+            }
+        }
+        image
+    }
+
+    /// Returns the board rotated 90° clockwise
+    pub fn rotate90(&self) -> Board {
+        self.transform(Symmetry::Rotate90)
+    }
+
+    /// Returns the board rotated 180°
+    pub fn rotate180(&self) -> Board {
+        self.transform(Symmetry::Rotate180)
+    }
+
+    /// Returns the board rotated 270° clockwise (90° counter-clockwise)
+    pub fn rotate270(&self) -> Board {
+        self.transform(Symmetry::Rotate270)
+    }
+
+    /// Returns the board mirrored left to right
+    pub fn mirror_horizontal(&self) -> Board {
+        self.transform(Symmetry::MirrorHorizontal)
+    }
+
+    /// Returns the board mirrored top to bottom
+    pub fn mirror_vertical(&self) -> Board {
+        self.transform(Symmetry::MirrorVertical)
+    }
+
+    /// Counts the pieces the given player has on the board
+    pub(crate) fn count_pieces(&self, player: Player) -> usize {
+        self.cells.iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&cell| cell == Cell::Occupied(player))
+            .count()
+    }
+
     /// Resets the board to empty state
     pub fn reset(&mut self) {
-        self.cells = [[Cell::Empty; 3]; 3];
+        *self = Board::with_rules(self.rules);
     }
 }
 
@@ -146,22 +740,160 @@ impl Default for Board {
     }
 }
 
-impl fmt::Display for Board {
+/// The first move that [`Board::from_moves`] or [`Board::from_player_moves`]
+/// could not play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove {
+    /// Position of the move in the input, starting at 0
+    pub index: usize,
+    /// Why the move was rejected, as returned by [`Board::make_move`]
+    pub reason: &'static str,
+}
+
+impl fmt::Display for IllegalMove {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, row) in self.cells.iter().enumerate() {
-            for (j, cell) in row.iter().enumerate() {
-                match cell {
-                    Cell::Empty => write!(f, ".")?,
-                    Cell::Occupied(player) => write!(f, "{}", player)?,
-                }
-                if j < 2 {
-                    write!(f, " ")?;
-                }
-            }
-            if i < 2 {
-                writeln!(f)?;
+        write!(f, "move {}: {}", self.index + 1, self.reason)
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// Builds a standard-rules board from cells indexed `[row][col]`
+///
+/// The position must pass [`Board::validate`].
+impl TryFrom<[[Cell; 3]; 3]> for Board {
+    type Error = &'static str;
+
+    fn try_from(cells: [[Cell; 3]; 3]) -> Result<Self, Self::Error> {
+        let mut board = Board::new();
+        board.cells = cells;
+        board.validate()?;
+        board.refresh();
+        Ok(board)
+    }
+}
+
+/// Parses a standard-rules board from its cells in row-major order
+///
+/// Each cell is `X`, `O`, or `.` for empty. Whitespace and `/` are ignored,
+/// so both the compact `X.O/.X./...` and the `Display` output parse. The
+/// position must pass [`Board::validate`].
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Cell, Player};
+///
+/// let board: Board = "X.O/.X./...".parse().unwrap();
+/// assert_eq!(board[(1, 1)], Cell::Occupied(Player::X));
+/// assert_eq!(board.to_string().parse::<Board>(), Ok(board));
+/// assert_eq!("XXX/OOO/...".parse::<Board>(), Err("Both players completed a line"));
+/// ```
+impl FromStr for Board {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = [[Cell::Empty; 3]; 3];
+        let mut count = 0;
+        for c in s.chars().filter(|&c| c != '/' && !c.is_whitespace()) {
+            let cell = match c {
+                'X' => Cell::Occupied(Player::X),
+                'O' => Cell::Occupied(Player::O),
+                '.' => Cell::Empty,
+                _ => return Err("Board cells must be X, O, or ."),
+            };
+            if count == 9 {
+                return Err("Board must have nine cells");
             }
+            cells[count / 3][count % 3] = cell;
+            count += 1;
         }
-        Ok(())
+        if count < 9 {
+            return Err("Board must have nine cells");
+        }
+        Board::try_from(cells)
+    }
+}
+
+impl From<&Board> for [[Cell; 3]; 3] {
+    fn from(board: &Board) -> Self {
+        board.to_array()
+    }
+}
+
+/// Reads the cell at `(row, col)`
+///
+/// There is no `IndexMut`: moves go through [`Board::make_move`], which keeps
+/// the cached result up to date.
+///
+/// # Panics
+///
+/// Panics if `row` or `col` is 3 or more, like slice indexing.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Cell, Player};
+///
+/// let mut board = Board::new();
+/// board.make_move(1, 2, Player::X).unwrap();
+/// assert_eq!(board[(1, 2)], Cell::Occupied(Player::X));
+/// assert_eq!(board[(0, 0)], Cell::Empty);
+/// ```
+impl Index<(usize, usize)> for Board {
+    type Output = Cell;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Cell {
+        &self.cells[row][col]
+    }
+}
+
+/// Reads the cell at a [`Pos`], which is always in range
+impl Index<Pos> for Board {
+    type Output = Cell;
+
+    fn index(&self, pos: Pos) -> &Cell {
+        &self.cells[pos.row()][pos.col()]
+    }
+}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = ((usize, usize), Cell);
+    type IntoIter = Cells<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells()
+    }
+}
+
+/// Iterator over the cells of a [`Board`], returned by [`Board::cells`]
+#[derive(Debug, Clone)]
+pub struct Cells<'a> {
+    board: &'a Board,
+    index: usize,
+}
+
+impl Iterator for Cells<'_> {
+    type Item = ((usize, usize), Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == 9 {
+            return None;
+        }
+        let (row, col) = (self.index / 3, self.index % 3);
+        self.index += 1;
+        Some(((row, col), self.board.cells[row][col]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (9 - self.index, Some(9 - self.index))
+    }
+}
+
+impl ExactSizeIterator for Cells<'_> {}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter().fmt(f)
     }
 }
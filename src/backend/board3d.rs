@@ -0,0 +1,235 @@
+//! 3x3x3 TicTacToe on bitboards
+
+use std::fmt;
+use crate::backend::game::GameResult;
+use crate::backend::player::{Player, Cell};
+use crate::backend::variant::GameBoard;
+
+/// Number of cells on a 3x3x3 board
+const CELLS: usize = 27;
+
+/// Bit index of the cell at (layer, row, col)
+#[inline]
+const fn bit(layer: usize, row: usize, col: usize) -> usize {
+    layer * 9 + row * 3 + col
+}
+
+/// Generates the bitmasks of every winning line on a 3x3x3 cube
+///
+/// A line starts at a cell and advances along one of the 13 directions that
+/// cover the positive half-space; it is counted once if the step before the
+/// start leaves the cube and the two steps after it stay inside.
+const fn generate_lines() -> [u32; 49] {
+    const DIRECTIONS: [(isize, isize, isize); 13] = [
+        (0, 0, 1), (0, 1, 0), (1, 0, 0),
+        (0, 1, 1), (0, 1, -1), (1, 0, 1), (1, 0, -1), (1, 1, 0), (1, -1, 0),
+        (1, 1, 1), (1, 1, -1), (1, -1, 1), (1, -1, -1),
+    ];
+    const fn inside(v: isize) -> bool {
+        v >= 0 && v < 3
+    }
+
+    let mut lines = [0u32; 49];
+    let mut count = 0;
+    let mut start = 0;
+    while start < CELLS {
+        let (l, r, c) = ((start / 9) as isize, ((start / 3) % 3) as isize, (start % 3) as isize);
+        let mut d = 0;
+        while d < DIRECTIONS.len() {
+            let (dl, dr, dc) = DIRECTIONS[d];
+            let before = inside(l - dl) && inside(r - dr) && inside(c - dc);
+            let end = inside(l + 2 * dl) && inside(r + 2 * dr) && inside(c + 2 * dc);
+            if !before && end {
+                let mut mask = 0u32;
+                let mut step = 0;
+                while step < 3 {
+                    let cell = bit(
+                        (l + step * dl) as usize,
+                        (r + step * dr) as usize,
+                        (c + step * dc) as usize,
+                    );
+                    mask |= 1 << cell;
+                    step += 1;
+                }
+                lines[count] = mask;
+                count += 1;
+            }
+            d += 1;
+        }
+        start += 1;
+    }
+    assert!(count == 49);
+    lines
+}
+
+/// Bitmasks of the 49 winning lines of a 3x3x3 board
+pub const WIN_LINES_3D: [u32; 49] = generate_lines();
+
+/// A 3x3x3 TicTacToe board
+///
+/// Each player's pieces are stored as a 27-bit bitboard, so win detection is a
+/// handful of mask comparisons against [`WIN_LINES_3D`]. Moves are addressed
+/// as `(layer, row, col)`. The game result is cached and updated on each move.
+///
+/// Unlike the 3x3 game, the first player can always force a win on a 3x3x3
+/// cube, which makes it a useful stress test for engines and statistics.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board3D, Player, GameResult};
+///
+/// let mut board = Board3D::new();
+/// board.make_move(0, 0, 0, Player::X).unwrap();
+/// board.make_move(1, 1, 1, Player::X).unwrap();
+/// board.make_move(2, 2, 2, Player::X).unwrap();
+/// assert_eq!(board.game_result(), GameResult::Win(Player::X));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Board3D {
+    x: u32,
+    o: u32,
+    result: GameResult,
+}
+
+impl Board3D {
+    /// Creates a new empty board
+    pub fn new() -> Self {
+        Board3D {
+            x: 0,
+            o: 0,
+            result: GameResult::InProgress,
+        }
+    }
+
+    /// Gets the cell at the given position
+    pub fn get(&self, layer: usize, row: usize, col: usize) -> Option<Cell> {
+        if layer >= 3 || row >= 3 || col >= 3 {
+            return None;
+        }
+        let mask = 1 << bit(layer, row, col);
+        Some(if self.x & mask != 0 {
+            Cell::Occupied(Player::X)
+        } else if self.o & mask != 0 {
+            Cell::Occupied(Player::O)
+        } else {
+            Cell::Empty
+        })
+    }
+
+    /// Makes a move on the board
+    pub fn make_move(&mut self, layer: usize, row: usize, col: usize, player: Player) -> Result<(), &'static str> {
+        if layer >= 3 || row >= 3 || col >= 3 {
+            return Err("Position out of bounds");
+        }
+
+        let mask = 1 << bit(layer, row, col);
+        if (self.x | self.o) & mask != 0 {
+            return Err("Cell already occupied");
+        }
+
+        if self.result != GameResult::InProgress {
+            return Err("Game is already over");
+        }
+
+        let pieces = match player {
+            Player::X => &mut self.x,
+            Player::O => &mut self.o,
+        };
+        *pieces |= mask;
+        let pieces = *pieces;
+
+        self.result = if WIN_LINES_3D.iter().any(|&line| line & mask != 0 && pieces & line == line) {
+            GameResult::Win(player)
+        } else if (self.x | self.o).count_ones() as usize == CELLS {
+            GameResult::Draw
+        } else {
+            GameResult::InProgress
+        };
+        Ok(())
+    }
+
+    /// Checks if a move is valid
+    pub fn is_valid_move(&self, layer: usize, row: usize, col: usize) -> bool {
+        layer < 3
+            && row < 3
+            && col < 3
+            && (self.x | self.o) & (1 << bit(layer, row, col)) == 0
+            && self.result == GameResult::InProgress
+    }
+
+    /// Gets all valid moves
+    pub fn valid_moves(&self) -> Vec<(usize, usize, usize)> {
+        let mut moves = Vec::new();
+        if self.result != GameResult::InProgress {
+            return moves;
+        }
+
+        let occupied = self.x | self.o;
+        for cell in 0..CELLS {
+            if occupied & (1 << cell) == 0 {
+                moves.push((cell / 9, (cell / 3) % 3, cell % 3));
+            }
+        }
+        moves
+    }
+
+    /// Returns the current game result
+    #[inline]
+    pub fn game_result(&self) -> GameResult {
+        self.result
+    }
+
+    /// Resets the board to empty state
+    pub fn reset(&mut self) {
+        *self = Board3D::new();
+    }
+}
+
+impl Default for Board3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameBoard for Board3D {
+    type Move = (usize, usize, usize);
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        self.valid_moves()
+    }
+
+    fn play(&mut self, (layer, row, col): Self::Move, player: Player) -> Result<(), &'static str> {
+        self.make_move(layer, row, col, player)
+    }
+
+    fn result(&self) -> GameResult {
+        self.game_result()
+    }
+}
+
+impl fmt::Display for Board3D {
+    /// Prints the three layers top to bottom, separated by blank lines
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for layer in 0..3 {
+            for row in 0..3 {
+                for col in 0..3 {
+                    match self.get(layer, row, col) {
+                        Some(Cell::Occupied(player)) => write!(f, "{}", player)?,
+                        _ => write!(f, ".")?,
+                    }
+                    if col < 2 {
+                        write!(f, " ")?;
+                    }
+                }
+                if row < 2 || layer < 2 {
+                    writeln!(f)?;
+                }
+            }
+            if layer < 2 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
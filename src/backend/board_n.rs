@@ -0,0 +1,196 @@
+//! Generic NxN board with k-in-a-row wins
+
+use std::fmt;
+use crate::backend::game::GameResult;
+use crate::backend::player::{Player, Cell};
+use crate::backend::variant::GameBoard;
+
+/// An NxN board where `win_length` pieces in a row win
+///
+/// The board size is a const generic, so boards stay stack-allocated and
+/// cheaply cloneable like the 3x3 [`Board`](crate::backend::Board). As with
+/// `Board`, the game result is cached and updated incrementally on each move.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{BoardN, Player, GameResult};
+///
+/// // 5x5 board, four in a row wins
+/// let mut board = BoardN::<5>::new(4);
+/// for col in 0..4 {
+///     board.make_move(2, col, Player::X).unwrap();
+/// }
+/// assert_eq!(board.game_result(), GameResult::Win(Player::X));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardN<const N: usize> {
+    cells: [[Cell; N]; N],
+    win_length: usize,
+    filled: usize,
+    result: GameResult,
+}
+
+impl<const N: usize> BoardN<N> {
+    /// Creates a new empty board with the given win length
+    ///
+    /// # Panics
+    ///
+    /// Panics if `win_length` is zero or larger than `N`.
+    pub fn new(win_length: usize) -> Self {
+        assert!(win_length > 0 && win_length <= N, "win length must be between 1 and N");
+        BoardN {
+            cells: [[Cell::Empty; N]; N],
+            win_length,
+            filled: 0,
+            result: GameResult::InProgress,
+        }
+    }
+
+    /// Returns the side length of the board
+    pub fn size(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of pieces in a row needed to win
+    pub fn win_length(&self) -> usize {
+        self.win_length
+    }
+
+    /// Gets the cell at the given position
+    pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
+        if row < N && col < N {
+            Some(self.cells[row][col])
+        } else {
+            None
+        }
+    }
+
+    /// Makes a move on the board
+    pub fn make_move(&mut self, row: usize, col: usize, player: Player) -> Result<(), &'static str> {
+        if row >= N || col >= N {
+            return Err("Position out of bounds");
+        }
+
+        if self.cells[row][col] != Cell::Empty {
+            return Err("Cell already occupied");
+        }
+
+        if self.result != GameResult::InProgress {
+            return Err("Game is already over");
+        }
+
+        self.cells[row][col] = Cell::Occupied(player);
+        self.filled += 1;
+        self.result = if self.completes_line(row, col, player) {
+            GameResult::Win(player)
+        } else if self.filled == N * N {
+            GameResult::Draw
+        } else {
+            GameResult::InProgress
+        };
+        Ok(())
+    }
+
+    /// Checks whether the piece at (row, col) is part of a winning run
+    fn completes_line(&self, row: usize, col: usize, player: Player) -> bool {
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        let target = Cell::Occupied(player);
+
+        DIRECTIONS.iter().any(|&(dr, dc)| {
+            let run = |sign: isize| {
+                let mut count = 0;
+                let (mut r, mut c) = (row as isize, col as isize);
+                loop {
+                    r += dr * sign;
+                    c += dc * sign;
+                    if r < 0 || c < 0 || r >= N as isize || c >= N as isize {
+                        break;
+                    }
+                    if self.cells[r as usize][c as usize] != target {
+                        break;
+                    }
+                    count += 1;
+                }
+                count
+            };
+            1 + run(1) + run(-1) >= self.win_length
+        })
+    }
+
+    /// Checks if a move is valid
+    pub fn is_valid_move(&self, row: usize, col: usize) -> bool {
+        row < N && col < N && self.cells[row][col] == Cell::Empty && self.result == GameResult::InProgress
+    }
+
+    /// Gets all valid moves
+    pub fn valid_moves(&self) -> Vec<(usize, usize)> {
+        let mut moves = Vec::new();
+        if self.result != GameResult::InProgress {
+            return moves;
+        }
+
+        for row in 0..N {
+            for col in 0..N {
+                if self.cells[row][col] == Cell::Empty {
+                    moves.push((row, col));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Returns the current game result
+    #[inline]
+    pub fn game_result(&self) -> GameResult {
+        self.result
+    }
+
+    /// Resets the board to empty state, keeping the win length
+    pub fn reset(&mut self) {
+        *self = BoardN::new(self.win_length);
+    }
+}
+
+impl<const N: usize> Default for BoardN<N> {
+    /// Creates an empty board where a full row, column, or diagonal wins
+    fn default() -> Self {
+        Self::new(N)
+    }
+}
+
+impl<const N: usize> GameBoard for BoardN<N> {
+    type Move = (usize, usize);
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        self.valid_moves()
+    }
+
+    fn play(&mut self, (row, col): Self::Move, player: Player) -> Result<(), &'static str> {
+        self.make_move(row, col, player)
+    }
+
+    fn result(&self) -> GameResult {
+        self.game_result()
+    }
+}
+
+impl<const N: usize> fmt::Display for BoardN<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate() {
+                match cell {
+                    Cell::Empty => write!(f, ".")?,
+                    Cell::Occupied(player) => write!(f, "{}", player)?,
+                }
+                if j + 1 < N {
+                    write!(f, " ")?;
+                }
+            }
+            if i + 1 < N {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
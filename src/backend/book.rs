@@ -0,0 +1,224 @@
+//! Opening books and the book-probing engine wrapper
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use crate::backend::board::Board;
+use crate::backend::engine::{Engine, EngineMetadata};
+use crate::backend::player::Player;
+
+/// File signature of the binary book format
+const MAGIC: &[u8; 4] = b"ZTTB";
+/// Current version of the binary book format
+const VERSION: u8 = 1;
+
+/// A weighted book move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookMove {
+    pub position: (usize, usize),
+    pub weight: u32,
+}
+
+/// Preferred moves for early positions
+///
+/// Positions are stored by their canonical form (see [`Board::canonical`]),
+/// so one entry covers all eight rotations and reflections of a position.
+/// Moves are translated to and from the canonical orientation automatically.
+///
+/// # Binary Format
+///
+/// All integers are little-endian:
+///
+/// ```text
+/// "ZTTB" | version: u8 | entries: u32
+/// per entry: board code: u16 | moves: u8
+/// per move:  cell (row * 3 + col): u8 | weight: u32
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, OpeningBook};
+///
+/// let mut book = OpeningBook::new();
+/// book.add(&Board::new(), (0, 0), 10).unwrap();
+///
+/// // Any corner is equivalent to the top-left one on an empty board
+/// let moves = book.lookup(&Board::new());
+/// assert_eq!(moves.len(), 1);
+///
+/// let mut bytes = Vec::new();
+/// book.save(&mut bytes).unwrap();
+/// assert_eq!(OpeningBook::load(&mut bytes.as_slice()).unwrap(), book);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpeningBook {
+    entries: HashMap<u16, Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    /// Creates an empty book
+    pub fn new() -> Self {
+        OpeningBook {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Adds a move for a position, accumulating weight if it already exists
+    pub fn add(&mut self, board: &Board, position: (usize, usize), weight: u32) -> Result<(), &'static str> {
+        if !board.is_valid_move(position.0, position.1) {
+            return Err("Book move is not valid in this position");
+        }
+        let (canonical, symmetry) = board.canonical();
+        let position = symmetry.apply(position);
+        let moves = self.entries.entry(canonical.encode()).or_default();
+        match moves.iter_mut().find(|m| m.position == position) {
+            Some(existing) => existing.weight = existing.weight.saturating_add(weight),
+            None => moves.push(BookMove { position, weight }),
+        }
+        Ok(())
+    }
+
+    /// Returns the book moves for a position, in this board's orientation
+    ///
+    /// Symmetric positions can map several canonical moves onto one square;
+    /// their weights are combined. Moves are sorted by descending weight.
+    pub fn lookup(&self, board: &Board) -> Vec<BookMove> {
+        let (canonical, symmetry) = board.canonical();
+        let Some(moves) = self.entries.get(&canonical.encode()) else {
+            return Vec::new();
+        };
+
+        let inverse = symmetry.inverse();
+        let mut result: Vec<BookMove> = Vec::with_capacity(moves.len());
+        for m in moves {
+            let position = inverse.apply(m.position);
+            match result.iter_mut().find(|r| r.position == position) {
+                Some(existing) => existing.weight = existing.weight.saturating_add(m.weight),
+                None => result.push(BookMove { position, weight: m.weight }),
+            }
+        }
+        result.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        result
+    }
+
+    /// Returns the highest-weight book move for a position
+    pub fn best_move(&self, board: &Board) -> Option<(usize, usize)> {
+        self.lookup(board)
+            .into_iter()
+            .find(|m| board.is_valid_move(m.position.0, m.position.1))
+            .map(|m| m.position)
+    }
+
+    /// Returns the number of positions in the book
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the book has no positions
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the book in the binary format
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+
+        let mut codes: Vec<u16> = self.entries.keys().copied().collect();
+        codes.sort_unstable();
+        for code in codes {
+            let moves = &self.entries[&code];
+            writer.write_all(&code.to_le_bytes())?;
+            writer.write_all(&[moves.len() as u8])?;
+            for m in moves {
+                writer.write_all(&[(m.position.0 * 3 + m.position.1) as u8])?;
+                writer.write_all(&m.weight.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a book in the binary format
+    pub fn load<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let invalid = |msg: &'static str| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let mut header = [0u8; 9];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("Not an opening book"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("Unsupported opening book version"));
+        }
+        let count = u32::from_le_bytes([header[5], header[6], header[7], header[8]]);
+
+        let mut book = OpeningBook::new();
+        for _ in 0..count {
+            let mut entry = [0u8; 3];
+            reader.read_exact(&mut entry)?;
+            let code = u16::from_le_bytes([entry[0], entry[1]]);
+            let mut moves = Vec::with_capacity(entry[2] as usize);
+            for _ in 0..entry[2] {
+                let mut raw = [0u8; 5];
+                reader.read_exact(&mut raw)?;
+                if raw[0] >= 9 {
+                    return Err(invalid("Book move out of bounds"));
+                }
+                moves.push(BookMove {
+                    position: (raw[0] as usize / 3, raw[0] as usize % 3),
+                    weight: u32::from_le_bytes([raw[1], raw[2], raw[3], raw[4]]),
+                });
+            }
+            book.entries.insert(code, moves);
+        }
+        Ok(book)
+    }
+}
+
+/// Plays book moves when available, otherwise defers to an inner engine
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Engine, BookEngine, FastEngine, OpeningBook};
+///
+/// let mut book = OpeningBook::new();
+/// book.add(&Board::new(), (1, 1), 1).unwrap();
+///
+/// let engine = BookEngine::new(book, FastEngine);
+/// assert_eq!(engine.choose_move(&Board::new(), Player::X), Some((1, 1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BookEngine<E> {
+    book: OpeningBook,
+    inner: E,
+}
+
+impl<E: Engine> BookEngine<E> {
+    /// Wraps `inner` with an opening book
+    pub fn new(book: OpeningBook, inner: E) -> Self {
+        BookEngine { book, inner }
+    }
+
+    /// Returns the opening book
+    pub fn book(&self) -> &OpeningBook {
+        &self.book
+    }
+}
+
+impl<E: Engine> Engine for BookEngine<E> {
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.book
+            .best_move(board)
+            .or_else(|| self.inner.choose_move(board, player))
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("book"),
+            description: Some("Plays opening book moves, falling back to the wrapped engine"),
+            ..EngineMetadata::default()
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Engine wrappers and combinators
+//!
+//! These build new engines out of existing ones without writing a new struct
+//! for every variation, e.g. adding noise to a strong engine to produce an
+//! opponent of intermediate strength.
+
+use crate::backend::board::Board;
+use crate::backend::engine::StatefulEngine;
+use crate::backend::player::Player;
+use crate::backend::rng::Rng;
+
+/// Epsilon-greedy wrapper around another engine
+///
+/// With probability `1 - epsilon` the wrapped engine's move is played; with
+/// probability `epsilon` a uniformly random valid move is played instead.
+/// Useful for generating diverse self-play data and for testing how engines
+/// cope with opponents that make mistakes.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, FastEngine, NoisyEngine, StatefulEngine};
+///
+/// let mut engine = NoisyEngine::seeded(FastEngine, 0.1, 42);
+/// let chosen = engine.choose_move_mut(&Board::new(), Player::X);
+/// assert!(chosen.is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct NoisyEngine<E> {
+    inner: E,
+    epsilon: f64,
+    rng: Rng,
+}
+
+impl<E: StatefulEngine> NoisyEngine<E> {
+    /// Wraps `inner`, seeding the random generator from entropy
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is not in `[0, 1]`.
+    pub fn new(inner: E, epsilon: f64) -> Self {
+        Self::with_rng(inner, epsilon, Rng::from_entropy())
+    }
+
+    /// Wraps `inner` with a fixed seed for reproducible noise
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is not in `[0, 1]`.
+    pub fn seeded(inner: E, epsilon: f64, seed: u64) -> Self {
+        Self::with_rng(inner, epsilon, Rng::new(seed))
+    }
+
+    fn with_rng(inner: E, epsilon: f64, rng: Rng) -> Self {
+        assert!((0.0..=1.0).contains(&epsilon), "epsilon must be in [0, 1]");
+        NoisyEngine { inner, epsilon, rng }
+    }
+
+    /// Returns the probability of playing a random move
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Returns the wrapped engine
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+impl<E: StatefulEngine> StatefulEngine for NoisyEngine<E> {
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        if self.rng.next_f64() < self.epsilon {
+            self.rng.choose(&board.valid_moves())
+        } else {
+            self.inner.choose_move_mut(board, player)
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::stream(seed, 0);
+        self.inner.reseed(Rng::stream(seed, 1).next_u64());
+    }
+
+    fn name(&self) -> &str {
+        "noisy"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Plays a random move with a fixed probability, otherwise defers to the wrapped engine")
+    }
+}
+
+/// Falls back to a second engine when the first has no move
+///
+/// `ChainEngine(a, b)` asks `a` first and uses `b` only if `a` returns `None`.
+/// This lets partial strategies, such as an engine that only knows openings,
+/// be completed by a general one. Either side may be a [`StatefulEngine`];
+/// reseeding reaches both.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, StatefulEngine, ChainEngine, FastEngine, FnEngine};
+///
+/// // Take the center if free, otherwise defer to FastEngine
+/// let center = FnEngine::new(|board: &Board, _: Player| {
+///     board.is_valid_move(1, 1).then_some((1, 1))
+/// });
+/// let mut engine = ChainEngine(center, FastEngine);
+///
+/// let mut board = Board::new();
+/// assert_eq!(engine.choose_move_mut(&board, Player::X), Some((1, 1)));
+/// board.make_move(1, 1, Player::X).unwrap();
+/// assert_eq!(engine.choose_move_mut(&board, Player::O), Some((0, 0)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ChainEngine<A, B>(pub A, pub B);
+
+impl<A: StatefulEngine, B: StatefulEngine> StatefulEngine for ChainEngine<A, B> {
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.0
+            .choose_move_mut(board, player)
+            .or_else(|| self.1.choose_move_mut(board, player))
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.0.reseed(Rng::stream(seed, 1).next_u64());
+        self.1.reseed(Rng::stream(seed, 2).next_u64());
+    }
+
+    fn name(&self) -> &str {
+        "chain"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Plays the first engine's move, falling back to the second")
+    }
+}
+
+/// Probabilistic blend of two engines
+///
+/// Each move is chosen by `a` with probability `p` and by `b` otherwise, which
+/// gives a smooth range of strengths between two engines.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, FastEngine, FnEngine, MixEngine, StatefulEngine};
+///
+/// let last = FnEngine::new(|board: &Board, _: Player| board.valid_moves().pop());
+/// let mut engine = MixEngine::seeded(FastEngine, last, 0.75, 7);
+/// let chosen = engine.choose_move_mut(&Board::new(), Player::X);
+/// assert!(chosen == Some((0, 0)) || chosen == Some((2, 2)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MixEngine<A, B> {
+    a: A,
+    b: B,
+    p: f64,
+    rng: Rng,
+}
+
+impl<A: StatefulEngine, B: StatefulEngine> MixEngine<A, B> {
+    /// Blends `a` and `b`, seeding the random generator from entropy
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `[0, 1]`.
+    pub fn new(a: A, b: B, p: f64) -> Self {
+        Self::with_rng(a, b, p, Rng::from_entropy())
+    }
+
+    /// Blends `a` and `b` with a fixed seed for reproducible choices
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `[0, 1]`.
+    pub fn seeded(a: A, b: B, p: f64, seed: u64) -> Self {
+        Self::with_rng(a, b, p, Rng::new(seed))
+    }
+
+    fn with_rng(a: A, b: B, p: f64, rng: Rng) -> Self {
+        assert!((0.0..=1.0).contains(&p), "p must be in [0, 1]");
+        MixEngine { a, b, p, rng }
+    }
+
+    /// Returns the probability of using the first engine
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl<A: StatefulEngine, B: StatefulEngine> StatefulEngine for MixEngine<A, B> {
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        if self.rng.next_f64() < self.p {
+            self.a.choose_move_mut(board, player)
+        } else {
+            self.b.choose_move_mut(board, player)
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::stream(seed, 0);
+        self.a.reseed(Rng::stream(seed, 1).next_u64());
+        self.b.reseed(Rng::stream(seed, 2).next_u64());
+    }
+
+    fn name(&self) -> &str {
+        "mix"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Picks one of two engines at random for every move")
+    }
+}
@@ -5,6 +5,8 @@
 
 use crate::backend::board::Board;
 use crate::backend::player::Player;
+use crate::backend::pos::{Move, Pos};
+use crate::backend::rng::Rng;
 
 /// Trait for implementing custom game engines
 ///
@@ -25,6 +27,204 @@ pub trait Engine {
     ///
     /// Returns `None` if no valid moves are available or the game is over.
     fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)>;
+
+    /// Choose a move as a [`Move`]; the typed form of `choose_move`
+    ///
+    /// Returns `None` if `choose_move` does, or if it returns an out-of-range
+    /// position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player, Pos, Engine, FastEngine};
+    ///
+    /// let mv = FastEngine.choose(&Board::new(), Player::X).unwrap();
+    /// assert_eq!(mv.pos, Pos::new(0, 0).unwrap());
+    /// assert_eq!(mv.player, Player::X);
+    /// ```
+    fn choose(&self, board: &Board, player: Player) -> Option<Move> {
+        let (row, col) = self.choose_move(board, player)?;
+        Some(Move::new(Pos::new(row, col)?, player))
+    }
+
+    /// Score every valid move for the given player
+    ///
+    /// Returns one `(move, score)` pair per valid move, sorted best first.
+    /// Higher scores are better; the scale is engine-specific. The default
+    /// implementation scores the move returned by `choose_move` as `1.0` and
+    /// every other valid move as `0.0`. Engines that search or evaluate
+    /// positions should override it to expose their full ranking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player, Engine, FastEngine};
+    ///
+    /// let scores = FastEngine.evaluate_moves(&Board::new(), Player::X);
+    /// assert_eq!(scores.len(), 9);
+    /// assert_eq!(scores[0], ((0, 0), 1.0));
+    /// ```
+    fn evaluate_moves(&self, board: &Board, player: Player) -> Vec<((usize, usize), f64)> {
+        let chosen = self.choose_move(board, player);
+        let mut scores: Vec<((usize, usize), f64)> = board
+            .valid_moves()
+            .into_iter()
+            .map(|mv| (mv, if Some(mv) == chosen { 1.0 } else { 0.0 }))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    /// Returns the metadata this engine reports through
+    /// [`StatefulEngine::name`], [`StatefulEngine::version`], and
+    /// [`StatefulEngine::description`]
+    ///
+    /// Every `Engine` is a `StatefulEngine`, and that is where the metadata is
+    /// read. Fields left `None` keep the `StatefulEngine` defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, BoxedEngine, Engine, EngineMetadata, Player};
+    ///
+    /// struct Corner;
+    ///
+    /// impl Engine for Corner {
+    ///     fn choose_move(&self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+    ///         [(0, 0), (0, 2), (2, 0), (2, 2)].into_iter().find(|&(r, c)| board.is_valid_move(r, c))
+    ///     }
+    ///
+    ///     fn metadata(&self) -> EngineMetadata<'_> {
+    ///         EngineMetadata {
+    ///             description: Some("Takes a corner when it can"),
+    ///             ..EngineMetadata::default()
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let engine: BoxedEngine = Box::new(Corner);
+    /// assert_eq!(engine.name(), "Corner");
+    /// assert_eq!(engine.version(), None);
+    /// assert_eq!(engine.description(), Some("Takes a corner when it can"));
+    /// ```
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata::default()
+    }
+}
+
+/// Descriptive metadata returned by [`Engine::metadata`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EngineMetadata<'a> {
+    /// Short name for tables and reports
+    pub name: Option<&'a str>,
+    /// Version of the engine or the crate it comes from
+    pub version: Option<&'a str>,
+    /// One-line description of how the engine plays
+    pub description: Option<&'a str>,
+}
+
+/// Version reported by the engines defined in this crate
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the crate version for types defined in this crate, `None` otherwise
+fn builtin_version(type_name: &str) -> Option<&'static str> {
+    type_name.starts_with("zttt_rs::").then_some(CRATE_VERSION)
+}
+
+/// Strips the module path and generic parameters from a type name
+fn short_type_name(name: &str) -> &str {
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+/// Trait for engines that update internal state when choosing moves
+///
+/// [`Engine::choose_move`] takes `&self`, which keeps engines shareable across
+/// threads but forces interior mutability on anything that keeps RNG state,
+/// transposition tables, or learned values. `StatefulEngine` takes `&mut self`
+/// instead. Every [`Engine`] is automatically a `StatefulEngine`, so APIs that
+/// accept a `StatefulEngine` (such as the simulator) work with both kinds.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, StatefulEngine};
+///
+/// /// Plays the first valid move and counts how often it was asked
+/// struct CountingEngine {
+///     calls: usize,
+/// }
+///
+/// impl StatefulEngine for CountingEngine {
+///     fn choose_move_mut(&mut self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+///         self.calls += 1;
+///         board.valid_moves().into_iter().next()
+///     }
+/// }
+///
+/// let mut engine = CountingEngine { calls: 0 };
+/// engine.choose_move_mut(&Board::new(), Player::X);
+/// assert_eq!(engine.calls, 1);
+/// ```
+pub trait StatefulEngine {
+    /// Choose a move for the given player, possibly updating engine state
+    ///
+    /// Returns `None` if no valid moves are available or the game is over.
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)>;
+
+    /// Choose a move as a [`Move`]; the typed form of `choose_move_mut`
+    fn choose_mut(&mut self, board: &Board, player: Player) -> Option<Move> {
+        let (row, col) = self.choose_move_mut(board, player)?;
+        Some(Move::new(Pos::new(row, col)?, player))
+    }
+
+    /// Reseeds any random state before a game
+    ///
+    /// Seeded simulations call this with a per-game seed so that runs are
+    /// reproducible regardless of game order or thread count. Engines without
+    /// randomness keep the default, which does nothing.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Returns a short name for tables and reports
+    ///
+    /// Defaults to the type name without its module path or generic
+    /// parameters. [`Engine`] implementations set it through
+    /// [`Engine::metadata`].
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+
+    /// Returns the engine's version, if it has one
+    ///
+    /// Defaults to the crate version for the built-in engines and `None` for
+    /// everything else.
+    fn version(&self) -> Option<&str> {
+        builtin_version(std::any::type_name::<Self>())
+    }
+
+    /// Returns a one-line description of how the engine plays
+    fn description(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<E: Engine + ?Sized> StatefulEngine for E {
+    #[inline]
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.choose_move(board, player)
+    }
+
+    fn name(&self) -> &str {
+        self.metadata().name.unwrap_or_else(|| short_type_name(std::any::type_name::<E>()))
+    }
+
+    fn version(&self) -> Option<&str> {
+        self.metadata().version.or_else(|| builtin_version(std::any::type_name::<E>()))
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.metadata().description
+    }
 }
 
 /// A fast engine optimized for high-speed simulations
@@ -56,4 +256,121 @@ impl Engine for FastEngine {
     fn choose_move(&self, board: &Board, _player: Player) -> Option<(usize, usize)> {
         board.valid_moves().into_iter().next()
     }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("fast"),
+            description: Some("Plays the first empty cell in row-major order"),
+            ..EngineMetadata::default()
+        }
+    }
+}
+
+/// An engine that plays uniformly random valid moves
+///
+/// The random generator is part of the engine state, so `RandomEngine` is a
+/// [`StatefulEngine`]. Seed it with [`RandomEngine::seeded`] for reproducible
+/// games.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, RandomEngine, StatefulEngine};
+///
+/// let mut engine = RandomEngine::seeded(42);
+/// let (row, col) = engine.choose_move_mut(&Board::new(), Player::X).unwrap();
+/// assert!(row < 3 && col < 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomEngine {
+    rng: Rng,
+}
+
+impl RandomEngine {
+    /// Creates an engine seeded from entropy
+    pub fn new() -> Self {
+        RandomEngine { rng: Rng::from_entropy() }
+    }
+
+    /// Creates an engine with a fixed seed
+    pub fn seeded(seed: u64) -> Self {
+        RandomEngine { rng: Rng::new(seed) }
+    }
+}
+
+impl Default for RandomEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulEngine for RandomEngine {
+    fn choose_move_mut(&mut self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+        self.rng.choose(&board.valid_moves())
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Plays a uniformly random empty cell")
+    }
+}
+
+/// An engine backed by a closure
+///
+/// Wraps any `Fn(&Board, Player) -> Option<(usize, usize)>` so it can be used
+/// wherever an [`Engine`] is expected. Handy for quick experiments and for
+/// tests that need scripted moves.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Engine, FnEngine};
+///
+/// // Always take the last free cell
+/// let engine = FnEngine::new(|board: &Board, _player: Player| board.valid_moves().pop());
+/// assert_eq!(engine.choose_move(&Board::new(), Player::X), Some((2, 2)));
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnEngine<F> {
+    f: F,
+}
+
+impl<F> FnEngine<F>
+where
+    F: Fn(&Board, Player) -> Option<(usize, usize)>,
+{
+    /// Creates an engine that calls `f` to choose each move
+    pub fn new(f: F) -> Self {
+        FnEngine { f }
+    }
+}
+
+impl<F> Engine for FnEngine<F>
+where
+    F: Fn(&Board, Player) -> Option<(usize, usize)>,
+{
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        (self.f)(board, player)
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("fn"),
+            description: Some("Plays the moves chosen by a closure"),
+            ..EngineMetadata::default()
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for FnEngine<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FnEngine")
+    }
 }
@@ -0,0 +1,149 @@
+//! Enumeration of every reachable position
+//!
+//! Starting from the empty board with X to move, alternating play reaches
+//! 5478 distinct positions (including the empty board and finished games),
+//! or 765 up to the board's eight symmetries.
+
+use crate::backend::board::Board;
+use crate::backend::game::{GameResult, Rules};
+use crate::backend::player::Player;
+use crate::backend::symmetry::Symmetry;
+
+/// Number of distinct encodings, `3^9`
+const CODES: usize = 19_683;
+
+/// Returns an iterator over every position reachable by legal play
+///
+/// Positions come in order of ply (pieces on the board), and within a ply in
+/// order of [`Board::encode`]. Narrow the set with the filter methods on
+/// [`Positions`].
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{enumerate_positions, Player};
+///
+/// assert_eq!(enumerate_positions().count(), 5478);
+/// assert_eq!(enumerate_positions().distinct().count(), 765);
+/// assert_eq!(enumerate_positions().ply(1).count(), 9);
+/// assert_eq!(enumerate_positions().ply(1).distinct().count(), 3);
+///
+/// // Positions where O is to move and the game is not over
+/// let mut o_to_move = enumerate_positions().to_move(Player::O).in_progress();
+/// assert!(o_to_move.all(|board| board.current_player() == Some(Player::O)));
+/// ```
+pub fn enumerate_positions() -> Positions {
+    Positions {
+        rules: Rules::Standard,
+        ply: None,
+        to_move: None,
+        in_progress: false,
+        distinct: false,
+        boards: None,
+    }
+}
+
+/// Iterator over reachable positions, returned by [`enumerate_positions`]
+///
+/// Filters must be set before the first call to `next`.
+#[derive(Debug, Clone)]
+pub struct Positions {
+    rules: Rules,
+    ply: Option<usize>,
+    to_move: Option<Player>,
+    in_progress: bool,
+    distinct: bool,
+    boards: Option<std::vec::IntoIter<Board>>,
+}
+
+impl Positions {
+    /// Sets the rules of the returned boards
+    ///
+    /// Misère play reaches the same positions, but with different results.
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Keeps only positions with exactly `ply` pieces on the board
+    pub fn ply(mut self, ply: usize) -> Self {
+        self.ply = Some(ply);
+        self
+    }
+
+    /// Keeps only positions where `player` would move next
+    ///
+    /// Finished positions are included unless [`in_progress`](Self::in_progress)
+    /// is also set.
+    pub fn to_move(mut self, player: Player) -> Self {
+        self.to_move = Some(player);
+        self
+    }
+
+    /// Keeps only positions where the game is not over
+    pub fn in_progress(mut self) -> Self {
+        self.in_progress = true;
+        self
+    }
+
+    /// Keeps only the canonical representative of each symmetry class
+    ///
+    /// See [`Board::canonical`].
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    fn collect_boards(&self) -> Vec<Board> {
+        let mut seen = vec![false; CODES];
+        let mut layer = vec![Board::with_rules(self.rules)];
+        let mut boards = Vec::new();
+        let mut player = Player::X;
+
+        for ply in 0..=9 {
+            let mut next = Vec::new();
+            for board in &layer {
+                if board.game_result() != GameResult::InProgress {
+                    continue;
+                }
+                for (row, col) in board.valid_moves() {
+                    let mut child = board.clone();
+                    child.make_move(row, col, player).expect("valid move");
+                    let code = child.encode() as usize;
+                    if !seen[code] {
+                        seen[code] = true;
+                        next.push(child);
+                    }
+                }
+            }
+
+            if self.ply.is_none_or(|wanted| wanted == ply) {
+                boards.extend(layer.iter().filter(|board| self.keeps(board, player)).cloned());
+            }
+            if self.ply == Some(ply) {
+                break;
+            }
+            next.sort_by_key(Board::encode);
+            layer = next;
+            player = player.opponent();
+        }
+        boards
+    }
+
+    fn keeps(&self, board: &Board, player: Player) -> bool {
+        (!self.in_progress || board.game_result() == GameResult::InProgress)
+            && self.to_move.is_none_or(|wanted| wanted == player)
+            && (!self.distinct || board.canonical().1 == Symmetry::Identity)
+    }
+}
+
+impl Iterator for Positions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        if self.boards.is_none() {
+            self.boards = Some(self.collect_boards().into_iter());
+        }
+        self.boards.as_mut()?.next()
+    }
+}
@@ -0,0 +1,79 @@
+//! Static positional evaluation
+//!
+//! [`evaluate`] scores a position without searching it, combining three
+//! classic TicTacToe features: lines a player can still complete, ownership
+//! of the center and corners, and squares that would create a fork. It is the
+//! horizon heuristic of a depth-limited [`MinimaxEngine`](crate::backend::MinimaxEngine)
+//! and a ready-made callback for [`EvalEngine`](crate::backend::EvalEngine).
+
+use crate::backend::board::{Board, WIN_LINES};
+use crate::backend::game::{GameResult, Rules};
+use crate::backend::player::{Cell, Player};
+use crate::backend::threats::fork_moves;
+
+/// Score of a won position; every unfinished position scores less
+pub const WIN_EVAL: i32 = 100;
+
+/// Weight of a line without opponent pieces, by the player's pieces in it
+const LINE_WEIGHTS: [i32; 3] = [0, 1, 4];
+/// Weight of owning the center
+const CENTER_WEIGHT: i32 = 2;
+/// Weight of owning a corner
+const CORNER_WEIGHT: i32 = 1;
+/// Weight of each empty square that would create a fork
+const FORK_WEIGHT: i32 = 3;
+
+/// Scores `board` for `player`; higher is better
+///
+/// Finished games score [`WIN_EVAL`], `-WIN_EVAL`, or `0`. Otherwise the
+/// score is the player's features minus the opponent's, and the two views are
+/// symmetric: `evaluate(board, p) == -evaluate(board, p.opponent())`. Under
+/// misère rules completing a line loses, so the positional score is negated.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{eval, Board, Player};
+///
+/// // The center is worth more than a corner, which beats an edge
+/// let center = Board::from_moves(&[(1, 1)]).unwrap();
+/// let corner = Board::from_moves(&[(0, 0)]).unwrap();
+/// let edge = Board::from_moves(&[(0, 1)]).unwrap();
+/// assert!(eval::evaluate(&center, Player::X) > eval::evaluate(&corner, Player::X));
+/// assert!(eval::evaluate(&corner, Player::X) > eval::evaluate(&edge, Player::X));
+/// assert_eq!(eval::evaluate(&center, Player::O), -eval::evaluate(&center, Player::X));
+/// ```
+pub fn evaluate(board: &Board, player: Player) -> i32 {
+    match board.game_result() {
+        GameResult::Win(winner) if winner == player => return WIN_EVAL,
+        GameResult::Win(_) => return -WIN_EVAL,
+        GameResult::Draw => return 0,
+        GameResult::InProgress => {}
+    }
+
+    let score = features(board, player) - features(board, player.opponent());
+    match board.rules() {
+        Rules::Standard => score,
+        Rules::Misere => -score,
+    }
+}
+
+/// Sums the weighted features of `player`'s position
+fn features(board: &Board, player: Player) -> i32 {
+    let own = Cell::Occupied(player);
+    let blocker = Cell::Occupied(player.opponent());
+
+    let lines: i32 = WIN_LINES
+        .iter()
+        .filter(|line| line.iter().all(|&pos| board[pos] != blocker))
+        .map(|line| LINE_WEIGHTS[line.iter().filter(|&&pos| board[pos] == own).count()])
+        .sum();
+
+    let center = if board[(1, 1)] == own { CENTER_WEIGHT } else { 0 };
+    let corners = [(0, 0), (0, 2), (2, 0), (2, 2)]
+        .iter()
+        .filter(|&&pos| board[pos] == own)
+        .count() as i32;
+
+    lines + center + corners * CORNER_WEIGHT + fork_moves(board, player).len() as i32 * FORK_WEIGHT
+}
@@ -0,0 +1,201 @@
+//! Configurable text rendering of boards
+
+use std::fmt;
+use crate::backend::board::Board;
+use crate::backend::player::{Player, Cell};
+
+const X_COLOR: &str = "\x1b[1;36m";
+const O_COLOR: &str = "\x1b[1;35m";
+const HIGHLIGHT: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// Returns the coordinate name of a cell, e.g. `"b2"` for the center
+pub(crate) fn square_name(row: usize, col: usize) -> String {
+    format!("{}{}", (b'a' + col as u8) as char, row + 1)
+}
+
+/// Parses a coordinate name such as `"b2"` into `(row, col)`
+pub(crate) fn parse_square(name: &str) -> Option<(usize, usize)> {
+    match name.as_bytes() {
+        &[col @ b'a'..=b'c', row @ b'1'..=b'3'] => Some(((row - b'1') as usize, (col - b'a') as usize)),
+        _ => None,
+    }
+}
+
+/// Formats a [`Board`] with optional grid lines, coordinates, spacing, and colors
+///
+/// The default options produce the same output as the board's `Display`
+/// implementation. Columns are labelled `a`-`c` and rows `1`-`3` from the top.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player};
+///
+/// let mut board = Board::new();
+/// board.make_move(1, 1, Player::X).unwrap();
+/// board.make_move(0, 2, Player::O).unwrap();
+///
+/// let text = board.formatter().grid(true).coordinates(true).to_string();
+/// assert_eq!(text, "  a b c\n1 .|.|O\n  -+-+-\n2 .|X|.\n  -+-+-\n3 .|.|.");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BoardFormatter<'a> {
+    board: &'a Board,
+    grid: bool,
+    coordinates: bool,
+    wide: bool,
+    unicode: bool,
+    colors: bool,
+    highlight: Option<(usize, usize)>,
+}
+
+impl<'a> BoardFormatter<'a> {
+    /// Creates a formatter with the plain `Display` style
+    pub fn new(board: &'a Board) -> Self {
+        BoardFormatter {
+            board,
+            grid: false,
+            coordinates: false,
+            wide: false,
+            unicode: false,
+            colors: false,
+            highlight: None,
+        }
+    }
+
+    /// Draws lines between rows and columns
+    pub fn grid(mut self, enabled: bool) -> Self {
+        self.grid = enabled;
+        self
+    }
+
+    /// Labels columns `a`-`c` and rows `1`-`3`
+    pub fn coordinates(mut self, enabled: bool) -> Self {
+        self.coordinates = enabled;
+        self
+    }
+
+    /// Pads every cell with a space on each side
+    pub fn wide(mut self, enabled: bool) -> Self {
+        self.wide = enabled;
+        self
+    }
+
+    /// Uses Unicode box drawing for grid lines and `·` for empty cells
+    pub fn unicode(mut self, enabled: bool) -> Self {
+        self.unicode = enabled;
+        self
+    }
+
+    /// Colors X and O with ANSI escape codes
+    pub fn colors(mut self, enabled: bool) -> Self {
+        self.colors = enabled;
+        self
+    }
+
+    /// Marks one cell, typically the last move played
+    ///
+    /// With colors the cell is drawn in reverse video; without colors it is
+    /// drawn in brackets, which pads every cell as with [`wide`](Self::wide)
+    /// so the columns stay aligned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Player};
+    ///
+    /// let mut board = Board::new();
+    /// board.make_move(1, 1, Player::X).unwrap();
+    /// board.make_move(0, 2, Player::O).unwrap();
+    ///
+    /// let text = board.formatter().highlight(Some((0, 2))).to_string();
+    /// assert_eq!(text, " .  . [O]\n .  X  . \n .  .  . ");
+    /// ```
+    pub fn highlight(mut self, cell: Option<(usize, usize)>) -> Self {
+        self.highlight = cell;
+        self
+    }
+
+    /// Returns true if cells are padded to three characters
+    fn is_wide(&self) -> bool {
+        self.wide || (self.highlight.is_some() && !self.colors)
+    }
+
+    /// Returns the width of one cell in characters
+    fn cell_width(&self) -> usize {
+        if self.is_wide() { 3 } else { 1 }
+    }
+
+    /// Returns the text between two cells in a row
+    fn separator(&self) -> &'static str {
+        match (self.grid, self.unicode) {
+            (true, true) => "│",
+            (true, false) => "|",
+            (false, _) if self.is_wide() => "",
+            (false, _) => " ",
+        }
+    }
+
+    fn write_cell(&self, f: &mut fmt::Formatter<'_>, (row, col): (usize, usize)) -> fmt::Result {
+        let cell = self.board.get(row, col).unwrap_or(Cell::Empty);
+        let highlighted = self.highlight == Some((row, col));
+        let (open, close) = match (self.is_wide(), highlighted && !self.colors) {
+            (true, true) => ("[", "]"),
+            (true, false) => (" ", " "),
+            (false, _) => ("", ""),
+        };
+        let symbol = match cell {
+            Cell::Empty if self.unicode => "·",
+            Cell::Empty => ".",
+            Cell::Occupied(Player::X) => "X",
+            Cell::Occupied(Player::O) => "O",
+        };
+        let color = match cell {
+            Cell::Occupied(Player::X) if self.colors => X_COLOR,
+            Cell::Occupied(Player::O) if self.colors => O_COLOR,
+            _ => "",
+        };
+        let reverse = if highlighted && self.colors { HIGHLIGHT } else { "" };
+        let reset = if color.is_empty() && reverse.is_empty() { "" } else { RESET };
+        write!(f, "{open}{reverse}{color}{symbol}{reset}{close}")
+    }
+
+    fn write_rule(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (line, cross) = if self.unicode { ("─", "┼") } else { ("-", "+") };
+        let segment = line.repeat(self.cell_width());
+        write!(f, "{segment}{cross}{segment}{cross}{segment}")
+    }
+}
+
+impl fmt::Display for BoardFormatter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let margin = if self.coordinates { "  " } else { "" };
+        if self.coordinates {
+            let gap = " ".repeat(self.separator().chars().count());
+            let pad = if self.is_wide() { " " } else { "" };
+            writeln!(f, "{margin}{pad}a{pad}{gap}{pad}b{pad}{gap}{pad}c")?;
+        }
+
+        for row in 0..3 {
+            if self.coordinates {
+                write!(f, "{} ", row + 1)?;
+            }
+            for col in 0..3 {
+                self.write_cell(f, (row, col))?;
+                if col < 2 {
+                    f.write_str(self.separator())?;
+                }
+            }
+            if row < 2 {
+                writeln!(f)?;
+                if self.grid {
+                    f.write_str(margin)?;
+                    self.write_rule(f)?;
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
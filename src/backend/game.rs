@@ -1,6 +1,13 @@
-//! Game result type
+//! Game result type, turn-managed game wrapper, and single-game play
 
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+use crate::backend::board::Board;
+use crate::backend::engine::StatefulEngine;
+use crate::backend::format::BoardFormatter;
 use crate::backend::player::Player;
+use crate::backend::record::GameRecord;
 
 /// Represents the result of a game
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,3 +16,302 @@ pub enum GameResult {
     Draw,
     InProgress,
 }
+
+impl GameResult {
+    /// Returns the winner, or `None` for a draw or a game in progress
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{GameResult, Player};
+    ///
+    /// let result = GameResult::Win(Player::O);
+    /// assert_eq!(result.winner(), Some(Player::O));
+    /// assert_eq!(result.loser(), Some(Player::X));
+    /// assert!(result.is_terminal() && !result.is_draw());
+    ///
+    /// assert_eq!(GameResult::Draw.winner(), None);
+    /// assert!(!GameResult::InProgress.is_terminal());
+    /// ```
+    #[inline]
+    pub fn winner(self) -> Option<Player> {
+        match self {
+            GameResult::Win(player) => Some(player),
+            GameResult::Draw | GameResult::InProgress => None,
+        }
+    }
+
+    /// Returns the loser, or `None` for a draw or a game in progress
+    #[inline]
+    pub fn loser(self) -> Option<Player> {
+        self.winner().map(|player| player.opponent())
+    }
+
+    /// Returns true if the game is over, by a win or a draw
+    #[inline]
+    pub fn is_terminal(self) -> bool {
+        self != GameResult::InProgress
+    }
+
+    /// Returns true if the game ended in a draw
+    #[inline]
+    pub fn is_draw(self) -> bool {
+        self == GameResult::Draw
+    }
+}
+
+/// Rule set a game is played under
+///
+/// Under [`Rules::Misere`] completing three in a row loses instead of wins,
+/// so the opponent of the player who completed the line is the winner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rules {
+    /// Completing a line wins
+    #[default]
+    Standard,
+    /// Completing a line loses
+    Misere,
+}
+
+impl Rules {
+    /// Returns the winner when `player` completes a line
+    #[inline]
+    pub fn winner(self, player: Player) -> Player {
+        match self {
+            Rules::Standard => player,
+            Rules::Misere => player.opponent(),
+        }
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rules::Standard => write!(f, "standard"),
+            Rules::Misere => write!(f, "misere"),
+        }
+    }
+}
+
+impl FromStr for Rules {
+    type Err = &'static str;
+
+    /// Parses `"standard"` or `"misere"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Rules::Standard),
+            "misere" => Ok(Rules::Misere),
+            _ => Err("Rules must be \"standard\" or \"misere\""),
+        }
+    }
+}
+
+/// A game result together with the line that decided it
+///
+/// Returned by [`Board::outcome`]. `line` is `Some` exactly when `result` is
+/// a win, so UIs and analyzers can highlight the winning triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub result: GameResult,
+    pub line: Option<[(usize, usize); 3]>,
+}
+
+/// A game of TicTacToe with turn order and move history
+///
+/// `Game` wraps a [`Board`] and enforces alternating turns, so callers only
+/// supply coordinates and can never play the same side twice in a row. Every
+/// move is recorded, which allows taking moves back with [`Game::undo`].
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Game, Player, GameResult};
+///
+/// let mut game = Game::new();
+/// game.play(1, 1).unwrap();
+/// assert_eq!(game.current_player(), Player::O);
+///
+/// game.undo();
+/// assert_eq!(game.current_player(), Player::X);
+/// assert_eq!(game.result(), GameResult::InProgress);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    board: Board,
+    starting_player: Player,
+    current_player: Player,
+    moves: Vec<(usize, usize)>,
+}
+
+impl Game {
+    /// Creates a new game where X moves first
+    pub fn new() -> Self {
+        Self::with_starting_player(Player::X)
+    }
+
+    /// Creates a new game where the given player moves first
+    pub fn with_starting_player(player: Player) -> Self {
+        Self::with_board(Board::new(), player)
+    }
+
+    /// Creates a new game under the given rules where X moves first
+    pub fn with_rules(rules: Rules) -> Self {
+        Self::with_board(Board::with_rules(rules), Player::X)
+    }
+
+    /// Creates a new game under the given rules where `player` moves first
+    pub fn with_rules_and_starting_player(rules: Rules, player: Player) -> Self {
+        Self::with_board(Board::with_rules(rules), player)
+    }
+
+    fn with_board(board: Board, player: Player) -> Self {
+        Game {
+            board,
+            starting_player: player,
+            current_player: player,
+            moves: Vec::with_capacity(9),
+        }
+    }
+
+    /// Plays a move for the player whose turn it is
+    pub fn play(&mut self, row: usize, col: usize) -> Result<(), &'static str> {
+        self.board.make_move(row, col, self.current_player)?;
+        self.moves.push((row, col));
+        self.current_player = self.current_player.opponent();
+        Ok(())
+    }
+
+    /// Takes back the last move, returning its position
+    ///
+    /// Returns `None` if no moves have been played.
+    pub fn undo(&mut self) -> Option<(usize, usize)> {
+        let (row, col) = self.moves.pop()?;
+        self.board.clear_cell(row, col);
+        self.current_player = self.current_player.opponent();
+        Some((row, col))
+    }
+
+    /// Returns the current game result
+    pub fn result(&self) -> GameResult {
+        self.board.game_result()
+    }
+
+    /// Returns the current game result together with the winning line
+    pub fn outcome(&self) -> Outcome {
+        self.board.outcome()
+    }
+
+    /// Returns the player whose turn it is
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Returns the player who moved first
+    pub fn starting_player(&self) -> Player {
+        self.starting_player
+    }
+
+    /// Returns the moves played so far, in order
+    pub fn moves(&self) -> &[(usize, usize)] {
+        &self.moves
+    }
+
+    /// Returns the most recent move, or `None` if no moves have been played
+    pub fn last_move(&self) -> Option<(usize, usize)> {
+        self.moves.last().copied()
+    }
+
+    /// Returns the rules this game is played under
+    pub fn rules(&self) -> Rules {
+        self.board.rules()
+    }
+
+    /// Returns the underlying board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns a formatter for the board with the last move highlighted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Game;
+    ///
+    /// let mut game = Game::new();
+    /// game.play(1, 1).unwrap();
+    /// game.play(0, 0).unwrap();
+    /// assert_eq!(game.last_move(), Some((0, 0)));
+    /// assert_eq!(game.formatter().to_string(), "[O] .  . \n .  X  . \n .  .  . ");
+    /// ```
+    pub fn formatter(&self) -> BoardFormatter<'_> {
+        self.board.formatter().highlight(self.last_move())
+    }
+
+    /// Resets the game to an empty board with the same starting player
+    pub fn reset(&mut self) {
+        self.board.reset();
+        self.moves.clear();
+        self.current_player = self.starting_player;
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plays one standard game between two engines and returns its record
+///
+/// `engine_x` and `engine_o` choose moves for their respective players,
+/// starting with `starting_player`. Each engine call is timed into the
+/// record's `move_durations`. The game stops early if an engine has no
+/// move, in which case the record's result is `InProgress`.
+///
+/// # Panics
+///
+/// Panics if an engine returns an invalid move.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{play_game, FastEngine, GameResult, MinimaxEngine, Player};
+///
+/// let record = play_game(&mut FastEngine, &mut MinimaxEngine::new(), Player::X);
+/// assert_eq!(record.moves[0], (0, 0));
+/// assert_eq!(record.result, GameResult::Win(Player::O));
+/// assert_eq!(record.to_game().unwrap().result(), record.result);
+/// ```
+pub fn play_game<X, O>(engine_x: &mut X, engine_o: &mut O, starting_player: Player) -> GameRecord
+where
+    X: StatefulEngine + ?Sized,
+    O: StatefulEngine + ?Sized,
+{
+    play_game_with_rules(engine_x, engine_o, starting_player, Rules::Standard)
+}
+
+/// Plays one game under the given rules, like [`play_game`]
+pub fn play_game_with_rules<X, O>(engine_x: &mut X, engine_o: &mut O, starting_player: Player, rules: Rules) -> GameRecord
+where
+    X: StatefulEngine + ?Sized,
+    O: StatefulEngine + ?Sized,
+{
+    let mut game = Game::with_rules_and_starting_player(rules, starting_player);
+    let mut move_durations = Vec::with_capacity(9);
+    while game.result() == GameResult::InProgress {
+        let player = game.current_player();
+        let started = Instant::now();
+        let chosen = match player {
+            Player::X => engine_x.choose_move_mut(game.board(), player),
+            Player::O => engine_o.choose_move_mut(game.board(), player),
+        };
+        let elapsed = started.elapsed();
+        let Some((row, col)) = chosen else { break };
+        game.play(row, col).expect("engine returned an invalid move");
+        move_durations.push(elapsed);
+    }
+    let mut record = GameRecord::from_game(&game);
+    record.move_durations = move_durations;
+    record
+}
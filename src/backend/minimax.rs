@@ -0,0 +1,412 @@
+//! Minimax search engine with alpha-beta pruning
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::backend::board::Board;
+use crate::backend::engine::{Engine, EngineMetadata};
+use crate::backend::eval;
+use crate::backend::game::GameResult;
+use crate::backend::player::Player;
+use crate::backend::solver::{GameValue, Solution};
+
+/// Score of winning immediately; each further ply to the win costs one point
+const WIN_SCORE: i32 = 10;
+/// Bound larger than any reachable score
+const INFINITY: i32 = 1000;
+/// Number of nodes between clock checks when a time limit is set
+const CLOCK_INTERVAL: u64 = 256;
+/// Positions with at least this many empty cells skip symmetric moves
+///
+/// Later positions are rarely symmetric, so the check would cost more than
+/// it saves.
+const SYMMETRY_MIN_EMPTY: usize = 5;
+
+/// A minimax engine with alpha-beta pruning
+///
+/// The search uses iterative deepening: it searches one ply deep, then two,
+/// and so on, trying the best move found by the previous iteration first at
+/// every position. Moves that are symmetric images of one another (such as
+/// the four corners of the empty board) are searched only once. Without
+/// limits the engine searches to the end of the game
+/// and plays perfectly. With [`MinimaxEngine::with_depth`] the search stops
+/// after the given number of plies and scores unresolved positions with
+/// [`eval::evaluate`], producing a family of weaker but faster opponents:
+/// depth 1 only grabs the best-placed square, while deeper
+/// searches see more tactics and depth 9 plays perfectly.
+///
+/// [`node_limit`](MinimaxEngine::node_limit) and
+/// [`time_limit`](MinimaxEngine::time_limit) bound the work per move. When a
+/// budget runs out, the answer of the deepest completed iteration is used.
+///
+/// Scores are from the point of view of the player to move: `10 - n` for a
+/// win in `n` plies, `n - 10` for a loss in `n` plies, and `0` for a draw, so
+/// the engine takes the fastest win and delays a loss as long as possible.
+/// [`SearchResult::eval`] turns an exact score into an [`Eval`]. Positions
+/// left unresolved at the depth limit score their static evaluation, clamped
+/// so that it never outranks a proven result.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Engine, MinimaxEngine};
+///
+/// let mut board = Board::new();
+/// board.make_move(0, 0, Player::X).unwrap();
+/// board.make_move(1, 1, Player::O).unwrap();
+/// board.make_move(0, 1, Player::X).unwrap();
+///
+/// // O must block the top row
+/// let engine = MinimaxEngine::new();
+/// assert_eq!(engine.choose_move(&board, Player::O), Some((0, 2)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MinimaxEngine {
+    max_depth: Option<usize>,
+    max_nodes: Option<u64>,
+    max_time: Option<Duration>,
+}
+
+/// Exact value of a position for the player to move
+///
+/// Distances count plies, including the player's own moves: `WinIn(1)` wins
+/// with the next move, and `LossIn(2)` loses to the opponent's reply.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Eval, MinimaxEngine, Player};
+///
+/// // X on a1 and b1, O on a2 and b2: X wins at once
+/// let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1)]).unwrap();
+/// let result = MinimaxEngine::new().search(&board, Player::X);
+/// assert_eq!(result.eval(), Some(Eval::WinIn(1)));
+/// assert_eq!(result.eval().unwrap().to_string(), "win in 1");
+///
+/// assert_eq!(MinimaxEngine::new().search(&Board::new(), Player::X).eval(), Some(Eval::Draw));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Eval {
+    /// The player to move wins in this many plies
+    WinIn(u8),
+    /// The player to move loses in this many plies
+    LossIn(u8),
+    /// Perfect play ends in a draw
+    Draw,
+}
+
+impl Eval {
+    /// Converts an exact minimax score, `10 - n` for a win or `n - 10` for a loss
+    pub fn from_score(score: i32) -> Eval {
+        match score {
+            0 => Eval::Draw,
+            s if s > 0 => Eval::WinIn((WIN_SCORE - s) as u8),
+            s => Eval::LossIn((WIN_SCORE + s) as u8),
+        }
+    }
+
+    /// Returns the minimax score of this value
+    pub fn score(self) -> i32 {
+        match self {
+            Eval::WinIn(n) => WIN_SCORE - n as i32,
+            Eval::LossIn(n) => n as i32 - WIN_SCORE,
+            Eval::Draw => 0,
+        }
+    }
+}
+
+impl From<Solution> for Eval {
+    fn from(solution: Solution) -> Eval {
+        match solution.value {
+            GameValue::Win => Eval::WinIn(solution.distance),
+            GameValue::Loss => Eval::LossIn(solution.distance),
+            GameValue::Draw => Eval::Draw,
+        }
+    }
+}
+
+impl fmt::Display for Eval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eval::WinIn(n) => write!(f, "win in {}", n),
+            Eval::LossIn(n) => write!(f, "loss in {}", n),
+            Eval::Draw => write!(f, "draw"),
+        }
+    }
+}
+
+/// Outcome of a [`MinimaxEngine::search`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Best move found, or `None` if the game is over
+    pub best_move: Option<(usize, usize)>,
+    /// Score of the best move for the player to move
+    pub score: i32,
+    /// Depth of the deepest completed iteration
+    pub depth: usize,
+    /// Number of positions visited, including any unfinished iteration
+    pub nodes: u64,
+    /// True if the score is exact rather than limited by depth or budget
+    pub complete: bool,
+}
+
+impl MinimaxEngine {
+    /// Creates an engine that searches to the end of the game
+    pub fn new() -> Self {
+        MinimaxEngine {
+            max_depth: None,
+            max_nodes: None,
+            max_time: None,
+        }
+    }
+
+    /// Creates an engine that searches at most `depth` plies ahead
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Board, Engine, MinimaxEngine, Player};
+    ///
+    /// // One ply deep, the center scores best
+    /// let engine = MinimaxEngine::with_depth(1);
+    /// assert_eq!(engine.choose_move(&Board::new(), Player::X), Some((1, 1)));
+    /// assert_eq!(engine.search(&Board::new(), Player::X).score, 6);
+    /// ```
+    pub fn with_depth(depth: usize) -> Self {
+        MinimaxEngine {
+            max_depth: Some(depth),
+            ..Self::new()
+        }
+    }
+
+    /// Limits each search to roughly `nodes` visited positions
+    pub fn node_limit(mut self, nodes: u64) -> Self {
+        self.max_nodes = Some(nodes);
+        self
+    }
+
+    /// Limits each search to roughly `limit` of wall-clock time
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.max_time = Some(limit);
+        self
+    }
+
+    /// Returns the depth limit, if any
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the node limit, if any
+    pub fn max_nodes(&self) -> Option<u64> {
+        self.max_nodes
+    }
+
+    /// Returns the time limit, if any
+    pub fn max_time(&self) -> Option<Duration> {
+        self.max_time
+    }
+
+    /// Searches `board` for `player` by iterative deepening
+    ///
+    /// The first iteration always runs to completion so that a move is found
+    /// even with a tiny budget.
+    pub fn search(&self, board: &Board, player: Player) -> SearchResult {
+        let mut search = Search::new(self);
+        let empties = board.valid_moves().len();
+        let mut order = candidate_moves(board);
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            complete: true,
+        };
+        if order.is_empty() {
+            return result;
+        }
+
+        let limit = self.max_depth.unwrap_or(empties).clamp(1, empties);
+        for depth in 1..=limit {
+            search.enforce_budget = depth > 1;
+            let Some((best, score)) = search.root(board, player, depth, &order) else {
+                break;
+            };
+
+            result.best_move = Some(best);
+            result.score = score;
+            result.depth = depth;
+            result.complete = depth == empties || is_proven(score, depth);
+            if result.complete {
+                break;
+            }
+
+            let index = order.iter().position(|&mv| mv == best).unwrap();
+            order[..=index].rotate_right(1);
+        }
+        result.nodes = search.nodes;
+        result
+    }
+
+    /// Scores the move (row, col) for `player` with a full window
+    fn score_move(&self, board: &Board, player: Player, (row, col): (usize, usize)) -> i32 {
+        let mut child = board.clone();
+        child.make_move(row, col, player).unwrap();
+        let depth = self.max_depth.unwrap_or(usize::MAX).max(1);
+        -Search::new(self).negamax(&child, player.opponent(), depth - 1, 1, -INFINITY, INFINITY)
+    }
+}
+
+impl SearchResult {
+    /// Returns the exact value of the position, or `None` if the search
+    /// stopped at a depth limit or budget without proving it
+    ///
+    /// Also returns `None` for a finished game, which has no move to score.
+    pub fn eval(&self) -> Option<Eval> {
+        (self.complete && self.best_move.is_some()).then(|| Eval::from_score(self.score))
+    }
+}
+
+impl Engine for MinimaxEngine {
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.search(board, player).best_move
+    }
+
+    /// Returns exact scores at the engine's depth limit; node and time limits are ignored
+    fn evaluate_moves(&self, board: &Board, player: Player) -> Vec<((usize, usize), f64)> {
+        let mut scores: Vec<((usize, usize), f64)> = board
+            .valid_moves()
+            .into_iter()
+            .map(|mv| (mv, self.score_move(board, player, mv) as f64))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("minimax"),
+            description: Some("Depth-limited alpha-beta search"),
+            ..EngineMetadata::default()
+        }
+    }
+}
+
+/// Returns the moves worth searching, skipping symmetric duplicates early on
+fn candidate_moves(board: &Board) -> Vec<(usize, usize)> {
+    let moves = board.valid_moves();
+    if moves.len() >= SYMMETRY_MIN_EMPTY {
+        board.distinct_moves()
+    } else {
+        moves
+    }
+}
+
+/// Scores an unresolved position `ply` plies from the root for `to_move`
+///
+/// Any win or loss found within `ply` plies scores at least `WIN_SCORE - ply`
+/// in magnitude, so the evaluation is clamped just below that.
+fn heuristic(board: &Board, to_move: Player, ply: usize) -> i32 {
+    let bound = (WIN_SCORE - 1 - ply as i32).max(0);
+    eval::evaluate(board, to_move).clamp(-bound, bound)
+}
+
+/// Returns true if `score` from a search `depth` plies deep is a proven win or loss
+fn is_proven(score: i32, depth: usize) -> bool {
+    score.abs() >= WIN_SCORE - depth as i32
+}
+
+/// State of one search: counters, budget, and best moves from earlier iterations
+struct Search {
+    max_nodes: Option<u64>,
+    deadline: Option<Instant>,
+    enforce_budget: bool,
+    nodes: u64,
+    aborted: bool,
+    best_moves: HashMap<u16, (usize, usize)>,
+}
+
+impl Search {
+    fn new(engine: &MinimaxEngine) -> Self {
+        Search {
+            max_nodes: engine.max_nodes,
+            deadline: engine.max_time.map(|limit| Instant::now() + limit),
+            enforce_budget: false,
+            nodes: 0,
+            aborted: false,
+            best_moves: HashMap::new(),
+        }
+    }
+
+    /// Returns true once the node or time budget is spent
+    fn out_of_budget(&self) -> bool {
+        if !self.enforce_budget {
+            return false;
+        }
+        self.max_nodes.is_some_and(|max| self.nodes > max)
+            || (self.nodes.is_multiple_of(CLOCK_INTERVAL) && self.deadline.is_some_and(|d| Instant::now() >= d))
+    }
+
+    /// Searches the root to `depth` plies, or returns `None` if the budget ran out
+    fn root(&mut self, board: &Board, player: Player, depth: usize, order: &[(usize, usize)]) -> Option<((usize, usize), i32)> {
+        let mut best = None;
+        let mut alpha = -INFINITY;
+        for &(row, col) in order {
+            let mut child = board.clone();
+            child.make_move(row, col, player).unwrap();
+            let score = -self.negamax(&child, player.opponent(), depth - 1, 1, -INFINITY, -alpha);
+            if self.aborted {
+                return None;
+            }
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some((row, col));
+            }
+        }
+        best.map(|mv| (mv, alpha))
+    }
+
+    /// Scores `board`, reached `ply` plies from the root, for `to_move` using
+    /// negamax with alpha-beta pruning
+    fn negamax(&mut self, board: &Board, to_move: Player, depth: usize, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.nodes += 1;
+        if self.aborted || self.out_of_budget() {
+            self.aborted = true;
+            return 0;
+        }
+
+        match board.game_result() {
+            GameResult::Win(player) if player == to_move => return WIN_SCORE - ply as i32,
+            GameResult::Win(_) => return ply as i32 - WIN_SCORE,
+            GameResult::Draw => return 0,
+            GameResult::InProgress => {}
+        }
+
+        if depth == 0 {
+            return heuristic(board, to_move, ply);
+        }
+
+        let code = board.encode();
+        let mut moves = candidate_moves(board);
+        if let Some(index) = self.best_moves.get(&code).and_then(|mv| moves.iter().position(|m| m == mv)) {
+            moves[..=index].rotate_right(1);
+        }
+
+        let mut best = -INFINITY;
+        let mut best_move = moves[0];
+        for (row, col) in moves {
+            let mut child = board.clone();
+            child.make_move(row, col, to_move).unwrap();
+            let score = -self.negamax(&child, to_move.opponent(), depth - 1, ply + 1, -beta, -alpha);
+            if score > best {
+                best = score;
+                best_move = (row, col);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        self.best_moves.insert(code, best_move);
+        best
+    }
+}
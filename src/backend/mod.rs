@@ -4,21 +4,99 @@
 //!
 //! This module contains the fundamental building blocks:
 //! - **Board**: Game state representation and game logic
+//! - **BoardFormatter**: Text rendering with grid lines, coordinates, and colors
+//! - **BoardN**: NxN board with configurable k-in-a-row wins
+//! - **Board3D**: 3x3x3 bitboard variant with 49 win lines
+//! - **GameBoard & VariantEngine**: Board-agnostic traits shared by all variants
+//! - **StrictBoard**: Board wrapper enforcing turn order for untrusted input
+//! - **eval**: Static positional evaluation for depth-limited search
 //! - **Player & Cell**: Basic game types
 //! - **GameResult**: Game outcome representation
+//! - **Rules**: Standard or misère (completing a line loses) play
+//! - **Game**: Turn-managed game with move history
+//! - **GameRecord**: Completed game with a PGN-style text notation
+//! - **Replay**: Frame-by-frame replay of a move list with annotations
 //! - **Engine**: Trait for move selection strategies
+//! - **StatefulEngine**: Move selection with mutable engine state
 //! - **FastEngine**: High-speed move selection implementation
+//! - **RandomEngine**: Seedable uniformly random move selection
+//! - **MinimaxEngine**: Alpha-beta search with optional depth limit
+//! - **TranspositionTable & CachedEngine**: Moves of deterministic engines shared across games
+//! - **FnEngine**: Closure adapter for quick custom engines
+//! - **Combinators**: Engine wrappers such as `NoisyEngine`, `ChainEngine`, `MixEngine`
+//! - **OpeningBook & BookEngine**: Symmetry-aware opening books with binary load/save
+//! - **SolvedDatabase**: Exhaustive perfect-play values for every reachable position
+//! - **PerfectLookupEngine**: Optimal play by table lookup
+//! - **QLearningEngine**: Tabular Q-learning through self-play
+//! - **EvalEngine**: Shallow search with a pluggable evaluation function
+//! - **AsyncEngine**: Engines computing moves asynchronously (`tokio` feature)
+//! - **RemoteEngine**: Engines hosted over TCP (`remote` feature)
+//! - **ProcessEngine**: External engine binaries over a line protocol
+//! - **Symmetry**: Rotations and reflections of the board
+//! - **EngineRegistry**: Construct engines at runtime from specs like `"random:seed=42"`
+//! - **Rng**: Small seedable random number generator
 //!
 //! The backend is optimized for maximum performance and minimal memory overhead,
 //! making it ideal for high-throughput game simulations.
 
 pub mod board;
+pub mod enumerate;
+pub mod threats;
+pub mod eval;
+pub mod pos;
+pub mod format;
+pub mod replay;
+pub mod record;
 pub mod player;
 pub mod game;
 pub mod engine;
+pub mod strict;
+pub mod variant;
+pub mod board_n;
+pub mod board3d;
+pub mod rng;
+pub mod combinators;
+pub mod minimax;
+pub mod transposition;
+pub mod registry;
+pub mod symmetry;
+pub mod book;
+pub mod solver;
+pub mod qlearning;
+pub mod eval_engine;
+pub mod process;
+#[cfg(feature = "tokio")]
+pub mod async_engine;
+#[cfg(feature = "remote")]
+pub mod remote;
 
 // Public API
-pub use board::Board;
+pub use board::{Board, Cells, IllegalMove, WIN_LINES};
+pub use pos::{Move, Pos};
+pub use enumerate::{enumerate_positions, Positions};
+pub use threats::{TacticalStatus, Threats};
+pub use format::BoardFormatter;
+pub use replay::{Replay, Frame, Frames};
+pub use record::GameRecord;
 pub use player::{Player, Cell};
-pub use game::GameResult;
-pub use engine::{Engine, FastEngine};
+pub use game::{GameResult, Game, Outcome, Rules, play_game, play_game_with_rules};
+pub use engine::{Engine, EngineMetadata, StatefulEngine, FastEngine, RandomEngine, FnEngine};
+pub use strict::StrictBoard;
+pub use variant::{GameBoard, VariantEngine, play_out};
+pub use board_n::BoardN;
+pub use board3d::{Board3D, WIN_LINES_3D};
+pub use rng::Rng;
+pub use combinators::{NoisyEngine, ChainEngine, MixEngine};
+pub use minimax::{Eval, MinimaxEngine, SearchResult};
+pub use transposition::{TranspositionTable, TableStats, CachedEngine};
+pub use registry::{EngineRegistry, EngineParams, EngineFactory, BoxedEngine};
+pub use symmetry::Symmetry;
+pub use book::{OpeningBook, BookMove, BookEngine};
+pub use solver::{SolvedDatabase, Solution, GameValue, PerfectLookupEngine};
+pub use qlearning::{QLearningEngine, QLearningConfig};
+pub use eval_engine::EvalEngine;
+pub use process::ProcessEngine;
+#[cfg(feature = "tokio")]
+pub use async_engine::{AsyncEngine, BlockingEngine};
+#[cfg(feature = "remote")]
+pub use remote::{RemoteEngine, serve_engine, serve_connection};
@@ -0,0 +1,131 @@
+//! Board positions and moves as dedicated types
+//!
+//! A [`Pos`] can only hold one of the nine cells, so the row and column cannot
+//! be swapped or out of range once constructed. A [`Move`] pairs a position
+//! with the player making it. Both convert to and from the `(row, col)`
+//! tuples used throughout the rest of the API.
+//!
+//! The tuple signatures of [`Board::make_move`](crate::backend::Board::make_move),
+//! [`Engine::choose_move`](crate::backend::Engine::choose_move) and
+//! [`Engine::evaluate_moves`](crate::backend::Engine::evaluate_moves) remain
+//! the primary API. [`Board::apply_move`](crate::backend::Board::apply_move),
+//! [`Engine::choose`](crate::backend::Engine::choose) and
+//! [`StatefulEngine::choose_mut`](crate::backend::StatefulEngine::choose_mut)
+//! are typed wrappers over them.
+
+use std::fmt;
+use std::str::FromStr;
+use crate::backend::format::{parse_square, square_name};
+use crate::backend::player::Player;
+
+/// One of the nine cells of a [`Board`](crate::backend::Board)
+///
+/// Cells are numbered 0 to 8 in row-major order. `Display` and `FromStr` use
+/// coordinate names: columns `a`-`c`, rows `1`-`3` from the top.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::Pos;
+///
+/// let center = Pos::new(1, 1).unwrap();
+/// assert_eq!(center.index(), 4);
+/// assert_eq!(center.to_string(), "b2");
+/// assert_eq!("b2".parse::<Pos>(), Ok(center));
+/// assert_eq!(<(usize, usize)>::from(center), (1, 1));
+/// assert!(Pos::new(3, 0).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos(u8);
+
+/// A player placing a piece at a position
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Move, Player, Pos};
+///
+/// let mut board = Board::new();
+/// let mv = Move::new(Pos::new(0, 2).unwrap(), Player::X);
+/// board.apply_move(mv).unwrap();
+/// assert_eq!(mv.to_string(), "X c1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub pos: Pos,
+    pub player: Player,
+}
+
+impl Pos {
+    /// Every position in row-major order
+    pub const ALL: [Pos; 9] = [Pos(0), Pos(1), Pos(2), Pos(3), Pos(4), Pos(5), Pos(6), Pos(7), Pos(8)];
+
+    /// Returns the position at `(row, col)`, or `None` if either is 3 or more
+    pub fn new(row: usize, col: usize) -> Option<Pos> {
+        (row < 3 && col < 3).then(|| Pos((row * 3 + col) as u8))
+    }
+
+    /// Returns the position with row-major index `index`, or `None` if it is 9 or more
+    pub fn from_index(index: usize) -> Option<Pos> {
+        (index < 9).then_some(Pos(index as u8))
+    }
+
+    /// Returns the row, from 0 at the top
+    pub fn row(self) -> usize {
+        self.0 as usize / 3
+    }
+
+    /// Returns the column, from 0 at the left
+    pub fn col(self) -> usize {
+        self.0 as usize % 3
+    }
+
+    /// Returns the row-major index in `0..9`
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row(), pos.col())
+    }
+}
+
+impl TryFrom<(usize, usize)> for Pos {
+    type Error = &'static str;
+
+    fn try_from((row, col): (usize, usize)) -> Result<Self, Self::Error> {
+        Pos::new(row, col).ok_or("Position out of bounds")
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&square_name(self.row(), self.col()))
+    }
+}
+
+impl FromStr for Pos {
+    type Err = &'static str;
+
+    /// Parses a coordinate name such as `"b2"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (row, col) = parse_square(s).ok_or("Invalid square name")?;
+        Ok(Pos((row * 3 + col) as u8))
+    }
+}
+
+impl Move {
+    /// Creates a move of `player` at `pos`
+    pub fn new(pos: Pos, player: Player) -> Self {
+        Move { pos, player }
+    }
+}
+
+impl fmt::Display for Move {
+    /// Writes the player and the square, e.g. `X b2`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.player, self.pos)
+    }
+}
@@ -0,0 +1,205 @@
+//! External engine processes speaking a line protocol
+//!
+//! The harness talks to the engine over its stdin and stdout, one command per
+//! line, in the spirit of chess's UCI:
+//!
+//! | Harness sends | Engine replies |
+//! |---------------|----------------|
+//! | `zttt` | any `id name <name>` lines, then `ztttok` |
+//! | `position <cells> <rules>` | nothing |
+//! | `go <player>` | `bestmove <row> <col>` or `bestmove none` |
+//! | `quit` | exits |
+//!
+//! `<cells>` lists the nine cells in row-major order as `X`, `O`, or `.`,
+//! `<rules>` is `standard` or `misere`, and `<player>` is `X` or `O`. Lines the
+//! harness does not expect, such as debug output, are ignored.
+
+use std::ffi::OsStr;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::backend::board::Board;
+use crate::backend::engine::StatefulEngine;
+use crate::backend::player::Player;
+
+/// How long the engine may take to send an expected line, by default
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a process told to quit has to exit before it is killed
+const QUIT_GRACE: Duration = Duration::from_millis(100);
+
+/// An engine running as a separate process
+///
+/// The process is started and handshaked on creation and told to quit when
+/// the engine is dropped; it is killed if it has not exited shortly after.
+/// An engine that takes longer than the read timeout to reply is killed too,
+/// since a late reply would be taken as the answer to the next request.
+///
+/// # Example
+///
+/// ```no_run
+/// use zttt_rs::backend::{Board, Player, ProcessEngine};
+///
+/// let mut engine = ProcessEngine::spawn("./my-bot").unwrap();
+/// println!("Playing against {}", engine.name().unwrap_or("unknown"));
+/// let chosen = engine.request_move(&Board::new(), Player::X).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ProcessEngine {
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines of the engine's output, read on a separate thread so waits can
+    /// time out
+    lines: Receiver<io::Result<String>>,
+    read_timeout: Option<Duration>,
+    name: Option<String>,
+}
+
+impl ProcessEngine {
+    /// Starts `program` with no arguments
+    pub fn spawn<S: AsRef<OsStr>>(program: S) -> io::Result<Self> {
+        Self::from_command(Command::new(program))
+    }
+
+    /// Starts a configured command, replacing its stdin and stdout with pipes
+    ///
+    /// The handshake waits up to ten seconds for the engine.
+    pub fn from_command(mut command: Command) -> io::Result<Self> {
+        let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut engine = ProcessEngine {
+            child,
+            stdin,
+            lines,
+            read_timeout: Some(DEFAULT_READ_TIMEOUT),
+            name: None,
+        };
+        engine.handshake()?;
+        Ok(engine)
+    }
+
+    /// Returns the name the engine reported during the handshake
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets how long to wait for each line of a reply, or `None` to wait
+    /// forever
+    ///
+    /// Defaults to ten seconds. A request that times out fails
+    /// with [`io::ErrorKind::TimedOut`] and kills the process.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    /// Returns the read timeout
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Asks the engine for a move, reporting I/O and protocol errors
+    pub fn request_move(&mut self, board: &Board, player: Player) -> io::Result<Option<(usize, usize)>> {
+        writeln!(self.stdin, "position {} {}", board.cell_string(), board.rules())?;
+        writeln!(self.stdin, "go {}", player)?;
+        self.stdin.flush()?;
+
+        loop {
+            let line = self.read_line()?;
+            let mut words = line.split_whitespace();
+            if words.next() != Some("bestmove") {
+                continue;
+            }
+            return match (words.next(), words.next()) {
+                (Some("none"), None) => Ok(None),
+                (Some(row), Some(col)) => {
+                    let coord = |s: &str| s.parse::<usize>().ok().filter(|&n| n < 3);
+                    match (coord(row), coord(col)) {
+                        (Some(row), Some(col)) => Ok(Some((row, col))),
+                        _ => Err(invalid("Move coordinates must be 0, 1, or 2")),
+                    }
+                }
+                _ => Err(invalid("Malformed bestmove")),
+            };
+        }
+    }
+
+    /// Sends `zttt` and waits for `ztttok`, recording the engine's name
+    fn handshake(&mut self) -> io::Result<()> {
+        writeln!(self.stdin, "zttt")?;
+        self.stdin.flush()?;
+        loop {
+            let line = self.read_line()?;
+            if line == "ztttok" {
+                return Ok(());
+            }
+            if let Some(name) = line.strip_prefix("id name ") {
+                self.name = Some(name.trim().to_string());
+            }
+        }
+    }
+
+    /// Reads one line without its terminator, failing if the engine exited
+    /// or did not send a line within the read timeout
+    fn read_line(&mut self) -> io::Result<String> {
+        let closed = || io::Error::new(io::ErrorKind::UnexpectedEof, "Engine process closed its output");
+        let line = match self.read_timeout {
+            Some(timeout) => match self.lines.recv_timeout(timeout) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = self.child.kill();
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "Engine process did not reply in time"));
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err(closed()),
+            },
+            None => self.lines.recv().map_err(|_| closed())?,
+        };
+        Ok(line?.trim_end().to_string())
+    }
+}
+
+impl StatefulEngine for ProcessEngine {
+    /// Returns `None` on I/O or protocol errors; use
+    /// [`request_move`](ProcessEngine::request_move) to see them
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.request_move(board, player).ok().flatten()
+    }
+
+    fn name(&self) -> &str {
+        ProcessEngine::name(self).unwrap_or("process")
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("External engine process speaking the text protocol")
+    }
+}
+
+impl Drop for ProcessEngine {
+    fn drop(&mut self) {
+        let _ = writeln!(self.stdin, "quit");
+        let _ = self.stdin.flush();
+        let deadline = Instant::now() + QUIT_GRACE;
+        while let Ok(None) = self.child.try_wait() {
+            if Instant::now() >= deadline {
+                let _ = self.child.kill();
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        let _ = self.child.wait();
+    }
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
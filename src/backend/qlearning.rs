@@ -0,0 +1,297 @@
+//! Tabular Q-learning engine trained by self-play
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use crate::backend::board::Board;
+use crate::backend::engine::{Engine, EngineMetadata};
+use crate::backend::game::{GameResult, Rules};
+use crate::backend::player::Player;
+use crate::backend::rng::Rng;
+
+/// File signature of the binary Q-table format
+const MAGIC: &[u8; 4] = b"ZTTQ";
+/// Current version of the binary Q-table format
+const VERSION: u8 = 1;
+
+/// Hyperparameters for [`QLearningEngine`]
+///
+/// Exploration follows a linear schedule: epsilon starts at `epsilon_start`
+/// and decreases to `epsilon_end` over `epsilon_decay_episodes` episodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QLearningConfig {
+    /// Step size of each update, in `(0, 1]`
+    pub learning_rate: f64,
+    /// Weight of future rewards, in `[0, 1]`
+    pub discount: f64,
+    /// Probability of a random move at the start of training
+    pub epsilon_start: f64,
+    /// Probability of a random move once the schedule has finished
+    pub epsilon_end: f64,
+    /// Number of episodes over which epsilon decays
+    pub epsilon_decay_episodes: u64,
+    /// Rules the engine trains under
+    pub rules: Rules,
+}
+
+impl Default for QLearningConfig {
+    fn default() -> Self {
+        QLearningConfig {
+            learning_rate: 0.5,
+            discount: 0.9,
+            epsilon_start: 1.0,
+            epsilon_end: 0.05,
+            epsilon_decay_episodes: 10_000,
+            rules: Rules::Standard,
+        }
+    }
+}
+
+impl QLearningConfig {
+    /// Checks that every parameter is in range
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !(self.learning_rate > 0.0 && self.learning_rate <= 1.0) {
+            return Err("Learning rate must be in (0, 1]");
+        }
+        if !(0.0..=1.0).contains(&self.discount) {
+            return Err("Discount must be in [0, 1]");
+        }
+        if !(0.0..=1.0).contains(&self.epsilon_start) || !(0.0..=1.0).contains(&self.epsilon_end) {
+            return Err("Epsilon must be in [0, 1]");
+        }
+        Ok(())
+    }
+}
+
+/// An engine that learns state-action values through self-play
+///
+/// Both sides of each training game share one table keyed by the position and
+/// the player to move. Values are from the mover's point of view: `1` for a
+/// win, `-1` for a loss, and `0` for a draw. When used as an [`Engine`] the
+/// learned table is played greedily, without exploration.
+///
+/// # Binary Format
+///
+/// All integers and floats are little-endian:
+///
+/// ```text
+/// "ZTTQ" | version: u8 | entries: u32
+/// per entry: state: u32 | values: 9 x f64 (row-major)
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Engine, QLearningConfig, QLearningEngine};
+///
+/// let mut engine = QLearningEngine::seeded(QLearningConfig::default(), 42).unwrap();
+/// engine.train(1000);
+/// assert_eq!(engine.episodes(), 1000);
+/// assert!(engine.choose_move(&Board::new(), Player::X).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct QLearningEngine {
+    config: QLearningConfig,
+    table: HashMap<u32, [f64; 9]>,
+    episodes: u64,
+    rng: Rng,
+}
+
+impl QLearningEngine {
+    /// Creates an untrained engine, seeding exploration from entropy
+    pub fn new(config: QLearningConfig) -> Result<Self, &'static str> {
+        Self::with_rng(config, Rng::from_entropy())
+    }
+
+    /// Creates an untrained engine with a fixed seed for reproducible training
+    pub fn seeded(config: QLearningConfig, seed: u64) -> Result<Self, &'static str> {
+        Self::with_rng(config, Rng::new(seed))
+    }
+
+    fn with_rng(config: QLearningConfig, rng: Rng) -> Result<Self, &'static str> {
+        config.validate()?;
+        Ok(QLearningEngine {
+            config,
+            table: HashMap::new(),
+            episodes: 0,
+            rng,
+        })
+    }
+
+    /// Returns the hyperparameters
+    pub fn config(&self) -> &QLearningConfig {
+        &self.config
+    }
+
+    /// Returns the number of training episodes played so far
+    pub fn episodes(&self) -> u64 {
+        self.episodes
+    }
+
+    /// Returns the number of states in the table
+    pub fn states(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns the current exploration probability
+    pub fn epsilon(&self) -> f64 {
+        let c = &self.config;
+        if c.epsilon_decay_episodes == 0 {
+            return c.epsilon_end;
+        }
+        let progress = (self.episodes as f64 / c.epsilon_decay_episodes as f64).min(1.0);
+        c.epsilon_start + (c.epsilon_end - c.epsilon_start) * progress
+    }
+
+    /// Returns the learned value of playing (row, col) for `player`
+    pub fn q_value(&self, board: &Board, player: Player, (row, col): (usize, usize)) -> f64 {
+        self.table
+            .get(&state_key(board, player))
+            .map_or(0.0, |values| values[row * 3 + col])
+    }
+
+    /// Plays `episodes` self-play games, updating the table after every move
+    pub fn train(&mut self, episodes: u64) {
+        for _ in 0..episodes {
+            self.play_episode();
+            self.episodes += 1;
+        }
+    }
+
+    /// Plays one self-play game from the empty board
+    fn play_episode(&mut self) {
+        let mut board = Board::with_rules(self.config.rules);
+        let mut player = Player::X;
+        let epsilon = self.epsilon();
+
+        while board.game_result() == GameResult::InProgress {
+            let moves = board.valid_moves();
+            let (row, col) = if self.rng.next_f64() < epsilon {
+                self.rng.choose(&moves).unwrap()
+            } else {
+                self.greedy(&board, player, &moves).unwrap()
+            };
+
+            let key = state_key(&board, player);
+            board.make_move(row, col, player).unwrap();
+            let target = match board.game_result() {
+                GameResult::Win(winner) if winner == player => 1.0,
+                GameResult::Win(_) => -1.0,
+                GameResult::Draw => 0.0,
+                GameResult::InProgress => {
+                    -self.config.discount * self.best_value(&board, player.opponent())
+                }
+            };
+
+            let value = &mut self.table.entry(key).or_insert([0.0; 9])[row * 3 + col];
+            *value += self.config.learning_rate * (target - *value);
+            player = player.opponent();
+        }
+    }
+
+    /// Returns the highest-valued move among `moves`, preferring earlier moves on ties
+    fn greedy(&self, board: &Board, player: Player, moves: &[(usize, usize)]) -> Option<(usize, usize)> {
+        let values = self.table.get(&state_key(board, player));
+        let value = |&(row, col): &(usize, usize)| values.map_or(0.0, |v| v[row * 3 + col]);
+        let mut best: Option<((usize, usize), f64)> = None;
+        for mv in moves {
+            let v = value(mv);
+            if best.is_none_or(|(_, b)| v > b) {
+                best = Some((*mv, v));
+            }
+        }
+        best.map(|(mv, _)| mv)
+    }
+
+    /// Returns the value of the best move for `player`
+    fn best_value(&self, board: &Board, player: Player) -> f64 {
+        let Some(values) = self.table.get(&state_key(board, player)) else {
+            return 0.0;
+        };
+        board
+            .valid_moves()
+            .into_iter()
+            .map(|(row, col)| values[row * 3 + col])
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Writes the learned table in the binary format
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.table.len() as u32).to_le_bytes())?;
+
+        let mut keys: Vec<u32> = self.table.keys().copied().collect();
+        keys.sort_unstable();
+        for key in keys {
+            writer.write_all(&key.to_le_bytes())?;
+            for value in &self.table[&key] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a table in the binary format into an engine with `config`
+    ///
+    /// The loaded engine starts with an episode count of zero.
+    pub fn load<R: Read>(reader: &mut R, config: QLearningConfig) -> io::Result<Self> {
+        let invalid = |msg: &'static str| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let mut engine = QLearningEngine::new(config).map_err(invalid)?;
+
+        let mut header = [0u8; 9];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("Not a Q-table"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("Unsupported Q-table version"));
+        }
+        let count = u32::from_le_bytes([header[5], header[6], header[7], header[8]]);
+
+        for _ in 0..count {
+            let mut key = [0u8; 4];
+            reader.read_exact(&mut key)?;
+            let mut values = [0.0; 9];
+            for value in &mut values {
+                let mut raw = [0u8; 8];
+                reader.read_exact(&mut raw)?;
+                *value = f64::from_le_bytes(raw);
+            }
+            engine.table.insert(u32::from_le_bytes(key), values);
+        }
+        Ok(engine)
+    }
+}
+
+impl Engine for QLearningEngine {
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.greedy(board, player, &board.valid_moves())
+    }
+
+    fn evaluate_moves(&self, board: &Board, player: Player) -> Vec<((usize, usize), f64)> {
+        let mut scores: Vec<((usize, usize), f64)> = board
+            .valid_moves()
+            .into_iter()
+            .map(|mv| (mv, self.q_value(board, player, mv)))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("qlearning"),
+            description: Some("Plays greedily from values learned by Q-learning self-play"),
+            ..EngineMetadata::default()
+        }
+    }
+}
+
+/// Returns the table key of a position with `player` to move
+fn state_key(board: &Board, player: Player) -> u32 {
+    let side = match player {
+        Player::X => 0,
+        Player::O => 1,
+    };
+    (board.encode() as u32) << 1 | side
+}
@@ -0,0 +1,321 @@
+//! Completed game records and their textual notation
+//!
+//! The notation is modelled on PGN: header lines of the form `[Key "Value"]`
+//! followed by numbered moves and a result token.
+//!
+//! ```text
+//! [Rules "standard"]
+//! [Start "X"]
+//! [X "minimax"]
+//! [MoveTimes "1200 5400 900 3100 800"]
+//!
+//! 1. a1 a2 2. b2 a3 3. c3 1-0
+//! ```
+//!
+//! Cells are named by column `a`-`c` and row `1`-`3` from the top. Each move
+//! number covers the starting player's move and the reply. The result token is
+//! `1-0` if X won, `0-1` if O won, `1/2-1/2` for a draw, and `*` otherwise.
+//! The optional `MoveTimes` header lists each move's duration in nanoseconds.
+
+use std::time::Duration;
+use crate::backend::format::{parse_square, square_name};
+use crate::backend::game::{Game, GameResult, Rules};
+use crate::backend::player::Player;
+use crate::backend::replay::Replay;
+
+/// A game's moves and result, independent of the engines that played it
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Game, GameRecord};
+///
+/// let mut game = Game::new();
+/// for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+///     game.play(row, col).unwrap();
+/// }
+/// let mut record = GameRecord::from_game(&game);
+/// record.set_tag("X", "minimax").unwrap();
+///
+/// let text = record.to_notation();
+/// assert!(text.ends_with("1. a1 a2 2. b2 a3 3. c3 1-0\n"));
+/// assert_eq!(GameRecord::from_notation(&text).unwrap(), record);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    /// Rules the game was played under
+    pub rules: Rules,
+    /// Player who moved first
+    pub starting_player: Player,
+    /// Moves in the order they were played
+    pub moves: Vec<(usize, usize)>,
+    /// Result after the last move
+    pub result: GameResult,
+    /// Time each engine took to choose each move, in the order of `moves`
+    ///
+    /// Empty if the game was not timed, e.g. for records built from a
+    /// [`Game`] or from notation without a `MoveTimes` header.
+    pub move_durations: Vec<Duration>,
+    /// Seed of the random source used for this game, if any
+    pub seed: Option<u64>,
+    /// Free-form headers such as engine names, in insertion order
+    ///
+    /// The keys `Rules`, `Start`, `Seed`, `MoveTimes`, and `Result` are
+    /// reserved for the fields above; [`set_tag`](Self::set_tag) rejects them.
+    pub tags: Vec<(String, String)>,
+}
+
+/// Header keys written from a record's fields rather than its tags
+const RESERVED_KEYS: [&str; 5] = ["Rules", "Start", "Seed", "MoveTimes", "Result"];
+
+impl GameRecord {
+    /// Creates a record from a move list, validating every move
+    pub fn new(rules: Rules, starting_player: Player, moves: &[(usize, usize)]) -> Result<Self, &'static str> {
+        let mut game = Game::with_rules_and_starting_player(rules, starting_player);
+        for &(row, col) in moves {
+            game.play(row, col)?;
+        }
+        Ok(Self::from_game(&game))
+    }
+
+    /// Creates a record of the moves played so far in `game`
+    pub fn from_game(game: &Game) -> Self {
+        GameRecord {
+            rules: game.rules(),
+            starting_player: game.starting_player(),
+            moves: game.moves().to_vec(),
+            result: game.result(),
+            move_durations: Vec::new(),
+            seed: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Replays the moves into a [`Game`]
+    pub fn to_game(&self) -> Result<Game, &'static str> {
+        let mut game = Game::with_rules_and_starting_player(self.rules, self.starting_player);
+        for &(row, col) in &self.moves {
+            game.play(row, col)?;
+        }
+        Ok(game)
+    }
+
+    /// Returns a [`Replay`] for stepping through the game's positions
+    ///
+    /// Returns an error if the record contains an illegal move.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{GameRecord, GameResult, Player, Rules};
+    ///
+    /// let record = GameRecord::new(Rules::Standard, Player::X, &[(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)]).unwrap();
+    /// let replay = record.replay().unwrap();
+    ///
+    /// for frame in &replay {
+    ///     println!("{}: {}", frame.number, frame.annotation());
+    /// }
+    /// assert_eq!(replay.frame(3).unwrap().position, (1, 1));
+    /// assert_eq!(replay.frame(5).unwrap().result, GameResult::Win(Player::X));
+    /// ```
+    pub fn replay(&self) -> Result<Replay, &'static str> {
+        Replay::from_record(self)
+    }
+
+    /// Returns the total time `player`'s engine spent choosing moves, or
+    /// zero if the game was not timed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{play_game, FastEngine, MinimaxEngine, Player};
+    ///
+    /// let record = play_game(&mut FastEngine, &mut MinimaxEngine::new(), Player::X);
+    /// assert_eq!(record.move_durations.len(), record.moves.len());
+    /// assert!(record.time_used(Player::O) > record.time_used(Player::X));
+    /// ```
+    pub fn time_used(&self, player: Player) -> Duration {
+        let first = if player == self.starting_player { 0 } else { 1 };
+        self.move_durations.iter().skip(first).step_by(2).sum()
+    }
+
+    /// Returns the value of a free-form header
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Sets a free-form header, replacing any previous value
+    ///
+    /// Returns an error if `key` is reserved, empty, or contains whitespace,
+    /// control characters, or brackets, since it could not be read back from
+    /// the notation. Values may hold any text.
+    pub fn set_tag(&mut self, key: impl Into<String>, value: impl Into<String>) -> Result<(), &'static str> {
+        let (key, value) = (key.into(), value.into());
+        check_tag_key(&key)?;
+        match self.tags.iter_mut().find(|(k, _)| *k == key) {
+            Some(tag) => tag.1 = value,
+            None => self.tags.push((key, value)),
+        }
+        Ok(())
+    }
+
+    /// Writes the record in the textual notation
+    ///
+    /// Tag values are escaped so they survive [`from_notation`](Self::from_notation).
+    /// Tags added directly to [`tags`](Self::tags) under a key that
+    /// [`set_tag`](Self::set_tag) would reject are left out.
+    pub fn to_notation(&self) -> String {
+        let mut out = format!("[Rules \"{}\"]\n[Start \"{}\"]\n", self.rules, self.starting_player);
+        if let Some(seed) = self.seed {
+            out.push_str(&format!("[Seed \"{}\"]\n", seed));
+        }
+        if !self.move_durations.is_empty() {
+            let times: Vec<String> = self.move_durations.iter().map(|time| time.as_nanos().to_string()).collect();
+            out.push_str(&format!("[MoveTimes \"{}\"]\n", times.join(" ")));
+        }
+        for (key, value) in self.tags.iter().filter(|(key, _)| check_tag_key(key).is_ok()) {
+            out.push_str(&format!("[{} \"{}\"]\n", key, escape_value(value)));
+        }
+        out.push('\n');
+
+        for (index, &(row, col)) in self.moves.iter().enumerate() {
+            if index % 2 == 0 {
+                out.push_str(&format!("{}. ", index / 2 + 1));
+            }
+            out.push_str(&square_name(row, col));
+            out.push(' ');
+        }
+        out.push_str(match self.result {
+            GameResult::Win(Player::X) => "1-0",
+            GameResult::Win(Player::O) => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::InProgress => "*",
+        });
+        out.push('\n');
+        out
+    }
+
+    /// Parses a record written by [`GameRecord::to_notation`]
+    ///
+    /// Missing `Rules` and `Start` headers default to standard rules with X
+    /// moving first. The moves are replayed, so an illegal move or a result
+    /// token that disagrees with the moves is an error.
+    pub fn from_notation(text: &str) -> Result<Self, &'static str> {
+        let mut rules = Rules::Standard;
+        let mut starting_player = Player::X;
+        let mut seed = None;
+        let mut move_durations = Vec::new();
+        let mut tags = Vec::new();
+        let mut claimed = None;
+        let mut moves = Vec::new();
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(header) = line.strip_prefix('[') {
+                let (key, value) = parse_header(header)?;
+                match key {
+                    "Rules" => rules = value.parse()?,
+                    "Start" => starting_player = parse_player(&value)?,
+                    "Seed" => seed = Some(value.parse().map_err(|_| "Seed must be an unsigned integer")?),
+                    "MoveTimes" => move_durations = parse_times(&value)?,
+                    "Result" => {}
+                    _ => tags.push((key.to_string(), value)),
+                }
+                continue;
+            }
+            for token in line.split_whitespace() {
+                match token {
+                    "1-0" => claimed = Some(GameResult::Win(Player::X)),
+                    "0-1" => claimed = Some(GameResult::Win(Player::O)),
+                    "1/2-1/2" => claimed = Some(GameResult::Draw),
+                    "*" => claimed = Some(GameResult::InProgress),
+                    _ if token.ends_with('.') && token[..token.len() - 1].parse::<usize>().is_ok() => {}
+                    _ => moves.push(parse_square(token).ok_or("Invalid move in notation")?),
+                }
+            }
+        }
+
+        let mut record = Self::new(rules, starting_player, &moves)?;
+        if claimed.is_some_and(|claimed| claimed != record.result) {
+            return Err("Result token does not match the moves");
+        }
+        if !move_durations.is_empty() && move_durations.len() != record.moves.len() {
+            return Err("MoveTimes must have one entry per move");
+        }
+        record.move_durations = move_durations;
+        record.seed = seed;
+        record.tags = tags;
+        Ok(record)
+    }
+}
+
+/// Checks that a tag key can be written as a header and read back
+fn check_tag_key(key: &str) -> Result<(), &'static str> {
+    if RESERVED_KEYS.contains(&key) {
+        return Err("Tag key is reserved");
+    }
+    if key.is_empty() || key.chars().any(|c| c.is_whitespace() || c.is_control() || c == '[' || c == ']') {
+        return Err("Tag key must be non-empty without spaces or brackets");
+    }
+    Ok(())
+}
+
+/// Escapes backslashes, quotes, and line breaks in a header value
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '"' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits `Key "Value"]` into its key and unescaped value
+fn parse_header(header: &str) -> Result<(&str, String), &'static str> {
+    let header = header.strip_suffix(']').ok_or("Header must end with ']'")?;
+    let (key, value) = header.split_once(' ').ok_or("Header must have a key and a value")?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or("Header value must be quoted")?;
+    if key.is_empty() {
+        return Err("Header must have a key and a value");
+    }
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some(escaped) => escaped,
+                None => '\\',
+            },
+            c => c,
+        });
+    }
+    Ok((key, unescaped))
+}
+
+/// Parses space-separated nanosecond durations
+fn parse_times(value: &str) -> Result<Vec<Duration>, &'static str> {
+    value
+        .split_whitespace()
+        .map(|nanos| nanos.parse().map(Duration::from_nanos).map_err(|_| "MoveTimes must be nanosecond counts"))
+        .collect()
+}
+
+fn parse_player(player: &str) -> Result<Player, &'static str> {
+    match player {
+        "X" => Ok(Player::X),
+        "O" => Ok(Player::O),
+        _ => Err("Player must be \"X\" or \"O\""),
+    }
+}
@@ -0,0 +1,171 @@
+//! Runtime engine construction by name
+//!
+//! CLIs, config files, and tournaments describe engines as strings such as
+//! `"fast"`, `"random:seed=42"`, or `"minimax:depth=5"`. The
+//! [`EngineRegistry`] turns those specs into boxed engines.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+use crate::backend::engine::{FastEngine, RandomEngine, StatefulEngine};
+use crate::backend::minimax::MinimaxEngine;
+use crate::backend::solver::PerfectLookupEngine;
+
+/// A boxed engine created at runtime
+pub type BoxedEngine = Box<dyn StatefulEngine + Send>;
+
+/// A function building an engine from its parameters
+pub type EngineFactory = Box<dyn Fn(&EngineParams) -> Result<BoxedEngine, &'static str> + Send + Sync>;
+
+/// Parameters parsed from an engine spec
+///
+/// A spec has the form `name` or `name:key=value,key=value`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineParams {
+    params: Vec<(String, String)>,
+}
+
+impl EngineParams {
+    /// Parses the parameter part of a spec (everything after the colon)
+    pub fn parse(params: &str) -> Result<Self, &'static str> {
+        let mut parsed = Vec::new();
+        for pair in params.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or("Engine parameter must be key=value")?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err("Engine parameter must be key=value");
+            }
+            parsed.push((key.to_string(), value.trim().to_string()));
+        }
+        Ok(EngineParams { params: parsed })
+    }
+
+    /// Returns the raw value of a parameter
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Returns a parameter parsed as `u64`
+    pub fn get_u64(&self, key: &str) -> Result<Option<u64>, &'static str> {
+        self.get(key)
+            .map(|v| v.parse().map_err(|_| "Engine parameter must be an integer"))
+            .transpose()
+    }
+
+    /// Returns a parameter parsed as `usize`
+    pub fn get_usize(&self, key: &str) -> Result<Option<usize>, &'static str> {
+        self.get(key)
+            .map(|v| v.parse().map_err(|_| "Engine parameter must be an integer"))
+            .transpose()
+    }
+
+    /// Returns a parameter parsed as `f64`
+    pub fn get_f64(&self, key: &str) -> Result<Option<f64>, &'static str> {
+        self.get(key)
+            .map(|v| v.parse().map_err(|_| "Engine parameter must be a number"))
+            .transpose()
+    }
+
+    /// Returns all parameters in spec order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// Maps engine names to factories
+///
+/// [`EngineRegistry::with_builtins`] registers the engines shipped with the
+/// crate:
+///
+/// | Spec | Engine |
+/// |------|--------|
+/// | `fast` | [`FastEngine`] |
+/// | `random[:seed=N]` | [`RandomEngine`] |
+/// | `minimax[:depth=N,nodes=N,time_ms=N]` | [`MinimaxEngine`] |
+/// | `perfect` | [`PerfectLookupEngine`] |
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, EngineRegistry};
+///
+/// let registry = EngineRegistry::with_builtins();
+/// let mut engine = registry.create("minimax:depth=2").unwrap();
+/// assert!(engine.choose_move_mut(&Board::new(), Player::X).is_some());
+/// assert!(registry.create("unknown").is_err());
+/// ```
+#[derive(Default)]
+pub struct EngineRegistry {
+    factories: HashMap<String, EngineFactory>,
+}
+
+impl EngineRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        EngineRegistry {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Creates a registry containing the built-in engines
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("fast", |_| Ok(Box::new(FastEngine)));
+        registry.register("random", |params| {
+            Ok(Box::new(match params.get_u64("seed")? {
+                Some(seed) => RandomEngine::seeded(seed),
+                None => RandomEngine::new(),
+            }))
+        });
+        registry.register("minimax", |params| {
+            let mut engine = match params.get_usize("depth")? {
+                Some(depth) => MinimaxEngine::with_depth(depth),
+                None => MinimaxEngine::new(),
+            };
+            if let Some(nodes) = params.get_u64("nodes")? {
+                engine = engine.node_limit(nodes);
+            }
+            if let Some(ms) = params.get_u64("time_ms")? {
+                engine = engine.time_limit(Duration::from_millis(ms));
+            }
+            Ok(Box::new(engine))
+        });
+        registry.register("perfect", |_| Ok(Box::new(PerfectLookupEngine)));
+        registry
+    }
+
+    /// Registers a factory under `name`, replacing any existing entry
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&EngineParams) -> Result<BoxedEngine, &'static str> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Returns true if an engine is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Returns the registered names in sorted order
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Creates an engine from a spec such as `"random:seed=42"`
+    pub fn create(&self, spec: &str) -> Result<BoxedEngine, &'static str> {
+        let (name, params) = spec.split_once(':').unwrap_or((spec, ""));
+        let factory = self.factories.get(name.trim()).ok_or("Unknown engine")?;
+        factory(&EngineParams::parse(params)?)
+    }
+}
+
+impl fmt::Debug for EngineRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineRegistry")
+            .field("engines", &self.names())
+            .finish()
+    }
+}
@@ -0,0 +1,236 @@
+//! Engines over TCP (requires the `remote` feature)
+//!
+//! Every message is a 4-byte big-endian length followed by that many bytes of
+//! UTF-8 JSON. The client sends the position and the player to move:
+//!
+//! ```text
+//! {"board": "X...O....", "player": "X", "rules": "standard"}
+//! ```
+//!
+//! `board` lists the nine cells in row-major order as `X`, `O`, or `.`, and
+//! `rules` is `"standard"` or `"misere"`. The server answers with a move, or
+//! `null` if it has none:
+//!
+//! ```text
+//! {"move": [1, 1]}
+//! ```
+//!
+//! Malformed requests are answered with `{"error": "<message>"}`. Engines
+//! written in any language can take part by implementing the server side.
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use serde_json::{json, Value};
+use crate::backend::board::Board;
+use crate::backend::engine::StatefulEngine;
+use crate::backend::game::Rules;
+use crate::backend::player::{Cell, Player};
+
+/// Largest accepted message, in bytes
+const MAX_MESSAGE_LEN: u32 = 64 * 1024;
+
+/// How long a read or write may block, by default
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An engine whose moves are chosen by a server over TCP
+///
+/// A request that fails part-way, including one the server does not answer
+/// within the read timeout, closes the connection, since a late reply would
+/// be taken as the answer to the next request. Later requests fail.
+///
+/// # Example
+///
+/// ```
+/// use std::net::TcpListener;
+/// use zttt_rs::backend::{serve_engine, Board, FastEngine, Player, RemoteEngine};
+///
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let addr = listener.local_addr().unwrap();
+/// std::thread::spawn(move || serve_engine(listener, FastEngine));
+///
+/// let mut engine = RemoteEngine::connect(addr).unwrap();
+/// assert_eq!(engine.request_move(&Board::new(), Player::X).unwrap(), Some((0, 0)));
+/// ```
+#[derive(Debug)]
+pub struct RemoteEngine {
+    stream: TcpStream,
+    /// Set once an I/O error has left the connection out of step
+    closed: bool,
+}
+
+impl RemoteEngine {
+    /// Connects to an engine server
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self::from_stream(TcpStream::connect(addr)?))
+    }
+
+    /// Uses an already connected stream
+    ///
+    /// Reads and writes time out after ten seconds.
+    pub fn from_stream(stream: TcpStream) -> Self {
+        // Requests are tiny and latency-bound
+        let _ = stream.set_nodelay(true);
+        let _ = stream.set_read_timeout(Some(DEFAULT_TIMEOUT));
+        let _ = stream.set_write_timeout(Some(DEFAULT_TIMEOUT));
+        RemoteEngine { stream, closed: false }
+    }
+
+    /// Sets how long to wait for the server's reply, or `None` to wait
+    /// forever
+    ///
+    /// Defaults to ten seconds. A request that times out fails with
+    /// [`io::ErrorKind::TimedOut`] and closes the connection. Fails if
+    /// `timeout` is zero.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_read_timeout(timeout)
+    }
+
+    /// Returns the read timeout
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.stream.read_timeout()
+    }
+
+    /// Asks the server for a move, reporting network and protocol errors
+    pub fn request_move(&mut self, board: &Board, player: Player) -> io::Result<Option<(usize, usize)>> {
+        let request = json!({
+            "board": board.cell_string(),
+            "player": player.to_string(),
+            "rules": board.rules().to_string(),
+        });
+        if self.closed {
+            return Err(io::Error::new(io::ErrorKind::NotConnected, "Connection was closed after an earlier error"));
+        }
+        let response = match write_message(&mut self.stream, &request).and_then(|()| read_message(&mut self.stream)) {
+            Ok(response) => response,
+            Err(e) => {
+                self.closed = true;
+                let _ = self.stream.shutdown(Shutdown::Both);
+                // Platforms report an expired timeout as either kind
+                return Err(match e.kind() {
+                    io::ErrorKind::WouldBlock => io::Error::new(io::ErrorKind::TimedOut, "Engine server did not reply in time"),
+                    _ => e,
+                });
+            }
+        };
+        if let Some(error) = response.get("error").and_then(Value::as_str) {
+            return Err(io::Error::other(error.to_string()));
+        }
+        match response.get("move") {
+            Some(Value::Null) => Ok(None),
+            Some(Value::Array(pair)) if pair.len() == 2 => {
+                let coord = |v: &Value| v.as_u64().filter(|&n| n < 3).map(|n| n as usize);
+                match (coord(&pair[0]), coord(&pair[1])) {
+                    (Some(row), Some(col)) => Ok(Some((row, col))),
+                    _ => Err(invalid("Move coordinates must be 0, 1, or 2")),
+                }
+            }
+            _ => Err(invalid("Response must contain a move")),
+        }
+    }
+}
+
+impl StatefulEngine for RemoteEngine {
+    /// Returns `None` on network or protocol errors; use
+    /// [`request_move`](RemoteEngine::request_move) to see them
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.request_move(board, player).ok().flatten()
+    }
+
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Engine served over TCP")
+    }
+}
+
+/// Hosts `engine` on `listener`, serving one connection at a time
+///
+/// Returns only if accepting a connection fails. Errors on an individual
+/// connection close that connection and the server moves on to the next.
+pub fn serve_engine<E: StatefulEngine>(listener: TcpListener, mut engine: E) -> io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        let _ = serve_connection(stream, &mut engine);
+    }
+}
+
+/// Answers requests on one connection until the client disconnects
+pub fn serve_connection<E: StatefulEngine + ?Sized>(mut stream: TcpStream, engine: &mut E) -> io::Result<()> {
+    let _ = stream.set_nodelay(true);
+    loop {
+        let request = match read_message(&mut stream) {
+            Ok(request) => request,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let response = match parse_request(&request) {
+            Ok((board, player)) => match engine.choose_move_mut(&board, player) {
+                Some((row, col)) => json!({ "move": [row, col] }),
+                None => json!({ "move": null }),
+            },
+            Err(message) => json!({ "error": message }),
+        };
+        write_message(&mut stream, &response)?;
+    }
+}
+
+/// Decodes a request into the position and the player to move
+fn parse_request(request: &Value) -> Result<(Board, Player), &'static str> {
+    let rules = match request.get("rules").and_then(Value::as_str) {
+        None => Rules::Standard,
+        Some(rules) => rules.parse()?,
+    };
+    let player = match request.get("player").and_then(Value::as_str) {
+        Some("X") => Player::X,
+        Some("O") => Player::O,
+        _ => return Err("Player must be \"X\" or \"O\""),
+    };
+    let cells = request.get("board").and_then(Value::as_str).ok_or("Missing board")?;
+    if cells.chars().count() != 9 {
+        return Err("Board must have 9 cells");
+    }
+
+    // Fill the cells directly, since replaying them in row-major order would
+    // reject finished positions with pieces after the deciding cell
+    let mut board = Board::with_rules(rules);
+    for (index, cell) in cells.chars().enumerate() {
+        board.cells[index / 3][index % 3] = match cell {
+            'X' => Cell::Occupied(Player::X),
+            'O' => Cell::Occupied(Player::O),
+            '.' => Cell::Empty,
+            _ => return Err("Board cells must be 'X', 'O', or '.'"),
+        };
+    }
+    board.validate()?;
+    board.refresh();
+    Ok((board, player))
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes one length-prefixed JSON message
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let bytes = serde_json::to_vec(message)?;
+    writer.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()
+}
+
+/// Reads one length-prefixed JSON message
+fn read_message<R: Read>(reader: &mut R) -> io::Result<Value> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        return Err(invalid("Message too long"));
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
@@ -0,0 +1,188 @@
+//! Step-by-step replay of a game's moves
+
+use std::fmt;
+use crate::backend::board::Board;
+use crate::backend::format::square_name;
+use crate::backend::game::{Game, GameResult, Rules};
+use crate::backend::player::Player;
+use crate::backend::record::GameRecord;
+
+/// A validated move list that can be replayed one frame at a time
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::Replay;
+///
+/// let replay = Replay::new(&[(1, 1), (0, 0), (2, 2)]).unwrap();
+/// let annotations: Vec<String> = replay.frames().map(|frame| frame.annotation()).collect();
+/// assert_eq!(annotations, ["X plays b2", "O plays a1", "X plays c3"]);
+///
+/// for frame in &replay {
+///     println!("{}\n", frame);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Replay {
+    start: Game,
+    moves: Vec<(usize, usize)>,
+}
+
+/// The position after one move of a [`Replay`]
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Move number, starting at 1
+    pub number: usize,
+    /// Player who made the move
+    pub player: Player,
+    /// Cell the move was played in
+    pub position: (usize, usize),
+    /// Board after the move
+    pub board: Board,
+    /// Game result after the move
+    pub result: GameResult,
+}
+
+impl Replay {
+    /// Creates a replay of a standard game started by X
+    ///
+    /// Returns an error if any move is illegal.
+    pub fn new(moves: &[(usize, usize)]) -> Result<Self, &'static str> {
+        Self::with_start(moves, Rules::Standard, Player::X)
+    }
+
+    /// Creates a replay with the given rules and starting player
+    pub fn with_start(moves: &[(usize, usize)], rules: Rules, starting_player: Player) -> Result<Self, &'static str> {
+        let start = Game::with_rules_and_starting_player(rules, starting_player);
+        let mut game = start.clone();
+        for &(row, col) in moves {
+            game.play(row, col)?;
+        }
+        Ok(Replay {
+            start,
+            moves: moves.to_vec(),
+        })
+    }
+
+    /// Creates a replay of the moves played in `game`
+    pub fn from_game(game: &Game) -> Self {
+        Replay {
+            start: Game::with_rules_and_starting_player(game.rules(), game.starting_player()),
+            moves: game.moves().to_vec(),
+        }
+    }
+
+    /// Creates a replay of a recorded game
+    pub fn from_record(record: &GameRecord) -> Result<Self, &'static str> {
+        Self::with_start(&record.moves, record.rules, record.starting_player)
+    }
+
+    /// Returns the number of moves
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns true if there are no moves
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Returns the board before the first move
+    pub fn initial_board(&self) -> &Board {
+        self.start.board()
+    }
+
+    /// Returns an iterator over the position after each move
+    pub fn frames(&self) -> Frames<'_> {
+        Frames {
+            moves: self.moves.iter(),
+            game: self.start.clone(),
+        }
+    }
+
+    /// Returns the frame after move number `ply`, counting from 1, or `None`
+    /// if there is no such move
+    pub fn frame(&self, ply: usize) -> Option<Frame> {
+        self.frames().nth(ply.checked_sub(1)?)
+    }
+
+    /// Returns the board after the first `ply` moves; ply 0 is the initial
+    /// board
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{Cell, Player, Replay};
+    ///
+    /// let replay = Replay::new(&[(1, 1), (0, 0), (2, 2)]).unwrap();
+    /// assert_eq!(replay.board_at(0).unwrap().get(1, 1), Some(Cell::Empty));
+    /// assert_eq!(replay.board_at(2).unwrap().get(0, 0), Some(Cell::Occupied(Player::O)));
+    /// assert!(replay.board_at(4).is_none());
+    /// ```
+    pub fn board_at(&self, ply: usize) -> Option<Board> {
+        match ply {
+            0 => Some(self.initial_board().clone()),
+            _ => self.frame(ply).map(|frame| frame.board),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Replay {
+    type Item = Frame;
+    type IntoIter = Frames<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames()
+    }
+}
+
+/// Iterator over the frames of a [`Replay`]
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    moves: std::slice::Iter<'a, (usize, usize)>,
+    game: Game,
+}
+
+impl Iterator for Frames<'_> {
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Frame> {
+        let &(row, col) = self.moves.next()?;
+        let player = self.game.current_player();
+        self.game.play(row, col).expect("replay moves are validated");
+        Some(Frame {
+            number: self.game.moves().len(),
+            player,
+            position: (row, col),
+            board: self.game.board().clone(),
+            result: self.game.result(),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.moves.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Frames<'_> {}
+
+impl Frame {
+    /// Describes the move, e.g. `"X plays b2"` or `"O plays c1 and wins"`
+    pub fn annotation(&self) -> String {
+        let square = square_name(self.position.0, self.position.1);
+        match self.result {
+            GameResult::InProgress => format!("{} plays {}", self.player, square),
+            GameResult::Win(winner) if winner == self.player => format!("{} plays {} and wins", self.player, square),
+            GameResult::Win(_) => format!("{} plays {} and loses", self.player, square),
+            GameResult::Draw => format!("{} plays {}; draw", self.player, square),
+        }
+    }
+}
+
+impl fmt::Display for Frame {
+    /// Writes the numbered annotation above the board with coordinates
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}. {}", self.number, self.annotation())?;
+        write!(f, "{}", self.board.formatter().grid(true).coordinates(true))
+    }
+}
@@ -0,0 +1,92 @@
+//! Small seedable random number generator
+//!
+//! Engines that need randomness use this instead of an external crate, keeping
+//! the library dependency-free. The generator is SplitMix64: a single `u64` of
+//! state, a few arithmetic operations per draw, and good statistical quality
+//! for simulation purposes. It is not cryptographically secure.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// A fast, seedable pseudo-random number generator
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::Rng;
+///
+/// let mut a = Rng::new(42);
+/// let mut b = Rng::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(a.below(9) < 9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Creates a generator seeded from OS-provided randomness
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Rng::new(hasher.finish())
+    }
+
+    /// Creates the `index`-th of a family of independent generators derived from `seed`
+    ///
+    /// Useful for giving each game of a run its own reproducible stream,
+    /// regardless of the order in which games are played.
+    ///
+    /// ```
+    /// use zttt_rs::backend::Rng;
+    ///
+    /// assert_eq!(Rng::stream(7, 3), Rng::stream(7, 3));
+    /// assert_ne!(Rng::stream(7, 3).next_u64(), Rng::stream(7, 4).next_u64());
+    /// ```
+    pub fn stream(seed: u64, index: u64) -> Self {
+        Rng::new(Rng::new(seed.wrapping_add(index.wrapping_mul(0x9E37_79B9_7F4A_7C15))).next_u64())
+    }
+
+    /// Returns the next random `u64`
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random `f64` in `[0, 1)`
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a random number in `0..n`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    #[inline]
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "range must not be empty");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Returns a random element of the slice, or `None` if it is empty
+    #[inline]
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> Option<T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(items[self.below(items.len())])
+        }
+    }
+}
@@ -0,0 +1,338 @@
+//! Exhaustive solver and perfect-play database
+//!
+//! TicTacToe is small enough to solve completely: there are 5478 positions
+//! reachable from the empty board with X moving first, only 765 of them
+//! distinct up to symmetry. [`SolvedDatabase::build`] solves every canonical
+//! position once and expands the results into a table indexed by
+//! [`Board::encode`], so lookups need no search and no canonicalization.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use crate::backend::board::Board;
+use crate::backend::engine::{Engine, EngineMetadata};
+use crate::backend::game::{GameResult, Rules};
+use crate::backend::player::Player;
+use crate::backend::symmetry::Symmetry;
+
+/// Number of distinct board encodings
+const TABLE_SIZE: usize = 19683;
+/// Marker for positions without a best move
+const NO_MOVE: u8 = u8::MAX;
+
+/// Game-theoretic value of a position for the player to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameValue {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl GameValue {
+    /// Returns the value from the opponent's point of view
+    pub fn flip(self) -> GameValue {
+        match self {
+            GameValue::Win => GameValue::Loss,
+            GameValue::Draw => GameValue::Draw,
+            GameValue::Loss => GameValue::Win,
+        }
+    }
+}
+
+impl fmt::Display for GameValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameValue::Win => write!(f, "win"),
+            GameValue::Draw => write!(f, "draw"),
+            GameValue::Loss => write!(f, "loss"),
+        }
+    }
+}
+
+/// Solved value of a position under perfect play
+///
+/// `distance` is the number of plies until the game ends when the winning
+/// side wins as fast as possible and the losing side delays as long as
+/// possible. Finished positions have distance zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Solution {
+    pub value: GameValue,
+    pub distance: u8,
+}
+
+impl Solution {
+    /// Returns a score where higher is better for the player to move
+    ///
+    /// Faster wins score higher than slower wins, and slower losses score
+    /// higher than faster losses.
+    pub fn score(&self) -> i32 {
+        match self.value {
+            GameValue::Win => 100 - self.distance as i32,
+            GameValue::Draw => 0,
+            GameValue::Loss => self.distance as i32 - 100,
+        }
+    }
+
+    /// Returns the solution one ply earlier, seen by the other player
+    fn parent(self) -> Solution {
+        Solution {
+            value: self.value.flip(),
+            distance: self.distance + 1,
+        }
+    }
+}
+
+/// Perfect-play values for every reachable position
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, GameValue, Rules, SolvedDatabase};
+///
+/// let db = SolvedDatabase::build(Rules::Standard);
+/// assert_eq!(db.len(), 5478);
+/// assert_eq!(db.canonical_len(), 765);
+///
+/// // Perfect play from the empty board is a draw
+/// assert_eq!(db.get(&Board::new()).unwrap().value, GameValue::Draw);
+/// ```
+pub struct SolvedDatabase {
+    rules: Rules,
+    table: Box<[Option<Solution>]>,
+    best: Box<[u8]>,
+    len: usize,
+    canonical_len: usize,
+}
+
+impl SolvedDatabase {
+    /// Solves every position reachable from the empty board with X first
+    pub fn build(rules: Rules) -> Self {
+        let mut memo = HashMap::new();
+        solve(&Board::with_rules(rules), Player::X, &mut memo);
+
+        let mut table = vec![None; TABLE_SIZE].into_boxed_slice();
+        let mut len = 0;
+        for (board, solution) in memo.values() {
+            for &symmetry in &Symmetry::ALL {
+                let slot = &mut table[board.transform(symmetry).encode() as usize];
+                if slot.is_none() {
+                    *slot = Some(*solution);
+                    len += 1;
+                }
+            }
+        }
+
+        let mut db = SolvedDatabase {
+            rules,
+            table,
+            best: vec![NO_MOVE; TABLE_SIZE].into_boxed_slice(),
+            len,
+            canonical_len: memo.len(),
+        };
+        for code in 0..TABLE_SIZE {
+            if db.table[code].is_some() {
+                let board = Board::decode(code as u16, rules);
+                if let Some(&(row, col)) = db.best_moves(&board).first() {
+                    db.best[code] = (row * 3 + col) as u8;
+                }
+            }
+        }
+        db
+    }
+
+    /// Returns a database shared by the whole process, building it on first use
+    pub fn shared(rules: Rules) -> &'static SolvedDatabase {
+        static STANDARD: OnceLock<SolvedDatabase> = OnceLock::new();
+        static MISERE: OnceLock<SolvedDatabase> = OnceLock::new();
+        let cell = match rules {
+            Rules::Standard => &STANDARD,
+            Rules::Misere => &MISERE,
+        };
+        cell.get_or_init(|| SolvedDatabase::build(rules))
+    }
+
+    /// Returns the rules the database was solved for
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Returns the solution for a position, or `None` if it is unreachable
+    ///
+    /// Boards played under different rules than the database are rejected.
+    #[inline]
+    pub fn get(&self, board: &Board) -> Option<Solution> {
+        if board.rules() != self.rules {
+            return None;
+        }
+        self.table[board.encode() as usize]
+    }
+
+    /// Returns the solution after each valid move, from the mover's point of view
+    pub fn move_values(&self, board: &Board) -> Vec<((usize, usize), Solution)> {
+        let Some(player) = board.current_player() else {
+            return Vec::new();
+        };
+        board
+            .valid_moves()
+            .into_iter()
+            .filter_map(|(row, col)| {
+                let mut child = board.clone();
+                child.make_move(row, col, player).ok()?;
+                Some(((row, col), self.get(&child)?.parent()))
+            })
+            .collect()
+    }
+
+    /// Returns every move that achieves the best score for the player to move
+    pub fn best_moves(&self, board: &Board) -> Vec<(usize, usize)> {
+        let values = self.move_values(board);
+        let Some(best) = values.iter().map(|(_, s)| s.score()).max() else {
+            return Vec::new();
+        };
+        values
+            .into_iter()
+            .filter(|(_, s)| s.score() == best)
+            .map(|(mv, _)| mv)
+            .collect()
+    }
+
+    /// Returns the first of the [`best_moves`](SolvedDatabase::best_moves) by table lookup
+    #[inline]
+    pub fn best_move(&self, board: &Board) -> Option<(usize, usize)> {
+        if board.rules() != self.rules {
+            return None;
+        }
+        self.best_move_by_code(board.encode())
+    }
+
+    #[inline]
+    fn best_move_by_code(&self, code: u16) -> Option<(usize, usize)> {
+        match self.best[code as usize] {
+            NO_MOVE => None,
+            cell => Some((cell as usize / 3, cell as usize % 3)),
+        }
+    }
+
+    /// Returns the number of reachable positions
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the database holds no positions
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of positions distinct up to symmetry
+    pub fn canonical_len(&self) -> usize {
+        self.canonical_len
+    }
+
+    /// Iterates over every reachable position and its solution
+    pub fn iter(&self) -> impl Iterator<Item = (Board, Solution)> + '_ {
+        self.table
+            .iter()
+            .enumerate()
+            .filter_map(move |(code, slot)| slot.map(|s| (Board::decode(code as u16, self.rules), s)))
+    }
+}
+
+impl fmt::Debug for SolvedDatabase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolvedDatabase")
+            .field("rules", &self.rules)
+            .field("len", &self.len)
+            .field("canonical_len", &self.canonical_len)
+            .finish()
+    }
+}
+
+/// Plays perfectly by looking up the best move in a [`SolvedDatabase`]
+///
+/// The database for the board's rules is built on first use and shared by
+/// every instance (see [`SolvedDatabase::shared`]), after which each move is a
+/// single table lookup. Games where O moves first are handled by swapping the
+/// colors of the pieces before the lookup.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Engine, PerfectLookupEngine};
+///
+/// let mut board = Board::new();
+/// board.make_move(0, 0, Player::X).unwrap();
+/// board.make_move(1, 1, Player::O).unwrap();
+/// board.make_move(0, 1, Player::X).unwrap();
+///
+/// // O must block the top row
+/// assert_eq!(PerfectLookupEngine.choose_move(&board, Player::O), Some((0, 2)));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerfectLookupEngine;
+
+impl Engine for PerfectLookupEngine {
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        let db = SolvedDatabase::shared(board.rules());
+        let code = board.encode();
+        if board.current_player() == Some(player) {
+            db.best_move_by_code(code)
+        } else {
+            db.best_move_by_code(swap_colors(code))
+        }
+    }
+
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some("perfect"),
+            description: Some("Perfect play from the solved position database"),
+            ..EngineMetadata::default()
+        }
+    }
+}
+
+/// Swaps the X and O pieces of an encoded board
+pub(crate) fn swap_colors(mut code: u16) -> u16 {
+    let mut swapped = 0;
+    let mut weight = 1;
+    for _ in 0..9 {
+        let digit = match code % 3 {
+            1 => 2,
+            2 => 1,
+            _ => 0,
+        };
+        swapped += digit * weight;
+        weight *= 3;
+        code /= 3;
+    }
+    swapped
+}
+
+/// Solves `board` with `to_move` to play, memoizing by canonical position
+fn solve(board: &Board, to_move: Player, memo: &mut HashMap<u16, (Board, Solution)>) -> Solution {
+    let (canonical, _) = board.canonical();
+    let code = canonical.encode();
+    if let Some(&(_, solution)) = memo.get(&code) {
+        return solution;
+    }
+
+    let solution = match board.game_result() {
+        GameResult::Win(winner) => Solution {
+            value: if winner == to_move { GameValue::Win } else { GameValue::Loss },
+            distance: 0,
+        },
+        GameResult::Draw => Solution { value: GameValue::Draw, distance: 0 },
+        GameResult::InProgress => canonical
+            .valid_moves()
+            .into_iter()
+            .map(|(row, col)| {
+                let mut child = canonical.clone();
+                child.make_move(row, col, to_move).unwrap();
+                solve(&child, to_move.opponent(), memo).parent()
+            })
+            .max_by_key(Solution::score)
+            .expect("in-progress position has a valid move"),
+    };
+
+    memo.insert(code, (canonical, solution));
+    solution
+}
@@ -0,0 +1,98 @@
+//! Turn-enforcing board wrapper for untrusted input
+
+use crate::backend::board::Board;
+use crate::backend::player::Player;
+
+/// A board that rejects out-of-turn moves and inconsistent positions
+///
+/// `Board::make_move` accepts any player so the simulation hot path stays as
+/// cheap as possible. `StrictBoard` adds the checks needed when positions or
+/// moves come from external sources: the piece counts must be consistent with
+/// alternating play from the starting player, and every move must be made by
+/// the player whose turn it is.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{StrictBoard, Player};
+///
+/// let mut board = StrictBoard::new();
+/// board.make_move(0, 0, Player::X).unwrap();
+/// assert!(board.make_move(1, 1, Player::X).is_err());
+/// assert!(board.make_move(1, 1, Player::O).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictBoard {
+    board: Board,
+    starting_player: Player,
+}
+
+impl StrictBoard {
+    /// Creates a new empty board where X moves first
+    pub fn new() -> Self {
+        Self::with_starting_player(Player::X)
+    }
+
+    /// Creates a new empty board where the given player moves first
+    pub fn with_starting_player(player: Player) -> Self {
+        StrictBoard {
+            board: Board::new(),
+            starting_player: player,
+        }
+    }
+
+    /// Wraps an existing board, validating its piece counts
+    ///
+    /// The starting player must have either the same number of pieces as the
+    /// opponent or exactly one more, and the position must pass
+    /// [`Board::validate`].
+    pub fn from_board(board: Board, starting_player: Player) -> Result<Self, &'static str> {
+        board.validate()?;
+        let first = board.count_pieces(starting_player);
+        let second = board.count_pieces(starting_player.opponent());
+        if first != second && first != second + 1 {
+            return Err("Inconsistent piece counts");
+        }
+        Ok(StrictBoard { board, starting_player })
+    }
+
+    /// Returns the player whose turn it is
+    pub fn next_player(&self) -> Player {
+        let first = self.board.count_pieces(self.starting_player);
+        let second = self.board.count_pieces(self.starting_player.opponent());
+        if first == second {
+            self.starting_player
+        } else {
+            self.starting_player.opponent()
+        }
+    }
+
+    /// Makes a move, rejecting it if it is not the given player's turn
+    pub fn make_move(&mut self, row: usize, col: usize, player: Player) -> Result<(), &'static str> {
+        if player != self.next_player() {
+            return Err("Not this player's turn");
+        }
+        self.board.make_move(row, col, player)
+    }
+
+    /// Returns the player who moved first
+    pub fn starting_player(&self) -> Player {
+        self.starting_player
+    }
+
+    /// Returns the underlying board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Consumes the wrapper and returns the underlying board
+    pub fn into_board(self) -> Board {
+        self.board
+    }
+}
+
+impl Default for StrictBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
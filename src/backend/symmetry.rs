@@ -0,0 +1,74 @@
+//! The eight symmetries of the 3x3 board
+
+/// A rotation or reflection of the 3x3 board
+///
+/// The eight symmetries form the dihedral group of the square. Each maps a
+/// cell `(row, col)` to its image with [`Symmetry::apply`]; applying the
+/// [`inverse`](Symmetry::inverse) maps it back.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::Symmetry;
+///
+/// // Rotating clockwise moves the top-left corner to the top-right
+/// assert_eq!(Symmetry::Rotate90.apply((0, 0)), (0, 2));
+/// assert_eq!(Symmetry::Rotate90.inverse().apply((0, 2)), (0, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Leaves the board unchanged
+    Identity,
+    /// Rotates the board 90° clockwise
+    Rotate90,
+    /// Rotates the board 180°
+    Rotate180,
+    /// Rotates the board 270° clockwise (90° counter-clockwise)
+    Rotate270,
+    /// Mirrors left and right (columns reversed)
+    MirrorHorizontal,
+    /// Mirrors top and bottom (rows reversed)
+    MirrorVertical,
+    /// Reflects across the main diagonal (rows and columns swapped)
+    Transpose,
+    /// Reflects across the anti-diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All eight symmetries, starting with the identity
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::MirrorHorizontal,
+        Symmetry::MirrorVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Maps a cell to its image under this symmetry
+    #[inline]
+    pub fn apply(self, (row, col): (usize, usize)) -> (usize, usize) {
+        match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, 2 - row),
+            Symmetry::Rotate180 => (2 - row, 2 - col),
+            Symmetry::Rotate270 => (2 - col, row),
+            Symmetry::MirrorHorizontal => (row, 2 - col),
+            Symmetry::MirrorVertical => (2 - row, col),
+            Symmetry::Transpose => (col, row),
+            Symmetry::AntiTranspose => (2 - col, 2 - row),
+        }
+    }
+
+    /// Returns the symmetry that undoes this one
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Fork and threat analysis
+//!
+//! A threat is an empty square that would win the game on the next move, as
+//! returned by [`Board::winning_moves`]. A fork creates two threats at once,
+//! which the opponent cannot both block. Under misère rules no move wins
+//! immediately, so there are no threats or forks and every position is quiet.
+
+use crate::backend::board::{Board, WIN_LINES};
+use crate::backend::game::GameResult;
+use crate::backend::player::{Cell, Player};
+
+/// Tactical situation of a position for the player to move, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TacticalStatus {
+    /// The game is over
+    Finished,
+    /// The player can win with one move
+    CanWin,
+    /// The opponent has two or more threats and the player cannot win first
+    Lost,
+    /// The opponent has exactly one threat, which the player must block
+    MustBlock,
+    /// The player can create a fork
+    CanFork,
+    /// None of the above
+    Quiet,
+}
+
+/// Threats, forks, and open lines of a position from one player's side
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, Threats, TacticalStatus};
+///
+/// // X on a1 and c3, O on b2 and c1: X must block a3, which also forks
+/// let board = Board::from_moves(&[(0, 0), (1, 1), (2, 2), (0, 2)]).unwrap();
+/// let threats = Threats::analyze(&board, Player::X);
+/// assert_eq!(threats.blocks, vec![(2, 0)]);
+/// assert!(threats.forks.contains(&(2, 0)));
+/// assert_eq!(threats.status, TacticalStatus::MustBlock);
+///
+/// let mut after = board.clone();
+/// after.make_move(2, 0, Player::X).unwrap();
+/// assert_eq!(Threats::analyze(&after, Player::O).status, TacticalStatus::Lost);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Threats {
+    pub player: Player,
+    /// Moves that win immediately
+    pub wins: Vec<(usize, usize)>,
+    /// Squares the opponent would win on, which must be blocked
+    pub blocks: Vec<(usize, usize)>,
+    /// Moves that create a fork for the player
+    pub forks: Vec<(usize, usize)>,
+    /// Squares where the opponent could create a fork
+    pub opponent_forks: Vec<(usize, usize)>,
+    /// Lines the player can still complete
+    pub open_lines: usize,
+    /// Lines the opponent can still complete
+    pub opponent_open_lines: usize,
+    pub status: TacticalStatus,
+}
+
+impl Threats {
+    /// Analyzes `board` with `player` to move
+    pub fn analyze(board: &Board, player: Player) -> Threats {
+        let wins = board.winning_moves(player);
+        let blocks = board.blocking_moves(player);
+        let forks = fork_moves(board, player);
+        let opponent_forks = fork_moves(board, player.opponent());
+
+        let status = if board.game_result() != GameResult::InProgress {
+            TacticalStatus::Finished
+        } else if !wins.is_empty() {
+            TacticalStatus::CanWin
+        } else if blocks.len() >= 2 {
+            TacticalStatus::Lost
+        } else if blocks.len() == 1 {
+            TacticalStatus::MustBlock
+        } else if !forks.is_empty() {
+            TacticalStatus::CanFork
+        } else {
+            TacticalStatus::Quiet
+        };
+
+        Threats {
+            player,
+            wins,
+            blocks,
+            forks,
+            opponent_forks,
+            open_lines: open_lines(board, player),
+            opponent_open_lines: open_lines(board, player.opponent()),
+            status,
+        }
+    }
+}
+
+/// Returns the moves after which `player` has two or more winning moves
+///
+/// Moves that win immediately are not forks and are excluded.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player, threats};
+///
+/// // X on opposite corners a1 and c3, O in the center
+/// let board = Board::from_moves(&[(0, 0), (1, 1), (2, 2)]).unwrap();
+/// assert_eq!(threats::fork_moves(&board, Player::X), vec![(0, 2), (2, 0)]);
+/// assert!(threats::fork_moves(&board, Player::O).is_empty());
+/// ```
+pub fn fork_moves(board: &Board, player: Player) -> Vec<(usize, usize)> {
+    if board.game_result() != GameResult::InProgress {
+        return Vec::new();
+    }
+    board
+        .valid_moves()
+        .into_iter()
+        .filter(|&(row, col)| {
+            let mut next = board.clone();
+            next.make_move(row, col, player).is_ok()
+                && next.game_result() == GameResult::InProgress
+                && next.winning_moves(player).len() >= 2
+        })
+        .collect()
+}
+
+/// Counts the lines that contain none of the opponent's pieces
+///
+/// These are the lines `player` could still complete; an empty board has
+/// eight open lines for each side.
+pub fn open_lines(board: &Board, player: Player) -> usize {
+    let blocker = Cell::Occupied(player.opponent());
+    WIN_LINES
+        .iter()
+        .filter(|line| line.iter().all(|&(row, col)| board[(row, col)] != blocker))
+        .count()
+}
@@ -0,0 +1,218 @@
+//! Transposition table shared across games
+//!
+//! A deterministic engine always answers the same position the same way, so
+//! its answer only needs computing once. [`CachedEngine`] remembers the moves
+//! of the engine it wraps in a [`TranspositionTable`] behind an `Arc`, which
+//! every clone of the engine shares: all games of a simulation, including
+//! those on other worker threads, reuse each search instead of repeating it.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use crate::backend::board::Board;
+use crate::backend::engine::{Engine, EngineMetadata, StatefulEngine};
+use crate::backend::game::Rules;
+use crate::backend::player::Player;
+
+/// Number of distinct board encodings
+const POSITIONS: usize = 19683;
+/// Marker for positions that have not been searched
+const EMPTY: u8 = 0;
+/// Marker for positions where the engine returned no move
+const NO_MOVE: u8 = 10;
+
+/// Moves chosen by a deterministic engine, indexed by position
+///
+/// Every position, player to move, and rule set has its own slot, so lookups
+/// and inserts are single atomic operations and the table can be shared
+/// between threads without locking. Counters of hits and misses are kept to
+/// judge how much searching the table saves.
+///
+/// A table remembers the answers of one engine configuration; sharing it
+/// between engines that would answer differently mixes up their moves.
+pub struct TranspositionTable {
+    slots: Box<[AtomicU8]>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    entries: AtomicU64,
+}
+
+/// Usage counters of a [`TranspositionTable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TableStats {
+    /// Lookups answered from the table
+    pub hits: u64,
+    /// Lookups that required a search
+    pub misses: u64,
+    /// Positions stored
+    pub entries: u64,
+}
+
+impl TableStats {
+    /// Returns the fraction of lookups answered from the table, or `0.0`
+    /// before the first lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl TranspositionTable {
+    /// Creates an empty table
+    pub fn new() -> Self {
+        TranspositionTable {
+            slots: (0..POSITIONS * 4).map(|_| AtomicU8::new(EMPTY)).collect(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            entries: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the stored move for `player` on `board`
+    ///
+    /// The outer `Option` is `None` if the position has not been stored; the
+    /// inner one is the engine's answer. Counts a hit or a miss.
+    pub fn get(&self, board: &Board, player: Player) -> Option<Option<(usize, usize)>> {
+        let slot = self.slots[slot(board, player)].load(Ordering::Relaxed);
+        if slot == EMPTY {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(match slot {
+            NO_MOVE => None,
+            index => Some(((index - 1) as usize / 3, (index - 1) as usize % 3)),
+        })
+    }
+
+    /// Stores the engine's answer for `player` on `board`
+    pub fn insert(&self, board: &Board, player: Player, chosen: Option<(usize, usize)>) {
+        let value = match chosen {
+            Some((row, col)) => (row * 3 + col + 1) as u8,
+            None => NO_MOVE,
+        };
+        let previous = self.slots[slot(board, player)].swap(value, Ordering::Relaxed);
+        if previous == EMPTY {
+            self.entries.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the hit, miss, and entry counts so far
+    pub fn stats(&self) -> TableStats {
+        TableStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Removes every entry and resets the counters
+    pub fn clear(&self) {
+        for slot in self.slots.iter() {
+            slot.store(EMPTY, Ordering::Relaxed);
+        }
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.entries.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TranspositionTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TranspositionTable").field("stats", &self.stats()).finish()
+    }
+}
+
+/// Returns the slot of `player` to move on `board`
+fn slot(board: &Board, player: Player) -> usize {
+    let rules = match board.rules() {
+        Rules::Standard => 0,
+        Rules::Misere => 2,
+    };
+    let player = match player {
+        Player::X => 0,
+        Player::O => 1,
+    };
+    (rules + player) * POSITIONS + board.encode() as usize
+}
+
+/// Wrapper that answers each position from a shared [`TranspositionTable`]
+///
+/// The wrapped engine must be deterministic: its first answer to a position
+/// is replayed from then on. Clones share the table, so a `CachedEngine`
+/// passed to a parallel simulation searches each position once across all
+/// worker threads.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use zttt_rs::backend::{CachedEngine, MinimaxEngine, TranspositionTable};
+/// use zttt_rs::simulation::{SimulationConfig, Simulator};
+///
+/// let table = Arc::new(TranspositionTable::new());
+/// let engine = CachedEngine::new(MinimaxEngine::new(), Arc::clone(&table));
+/// let config = SimulationConfig::builder()
+///     .num_games(100)
+///     .engines(engine.clone(), engine)
+///     .build();
+/// let result = Simulator::new(config).run_sequential();
+/// assert_eq!(result.draws, 100);
+///
+/// // Only the first game searched; the other 99 were replayed from the table
+/// let stats = table.stats();
+/// assert_eq!(stats.misses, 9);
+/// assert!(stats.hit_rate() > 0.98);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedEngine<E> {
+    inner: E,
+    table: Arc<TranspositionTable>,
+}
+
+impl<E: Engine> CachedEngine<E> {
+    /// Wraps `inner`, storing its moves in `table`
+    pub fn new(inner: E, table: Arc<TranspositionTable>) -> Self {
+        CachedEngine { inner, table }
+    }
+
+    /// Returns the wrapped engine
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns the shared table
+    pub fn table(&self) -> &Arc<TranspositionTable> {
+        &self.table
+    }
+}
+
+impl<E: Engine> Engine for CachedEngine<E> {
+    fn choose_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        if let Some(chosen) = self.table.get(board, player) {
+            return chosen;
+        }
+        let chosen = self.inner.choose_move(board, player);
+        self.table.insert(board, player, chosen);
+        chosen
+    }
+
+    /// Returns the wrapped engine's metadata, since the moves are the same
+    fn metadata(&self) -> EngineMetadata<'_> {
+        EngineMetadata {
+            name: Some(self.inner.name()),
+            version: self.inner.version(),
+            description: self.inner.description(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Board-agnostic game interface shared by all board variants
+//!
+//! The classic 3x3 [`Board`] is the fast path for simulations, but the same
+//! engines and simulation loop can drive other board shapes through the
+//! [`GameBoard`] and [`VariantEngine`] traits.
+
+use std::fmt::Debug;
+use crate::backend::board::Board;
+use crate::backend::board_n::BoardN;
+use crate::backend::board3d::Board3D;
+use crate::backend::engine::{Engine, FastEngine};
+use crate::backend::game::GameResult;
+use crate::backend::player::Player;
+
+/// Common interface for playable boards
+///
+/// Implemented by [`Board`] and every board variant, so generic code such as
+/// [`play_out`] and the variant simulator works with any of them.
+pub trait GameBoard: Clone {
+    /// Coordinates of a single move on this board
+    type Move: Copy + Debug + PartialEq;
+
+    /// Returns all legal moves, or an empty list if the game is over
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Places a piece for the given player
+    fn play(&mut self, mv: Self::Move, player: Player) -> Result<(), &'static str>;
+
+    /// Returns the current game result
+    fn result(&self) -> GameResult;
+}
+
+impl GameBoard for Board {
+    type Move = (usize, usize);
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        self.valid_moves()
+    }
+
+    fn play(&mut self, (row, col): Self::Move, player: Player) -> Result<(), &'static str> {
+        self.make_move(row, col, player)
+    }
+
+    fn result(&self) -> GameResult {
+        self.game_result()
+    }
+}
+
+/// Move selection for any [`GameBoard`]
+///
+/// Every [`Engine`] is a `VariantEngine<Board>`, so 3x3 engines work
+/// unchanged. Engines that support other boards implement this trait for them
+/// directly.
+pub trait VariantEngine<B: GameBoard> {
+    /// Choose a move for the given player on the given board
+    ///
+    /// Returns `None` if no valid moves are available or the game is over.
+    fn choose_variant_move(&self, board: &B, player: Player) -> Option<B::Move>;
+}
+
+impl<E: Engine + ?Sized> VariantEngine<Board> for E {
+    fn choose_variant_move(&self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.choose_move(board, player)
+    }
+}
+
+impl<const N: usize> VariantEngine<BoardN<N>> for FastEngine {
+    fn choose_variant_move(&self, board: &BoardN<N>, _player: Player) -> Option<(usize, usize)> {
+        board.legal_moves().into_iter().next()
+    }
+}
+
+impl VariantEngine<Board3D> for FastEngine {
+    fn choose_variant_move(&self, board: &Board3D, _player: Player) -> Option<(usize, usize, usize)> {
+        board.legal_moves().into_iter().next()
+    }
+}
+
+/// Plays a game to completion from the given board
+///
+/// `engine_x` and `engine_o` choose moves for their respective players,
+/// starting with `starting_player`. The game stops early if an engine has
+/// no move, in which case the returned result is `InProgress`.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{play_out, BoardN, FastEngine, GameResult, Player};
+///
+/// let result = play_out(BoardN::<4>::new(3), &FastEngine, &FastEngine, Player::X);
+/// assert_ne!(result, GameResult::InProgress);
+/// ```
+pub fn play_out<B, X, O>(mut board: B, engine_x: &X, engine_o: &O, starting_player: Player) -> GameResult
+where
+    B: GameBoard,
+    X: VariantEngine<B> + ?Sized,
+    O: VariantEngine<B> + ?Sized,
+{
+    let mut current = starting_player;
+    while board.result() == GameResult::InProgress {
+        let chosen = match current {
+            Player::X => engine_x.choose_variant_move(&board, current),
+            Player::O => engine_o.choose_variant_move(&board, current),
+        };
+        let Some(mv) = chosen else { break };
+        board.play(mv, current).expect("engine returned an invalid move");
+        current = current.opponent();
+    }
+    board.result()
+}
@@ -0,0 +1,271 @@
+//! Named benchmark scenarios
+//!
+//! A fixed set of scenarios covers board operations, each built-in engine,
+//! and the simulators. The names are stable, so results can be tracked over
+//! time; the `benches/` criterion suite runs the same scenarios. For a quick
+//! check without criterion, [`run_all`] measures each one for a time budget.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use zttt_rs::bench;
+//!
+//! let mut scenario = bench::scenario("simulator/fast").unwrap();
+//! let report = scenario.measure(Duration::from_millis(10));
+//! assert_eq!(report.unit, "games");
+//! println!("{}", report);
+//! ```
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::backend::{
+    Board, FastEngine, GameResult, MinimaxEngine, PerfectLookupEngine, Player, RandomEngine, Rules,
+    SolvedDatabase, StatefulEngine,
+};
+use crate::simulation::{BatchPolicy, BatchSimulator, ParallelConfig, ParallelSimulator, SimulationConfig, Simulator};
+
+/// Games per iteration of the simulator scenarios
+const SIMULATOR_GAMES: usize = 1_000;
+
+/// Games per iteration of the parallel simulator scenario
+const PARALLEL_GAMES: usize = 100_000;
+
+/// The moves of a drawn game, used by the board scenarios
+const DRAW: [(usize, usize); 9] = [(0, 0), (1, 1), (0, 1), (0, 2), (2, 0), (1, 0), (1, 2), (2, 1), (2, 2)];
+
+type Routine = Box<dyn FnMut() -> u64>;
+
+/// A named piece of work to time
+pub struct Scenario {
+    name: &'static str,
+    description: &'static str,
+    unit: &'static str,
+    units_per_iteration: u64,
+    routine: Routine,
+}
+
+/// Timing of a [`Scenario`] measured with [`Scenario::measure`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub name: &'static str,
+    /// What one unit of work is, e.g. `games` or `moves`
+    pub unit: &'static str,
+    pub iterations: u64,
+    /// Units of work done across all iterations
+    pub units: u64,
+    pub elapsed: Duration,
+}
+
+impl Scenario {
+    fn new(
+        name: &'static str,
+        description: &'static str,
+        unit: &'static str,
+        units_per_iteration: u64,
+        routine: impl FnMut() -> u64 + 'static,
+    ) -> Self {
+        Scenario { name, description, unit, units_per_iteration, routine: Box::new(routine) }
+    }
+
+    /// Returns the stable name, e.g. `engine/minimax`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns a one-line description of the work
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Returns what one unit of work is, e.g. `games`
+    pub fn unit(&self) -> &'static str {
+        self.unit
+    }
+
+    /// Returns the units of work done by one iteration
+    pub fn units_per_iteration(&self) -> u64 {
+        self.units_per_iteration
+    }
+
+    /// Runs one iteration and returns a checksum of its work
+    pub fn run_once(&mut self) -> u64 {
+        black_box((self.routine)())
+    }
+
+    /// Runs iterations until `budget` has passed (at least one) and reports the timing
+    pub fn measure(&mut self, budget: Duration) -> BenchReport {
+        let start = Instant::now();
+        let mut iterations = 0;
+        loop {
+            self.run_once();
+            iterations += 1;
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        BenchReport {
+            name: self.name,
+            unit: self.unit,
+            iterations,
+            units: iterations * self.units_per_iteration,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl BenchReport {
+    /// Returns units of work per second
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.units as f64 / secs
+        }
+    }
+}
+
+impl fmt::Display for BenchReport {
+    /// Writes e.g. `simulator/fast: 1823456 games/sec (1200 iterations)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:.0} {}/sec ({} iterations)", self.name, self.throughput(), self.unit, self.iterations)
+    }
+}
+
+/// Returns every scenario, in a stable order
+///
+/// Building the list solves the standard board once for `engine/perfect_lookup`.
+pub fn scenarios() -> Vec<Scenario> {
+    let positions = positions();
+    SolvedDatabase::shared(Rules::Standard);
+
+    vec![
+        Scenario::new("board/play_game", "plays a fixed drawn game move by move", "moves", 9, || {
+            let mut board = Board::new();
+            let mut player = Player::X;
+            for (row, col) in DRAW {
+                board.make_move(row, col, player).unwrap();
+                player = player.opponent();
+            }
+            board.encode() as u64
+        }),
+        Scenario::new("board/game_result", "checks the result after every move of a fixed game", "checks", 9, {
+            let boards = boards();
+            move || boards.iter().filter(|board| black_box(*board).game_result() != GameResult::InProgress).count() as u64
+        }),
+        Scenario::new("board/valid_moves", "lists the valid moves after every move of a fixed game", "boards", 9, {
+            let boards = boards();
+            move || boards.iter().map(|board| black_box(board).valid_moves().len() as u64).sum()
+        }),
+        engine_scenario("engine/fast", "asks FastEngine for a move in each benchmark position", &positions, FastEngine),
+        engine_scenario(
+            "engine/random",
+            "asks a seeded RandomEngine for a move in each benchmark position",
+            &positions,
+            RandomEngine::seeded(1),
+        ),
+        engine_scenario(
+            "engine/minimax",
+            "asks MinimaxEngine for a move in each benchmark position",
+            &positions,
+            MinimaxEngine::new(),
+        ),
+        engine_scenario(
+            "engine/perfect_lookup",
+            "asks PerfectLookupEngine for a move in each benchmark position",
+            &positions,
+            PerfectLookupEngine,
+        ),
+        Scenario::new("simulator/fast", "runs 1,000 FastEngine self-play games", "games", SIMULATOR_GAMES as u64, || {
+            let config = SimulationConfig::builder().num_games(SIMULATOR_GAMES).engine(FastEngine).build();
+            Simulator::new(config).run_sequential().x_wins as u64
+        }),
+        Scenario::new("simulator/random", "runs 1,000 seeded RandomEngine self-play games", "games", SIMULATOR_GAMES as u64, || {
+            let config = SimulationConfig::builder()
+                .num_games(SIMULATOR_GAMES)
+                .engine(RandomEngine::new())
+                .seed(1)
+                .build();
+            Simulator::new(config).run_sequential().x_wins as u64
+        }),
+        Scenario::new(
+            "simulator/batch_random",
+            "runs 100,000 seeded random games in lockstep batches",
+            "games",
+            PARALLEL_GAMES as u64,
+            || {
+                let simulator = BatchSimulator::builder()
+                    .num_games(PARALLEL_GAMES)
+                    .policy(BatchPolicy::Random)
+                    .seed(1)
+                    .build();
+                simulator.run().x_wins as u64
+            },
+        ),
+        Scenario::new(
+            "simulator/parallel_fast",
+            "runs 100,000 FastEngine self-play games on all cores",
+            "games",
+            PARALLEL_GAMES as u64,
+            || {
+                let config = ParallelConfig::builder().num_games(PARALLEL_GAMES).engine(FastEngine).build();
+                ParallelSimulator::new(config).run_parallel().x_wins as u64
+            },
+        ),
+    ]
+}
+
+/// Returns the scenario called `name`
+pub fn scenario(name: &str) -> Option<Scenario> {
+    scenarios().into_iter().find(|scenario| scenario.name == name)
+}
+
+/// Measures every scenario for `budget` each and returns the reports in order
+pub fn run_all(budget: Duration) -> Vec<BenchReport> {
+    scenarios().iter_mut().map(|scenario| scenario.measure(budget)).collect()
+}
+
+/// Returns a scenario asking `engine` for a move in each of `positions`
+fn engine_scenario(
+    name: &'static str,
+    description: &'static str,
+    positions: &[(Board, Player)],
+    mut engine: impl StatefulEngine + 'static,
+) -> Scenario {
+    let positions = positions.to_vec();
+    Scenario::new(name, description, "moves", positions.len() as u64, move || {
+        positions
+            .iter()
+            .filter_map(|(board, player)| engine.choose_move_mut(black_box(board), *player))
+            .map(|(row, col)| (row * 3 + col) as u64)
+            .sum()
+    })
+}
+
+/// Returns the boards after each move of the drawn game
+fn boards() -> Vec<Board> {
+    let mut board = Board::new();
+    let mut player = Player::X;
+    DRAW.iter()
+        .map(|&(row, col)| {
+            board.make_move(row, col, player).unwrap();
+            player = player.opponent();
+            board.clone()
+        })
+        .collect()
+}
+
+/// Returns the positions engines are benchmarked on: the empty board and
+/// every unfinished board of the drawn game, with the player to move
+fn positions() -> Vec<(Board, Player)> {
+    std::iter::once(Board::new())
+        .chain(boards())
+        .filter(|board| board.game_result() == GameResult::InProgress)
+        .map(|board| {
+            let player = board.current_player().unwrap_or(Player::X);
+            (board, player)
+        })
+        .collect()
+}
@@ -0,0 +1,272 @@
+//! gRPC simulation server
+//!
+//! Clients submit a simulation, poll its progress, and fetch the aggregated
+//! results once it finishes. The API is described in `proto/zttt.proto`.
+//!
+//! ```text
+//! cargo run --features grpc --bin zttt-server -- --addr 127.0.0.1:50051
+//! ```
+//!
+//! With the `metrics` feature, `--metrics-addr HOST:PORT` also serves
+//! Prometheus metrics over plain HTTP.
+
+mod proto;
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tonic::{Request, Response, Status};
+use zttt_rs::backend::{EngineRegistry, Player, Rules};
+use zttt_rs::simulation::{SimulationConfig, SimulationResult, Simulator, MAX_SIMULATION_GAMES};
+#[cfg(feature = "metrics")]
+use zttt_rs::simulation::SimulationMetrics;
+use proto::simulation_service_server::{SimulationService, SimulationServiceServer};
+use proto::{JobRequest, ProgressResponse, ResultResponse, SubmitRequest, SubmitResponse};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:50051";
+/// Games between progress updates of a running job
+const PROGRESS_INTERVAL: usize = 100;
+/// Jobs that may run at the same time
+const MAX_RUNNING_JOBS: usize = 8;
+/// Finished jobs kept for clients to fetch; older ones are evicted
+const MAX_FINISHED_JOBS: usize = 1000;
+
+/// Where a job has got to
+enum JobState {
+    Running,
+    Finished(Box<SimulationResult>),
+    /// The simulation panicked
+    Failed,
+}
+
+/// A simulation running in the background
+struct Job {
+    num_games: u64,
+    completed: Arc<AtomicU64>,
+    state: Arc<Mutex<JobState>>,
+}
+
+impl Job {
+    fn is_running(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), JobState::Running)
+    }
+}
+
+/// Holds one slot of the queue depth gauge, releasing it when dropped so a
+/// panicking job still leaves the queue
+#[cfg(feature = "metrics")]
+struct QueueSlot(prometheus::IntGauge);
+
+#[cfg(feature = "metrics")]
+impl QueueSlot {
+    fn new(gauge: &prometheus::IntGauge) -> Self {
+        gauge.inc();
+        QueueSlot(gauge.clone())
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+#[derive(Default)]
+struct Server {
+    registry: Arc<EngineRegistry>,
+    /// Jobs by id; ids increase, so the first finished jobs are the oldest
+    jobs: Mutex<BTreeMap<u64, Job>>,
+    next_id: AtomicU64,
+    #[cfg(feature = "metrics")]
+    metrics: SimulationMetrics,
+}
+
+impl Server {
+    fn new() -> Self {
+        Server {
+            registry: Arc::new(EngineRegistry::with_builtins()),
+            ..Default::default()
+        }
+    }
+
+    /// Validates a request and turns it into a simulation config
+    fn config(&self, request: &SubmitRequest) -> Result<SimulationConfig, &'static str> {
+        let spec_x = if request.engine_x.is_empty() { "fast" } else { &request.engine_x };
+        let spec_o = if request.engine_o.is_empty() { spec_x } else { &request.engine_o };
+        let starting_player = match request.starting_player.as_str() {
+            "" | "X" => Player::X,
+            "O" => Player::O,
+            _ => return Err("Starting player must be \"X\" or \"O\""),
+        };
+        if request.num_games > MAX_SIMULATION_GAMES as u64 {
+            return Err("Too many games for one request");
+        }
+        let rules = match request.rules.as_str() {
+            "" => Rules::Standard,
+            rules => rules.parse()?,
+        };
+
+        let mut builder = SimulationConfig::builder()
+            .num_games(request.num_games as usize)
+            .boxed_engines(self.registry.create(spec_x)?, self.registry.create(spec_o)?)
+            .starting_player(starting_player)
+            .rules(rules);
+        if let Some(seed) = request.seed {
+            builder = builder.seed(seed);
+        }
+        Ok(builder.build())
+    }
+
+    fn with_job<T>(&self, id: u64, f: impl FnOnce(&Job) -> T) -> Result<T, Status> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.get(&id).map(f).ok_or_else(|| Status::not_found("Unknown job id"))
+    }
+}
+
+#[tonic::async_trait]
+impl SimulationService for Server {
+    async fn submit(&self, request: Request<SubmitRequest>) -> Result<Response<SubmitResponse>, Status> {
+        let request = request.into_inner();
+        let config = self.config(&request).map_err(Status::invalid_argument)?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let finished: Vec<u64> = jobs.iter().filter(|(_, job)| !job.is_running()).map(|(&id, _)| id).collect();
+        if jobs.len() - finished.len() >= MAX_RUNNING_JOBS {
+            return Err(Status::resource_exhausted("Too many simulations running"));
+        }
+        for id in &finished[..finished.len().saturating_sub(MAX_FINISHED_JOBS)] {
+            jobs.remove(id);
+        }
+
+        let job = Job {
+            num_games: request.num_games,
+            completed: Arc::new(AtomicU64::new(0)),
+            state: Arc::new(Mutex::new(JobState::Running)),
+        };
+        let completed = Arc::clone(&job.completed);
+        let state = Arc::clone(&job.state);
+        #[cfg(feature = "metrics")]
+        let mut metrics = self.metrics.clone();
+        #[cfg(feature = "metrics")]
+        let slot = QueueSlot::new(metrics.queue_depth());
+        tokio::task::spawn_blocking(move || {
+            #[cfg(feature = "metrics")]
+            let _slot = slot;
+            let mut simulator = Simulator::new(config).with_progress(PROGRESS_INTERVAL, move |games, _, _| {
+                completed.store(games as u64, Ordering::Relaxed);
+            });
+            #[cfg(feature = "metrics")]
+            let run = move || simulator.run_with_observer(&mut metrics);
+            #[cfg(not(feature = "metrics"))]
+            let run = move || simulator.run_sequential();
+            *state.lock().unwrap() = match panic::catch_unwind(AssertUnwindSafe(run)) {
+                Ok(result) => JobState::Finished(Box::new(result)),
+                Err(_) => JobState::Failed,
+            };
+        });
+
+        let job_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        jobs.insert(job_id, job);
+        Ok(Response::new(SubmitResponse { job_id }))
+    }
+
+    async fn progress(&self, request: Request<JobRequest>) -> Result<Response<ProgressResponse>, Status> {
+        self.with_job(request.into_inner().job_id, |job| {
+            Response::new(ProgressResponse {
+                games_completed: job.completed.load(Ordering::Relaxed),
+                num_games: job.num_games,
+                finished: !job.is_running(),
+            })
+        })
+    }
+
+    async fn result(&self, request: Request<JobRequest>) -> Result<Response<ResultResponse>, Status> {
+        let result = self.with_job(request.into_inner().job_id, |job| match &*job.state.lock().unwrap() {
+            JobState::Running => Err(Status::failed_precondition("Job has not finished")),
+            JobState::Finished(result) => Ok(SimulationResult::clone(result)),
+            JobState::Failed => Err(Status::internal("Simulation failed")),
+        })??;
+        Ok(Response::new(ResultResponse {
+            games_completed: result.games_completed as u64,
+            x_wins: result.x_wins as u64,
+            o_wins: result.o_wins as u64,
+            draws: result.draws as u64,
+            duration_secs: result.total_duration.as_secs_f64(),
+            throughput: result.throughput,
+        }))
+    }
+}
+
+/// Command-line options
+struct Options {
+    addr: std::net::SocketAddr,
+    #[cfg(feature = "metrics")]
+    metrics_addr: Option<std::net::SocketAddr>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut args = std::env::args().skip(1);
+    let mut addr = DEFAULT_ADDR.to_string();
+    #[cfg(feature = "metrics")]
+    let mut metrics_addr = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => addr = args.next().ok_or("--addr needs a value")?,
+            #[cfg(feature = "metrics")]
+            "--metrics-addr" => metrics_addr = Some(args.next().ok_or("--metrics-addr needs a value")?),
+            "--help" | "-h" => {
+                println!("Usage: zttt-server [--addr HOST:PORT] [--metrics-addr HOST:PORT]  (default {})", DEFAULT_ADDR);
+                std::process::exit(0);
+            }
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    let parse = |addr: String| addr.parse().map_err(|e| format!("Invalid address {}: {}", addr, e));
+    Ok(Options {
+        addr: parse(addr)?,
+        #[cfg(feature = "metrics")]
+        metrics_addr: metrics_addr.map(parse).transpose()?,
+    })
+}
+
+/// Answers every connection on `addr` with the metrics in the Prometheus
+/// text format, on a background thread
+#[cfg(feature = "metrics")]
+fn serve_metrics(addr: std::net::SocketAddr, metrics: SimulationMetrics) -> std::io::Result<()> {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind(addr)?;
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let body = metrics.encode();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = parse_args()?;
+    let server = Server::new();
+    #[cfg(feature = "metrics")]
+    if let Some(addr) = options.metrics_addr {
+        serve_metrics(addr, server.metrics.clone())?;
+        println!("metrics on http://{}/metrics", addr);
+    }
+    println!("zttt-server listening on {}", options.addr);
+    tonic::transport::Server::builder()
+        .add_service(SimulationServiceServer::new(server))
+        .serve(options.addr)
+        .await?;
+    Ok(())
+}
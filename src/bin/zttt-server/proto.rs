@@ -0,0 +1,59 @@
+//! Messages and generated service code for the `zttt` gRPC package
+//!
+//! Mirrors `proto/zttt.proto`.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SubmitRequest {
+    #[prost(uint64, tag = "1")]
+    pub num_games: u64,
+    #[prost(string, tag = "2")]
+    pub engine_x: String,
+    #[prost(string, tag = "3")]
+    pub engine_o: String,
+    #[prost(string, tag = "4")]
+    pub starting_player: String,
+    #[prost(string, tag = "5")]
+    pub rules: String,
+    #[prost(uint64, optional, tag = "6")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SubmitResponse {
+    #[prost(uint64, tag = "1")]
+    pub job_id: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct JobRequest {
+    #[prost(uint64, tag = "1")]
+    pub job_id: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProgressResponse {
+    #[prost(uint64, tag = "1")]
+    pub games_completed: u64,
+    #[prost(uint64, tag = "2")]
+    pub num_games: u64,
+    #[prost(bool, tag = "3")]
+    pub finished: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResultResponse {
+    #[prost(uint64, tag = "1")]
+    pub games_completed: u64,
+    #[prost(uint64, tag = "2")]
+    pub x_wins: u64,
+    #[prost(uint64, tag = "3")]
+    pub o_wins: u64,
+    #[prost(uint64, tag = "4")]
+    pub draws: u64,
+    #[prost(double, tag = "5")]
+    pub duration_secs: f64,
+    #[prost(double, tag = "6")]
+    pub throughput: f64,
+}
+
+include!(concat!(env!("OUT_DIR"), "/zttt.SimulationService.rs"));
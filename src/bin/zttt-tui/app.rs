@@ -0,0 +1,235 @@
+//! State of the terminal UI, independent of drawing
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use zttt_rs::backend::{
+    BoxedEngine, EngineRegistry, Game, GameResult, GameValue, Player, Solution, SolvedDatabase,
+};
+use zttt_rs::simulation::{SimulationConfig, SimulationResult, Simulator};
+
+/// Engine specs the user can cycle through
+pub const ENGINES: [&str; 5] = ["random", "fast", "minimax:depth=2", "minimax:depth=4", "perfect"];
+
+/// Games per batch sent from the simulation thread
+const BATCH_SIZE: usize = 500;
+/// Number of samples kept for the dashboard graphs
+const HISTORY_LEN: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Play,
+    Simulate,
+}
+
+pub struct App {
+    pub tab: Tab,
+    pub should_quit: bool,
+    pub registry: EngineRegistry,
+    pub play: PlayState,
+    pub sim: SimState,
+}
+
+/// Human (X) versus engine (O) on a cursor-driven board
+pub struct PlayState {
+    pub game: Game,
+    pub cursor: (usize, usize),
+    pub engine_index: usize,
+    engine: BoxedEngine,
+    pub message: String,
+}
+
+/// Engine-vs-engine simulation running in the background
+pub struct SimState {
+    pub engine_x: usize,
+    pub engine_o: usize,
+    pub totals: SimulationResult,
+    /// Games per second of each recent batch
+    pub throughput: Vec<u64>,
+    /// (games played, X win %, O win %, draw %) after each recent batch
+    pub rates: Vec<(f64, f64, f64, f64)>,
+    stop: Option<Arc<AtomicBool>>,
+    batches: Option<Receiver<SimulationResult>>,
+}
+
+impl App {
+    pub fn new() -> Self {
+        let registry = EngineRegistry::with_builtins();
+        let engine_index = 2;
+        let engine = registry.create(ENGINES[engine_index]).expect("built-in engine");
+        App {
+            tab: Tab::Play,
+            should_quit: false,
+            registry,
+            play: PlayState {
+                game: Game::new(),
+                cursor: (1, 1),
+                engine_index,
+                engine,
+                message: "Move with arrows, Enter to play".to_string(),
+            },
+            sim: SimState {
+                engine_x: 1,
+                engine_o: 0,
+                totals: SimulationResult::new(),
+                throughput: Vec::new(),
+                rates: Vec::new(),
+                stop: None,
+                batches: None,
+            },
+        }
+    }
+
+    pub fn toggle_tab(&mut self) {
+        self.tab = match self.tab {
+            Tab::Play => Tab::Simulate,
+            Tab::Simulate => Tab::Play,
+        };
+    }
+
+    pub fn move_cursor(&mut self, d_row: isize, d_col: isize) {
+        let (row, col) = self.play.cursor;
+        self.play.cursor = (
+            (row as isize + d_row).rem_euclid(3) as usize,
+            (col as isize + d_col).rem_euclid(3) as usize,
+        );
+    }
+
+    /// Plays the human's move at the cursor and the engine's reply
+    pub fn play_at_cursor(&mut self) {
+        let play = &mut self.play;
+        if play.game.result() != GameResult::InProgress {
+            return;
+        }
+        let (row, col) = play.cursor;
+        if let Err(e) = play.game.play(row, col) {
+            play.message = e.to_string();
+            return;
+        }
+        if play.game.result() == GameResult::InProgress {
+            let player = play.game.current_player();
+            if let Some((row, col)) = play.engine.choose_move_mut(play.game.board(), player) {
+                play.game.play(row, col).expect("engine returned an invalid move");
+            }
+        }
+        play.message = match play.game.result() {
+            GameResult::Win(Player::X) => "You win! Press n for a new game".to_string(),
+            GameResult::Win(Player::O) => "The engine wins. Press n for a new game".to_string(),
+            GameResult::Draw => "Draw. Press n for a new game".to_string(),
+            GameResult::InProgress => "Your move".to_string(),
+        };
+    }
+
+    pub fn new_game(&mut self) {
+        self.play.game = Game::new();
+        self.play.message = "Your move".to_string();
+    }
+
+    pub fn cycle_play_engine(&mut self) {
+        self.play.engine_index = (self.play.engine_index + 1) % ENGINES.len();
+        self.play.engine = self.registry.create(ENGINES[self.play.engine_index]).expect("built-in engine");
+        self.new_game();
+    }
+
+    /// Returns each legal move with its perfect-play value for the player to move
+    pub fn evaluations(&self) -> Vec<((usize, usize), Solution)> {
+        let mut values = SolvedDatabase::shared(self.play.game.rules()).move_values(self.play.game.board());
+        values.sort_by_key(|(_, s)| std::cmp::Reverse(s.score()));
+        values
+    }
+
+    pub fn cycle_sim_engine(&mut self, player: Player) {
+        if self.sim.stop.is_some() {
+            return;
+        }
+        let index = match player {
+            Player::X => &mut self.sim.engine_x,
+            Player::O => &mut self.sim.engine_o,
+        };
+        *index = (*index + 1) % ENGINES.len();
+    }
+
+    /// Starts or stops the background simulation
+    pub fn toggle_simulation(&mut self) {
+        if let Some(stop) = self.sim.stop.take() {
+            stop.store(true, Ordering::Relaxed);
+            self.sim.batches = None;
+            return;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let specs = (ENGINES[self.sim.engine_x], ENGINES[self.sim.engine_o]);
+        let flag = Arc::clone(&stop);
+        thread::spawn(move || {
+            let registry = EngineRegistry::with_builtins();
+            let (Ok(x), Ok(o)) = (registry.create(specs.0), registry.create(specs.1)) else {
+                return;
+            };
+            let mut simulator = Simulator::new(
+                SimulationConfig::builder()
+                    .num_games(BATCH_SIZE)
+                    .boxed_engines(x, o)
+                    .build(),
+            );
+            while !flag.load(Ordering::Relaxed) {
+                if sender.send(simulator.run_sequential()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.sim.totals = SimulationResult::new();
+        self.sim.throughput.clear();
+        self.sim.rates.clear();
+        self.sim.stop = Some(stop);
+        self.sim.batches = Some(receiver);
+    }
+
+    pub fn simulation_running(&self) -> bool {
+        self.sim.stop.is_some()
+    }
+
+    /// Folds finished batches into the dashboard
+    pub fn poll_simulation(&mut self) {
+        let Some(batches) = &self.sim.batches else {
+            return;
+        };
+        let batches: Vec<_> = batches.try_iter().collect();
+        let sim = &mut self.sim;
+        for batch in batches {
+            sim.totals.merge(&batch);
+            push_bounded(&mut sim.throughput, batch.throughput as u64);
+            let t = &sim.totals;
+            push_bounded(
+                &mut sim.rates,
+                (t.games_completed as f64, t.win_rate(Player::X), t.win_rate(Player::O), t.draw_rate()),
+            );
+        }
+    }
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        if let Some(stop) = &self.sim.stop {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+fn push_bounded<T>(history: &mut Vec<T>, value: T) {
+    if history.len() == HISTORY_LEN {
+        history.remove(0);
+    }
+    history.push(value);
+}
+
+/// Describes a perfect-play value, e.g. "win in 3"
+pub fn describe(solution: &Solution) -> String {
+    match solution.value {
+        GameValue::Win => format!("win in {}", solution.distance),
+        GameValue::Loss => format!("loss in {}", solution.distance),
+        GameValue::Draw => "draw".to_string(),
+    }
+}
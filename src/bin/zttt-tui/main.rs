@@ -0,0 +1,63 @@
+//! Terminal UI: play against an engine and watch simulations live
+//!
+//! The play tab has a cursor-driven board with a sidebar showing the
+//! perfect-play value of every move. The dashboard tab runs engine-vs-engine
+//! simulations in the background and graphs throughput and win rates.
+//!
+//! ```text
+//! cargo run --features tui --bin zttt-tui
+//! ```
+
+mod app;
+mod ui;
+
+use std::io;
+use std::time::Duration;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use zttt_rs::backend::Player;
+use app::{App, Tab};
+
+/// How often the dashboard refreshes while waiting for input
+const TICK: Duration = Duration::from_millis(100);
+
+fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let mut app = App::new();
+    while !app.should_quit {
+        app.poll_simulation();
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match (app.tab, key.code) {
+            (_, KeyCode::Char('q') | KeyCode::Esc) => app.should_quit = true,
+            (_, KeyCode::Tab) => app.toggle_tab(),
+            (Tab::Play, KeyCode::Up | KeyCode::Char('k')) => app.move_cursor(-1, 0),
+            (Tab::Play, KeyCode::Down | KeyCode::Char('j')) => app.move_cursor(1, 0),
+            (Tab::Play, KeyCode::Left | KeyCode::Char('h')) => app.move_cursor(0, -1),
+            (Tab::Play, KeyCode::Right | KeyCode::Char('l')) => app.move_cursor(0, 1),
+            (Tab::Play, KeyCode::Enter | KeyCode::Char(' ')) => app.play_at_cursor(),
+            (Tab::Play, KeyCode::Char('n')) => app.new_game(),
+            (Tab::Play, KeyCode::Char('e')) => app.cycle_play_engine(),
+            (Tab::Simulate, KeyCode::Char('s')) => app.toggle_simulation(),
+            (Tab::Simulate, KeyCode::Char('x')) => app.cycle_sim_engine(Player::X),
+            (Tab::Simulate, KeyCode::Char('o')) => app.cycle_sim_engine(Player::O),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,168 @@
+//! Drawing of the play screen and the simulation dashboard
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph, Sparkline, Tabs};
+use ratatui::Frame;
+use zttt_rs::backend::{Cell, GameValue, Player};
+use crate::app::{describe, App, Tab, ENGINES};
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let selected = match app.tab {
+        Tab::Play => 0,
+        Tab::Simulate => 1,
+    };
+    frame.render_widget(
+        Tabs::new(["Play", "Simulate"])
+            .select(selected)
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .block(Block::bordered().title(" zttt ")),
+        header,
+    );
+
+    let help = match app.tab {
+        Tab::Play => "arrows/hjkl move  enter play  n new game  e engine  tab dashboard  q quit",
+        Tab::Simulate => "s start/stop  x engine X  o engine O  tab play  q quit",
+    };
+    frame.render_widget(Paragraph::new(help).dark_gray(), footer);
+
+    match app.tab {
+        Tab::Play => draw_play(frame, body, app),
+        Tab::Simulate => draw_simulate(frame, body, app),
+    }
+}
+
+fn draw_play(frame: &mut Frame, area: Rect, app: &App) {
+    let [board_area, sidebar] = Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).areas(area);
+    let play = &app.play;
+    let board = play.game.board();
+
+    let mut lines = Vec::new();
+    for row in 0..3 {
+        let mut spans = Vec::new();
+        for col in 0..3 {
+            let (symbol, color) = match board.get(row, col) {
+                Some(Cell::Occupied(Player::X)) => ("X", Color::Cyan),
+                Some(Cell::Occupied(Player::O)) => ("O", Color::Magenta),
+                _ => ("·", Color::DarkGray),
+            };
+            let mut style = Style::new().fg(color).add_modifier(Modifier::BOLD);
+            if play.game.last_move() == Some((row, col)) {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            if play.cursor == (row, col) {
+                style = style.bg(Color::Yellow).fg(Color::Black);
+            }
+            spans.push(Span::styled(format!("  {}  ", symbol), style));
+            if col < 2 {
+                spans.push(Span::raw("│"));
+            }
+        }
+        lines.push(Line::from(spans));
+        if row < 2 {
+            lines.push(Line::raw("─────┼─────┼─────"));
+        }
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::raw(play.message.as_str()));
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(format!(" You (X) vs {} ", ENGINES[play.engine_index]))),
+        board_area,
+    );
+
+    let mut lines = vec![Line::raw(format!("Perfect play for {}:", play.game.current_player())), Line::raw("")];
+    for ((row, col), solution) in app.evaluations() {
+        let color = match solution.value {
+            GameValue::Win => Color::Green,
+            GameValue::Draw => Color::Yellow,
+            GameValue::Loss => Color::Red,
+        };
+        let mut line = Line::from(vec![
+            Span::raw(format!("({}, {})  ", row, col)),
+            Span::styled(describe(&solution), Style::new().fg(color)),
+        ]);
+        if play.cursor == (row, col) {
+            line = line.add_modifier(Modifier::REVERSED);
+        }
+        lines.push(line);
+    }
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Evaluation ")), sidebar);
+}
+
+fn draw_simulate(frame: &mut Frame, area: Rect, app: &App) {
+    let [summary, throughput, rates] = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Length(7),
+        Constraint::Min(8),
+    ])
+    .areas(area);
+    let sim = &app.sim;
+    let totals = &sim.totals;
+
+    let status = if app.simulation_running() { "running".green() } else { "stopped".red() };
+    let lines = vec![
+        Line::from(vec![
+            Span::raw(format!("X: {}   O: {}   ", ENGINES[sim.engine_x], ENGINES[sim.engine_o])),
+            status,
+        ]),
+        Line::raw(format!(
+            "{} games in {:.1}s ({:.0} games/s)",
+            totals.games_completed,
+            totals.total_duration.as_secs_f64(),
+            totals.throughput
+        )),
+        Line::raw(format!(
+            "X wins {:.1}%   O wins {:.1}%   draws {:.1}%",
+            totals.win_rate(Player::X),
+            totals.win_rate(Player::O),
+            totals.draw_rate()
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Simulation ")), summary);
+
+    frame.render_widget(
+        Sparkline::default()
+            .data(&sim.throughput)
+            .style(Style::new().fg(Color::Green))
+            .block(Block::bordered().title(" Throughput (games/s per batch) ")),
+        throughput,
+    );
+
+    let series = |select: fn(&(f64, f64, f64, f64)) -> f64| -> Vec<(f64, f64)> {
+        sim.rates.iter().map(|r| (r.0, select(r))).collect()
+    };
+    let (x_wins, o_wins, draws) = (series(|r| r.1), series(|r| r.2), series(|r| r.3));
+    let first = sim.rates.first().map_or(0.0, |r| r.0);
+    let last = sim.rates.last().map_or(1.0, |r| r.0).max(first + 1.0);
+    let dataset = |name: &'static str, data, color| {
+        Dataset::default()
+            .name(name)
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::new().fg(color))
+            .data(data)
+    };
+    let chart = Chart::new(vec![
+        dataset("X wins", &x_wins, Color::Cyan),
+        dataset("O wins", &o_wins, Color::Magenta),
+        dataset("draws", &draws, Color::Yellow),
+    ])
+    .block(Block::bordered().title(" Win rates (%) "))
+    .x_axis(
+        Axis::default()
+            .title("games")
+            .bounds([first, last])
+            .labels([format!("{:.0}", first), format!("{:.0}", last)]),
+    )
+    .y_axis(Axis::default().bounds([0.0, 100.0]).labels(["0", "50", "100"]));
+    frame.render_widget(chart, rates);
+}
@@ -0,0 +1,86 @@
+//! `zttt analyze`: positions, games, and engines against perfect play
+
+use clap::Args;
+use zttt_rs::analysis::{analyze, annotate_game, exploitability_with_rules};
+use zttt_rs::backend::{
+    Board, BoxedEngine, EngineRegistry, Game, GameRecord, Player, Pos, Rules, StatefulEngine,
+};
+use zttt_rs::simulation::estimate_strength;
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    /// Squares played so far, e.g. `b2 a1`; X moves first
+    #[arg(conflicts_with = "engine")]
+    moves: Vec<String>,
+    /// Label every move of the game instead of analyzing the final position
+    #[arg(long)]
+    annotate: bool,
+    /// Rate an engine spec by exploitability and strength instead
+    #[arg(long)]
+    engine: Option<String>,
+    /// Completing three in a row loses
+    #[arg(long)]
+    misere: bool,
+    /// Times each engine position is sampled when rating a random engine
+    #[arg(long, default_value_t = 20)]
+    samples: usize,
+}
+
+pub fn run(args: AnalyzeArgs) -> Result<(), String> {
+    let rules = if args.misere { Rules::Misere } else { Rules::Standard };
+    if let Some(spec) = &args.engine {
+        return rate_engine(spec, rules, args.samples);
+    }
+
+    let moves = args
+        .moves
+        .iter()
+        .map(|square| square.parse::<Pos>().map(|pos| (pos.row(), pos.col())).map_err(|e| format!("{}: {}", square, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if args.annotate {
+        let record = GameRecord::new(rules, Player::X, &moves)?;
+        for annotation in annotate_game(&record)? {
+            println!("{}", annotation);
+        }
+        return Ok(());
+    }
+
+    let mut game = Game::with_rules(rules);
+    for &(row, col) in &moves {
+        game.play(row, col)?;
+    }
+    let board: &Board = game.board();
+    println!("{}\n", board);
+    let player = board.current_player().ok_or("the game is over")?;
+    println!("{}", analyze(board, player)?);
+    Ok(())
+}
+
+fn rate_engine(spec: &str, rules: Rules, samples: usize) -> Result<(), String> {
+    let registry = EngineRegistry::with_builtins();
+    let create = || registry.create(spec).map_err(|e| format!("engine \"{}\": {}", spec, e));
+
+    println!("{}", exploitability_with_rules(create()?.as_mut(), rules, samples));
+    if rules == Rules::Standard {
+        println!("{}", estimate_strength(Spec(create()?)));
+    }
+    Ok(())
+}
+
+/// Lets a registry-built engine be passed where an engine value is expected
+struct Spec(BoxedEngine);
+
+impl StatefulEngine for Spec {
+    fn choose_move_mut(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        self.0.choose_move_mut(board, player)
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.0.reseed(seed);
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
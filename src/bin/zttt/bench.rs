@@ -0,0 +1,37 @@
+//! `zttt bench`: quick timings of the named benchmark scenarios
+
+use std::time::Duration;
+use clap::Args;
+use zttt_rs::bench;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Run only scenarios whose name starts with one of these, e.g. `engine/`
+    filters: Vec<String>,
+    /// Time spent on each scenario, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    budget_ms: u64,
+    /// List the scenarios without running them
+    #[arg(long)]
+    list: bool,
+}
+
+pub fn run(args: BenchArgs) -> Result<(), String> {
+    let selected: Vec<_> = bench::scenarios()
+        .into_iter()
+        .filter(|scenario| args.filters.is_empty() || args.filters.iter().any(|f| scenario.name().starts_with(f.as_str())))
+        .collect();
+    if selected.is_empty() {
+        return Err("no scenario matches the filters".to_string());
+    }
+
+    let budget = Duration::from_millis(args.budget_ms);
+    for mut scenario in selected {
+        if args.list {
+            println!("{:<28} {}", scenario.name(), scenario.description());
+        } else {
+            println!("{}", scenario.measure(budget));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! `zttt check`: play a position file and fail if any case misses its
+//! expected result
+
+use clap::Args;
+use zttt_rs::backend::EngineRegistry;
+use zttt_rs::simulation::{ScenarioRunner, ScenarioSuite};
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Position file, one `<board> [X|O] [1-0|0-1|1/2-1/2] # name` per line
+    file: String,
+    /// Engine spec playing the side to move in every case
+    #[arg(short, long, default_value = "minimax")]
+    engine: String,
+    /// Engine spec for the other side; defaults to the same engine
+    #[arg(short, long)]
+    opponent: Option<String>,
+    /// Games played from each position
+    #[arg(short = 'n', long, default_value_t = 1)]
+    games: usize,
+    /// Seed for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+pub fn run(args: CheckArgs) -> Result<(), String> {
+    let suite = ScenarioSuite::from_file(&args.file).map_err(|e| format!("{}: {}", args.file, e))?;
+    let registry = EngineRegistry::with_builtins();
+    let mut builder = ScenarioRunner::builder().games_per_case(args.games);
+    for spec in std::iter::once(&args.engine).chain(&args.opponent) {
+        let engine = registry.create(spec).map_err(|e| format!("engine \"{}\": {}", spec, e))?;
+        builder = builder.add_boxed(spec, engine);
+    }
+    if let Some(seed) = args.seed {
+        builder = builder.seed(seed);
+    }
+
+    let report = builder.build()?.run(&suite);
+    println!("{}", report);
+    if report.all_passed() {
+        Ok(())
+    } else {
+        Err(format!("{} of {} cases failed", report.failed(), report.outcomes.len()))
+    }
+}
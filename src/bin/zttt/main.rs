@@ -0,0 +1,61 @@
+//! Command-line front end to the library
+//!
+//! Engines are given as registry specs such as `fast`, `random:seed=42`, or
+//! `minimax:depth=3`.
+//!
+//! ```text
+//! cargo run --features cli --bin zttt -- simulate -x minimax:depth=2 -o random -n 10000
+//! cargo run --features cli --bin zttt -- simulate --config run.toml
+//! cargo run --features cli --bin zttt -- tournament fast random perfect --games 50
+//! cargo run --features cli --bin zttt -- analyze b2 a1
+//! cargo run --features cli --bin zttt -- play --difficulty hard
+//! cargo run --features cli --bin zttt -- bench engine/
+//! cargo run --features cli --bin zttt -- check positions.txt -e minimax:depth=3 -o random
+//! ```
+
+mod analyze;
+mod bench;
+mod check;
+mod play;
+mod simulate;
+mod tournament;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "zttt", version, about = "Simulate, rank, analyze, and play TicTacToe engines")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play many games between two engines and summarize the results
+    Simulate(simulate::SimulateArgs),
+    /// Run a round-robin tournament and print the standings
+    Tournament(tournament::TournamentArgs),
+    /// Analyze a position, annotate a game, or rate an engine against perfect play
+    Analyze(analyze::AnalyzeArgs),
+    /// Play against an engine in the terminal
+    Play(play::PlayArgs),
+    /// Time the named benchmark scenarios
+    Bench(bench::BenchArgs),
+    /// Play a position file and fail if any case misses its expected result
+    Check(check::CheckArgs),
+}
+
+fn main() {
+    let result = match Cli::parse().command {
+        Command::Simulate(args) => simulate::run(args),
+        Command::Tournament(args) => tournament::run(args),
+        Command::Analyze(args) => analyze::run(args),
+        Command::Play(args) => play::run(args),
+        Command::Bench(args) => bench::run(args),
+        Command::Check(args) => check::run(args),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(2);
+    }
+}
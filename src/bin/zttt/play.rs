@@ -0,0 +1,163 @@
+//! `zttt play`: a human against an engine in the terminal
+//!
+//! ```text
+//! zttt play --difficulty hard
+//! zttt play --engine "minimax:depth=3" --second
+//! ```
+
+use std::io::{self, BufRead, Write};
+use clap::Args;
+use zttt_rs::backend::{BoxedEngine, Cell, EngineRegistry, Game, GameResult, Player, Rules};
+
+#[derive(Args)]
+pub struct PlayArgs {
+    /// Engine spec, e.g. "random" or "minimax:depth=3"
+    #[arg(long, conflicts_with = "difficulty")]
+    engine: Option<String>,
+    /// easy, medium, hard, or perfect
+    #[arg(long, default_value = "medium")]
+    difficulty: String,
+    /// Let the engine move first
+    #[arg(long)]
+    second: bool,
+    /// Completing three in a row loses
+    #[arg(long)]
+    misere: bool,
+}
+
+struct Options {
+    engine: String,
+    human: Player,
+    rules: Rules,
+}
+
+/// Maps a difficulty level to an engine spec
+fn difficulty_spec(level: &str) -> Result<&'static str, String> {
+    match level {
+        "easy" => Ok("random"),
+        "medium" => Ok("minimax:depth=2"),
+        "hard" => Ok("minimax:depth=4"),
+        "perfect" => Ok("perfect"),
+        _ => Err(format!("Unknown difficulty: {} (expected easy, medium, hard, or perfect)", level)),
+    }
+}
+
+/// Draws the board, numbering empty cells 1-9 for input and bracketing the
+/// last move
+fn render(game: &Game) -> String {
+    let board = game.board();
+    let mut out = String::new();
+    for row in 0..3 {
+        let cells: Vec<String> = (0..3)
+            .map(|col| match board.get(row, col) {
+                Some(Cell::Occupied(player)) if game.last_move() == Some((row, col)) => format!("[{}]", player),
+                Some(Cell::Occupied(player)) => format!(" {} ", player),
+                _ => format!(" {} ", row * 3 + col + 1),
+            })
+            .collect();
+        out.push_str(&format!("{}|{}|{}\n", cells[0], cells[1], cells[2]));
+        if row < 2 {
+            out.push_str("---+---+---\n");
+        }
+    }
+    out
+}
+
+/// Reads the human's move; returns `None` on quit or end of input
+fn read_move(input: &mut impl BufRead, game: &Game) -> io::Result<Option<(usize, usize)>> {
+    loop {
+        print!("Your move (1-9, q to quit): ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line.eq_ignore_ascii_case("q") {
+            return Ok(None);
+        }
+        match line.parse::<usize>() {
+            Ok(n @ 1..=9) if game.board().is_valid_move((n - 1) / 3, (n - 1) % 3) => {
+                return Ok(Some(((n - 1) / 3, (n - 1) % 3)));
+            }
+            Ok(1..=9) => println!("That cell is taken."),
+            _ => println!("Enter a number from 1 to 9."),
+        }
+    }
+}
+
+/// Plays one game; returns `None` if the human quit
+fn play_game(input: &mut impl BufRead, engine: &mut BoxedEngine, options: &Options) -> io::Result<Option<GameResult>> {
+    let mut game = Game::with_rules(options.rules);
+    println!("\n{}", render(&game));
+
+    while game.result() == GameResult::InProgress {
+        let player = game.current_player();
+        let (row, col) = if player == options.human {
+            match read_move(input, &game)? {
+                Some(mv) => mv,
+                None => return Ok(None),
+            }
+        } else {
+            let mv = engine
+                .choose_move_mut(game.board(), player)
+                .expect("engine has a move while the game is in progress");
+            println!("Engine plays {}", mv.0 * 3 + mv.1 + 1);
+            mv
+        };
+        game.play(row, col).expect("move was validated");
+        println!("\n{}", render(&game));
+    }
+    Ok(Some(game.result()))
+}
+
+pub fn run(args: PlayArgs) -> Result<(), String> {
+    let options = Options {
+        engine: match args.engine {
+            Some(spec) => spec,
+            None => difficulty_spec(&args.difficulty)?.to_string(),
+        },
+        human: if args.second { Player::O } else { Player::X },
+        rules: if args.misere { Rules::Misere } else { Rules::Standard },
+    };
+    let mut engine = EngineRegistry::with_builtins()
+        .create(&options.engine)
+        .map_err(|e| format!("engine \"{}\": {}", options.engine, e))?;
+
+    println!("You are {} against \"{}\"{}.", options.human, options.engine, match options.rules {
+        Rules::Standard => "",
+        Rules::Misere => " (misère: three in a row loses)",
+    });
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let (mut wins, mut losses, mut draws) = (0, 0, 0);
+    loop {
+        match play_game(&mut input, &mut engine, &options) {
+            Ok(Some(GameResult::Win(player))) if player == options.human => {
+                wins += 1;
+                println!("You win!");
+            }
+            Ok(Some(GameResult::Win(_))) => {
+                losses += 1;
+                println!("The engine wins.");
+            }
+            Ok(Some(_)) => {
+                draws += 1;
+                println!("It's a draw.");
+            }
+            Ok(None) => break,
+            Err(e) => return Err(e.to_string()),
+        }
+        println!("Score: {} wins, {} losses, {} draws", wins, losses, draws);
+
+        print!("Play again? [y/N] ");
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        if input.read_line(&mut answer).is_err() || !answer.trim().eq_ignore_ascii_case("y") {
+            break;
+        }
+    }
+    println!("Thanks for playing!");
+    Ok(())
+}
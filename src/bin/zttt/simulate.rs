@@ -0,0 +1,117 @@
+//! `zttt simulate`: engine-vs-engine runs
+
+use std::path::PathBuf;
+use clap::Args;
+use zttt_rs::backend::{EngineRegistry, Rules};
+use zttt_rs::simulation::{ParallelConfig, ParallelSimulator, SimulationConfig, Simulator, StartingPlayerPolicy};
+
+#[derive(Args)]
+pub struct SimulateArgs {
+    /// Load the run from a TOML or JSON config file
+    #[arg(long, conflicts_with_all = ["engine", "x", "o", "games", "seed", "misere", "alternate", "manifest"])]
+    config: Option<PathBuf>,
+    /// Engine spec playing both sides
+    #[arg(short, long, conflicts_with_all = ["x", "o"])]
+    engine: Option<String>,
+    /// Engine spec playing X
+    #[arg(short, long, requires = "o")]
+    x: Option<String>,
+    /// Engine spec playing O
+    #[arg(short, long, requires = "x")]
+    o: Option<String>,
+    /// Number of games
+    #[arg(short = 'n', long)]
+    games: Option<usize>,
+    /// Seed for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Completing three in a row loses
+    #[arg(long)]
+    misere: bool,
+    /// Alternate which side moves first
+    #[arg(long)]
+    alternate: bool,
+    /// Worker threads; more than one runs the games in parallel
+    #[arg(short = 'j', long, default_value_t = 1)]
+    threads: usize,
+    /// Save the run's configuration to a TOML or JSON file before starting
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// Show a progress bar on stderr
+    #[arg(long)]
+    progress: bool,
+    /// Print the run's reproducibility manifest after the results
+    #[arg(long)]
+    manifest: bool,
+}
+
+pub fn run(args: SimulateArgs) -> Result<(), String> {
+    let registry = EngineRegistry::with_builtins();
+    let config = match &args.config {
+        Some(path) => SimulationConfig::from_file(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None => build_config(&args, &registry)?,
+    };
+    if let Some(path) = &args.save {
+        config.to_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    let result = if args.threads > 1 {
+        let (spec_x, spec_o) = config.engine_specs().ok_or("engines have no registry specs")?;
+        let source = |spec: &str| {
+            let spec = spec.to_string();
+            move || EngineRegistry::with_builtins().create(&spec).expect("spec was validated")
+        };
+        let mut parallel = ParallelConfig::builder()
+            .num_games(config.num_games())
+            .num_threads(args.threads)
+            .rules(config.rules())
+            .starting_player_policy(config.starting_player_policy())
+            .record_games(config.record_policy())
+            .on_engine_error(config.engine_error_policy())
+            .manifest(config.manifest());
+        parallel = match spec_o {
+            Some(spec_o) => parallel.engine_sources(source(spec_x), source(spec_o)),
+            None => parallel.engine_source(source(spec_x)),
+        };
+        if let Some(seed) = config.seed() {
+            parallel = parallel.seed(seed);
+        }
+        let mut simulator = ParallelSimulator::new(parallel.build());
+        if args.progress {
+            simulator = simulator.with_progress_bar();
+        }
+        simulator.run_parallel()
+    } else {
+        let mut simulator = Simulator::new(config);
+        if args.progress {
+            simulator = simulator.with_progress_bar();
+        }
+        simulator.run_sequential()
+    };
+    println!("{}", result);
+    Ok(())
+}
+
+fn build_config(args: &SimulateArgs, registry: &EngineRegistry) -> Result<SimulationConfig, String> {
+    let mut builder = SimulationConfig::builder();
+    builder = match (&args.engine, &args.x, &args.o) {
+        (Some(spec), _, _) => builder.engine_spec(registry, spec).map_err(|e| format!("engine \"{}\": {}", spec, e))?,
+        (None, Some(x), Some(o)) => builder
+            .engine_specs(registry, x, o)
+            .map_err(|e| format!("engines \"{}\" / \"{}\": {}", x, o, e))?,
+        _ => builder,
+    };
+    if let Some(games) = args.games {
+        builder = builder.num_games(games);
+    }
+    if let Some(seed) = args.seed {
+        builder = builder.seed(seed);
+    }
+    if args.misere {
+        builder = builder.rules(Rules::Misere);
+    }
+    if args.alternate {
+        builder = builder.starting_player_policy(StartingPlayerPolicy::Alternate);
+    }
+    Ok(builder.manifest(args.manifest).build())
+}
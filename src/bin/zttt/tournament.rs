@@ -0,0 +1,53 @@
+//! `zttt tournament`: round-robin between engine specs
+
+use std::io;
+use clap::Args;
+use zttt_rs::backend::{EngineRegistry, Rules};
+use zttt_rs::simulation::Tournament;
+
+#[derive(Args)]
+pub struct TournamentArgs {
+    /// Engine specs to enter, at least two; each is listed under its spec
+    #[arg(required = true, num_args = 2..)]
+    engines: Vec<String>,
+    /// Games each pair of engines plays
+    #[arg(short = 'n', long, default_value_t = 100)]
+    games: usize,
+    /// Seed for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Completing three in a row loses
+    #[arg(long)]
+    misere: bool,
+    /// Print the cross-table as CSV instead of a table
+    #[arg(long)]
+    csv: bool,
+    /// Show a progress bar on stderr
+    #[arg(long)]
+    progress: bool,
+}
+
+pub fn run(args: TournamentArgs) -> Result<(), String> {
+    let registry = EngineRegistry::with_builtins();
+    let mut builder = Tournament::builder()
+        .games_per_matchup(args.games)
+        .rules(if args.misere { Rules::Misere } else { Rules::Standard });
+    for spec in &args.engines {
+        let engine = registry.create(spec).map_err(|e| format!("engine \"{}\": {}", spec, e))?;
+        builder = builder.add_boxed(spec, engine);
+    }
+    if let Some(seed) = args.seed {
+        builder = builder.seed(seed);
+    }
+    if args.progress {
+        builder = builder.progress_bar();
+    }
+
+    let result = builder.build()?.run();
+    if args.csv {
+        result.write_csv(io::stdout().lock()).map_err(|e| e.to_string())
+    } else {
+        println!("{}", result);
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+//! REST API for playing and simulating (requires the `http` feature)
+//!
+//! | Method | Path | Body | Response |
+//! |--------|------|------|----------|
+//! | `POST` | `/games` | [`NewGame`] | [`GameView`] |
+//! | `GET` | `/games/{id}` | | [`GameView`] |
+//! | `POST` | `/games/{id}/moves` | [`MoveRequest`] | [`GameView`] |
+//! | `GET` | `/games/{id}/suggestion?engine=SPEC` | | [`Suggestion`] |
+//! | `GET` | `/games/{id}/analysis` | | [`AnalysisView`] |
+//! | `POST` | `/simulations` | [`SimulationRequest`] | [`SimulationSummary`] |
+//! | `GET` | `/metrics` | | Prometheus text format (`metrics` feature) |
+//!
+//! Engines are named by [`EngineRegistry`] specs such as `"minimax:depth=3"`.
+//! Errors are returned as `{"error": "<message>"}` with a 4xx status. The
+//! server keeps the newest [`MAX_GAMES`] games; older ones are forgotten.
+//!
+//! ```no_run
+//! # async fn run() -> std::io::Result<()> {
+//! zttt_rs::http::serve("127.0.0.1:8080").await
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use crate::analysis::analyze;
+use crate::backend::{EngineRegistry, Game, GameResult, Player, Rules};
+use crate::simulation::{SimulationConfig, SimulationResult, Simulator};
+pub use crate::simulation::MAX_SIMULATION_GAMES;
+
+/// Games kept in memory; creating another forgets the oldest
+pub const MAX_GAMES: usize = 10_000;
+#[cfg(feature = "metrics")]
+use crate::simulation::SimulationMetrics;
+
+/// Body of `POST /games`; every field is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct NewGame {
+    /// `"standard"` (default) or `"misere"`
+    pub rules: Option<String>,
+    /// `"X"` (default) or `"O"`
+    pub starting_player: Option<String>,
+}
+
+/// A game as returned by the API
+#[derive(Debug, Serialize)]
+pub struct GameView {
+    pub id: u64,
+    /// Cells in row-major order as `X`, `O`, or `.`
+    pub board: String,
+    pub rules: String,
+    pub current_player: String,
+    /// `"X"`, `"O"`, `"draw"`, or `"in_progress"`
+    pub result: String,
+    pub moves: Vec<[usize; 2]>,
+}
+
+/// Body of `POST /games/{id}/moves`
+#[derive(Debug, Deserialize)]
+pub struct MoveRequest {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Query of `GET /games/{id}/suggestion`
+#[derive(Debug, Deserialize)]
+pub struct SuggestionQuery {
+    /// Engine spec; defaults to `"minimax"`
+    pub engine: Option<String>,
+}
+
+/// An engine's suggested move, or `null` if the game is over
+#[derive(Debug, Serialize)]
+pub struct Suggestion {
+    #[serde(rename = "move")]
+    pub position: Option<[usize; 2]>,
+}
+
+/// Perfect-play analysis of a game's current position, for the player to move
+#[derive(Debug, Serialize)]
+pub struct AnalysisView {
+    /// e.g. `"draw"` or `"win in 3"`
+    pub value: String,
+    pub best_moves: Vec<[usize; 2]>,
+    /// Every legal move, best first
+    pub moves: Vec<MoveValue>,
+}
+
+/// One legal move in an [`AnalysisView`]
+#[derive(Debug, Serialize)]
+pub struct MoveValue {
+    #[serde(rename = "move")]
+    pub position: [usize; 2],
+    pub value: String,
+}
+
+/// Body of `POST /simulations`
+#[derive(Debug, Deserialize)]
+pub struct SimulationRequest {
+    pub num_games: usize,
+    /// Engine spec for X; defaults to `"fast"`
+    pub engine_x: Option<String>,
+    /// Engine spec for O; defaults to the spec for X
+    pub engine_o: Option<String>,
+    pub starting_player: Option<String>,
+    pub rules: Option<String>,
+    pub seed: Option<u64>,
+}
+
+/// Aggregated results of a simulation
+#[derive(Debug, Serialize)]
+pub struct SimulationSummary {
+    pub games_completed: usize,
+    pub x_wins: usize,
+    pub o_wins: usize,
+    pub draws: usize,
+    pub duration_secs: f64,
+    pub throughput: f64,
+    /// The run's [`RunManifest`](crate::simulation::RunManifest) entries
+    pub manifest: BTreeMap<&'static str, String>,
+}
+
+/// An error returned as JSON with a status code
+#[derive(Debug)]
+struct ApiError(StatusCode, &'static str);
+
+impl ApiError {
+    fn bad_request(message: &'static str) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+#[derive(Default)]
+struct AppState {
+    registry: EngineRegistry,
+    /// Games by id; ids increase, so the first game is the oldest
+    games: Mutex<BTreeMap<u64, Game>>,
+    next_id: AtomicU64,
+    #[cfg(feature = "metrics")]
+    metrics: SimulationMetrics,
+}
+
+/// Builds the API router with a fresh, in-memory game store
+pub fn router() -> Router {
+    let state = AppState {
+        registry: EngineRegistry::with_builtins(),
+        ..Default::default()
+    };
+    let router = Router::new()
+        .route("/games", post(create_game))
+        .route("/games/{id}", get(get_game))
+        .route("/games/{id}/moves", post(play_move))
+        .route("/games/{id}/suggestion", get(suggest_move))
+        .route("/games/{id}/analysis", get(analyze_game))
+        .route("/simulations", post(run_simulation));
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(metrics));
+    router.with_state(Arc::new(state))
+}
+
+/// Serves the API on `addr` until the process exits
+pub async fn serve(addr: &str) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router()).await
+}
+
+async fn create_game(
+    State(state): State<Arc<AppState>>,
+    body: Option<Json<NewGame>>,
+) -> Result<Json<GameView>, ApiError> {
+    let Json(request) = body.unwrap_or_default();
+    let rules = parse_rules(request.rules.as_deref())?;
+    let starting_player = parse_player(request.starting_player.as_deref())?;
+
+    let game = Game::with_rules_and_starting_player(rules, starting_player);
+    let id = state.next_id.fetch_add(1, Ordering::Relaxed);
+    let view = view(id, &game);
+    let mut games = state.games.lock().unwrap();
+    while games.len() >= MAX_GAMES {
+        games.pop_first();
+    }
+    games.insert(id, game);
+    Ok(Json(view))
+}
+
+async fn get_game(State(state): State<Arc<AppState>>, Path(id): Path<u64>) -> Result<Json<GameView>, ApiError> {
+    let games = state.games.lock().unwrap();
+    let game = games.get(&id).ok_or(ApiError(StatusCode::NOT_FOUND, "Unknown game id"))?;
+    Ok(Json(view(id, game)))
+}
+
+async fn play_move(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+    Json(request): Json<MoveRequest>,
+) -> Result<Json<GameView>, ApiError> {
+    let mut games = state.games.lock().unwrap();
+    let game = games.get_mut(&id).ok_or(ApiError(StatusCode::NOT_FOUND, "Unknown game id"))?;
+    game.play(request.row, request.col).map_err(ApiError::bad_request)?;
+    Ok(Json(view(id, game)))
+}
+
+async fn suggest_move(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+    Query(query): Query<SuggestionQuery>,
+) -> Result<Json<Suggestion>, ApiError> {
+    let game = state
+        .games
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .ok_or(ApiError(StatusCode::NOT_FOUND, "Unknown game id"))?;
+    let mut engine = state
+        .registry
+        .create(query.engine.as_deref().unwrap_or("minimax"))
+        .map_err(ApiError::bad_request)?;
+
+    let position = if game.result() == GameResult::InProgress {
+        tokio::task::spawn_blocking(move || engine.choose_move_mut(game.board(), game.current_player()))
+            .await
+            .map_err(|_| ApiError(StatusCode::INTERNAL_SERVER_ERROR, "Engine failed"))?
+    } else {
+        None
+    };
+    Ok(Json(Suggestion {
+        position: position.map(|(row, col)| [row, col]),
+    }))
+}
+
+async fn analyze_game(State(state): State<Arc<AppState>>, Path(id): Path<u64>) -> Result<Json<AnalysisView>, ApiError> {
+    let game = state
+        .games
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .ok_or(ApiError(StatusCode::NOT_FOUND, "Unknown game id"))?;
+    let analysis = analyze(game.board(), game.current_player()).map_err(ApiError::bad_request)?;
+    Ok(Json(AnalysisView {
+        value: analysis.value.to_string(),
+        best_moves: analysis.best_moves.iter().map(|&(row, col)| [row, col]).collect(),
+        moves: analysis
+            .moves
+            .iter()
+            .map(|&((row, col), eval)| MoveValue {
+                position: [row, col],
+                value: eval.to_string(),
+            })
+            .collect(),
+    }))
+}
+
+async fn run_simulation(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SimulationRequest>,
+) -> Result<Json<SimulationSummary>, ApiError> {
+    if request.num_games > MAX_SIMULATION_GAMES {
+        return Err(ApiError::bad_request("Too many games for one request"));
+    }
+    let spec_x = request.engine_x.as_deref().unwrap_or("fast");
+    let spec_o = request.engine_o.as_deref().unwrap_or(spec_x);
+    let mut builder = SimulationConfig::builder()
+        .num_games(request.num_games)
+        .engine_specs(&state.registry, spec_x, spec_o)
+        .map_err(ApiError::bad_request)?
+        .starting_player(parse_player(request.starting_player.as_deref())?)
+        .rules(parse_rules(request.rules.as_deref())?)
+        .manifest(true);
+    if let Some(seed) = request.seed {
+        builder = builder.seed(seed);
+    }
+    let config = builder.build();
+
+    let result = tokio::task::spawn_blocking(simulation(&state, config))
+        .await
+        .map_err(|_| ApiError(StatusCode::INTERNAL_SERVER_ERROR, "Simulation failed"))?;
+    Ok(Json(SimulationSummary {
+        games_completed: result.games_completed,
+        x_wins: result.x_wins,
+        o_wins: result.o_wins,
+        draws: result.draws,
+        duration_secs: result.total_duration.as_secs_f64(),
+        throughput: result.throughput,
+        manifest: result.manifest.map(|manifest| manifest.entries().into_iter().collect()).unwrap_or_default(),
+    }))
+}
+
+/// Returns a job running `config`, counted in the server's metrics
+#[cfg(feature = "metrics")]
+fn simulation(state: &AppState, config: SimulationConfig) -> impl FnOnce() -> SimulationResult {
+    let mut metrics = state.metrics.clone();
+    metrics.queue_depth().inc();
+    move || {
+        let result = Simulator::new(config).run_with_observer(&mut metrics);
+        metrics.queue_depth().dec();
+        result
+    }
+}
+
+/// Returns a job running `config`
+#[cfg(not(feature = "metrics"))]
+fn simulation(_state: &AppState, config: SimulationConfig) -> impl FnOnce() -> SimulationResult {
+    move || Simulator::new(config).run_sequential()
+}
+
+#[cfg(feature = "metrics")]
+async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    ([(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.encode())
+}
+
+fn view(id: u64, game: &Game) -> GameView {
+    GameView {
+        id,
+        board: game.board().cell_string(),
+        rules: game.rules().to_string(),
+        current_player: game.current_player().to_string(),
+        result: match game.result() {
+            GameResult::Win(player) => player.to_string(),
+            GameResult::Draw => "draw".to_string(),
+            GameResult::InProgress => "in_progress".to_string(),
+        },
+        moves: game.moves().iter().map(|&(row, col)| [row, col]).collect(),
+    }
+}
+
+fn parse_rules(rules: Option<&str>) -> Result<Rules, ApiError> {
+    rules.map_or(Ok(Rules::Standard), |r| r.parse().map_err(ApiError::bad_request))
+}
+
+fn parse_player(player: Option<&str>) -> Result<Player, ApiError> {
+    match player {
+        None | Some("X") => Ok(Player::X),
+        Some("O") => Ok(Player::O),
+        Some(_) => Err(ApiError::bad_request("Player must be \"X\" or \"O\"")),
+    }
+}
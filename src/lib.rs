@@ -14,9 +14,10 @@
 //! - **Pluggable engine trait**: Implement custom move selection logic for different use cases
 //! - **High-speed engine**: FastEngine for maximum throughput in simulations
 //!
-//! ### Simulation Module (Planned)
+//! ### Simulation Module
 //! High-performance simulation framework for batch processing:
-//! - **Sequential and parallel simulation runners**: Scale from single to multi-core
+//! - **Sequential simulation runner**: Configurable batch runs with aggregated results
+//! - **Parallel simulation runners (planned)**: Scale from single to multi-core
 //! - **Configurable simulation scenarios**: Flexible setup for various use cases
 //! - **Statistics and analysis**: Comprehensive data collection and insights
 //! - **Tournament system**: Engine matchups and comparisons
@@ -24,6 +25,29 @@
 //!
 //! See [`simulation`] module documentation for detailed planning and roadmap.
 //!
+//! ### Analysis Module
+//! Engine and game analysis against the solved game:
+//! - **Exploitability**: Expected score of a best response against an engine
+//! - **Move annotation**: Best/Inaccuracy/Blunder labels for every move of a game
+//! - **Position analysis**: Optimal value and moves plus every legal move's value
+//!
+//! ## Optional Features
+//!
+//! The core has no dependencies. Integrations are opt-in:
+//! - **`tokio`**: `AsyncEngine` and `Simulator::run_async`
+//! - **`remote`**: `RemoteEngine` and an engine server over TCP
+//! - **`wasm`**: `wasm_bindgen` exports of the board and built-in engines
+//! - **`http`**: a REST API for playing and simulating (see [`http`] when enabled)
+//! - **`grpc`**: the `zttt-server` binary, a gRPC simulation service (see `proto/zttt.proto`)
+//! - **`arrow`**: Arrow record batches and Parquet files of per-game and per-move data
+//! - **`zstd`**: compressed, seekable `.zttt` archives of game records
+//! - **`tui`**: the `zttt-tui` binary, a terminal UI for playing and watching simulations
+//! - **`config`**: loading and saving `SimulationConfig`s as TOML or JSON files
+//! - **`tracing`**: spans around simulations, games, engine calls, tournament matchups, and bracket series
+//! - **`metrics`**: Prometheus counters and gauges for long runs, served at `/metrics` by the REST API
+//! - **`indicatif`**: progress bars with ETA and live throughput for simulators and tournaments
+//! - **`cli`**: the `zttt` binary with `simulate`, `tournament`, `analyze`, `play`, and `bench` subcommands
+//!
 //! ## Example
 //!
 //! ```
@@ -44,9 +68,32 @@ pub mod backend;
 // Simulation module - high-performance batch simulation framework
 pub mod simulation;
 
+// Analysis against perfect play - exploitability and move quality
+pub mod analysis;
+
+// SVG rendering for reports
+pub mod render;
+
+// Named benchmark scenarios shared with the criterion suite
+pub mod bench;
+
+// REST API for demos and teaching material
+#[cfg(feature = "http")]
+pub mod http;
+
+// WebAssembly bindings for web frontends
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(test)]
 mod tests {
     use crate::backend::*;
+    use crate::simulation::*;
+
+    /// Clears move timings, which differ between runs that play the same games
+    fn untimed(records: &[GameRecord]) -> Vec<GameRecord> {
+        records.iter().map(|record| GameRecord { move_durations: Vec::new(), ..record.clone() }).collect()
+    }
 
     #[test]
     fn test_new_board() {
@@ -216,4 +263,3025 @@ mod tests {
         assert_eq!(board.game_result(), GameResult::Win(Player::X));
         assert!(board.make_move(2, 2, Player::O).is_err());
     }
+
+    #[test]
+    fn test_game_alternates_turns() {
+        let mut game = Game::new();
+        assert_eq!(game.current_player(), Player::X);
+        game.play(0, 0).unwrap();
+        assert_eq!(game.current_player(), Player::O);
+        game.play(1, 1).unwrap();
+        assert_eq!(game.board().get(1, 1), Some(Cell::Occupied(Player::O)));
+        assert_eq!(game.moves(), &[(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_game_rejected_move_keeps_turn() {
+        let mut game = Game::new();
+        game.play(0, 0).unwrap();
+        assert!(game.play(0, 0).is_err());
+        assert_eq!(game.current_player(), Player::O);
+        assert_eq!(game.moves().len(), 1);
+    }
+
+    #[test]
+    fn test_game_undo() {
+        let mut game = Game::with_starting_player(Player::O);
+        assert_eq!(game.undo(), None);
+        game.play(2, 2).unwrap();
+        game.play(0, 0).unwrap();
+        assert_eq!(game.undo(), Some((0, 0)));
+        assert_eq!(game.current_player(), Player::X);
+        assert_eq!(game.board().get(0, 0), Some(Cell::Empty));
+        assert_eq!(game.moves(), &[(2, 2)]);
+    }
+
+    #[test]
+    fn test_game_result_and_undo_after_win() {
+        let mut game = Game::new();
+        for &(row, col) in &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            game.play(row, col).unwrap();
+        }
+        assert_eq!(game.result(), GameResult::Win(Player::X));
+        assert!(game.play(2, 2).is_err());
+        game.undo();
+        assert_eq!(game.result(), GameResult::InProgress);
+    }
+
+    #[test]
+    fn test_strict_board_rejects_out_of_turn() {
+        let mut board = StrictBoard::new();
+        assert!(board.make_move(0, 0, Player::O).is_err());
+        board.make_move(0, 0, Player::X).unwrap();
+        assert!(board.make_move(1, 1, Player::X).is_err());
+        board.make_move(1, 1, Player::O).unwrap();
+        assert_eq!(board.next_player(), Player::X);
+    }
+
+    #[test]
+    fn test_strict_board_from_board_validates_counts() {
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        assert!(StrictBoard::from_board(board.clone(), Player::X).is_err());
+
+        board.make_move(1, 1, Player::O).unwrap();
+        let strict = StrictBoard::from_board(board.clone(), Player::X).unwrap();
+        assert_eq!(strict.next_player(), Player::O);
+        assert!(StrictBoard::from_board(board, Player::O).is_err());
+    }
+
+    #[test]
+    fn test_winning_line() {
+        let mut board = Board::new();
+        assert_eq!(board.winning_line(), None);
+        board.make_move(0, 2, Player::O).unwrap();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(2, 0, Player::O).unwrap();
+        assert_eq!(board.winning_line(), Some([(0, 2), (1, 1), (2, 0)]));
+
+        let outcome = board.outcome();
+        assert_eq!(outcome.result, GameResult::Win(Player::O));
+        assert_eq!(outcome.line, Some([(0, 2), (1, 1), (2, 0)]));
+    }
+
+    #[test]
+    fn test_outcome_without_winner() {
+        let board = Board::new();
+        let outcome = board.outcome();
+        assert_eq!(outcome.result, GameResult::InProgress);
+        assert_eq!(outcome.line, None);
+    }
+
+    #[test]
+    fn test_current_player_from_counts() {
+        let mut board = Board::new();
+        assert_eq!(board.current_player(), Some(Player::X));
+        board.make_move(0, 0, Player::X).unwrap();
+        assert_eq!(board.current_player(), Some(Player::O));
+        board.make_move(1, 1, Player::O).unwrap();
+        assert_eq!(board.current_player(), Some(Player::X));
+        board.make_move(2, 2, Player::O).unwrap();
+        assert_eq!(board.current_player(), None);
+    }
+
+    #[test]
+    fn test_cached_result_tracks_undo() {
+        let mut game = Game::new();
+        // X O X / X O O / O X X
+        for &(row, col) in &[(0, 0), (0, 1), (0, 2), (1, 1), (1, 0), (1, 2), (2, 1), (2, 0), (2, 2)] {
+            game.play(row, col).unwrap();
+        }
+        assert_eq!(game.result(), GameResult::Draw);
+        game.undo();
+        assert_eq!(game.result(), GameResult::InProgress);
+        assert_eq!(game.board().valid_moves(), vec![(2, 2)]);
+        game.play(2, 2).unwrap();
+        assert_eq!(game.result(), GameResult::Draw);
+    }
+
+    #[test]
+    fn test_cached_result_last_move_in_middle_of_line() {
+        let mut board = Board::new();
+        board.make_move(1, 0, Player::O).unwrap();
+        board.make_move(1, 2, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::InProgress);
+        board.make_move(1, 1, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::Win(Player::O));
+    }
+
+    #[test]
+    fn test_board_n_row_win() {
+        let mut board = BoardN::<4>::new(3);
+        board.make_move(3, 1, Player::O).unwrap();
+        board.make_move(3, 2, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::InProgress);
+        board.make_move(3, 3, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::Win(Player::O));
+        assert!(board.make_move(0, 0, Player::X).is_err());
+    }
+
+    #[test]
+    fn test_board_n_diagonal_wins() {
+        let mut board = BoardN::<5>::new(4);
+        for i in 1..5 {
+            board.make_move(i, i, Player::X).unwrap();
+        }
+        assert_eq!(board.game_result(), GameResult::Win(Player::X));
+
+        let mut board = BoardN::<5>::new(4);
+        for i in 0..4 {
+            board.make_move(i, 4 - i, Player::O).unwrap();
+        }
+        assert_eq!(board.game_result(), GameResult::Win(Player::O));
+    }
+
+    #[test]
+    fn test_board_n_bounds_and_draw() {
+        let mut board = BoardN::<2>::new(2);
+        assert!(board.make_move(2, 0, Player::X).is_err());
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        assert_eq!(board.valid_moves(), vec![(0, 1), (1, 0)]);
+
+        let mut board = BoardN::<4>::default();
+        // Fill with a pattern that has no complete line
+        let pattern = [[0, 0, 1, 1], [1, 1, 0, 0], [0, 0, 1, 1], [1, 1, 0, 0]];
+        for (row, cols) in pattern.iter().enumerate() {
+            for (col, &p) in cols.iter().enumerate() {
+                let player = if p == 0 { Player::X } else { Player::O };
+                board.make_move(row, col, player).unwrap();
+            }
+        }
+        assert_eq!(board.game_result(), GameResult::Draw);
+    }
+
+    #[test]
+    fn test_simulator_runs_all_games() {
+        let config = SimulationConfig::builder()
+            .num_games(100)
+            .engine(FastEngine)
+            .build();
+        let mut seen = 0;
+        let result = Simulator::new(config).run_with_callback(|_| seen += 1);
+        assert_eq!(seen, 100);
+        assert_eq!(result.games_completed, 100);
+        // FastEngine always plays the first free cell, so X wins down the left column
+        assert_eq!(result.x_wins, 100);
+        assert_eq!(result.win_rate(Player::X), 100.0);
+    }
+
+    #[test]
+    fn test_simulator_starting_player() {
+        let config = SimulationConfig::builder()
+            .num_games(10)
+            .starting_player(Player::O)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.o_wins, 10);
+    }
+
+    #[test]
+    fn test_simulate_variant_board_n() {
+        let result = simulate_variant(&BoardN::<4>::new(4), &FastEngine, 10, Player::X);
+        assert_eq!(result.games_completed, 10);
+        assert_eq!(result.x_wins + result.o_wins + result.draws, 10);
+    }
+
+    #[test]
+    fn test_misere_completing_line_loses() {
+        let mut game = Game::with_rules(Rules::Misere);
+        for &(row, col) in &[(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+            game.play(row, col).unwrap();
+        }
+        assert_eq!(game.result(), GameResult::Win(Player::O));
+        assert_eq!(game.outcome().line, Some([(0, 0), (1, 1), (2, 2)]));
+        game.undo();
+        assert_eq!(game.result(), GameResult::InProgress);
+        assert_eq!(game.rules(), Rules::Misere);
+    }
+
+    #[test]
+    fn test_misere_reset_keeps_rules() {
+        let mut board = Board::with_rules(Rules::Misere);
+        board.make_move(1, 1, Player::X).unwrap();
+        board.reset();
+        assert_eq!(board.rules(), Rules::Misere);
+        assert_eq!(board, Board::with_rules(Rules::Misere));
+    }
+
+    #[test]
+    fn test_simulator_misere_rules() {
+        let config = SimulationConfig::builder()
+            .num_games(10)
+            .rules(Rules::Misere)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        // Same games as under standard rules, but X's completed line now loses
+        assert_eq!(result.o_wins, 10);
+    }
+
+    #[test]
+    fn test_board3d_has_49_distinct_lines() {
+        let mut lines = WIN_LINES_3D.to_vec();
+        lines.sort();
+        lines.dedup();
+        assert_eq!(lines.len(), 49);
+        assert!(lines.iter().all(|line| line.count_ones() == 3));
+    }
+
+    #[test]
+    fn test_board3d_wins_across_layers() {
+        let mut board = Board3D::new();
+        board.make_move(0, 1, 2, Player::O).unwrap();
+        board.make_move(1, 1, 2, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::InProgress);
+        board.make_move(2, 1, 2, Player::O).unwrap();
+        assert_eq!(board.game_result(), GameResult::Win(Player::O));
+        assert!(!board.is_valid_move(0, 0, 0));
+        assert!(board.make_move(0, 0, 0, Player::X).is_err());
+    }
+
+    #[test]
+    fn test_board3d_moves_and_bounds() {
+        let mut board = Board3D::new();
+        assert_eq!(board.valid_moves().len(), 27);
+        assert!(board.make_move(3, 0, 0, Player::X).is_err());
+        board.make_move(1, 1, 1, Player::X).unwrap();
+        assert!(board.make_move(1, 1, 1, Player::O).is_err());
+        assert_eq!(board.get(1, 1, 1), Some(Cell::Occupied(Player::X)));
+        assert_eq!(board.valid_moves().len(), 26);
+    }
+
+    #[test]
+    fn test_simulate_variant_board3d() {
+        let result = simulate_variant(&Board3D::new(), &FastEngine, 10, Player::X);
+        assert_eq!(result.games_completed, 10);
+        // A 3x3x3 cube cannot end in a draw
+        assert_eq!(result.draws, 0);
+    }
+
+    /// Plays cells in reverse order and remembers how many moves it made
+    struct ReverseCountingEngine {
+        moves_made: usize,
+    }
+
+    impl StatefulEngine for ReverseCountingEngine {
+        fn choose_move_mut(&mut self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+            self.moves_made += 1;
+            board.valid_moves().pop()
+        }
+    }
+
+    #[test]
+    fn test_engine_is_stateful_engine() {
+        let mut engine = FastEngine;
+        assert_eq!(engine.choose_move_mut(&Board::new(), Player::X), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_simulator_accepts_stateful_engine() {
+        let config = SimulationConfig::builder()
+            .num_games(5)
+            .engines(ReverseCountingEngine { moves_made: 0 }, FastEngine)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.games_completed, 5);
+    }
+
+    #[test]
+    fn test_default_evaluate_moves_ranks_choice_first() {
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        let scores = FastEngine.evaluate_moves(&board, Player::O);
+        assert_eq!(scores.len(), 8);
+        assert_eq!(scores[0], ((0, 1), 1.0));
+        assert!(scores[1..].iter().all(|&(_, score)| score == 0.0));
+    }
+
+    #[test]
+    fn test_evaluate_moves_empty_when_game_over() {
+        let mut board = Board::new();
+        for &(row, col) in &[(0, 0), (0, 1), (0, 2)] {
+            board.make_move(row, col, Player::X).unwrap();
+        }
+        assert!(FastEngine.evaluate_moves(&board, Player::O).is_empty());
+    }
+
+    #[test]
+    fn test_fn_engine_scripted_moves() {
+        // X follows a script; the closure looks up the move by ply
+        let script = [(0, 0), (1, 0), (2, 0)];
+        let engine = FnEngine::new(move |board: &Board, _player: Player| {
+            let ply = 9 - board.valid_moves().len();
+            script.get(ply / 2).copied()
+        });
+        let config = SimulationConfig::builder()
+            .num_games(1)
+            .engines(engine, FnEngine::new(|board: &Board, _: Player| board.valid_moves().pop()))
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        // O fills from the bottom right, so X completes the left column
+        assert_eq!(result.x_wins, 1);
+    }
+
+    #[test]
+    fn test_fn_engine_can_return_none() {
+        let engine = FnEngine::new(|_: &Board, _: Player| None);
+        assert_eq!(engine.choose_move(&Board::new(), Player::X), None);
+    }
+
+    #[test]
+    fn test_rng_reproducible_and_in_range() {
+        let mut a = Rng::new(7);
+        for _ in 0..100 {
+            let mut b = a.clone();
+            assert_eq!(a.next_u64(), b.next_u64());
+            assert!(a.below(9) < 9);
+            let f = a.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+        assert_eq!(a.choose::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn test_noisy_engine_extremes() {
+        let board = Board::new();
+        let mut greedy = NoisyEngine::seeded(FastEngine, 0.0, 1);
+        for _ in 0..20 {
+            assert_eq!(greedy.choose_move_mut(&board, Player::X), Some((0, 0)));
+        }
+
+        let mut random = NoisyEngine::seeded(FastEngine, 1.0, 1);
+        let picks: Vec<_> = (0..50).map(|_| random.choose_move_mut(&board, Player::X).unwrap()).collect();
+        assert!(picks.iter().any(|&mv| mv != (0, 0)));
+        assert!(picks.iter().all(|&(row, col)| board.is_valid_move(row, col)));
+    }
+
+    #[test]
+    fn test_noisy_engine_seed_reproducible() {
+        let run = |seed| {
+            let config = SimulationConfig::builder()
+                .num_games(200)
+                .engine(NoisyEngine::seeded(FastEngine, 0.5, seed))
+                .build();
+            Simulator::new(config).run_sequential()
+        };
+        let (a, b) = (run(3), run(3));
+        assert_eq!((a.x_wins, a.o_wins, a.draws), (b.x_wins, b.o_wins, b.draws));
+    }
+
+    #[test]
+    fn test_chain_engine_fallback() {
+        let never = FnEngine::new(|_: &Board, _: Player| None);
+        let mut engine = ChainEngine(never, FastEngine);
+        assert_eq!(engine.choose_move_mut(&Board::new(), Player::X), Some((0, 0)));
+
+        let mut first = ChainEngine(FastEngine, never);
+        assert_eq!(first.choose_move_mut(&Board::new(), Player::X), Some((0, 0)));
+
+        // Stateful engines fit either slot and are reseeded through the chain
+        let mut random = ChainEngine(never, RandomEngine::new());
+        let mut play = |seed| {
+            random.reseed(seed);
+            (0..5).map(|_| random.choose_move_mut(&Board::new(), Player::X)).collect::<Vec<_>>()
+        };
+        assert_eq!(play(9), play(9));
+    }
+
+    #[test]
+    fn test_mix_engine_uses_both() {
+        let last = FnEngine::new(|board: &Board, _: Player| board.valid_moves().pop());
+        let board = Board::new();
+
+        let mut always_a = MixEngine::seeded(FastEngine, last, 1.0, 5);
+        assert!((0..20).all(|_| always_a.choose_move_mut(&board, Player::X) == Some((0, 0))));
+
+        let mut blend = MixEngine::seeded(FastEngine, last, 0.5, 5);
+        let picks: Vec<_> = (0..50).map(|_| blend.choose_move_mut(&board, Player::X)).collect();
+        assert!(picks.contains(&Some((0, 0))));
+        assert!(picks.contains(&Some((2, 2))));
+    }
+
+    #[test]
+    fn test_minimax_takes_win_and_blocks() {
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 0, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        // X to move can win at (0, 2) or must otherwise block (1, 2)
+        assert_eq!(MinimaxEngine::new().choose_move(&board, Player::X), Some((0, 2)));
+        let scores = MinimaxEngine::new().evaluate_moves(&board, Player::X);
+        assert_eq!(scores[0], ((0, 2), 9.0));
+    }
+
+    #[test]
+    fn test_minimax_self_play_draws() {
+        let config = SimulationConfig::builder()
+            .num_games(1)
+            .engine(MinimaxEngine::new())
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.draws, 1);
+    }
+
+    #[test]
+    fn test_random_engine_seeded() {
+        let board = Board::new();
+        let mut a = RandomEngine::seeded(9);
+        let mut b = RandomEngine::seeded(9);
+        for _ in 0..20 {
+            assert_eq!(a.choose_move_mut(&board, Player::X), b.choose_move_mut(&board, Player::X));
+        }
+    }
+
+    #[test]
+    fn test_engine_registry_specs() {
+        let registry = EngineRegistry::with_builtins();
+        assert_eq!(registry.names(), vec!["fast", "minimax", "perfect", "random"]);
+
+        let mut fast = registry.create("fast").unwrap();
+        assert_eq!(fast.choose_move_mut(&Board::new(), Player::X), Some((0, 0)));
+
+        let mut a = registry.create("random:seed=42").unwrap();
+        let mut b = registry.create("random: seed = 42").unwrap();
+        assert_eq!(a.choose_move_mut(&Board::new(), Player::X), b.choose_move_mut(&Board::new(), Player::X));
+
+        assert!(registry.create("minimax:depth=five").is_err());
+        assert!(registry.create("minimax:depth").is_err());
+        assert!(registry.create("perfect").is_ok());
+        assert!(registry.create("unknown").is_err());
+    }
+
+    #[test]
+    fn test_engine_registry_custom_factory_in_simulation() {
+        let mut registry = EngineRegistry::new();
+        registry.register("first", |_| Ok(Box::new(FastEngine)));
+        let config = SimulationConfig::builder()
+            .num_games(3)
+            .boxed_engine(registry.create("first").unwrap())
+            .build();
+        assert_eq!(Simulator::new(config).run_sequential().x_wins, 3);
+    }
+
+    #[test]
+    fn test_symmetry_inverse_round_trips() {
+        for &symmetry in &Symmetry::ALL {
+            for row in 0..3 {
+                for col in 0..3 {
+                    assert_eq!(symmetry.inverse().apply(symmetry.apply((row, col))), (row, col));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_and_canonical() {
+        assert_eq!(Board::new().encode(), 0);
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(0, 1, Player::O).unwrap();
+        assert_eq!(board.encode(), 1 + 2 * 3);
+
+        let (canonical, symmetry) = board.canonical();
+        assert!(canonical.encode() <= board.encode());
+        assert_eq!(canonical.get(symmetry.apply((0, 1)).0, symmetry.apply((0, 1)).1), Some(Cell::Occupied(Player::O)));
+    }
+
+    #[test]
+    fn test_opening_book_translates_symmetric_positions() {
+        let mut book = OpeningBook::new();
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        book.add(&board, (1, 1), 5).unwrap();
+        assert!(book.add(&board, (0, 0), 1).is_err());
+
+        // The same position rotated: X in the bottom-right corner
+        let mut rotated = Board::new();
+        rotated.make_move(2, 2, Player::X).unwrap();
+        assert_eq!(book.best_move(&rotated), Some((1, 1)));
+
+        // An edge reply is translated to the matching edge
+        book.add(&board, (0, 1), 10).unwrap();
+        let moves = book.lookup(&rotated);
+        assert_eq!(moves[0].weight, 10);
+        assert!(moves[0].position == (2, 1) || moves[0].position == (1, 2));
+    }
+
+    #[test]
+    fn test_opening_book_binary_round_trip() {
+        let mut book = OpeningBook::new();
+        book.add(&Board::new(), (1, 1), 3).unwrap();
+        let mut board = Board::new();
+        board.make_move(1, 1, Player::X).unwrap();
+        book.add(&board, (0, 0), 7).unwrap();
+
+        let mut bytes = Vec::new();
+        book.save(&mut bytes).unwrap();
+        let loaded = OpeningBook::load(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, book);
+        assert_eq!(loaded.len(), 2);
+        assert!(OpeningBook::load(&mut &b"nope"[..]).is_err());
+    }
+
+    #[test]
+    fn test_book_engine_falls_back() {
+        let mut book = OpeningBook::new();
+        book.add(&Board::new(), (1, 1), 1).unwrap();
+        let engine = BookEngine::new(book, FastEngine);
+        let mut board = Board::new();
+        assert_eq!(engine.choose_move(&board, Player::X), Some((1, 1)));
+        board.make_move(1, 1, Player::X).unwrap();
+        assert_eq!(engine.choose_move(&board, Player::O), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_solved_database_counts_and_root() {
+        let db = SolvedDatabase::build(Rules::Standard);
+        assert_eq!(db.len(), 5478);
+        assert_eq!(db.canonical_len(), 765);
+        assert_eq!(db.iter().count(), 5478);
+        let root = db.get(&Board::new()).unwrap();
+        assert_eq!(root, Solution { value: GameValue::Draw, distance: 9 });
+        assert_eq!(db.best_moves(&Board::new()).len(), 9);
+    }
+
+    #[test]
+    fn test_solved_database_distance_to_mate() {
+        let db = SolvedDatabase::build(Rules::Standard);
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 0, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        // X wins immediately at (0, 2)
+        assert_eq!(db.get(&board).unwrap(), Solution { value: GameValue::Win, distance: 1 });
+        assert_eq!(db.best_moves(&board), vec![(0, 2)]);
+
+        // Unreachable: X has two more pieces than O
+        let mut illegal = Board::new();
+        illegal.make_move(0, 0, Player::X).unwrap();
+        illegal.make_move(0, 1, Player::X).unwrap();
+        assert_eq!(db.get(&illegal), None);
+    }
+
+    #[test]
+    fn test_solved_database_agrees_with_minimax() {
+        let db = SolvedDatabase::build(Rules::Standard);
+        let engine = MinimaxEngine::new();
+        for (board, solution) in db.iter().filter(|(b, _)| b.valid_moves().len() >= 6) {
+            let player = board.current_player().unwrap();
+            let (row, col) = engine.choose_move(&board, player).unwrap();
+            let chosen = db
+                .move_values(&board)
+                .into_iter()
+                .find(|&(mv, _)| mv == (row, col))
+                .unwrap()
+                .1;
+            assert_eq!(chosen.value, solution.value);
+        }
+    }
+
+    #[test]
+    fn test_solved_database_misere() {
+        let db = SolvedDatabase::build(Rules::Misere);
+        assert_eq!(db.len(), 5478);
+        assert_eq!(db.get(&Board::new()), None);
+        // Misère TicTacToe is also a draw with perfect play
+        assert_eq!(db.get(&Board::with_rules(Rules::Misere)).unwrap().value, GameValue::Draw);
+    }
+
+    #[test]
+    fn test_perfect_lookup_engine_never_loses() {
+        let db = SolvedDatabase::shared(Rules::Standard);
+        assert!(std::ptr::eq(db, SolvedDatabase::shared(Rules::Standard)));
+
+        let config = SimulationConfig::builder()
+            .num_games(200)
+            .engines(PerfectLookupEngine, PerfectLookupEngine)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.draws, 200);
+
+        for starting in [Player::X, Player::O] {
+            let config = SimulationConfig::builder()
+                .num_games(200)
+                .engines(PerfectLookupEngine, RandomEngine::seeded(3))
+                .starting_player(starting)
+                .build();
+            let result = Simulator::new(config).run_sequential();
+            assert_eq!(result.o_wins, 0);
+            assert!(result.x_wins > 0);
+        }
+    }
+
+    #[test]
+    fn test_perfect_lookup_engine_matches_solver() {
+        let db = SolvedDatabase::shared(Rules::Misere);
+        for (board, _) in db.iter() {
+            let player = board.current_player().unwrap();
+            let chosen = PerfectLookupEngine.choose_move(&board, player);
+            assert_eq!(chosen, db.best_move(&board));
+            if let Some(mv) = chosen {
+                assert!(db.best_moves(&board).contains(&mv));
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimax_iterative_deepening_completes() {
+        let engine = MinimaxEngine::new();
+        let result = engine.search(&Board::new(), Player::X);
+        assert!(result.complete);
+        assert_eq!(result.depth, 9);
+        assert_eq!(result.score, 0);
+
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 0, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        // The immediate win is proven at depth 1 and ends the search
+        let result = engine.search(&board, Player::X);
+        assert_eq!(result.best_move, Some((0, 2)));
+        assert_eq!(result.depth, 1);
+        assert!(result.complete);
+    }
+
+    #[test]
+    fn test_minimax_budget_returns_shallow_answer() {
+        let engine = MinimaxEngine::new().node_limit(50);
+        let result = engine.search(&Board::new(), Player::X);
+        assert!(result.best_move.is_some());
+        assert!(!result.complete);
+        assert!(result.depth >= 1 && result.depth < 9);
+
+        let timed = MinimaxEngine::new().time_limit(std::time::Duration::ZERO);
+        assert!(timed.choose_move(&Board::new(), Player::X).is_some());
+
+        let registry = EngineRegistry::with_builtins();
+        assert!(registry.create("minimax:nodes=100,time_ms=5").is_ok());
+    }
+
+    #[test]
+    fn test_minimax_deepening_matches_solver() {
+        let db = SolvedDatabase::shared(Rules::Standard);
+        let engine = MinimaxEngine::new();
+        for (board, solution) in db.iter() {
+            if board.game_result() != GameResult::InProgress {
+                continue;
+            }
+            let player = board.current_player().unwrap();
+            let (row, col) = engine.choose_move(&board, player).unwrap();
+            let chosen = db.move_values(&board).into_iter().find(|&(mv, _)| mv == (row, col)).unwrap().1;
+            assert_eq!(chosen.value, solution.value);
+        }
+    }
+
+    #[test]
+    fn test_distinct_moves_skip_symmetric_images() {
+        let mut board = Board::new();
+        assert_eq!(board.distinct_moves().len(), 3);
+
+        board.make_move(1, 1, Player::X).unwrap();
+        assert_eq!(board.distinct_moves(), vec![(0, 0), (0, 1)]);
+
+        let mut corner = Board::new();
+        corner.make_move(0, 0, Player::X).unwrap();
+        assert_eq!(corner.distinct_moves(), vec![(0, 1), (0, 2), (1, 1), (1, 2), (2, 2)]);
+
+        // Asymmetric positions keep every move
+        corner.make_move(0, 1, Player::O).unwrap();
+        assert_eq!(corner.distinct_moves(), corner.valid_moves());
+    }
+
+    #[test]
+    fn test_qlearning_engine_learns_tactics() {
+        let mut engine = QLearningEngine::seeded(QLearningConfig::default(), 7).unwrap();
+        engine.train(20_000);
+        assert!(engine.epsilon() < 0.1);
+
+        // Take the immediate win in the top row
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 0, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        assert_eq!(engine.choose_move(&board, Player::X), Some((0, 2)));
+
+        let config = SimulationConfig::builder()
+            .num_games(200)
+            .engines(engine, RandomEngine::seeded(1))
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert!(result.x_wins > result.o_wins * 5);
+    }
+
+    #[test]
+    fn test_qlearning_engine_save_load_roundtrip() {
+        let mut engine = QLearningEngine::seeded(QLearningConfig::default(), 1).unwrap();
+        engine.train(200);
+
+        let mut bytes = Vec::new();
+        engine.save(&mut bytes).unwrap();
+        let loaded = QLearningEngine::load(&mut bytes.as_slice(), QLearningConfig::default()).unwrap();
+        assert_eq!(loaded.states(), engine.states());
+        assert_eq!(loaded.evaluate_moves(&Board::new(), Player::X), engine.evaluate_moves(&Board::new(), Player::X));
+
+        assert!(QLearningEngine::load(&mut &b"nope"[..], QLearningConfig::default()).is_err());
+        let bad = QLearningConfig { discount: 2.0, ..QLearningConfig::default() };
+        assert!(QLearningEngine::new(bad).is_err());
+    }
+
+    #[test]
+    fn test_eval_engine_search_and_callback() {
+        let calls = std::cell::Cell::new(0);
+        let engine = EvalEngine::new(
+            |_: &Board, _: Player| {
+                calls.set(calls.get() + 1);
+                0.0
+            },
+            2,
+        );
+        assert_eq!(engine.depth(), 2);
+
+        // O must block the top row even with a constant evaluation
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        board.make_move(1, 1, Player::O).unwrap();
+        board.make_move(0, 1, Player::X).unwrap();
+        assert_eq!(engine.choose_move(&board, Player::O), Some((0, 2)));
+        assert!(calls.get() > 0);
+
+        // Immediate wins outrank slower ones and every evaluation
+        board.make_move(0, 2, Player::O).unwrap();
+        board.make_move(2, 2, Player::X).unwrap();
+        let scores = engine.evaluate_moves(&board, Player::O);
+        assert_eq!(scores[0].0, (2, 0));
+        assert!(scores[0].1 > 1e8);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_run_async_matches_sequential() {
+        struct Scripted(Vec<(usize, usize)>);
+
+        impl AsyncEngine for Scripted {
+            async fn choose_move(&mut self, board: &Board, _player: Player) -> Option<(usize, usize)> {
+                tokio::task::yield_now().await;
+                self.0.iter().copied().find(|&(r, c)| board.is_valid_move(r, c))
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let mut simulator = Simulator::new(SimulationConfig::builder().num_games(20).build());
+        let mut x = Scripted(vec![(0, 0), (1, 0), (2, 0)]);
+        let mut o = BlockingEngine::new(FastEngine);
+        let result = runtime.block_on(simulator.run_async(&mut x, &mut o));
+        assert_eq!(result.games_completed, 20);
+        assert_eq!(result.x_wins, 20);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_remote_engine_in_simulation() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve_engine(listener, MinimaxEngine::new()));
+
+        let mut remote = RemoteEngine::connect(addr).unwrap();
+        let mut board = Board::with_rules(Rules::Misere);
+        board.make_move(1, 1, Player::X).unwrap();
+        assert!(remote.request_move(&board, Player::O).unwrap().is_some());
+
+        let config = SimulationConfig::builder()
+            .num_games(5)
+            .engines(remote, FastEngine)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.x_wins, 5);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_remote_engine_reports_protocol_errors() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve_engine(listener, FastEngine));
+
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        let mut ask = |request: &[u8]| {
+            stream.write_all(&(request.len() as u32).to_be_bytes()).unwrap();
+            stream.write_all(request).unwrap();
+            let mut len = [0u8; 4];
+            stream.read_exact(&mut len).unwrap();
+            let mut body = vec![0u8; u32::from_be_bytes(len) as usize];
+            stream.read_exact(&mut body).unwrap();
+            String::from_utf8(body).unwrap()
+        };
+        assert!(ask(br#"{"board": "XX", "player": "X"}"#).contains("error"));
+
+        // Finished positions get no move, even with pieces after the winning line
+        assert_eq!(ask(br#"{"board": "XXXOO....", "player": "O"}"#), r#"{"move":null}"#);
+        assert!(ask(br#"{"board": "XXXOOO...", "player": "X"}"#).contains("error"));
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_remote_engine_times_out_and_closes() {
+        use std::time::Duration;
+
+        // A server that accepts and never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+            drop(stream);
+        });
+
+        let mut remote = RemoteEngine::connect(addr).unwrap();
+        assert_eq!(remote.read_timeout().unwrap(), Some(Duration::from_secs(10)));
+        remote.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+        let error = remote.request_move(&Board::new(), Player::X).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert!(remote.request_move(&Board::new(), Player::X).is_err());
+        assert_eq!(remote.choose_move_mut(&Board::new(), Player::X), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_engine_line_protocol() {
+        // A shell bot that plays the first empty cell
+        let script = r#"
+            while read -r cmd arg rest; do
+                case "$cmd" in
+                    zttt) echo "id name first-empty"; echo ztttok ;;
+                    position) cells="$arg" ;;
+                    go)
+                        prefix="${cells%%.*}"
+                        i=${#prefix}
+                        echo "info thinking"
+                        echo "bestmove $((i / 3)) $((i % 3))" ;;
+                    quit) exit 0 ;;
+                esac
+            done
+        "#;
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg(script);
+        let mut engine = ProcessEngine::from_command(command).unwrap();
+        assert_eq!(engine.name(), Some("first-empty"));
+
+        let mut board = Board::new();
+        board.make_move(0, 0, Player::X).unwrap();
+        assert_eq!(engine.request_move(&board, Player::O).unwrap(), Some((0, 1)));
+
+        let config = SimulationConfig::builder()
+            .num_games(3)
+            .engines(engine, FastEngine)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.x_wins, 3);
+
+        assert_eq!("misere".parse::<Rules>(), Ok(Rules::Misere));
+        assert!("classic".parse::<Rules>().is_err());
+
+        // A bot that handshakes but never answers `go`
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg("read -r cmd; echo ztttok; while read -r cmd; do :; done");
+        let mut hung = ProcessEngine::from_command(command).unwrap();
+        hung.set_read_timeout(Some(std::time::Duration::from_millis(50)));
+        let error = hung.request_move(&Board::new(), Player::X).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert!(hung.request_move(&Board::new(), Player::X).is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_api_game_flow() {
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let app = crate::http::router();
+        let call = |method: &str, uri: &str, body: &str| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            runtime.block_on(async {
+                let response = app.clone().oneshot(request).await.unwrap();
+                let status = response.status();
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&bytes).unwrap())
+            })
+        };
+
+        let (status, game) = call("POST", "/games", r#"{"starting_player": "O"}"#);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(game["current_player"], "O");
+
+        let (status, game) = call("POST", "/games/0/moves", r#"{"row": 1, "col": 1}"#);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(game["board"], "....O....");
+        let (status, _) = call("POST", "/games/0/moves", r#"{"row": 1, "col": 1}"#);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, suggestion) = call("GET", "/games/0/suggestion?engine=fast", "");
+        assert_eq!(suggestion["move"], serde_json::json!([0, 0]));
+        let (_, analysis) = call("GET", "/games/0/analysis", "");
+        assert_eq!(analysis["value"], "draw");
+        assert_eq!(analysis["best_moves"], serde_json::json!([[0, 0], [0, 2], [2, 0], [2, 2]]));
+        assert_eq!(analysis["moves"].as_array().unwrap().len(), 8);
+        let (status, _) = call("GET", "/games/7", "");
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, summary) = call("POST", "/simulations", r#"{"num_games": 10, "engine_o": "minimax"}"#);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(summary["games_completed"], 10);
+        assert_eq!(summary["x_wins"], 0);
+        assert_eq!(summary["manifest"]["engine_o"], "minimax");
+
+        #[cfg(feature = "metrics")]
+        {
+            let request = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+            let text = runtime.block_on(async {
+                let response = app.clone().oneshot(request).await.unwrap();
+                String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+            });
+            assert!(text.contains("zttt_games_completed_total 10"));
+            assert!(!text.contains("result=\"x_win\"}"));
+            assert!(text.contains("zttt_queue_depth 0"));
+        }
+    }
+
+    #[test]
+    fn test_svg_render_board_and_game() {
+        use crate::render::{board_svg, game_svg, SvgRenderer};
+
+        let svg = board_svg(&Board::new());
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert!(!svg.contains("<circle") && !svg.contains("<text"));
+
+        let mut game = Game::new();
+        for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+            game.play(row, col).unwrap();
+        }
+        let svg = game_svg(&game);
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert_eq!(svg.matches("<text").count(), 5);
+        assert!(svg.contains(r#"<line class="win" x1="50" y1="50" x2="250" y2="250""#));
+
+        let plain = SvgRenderer::new().move_numbers(false).highlight_win(false).game(&game);
+        assert!(!plain.contains("<text") && !plain.contains("class=\"win\""));
+    }
+
+    #[test]
+    fn test_board_formatter_styles() {
+        let mut board = Board::new();
+        board.make_move(1, 1, Player::X).unwrap();
+        board.make_move(0, 2, Player::O).unwrap();
+
+        assert_eq!(board.formatter().to_string(), board.to_string());
+        assert_eq!(board.to_string(), ". . O\n. X .\n. . .");
+        assert_eq!(board.formatter().wide(true).to_string(), " .  .  O \n .  X  . \n .  .  . ");
+        assert_eq!(
+            board.formatter().grid(true).wide(true).unicode(true).coordinates(true).to_string(),
+            "   a   b   c\n1  · │ · │ O \n  ───┼───┼───\n2  · │ X │ · \n  ───┼───┼───\n3  · │ · │ · "
+        );
+
+        let colored = board.formatter().colors(true).to_string();
+        assert!(colored.contains("\x1b[1;36mX\x1b[0m") && colored.contains("\x1b[1;35mO\x1b[0m"));
+    }
+
+    #[test]
+    fn test_replay_frames_and_annotations() {
+        let moves = [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)];
+        let replay = Replay::new(&moves).unwrap();
+        assert_eq!(replay.len(), 5);
+        assert_eq!(replay.frames().len(), 5);
+
+        let frames: Vec<Frame> = replay.frames().collect();
+        assert_eq!(frames[0].annotation(), "X plays a1");
+        assert_eq!(frames[1].annotation(), "O plays a2");
+        assert_eq!(frames[4].annotation(), "X plays c3 and wins");
+        assert_eq!(frames[4].result, GameResult::Win(Player::X));
+        assert_eq!(frames[2].board.valid_moves().len(), 6);
+        assert!(frames[4].to_string().starts_with("5. X plays c3 and wins\n  a b c\n1 X|.|."));
+
+        let misere = Replay::with_start(&moves, Rules::Misere, Player::X).unwrap();
+        assert_eq!(misere.frames().last().unwrap().annotation(), "X plays c3 and loses");
+
+        let mut game = Game::with_starting_player(Player::O);
+        game.play(1, 1).unwrap();
+        assert_eq!(Replay::from_game(&game).frames().next().unwrap().annotation(), "O plays b2");
+
+        let record = GameRecord::new(Rules::Standard, Player::O, &[(1, 1), (0, 0), (2, 2)]).unwrap();
+        let replay = record.replay().unwrap();
+        assert_eq!(replay.frame(2).unwrap().player, Player::X);
+        assert!(replay.frame(0).is_none() && replay.frame(4).is_none());
+        assert_eq!(replay.board_at(0).unwrap(), *replay.initial_board());
+        assert_eq!(replay.board_at(3).unwrap(), record.to_game().unwrap().board().clone());
+        let mut broken = record.clone();
+        broken.moves.push((1, 1));
+        assert_eq!(broken.replay().unwrap_err(), "Cell already occupied");
+
+        assert_eq!(Replay::new(&[(1, 1), (1, 1)]).unwrap_err(), "Cell already occupied");
+        assert!(Replay::new(&[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]).is_err());
+    }
+
+    #[test]
+    fn test_game_record_notation_round_trip() {
+        let mut record = GameRecord::new(Rules::Misere, Player::O, &[(1, 1), (0, 0), (2, 2)]).unwrap();
+        record.seed = Some(42);
+        record.set_tag("X", "minimax:depth=3").unwrap();
+        record.set_tag("Event", "say \"hi\"").unwrap();
+        record.set_tag("X", "perfect").unwrap();
+
+        let text = record.to_notation();
+        assert_eq!(
+            text,
+            "[Rules \"misere\"]\n[Start \"O\"]\n[Seed \"42\"]\n[X \"perfect\"]\n[Event \"say \\\"hi\\\"\"]\n\n1. b2 a1 2. c3 *\n"
+        );
+        let parsed = GameRecord::from_notation(&text).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.tag("Event"), Some("say \"hi\""));
+
+        record.set_tag("Note", "two\nlines \\n and a ]").unwrap();
+        assert_eq!(GameRecord::from_notation(&record.to_notation()).unwrap(), record);
+        assert_eq!(record.set_tag("Result", "1-0").unwrap_err(), "Tag key is reserved");
+        assert!(record.set_tag("Two words", "x").is_err());
+        assert!(record.set_tag("Odd]", "x").is_err());
+        assert!(record.set_tag("", "x").is_err());
+        record.tags.push(("Seed".to_string(), "7".to_string()));
+        assert_eq!(GameRecord::from_notation(&record.to_notation()).unwrap().seed, Some(42));
+        assert_eq!(Replay::from_record(&parsed).unwrap().len(), 3);
+
+        let draw = GameRecord::from_notation("1. a1 b2 2. c3 b1 3. b3 a3 4. c1 c2 5. a2 1/2-1/2").unwrap();
+        assert_eq!(draw.result, GameResult::Draw);
+        assert_eq!(draw.to_game().unwrap().board().valid_moves().len(), 0);
+
+        assert_eq!(GameRecord::from_notation("1. b2 b2").unwrap_err(), "Cell already occupied");
+        assert_eq!(GameRecord::from_notation("1. d4").unwrap_err(), "Invalid move in notation");
+        assert_eq!(GameRecord::from_notation("1. b2 1-0").unwrap_err(), "Result token does not match the moves");
+        assert_eq!(
+            GameRecord::from_notation("[MoveTimes \"100 200\"]\n1. b2").unwrap_err(),
+            "MoveTimes must have one entry per move"
+        );
+        assert!(GameRecord::from_notation("[Start \"Z\"]\n1. b2").is_err());
+    }
+
+    #[test]
+    fn test_jsonl_records_stream() {
+        let config = SimulationConfig::builder()
+            .num_games(25)
+            .engines(FastEngine, MinimaxEngine::new())
+            .seed(7)
+            .build();
+        let mut output = Vec::new();
+        let result = Simulator::new(config).run_jsonl(&mut output).unwrap();
+        assert_eq!(result.games_completed, 25);
+
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 25);
+        assert!(lines[0].starts_with("{\"game\":0,\"moves\":[[0,0],"));
+        assert!(lines[24].starts_with("{\"game\":24,"));
+        assert!(lines.iter().all(|line| !line.ends_with("\"seed\":null}") && !line.contains("\"result\":\"X\"")));
+
+        let mut sink = JsonlSink::new(Vec::new()).batch_size(2);
+        let record = GameRecord::new(Rules::Standard, Player::X, &[(0, 0), (1, 1)]).unwrap();
+        sink.write(&record).unwrap();
+        sink.write(&record).unwrap();
+        sink.write(&record).unwrap();
+        assert_eq!(sink.games_written(), 3);
+        assert_eq!(sink.finish().unwrap().split(|&b| b == b'\n').count(), 4);
+
+        struct Broken;
+        impl std::io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk full"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut simulator = Simulator::new(SimulationConfig::builder().num_games(5000).build());
+        assert_eq!(simulator.run_jsonl(Broken).unwrap_err().to_string(), "disk full");
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_arrow_and_parquet_export() {
+        use arrow_array::{Array, ListArray, StringArray, UInt8Array};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let mut builder = RecordBatchBuilder::new();
+        let record = GameRecord::new(Rules::Standard, Player::O, &[(1, 1), (0, 2)]).unwrap();
+        builder.push(&record);
+        builder.push(&GameRecord::new(Rules::Standard, Player::X, &[(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)]).unwrap());
+        let (games, moves) = builder.finish().unwrap();
+        assert!(builder.is_empty());
+        assert_eq!((games.num_rows(), moves.num_rows()), (2, 7));
+
+        let results = games.column(3).as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(results.is_null(0));
+        assert_eq!(results.value(1), "X");
+        let cells = games.column(6).as_any().downcast_ref::<ListArray>().unwrap().value(0);
+        assert_eq!(cells.as_any().downcast_ref::<UInt8Array>().unwrap().values(), &[4, 2]);
+        let players = moves.column(2).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!((players.value(0), players.value(1), players.value(2)), ("O", "X", "X"));
+
+        let dir = std::env::temp_dir().join(format!("zttt-parquet-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (games_path, moves_path) = (dir.join("games.parquet"), dir.join("moves.parquet"));
+        let config = SimulationConfig::builder()
+            .num_games(300)
+            .engines(RandomEngine::seeded(3), FastEngine)
+            .manifest(true)
+            .build();
+        let result = Simulator::new(config)
+            .run_parquet(
+                std::fs::File::create(&games_path).unwrap(),
+                std::fs::File::create(&moves_path).unwrap(),
+            )
+            .unwrap();
+
+        let rows = |path: &std::path::Path| -> usize {
+            let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(path).unwrap())
+                .unwrap()
+                .build()
+                .unwrap();
+            reader.map(|batch| batch.unwrap().num_rows()).sum()
+        };
+        assert_eq!(rows(&games_path), result.games_completed);
+        assert!(rows(&moves_path) >= 5 * result.games_completed);
+        let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&moves_path).unwrap()).unwrap();
+        let metadata = builder.metadata().file_metadata().key_value_metadata().unwrap();
+        let num_games = metadata.iter().find(|kv| kv.key == "zttt.num_games").unwrap();
+        assert_eq!(num_games.value.as_deref(), Some("300"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_for_duration_budget() {
+        use std::time::Duration;
+
+        let config = SimulationConfig::builder().num_games(1).engines(FastEngine, MinimaxEngine::new()).build();
+        let mut simulator = Simulator::new(config);
+        let result = simulator.run_for(Duration::from_millis(30));
+        assert!(result.games_completed > 1);
+        assert_eq!(result.games_completed % 8, 0);
+        assert_eq!(result.x_wins, 0);
+        assert!(result.total_duration >= Duration::from_millis(30));
+
+        assert_eq!(simulator.run_for(Duration::ZERO).games_completed, 0);
+    }
+
+    #[test]
+    fn test_progress_callback_throttling() {
+        use std::sync::{Arc, Mutex};
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&calls);
+        let mut simulator = Simulator::new(SimulationConfig::builder().num_games(1050).build())
+            .with_progress(250, move |completed, total, partial: &SimulationResult| {
+                assert_eq!(partial.games_completed, completed);
+                log.lock().unwrap().push((completed, total));
+            });
+        let result = simulator.run_sequential();
+        assert_eq!(result.games_completed, 1050);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [(250, 1050), (500, 1050), (750, 1050), (1000, 1050), (1050, 1050)]
+        );
+
+        calls.lock().unwrap().clear();
+        simulator.run_with_records(|_| {});
+        assert_eq!(calls.lock().unwrap().len(), 5);
+
+        calls.lock().unwrap().clear();
+        let timed = simulator.run_for(std::time::Duration::from_millis(5));
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.last(), Some(&(timed.games_completed, 0)));
+        assert!(calls.iter().all(|&(_, total)| total == 0));
+    }
+
+    #[test]
+    fn test_progress_and_stops_with_skipped_games() {
+        use std::sync::{Arc, Mutex};
+
+        // O's engine fails whenever X opened in the centre, so some games are skipped
+        let fragile = FnEngine::new(|board: &Board, _: Player| -> Option<(usize, usize)> {
+            assert!(board.get(1, 1) != Some(Cell::Occupied(Player::X)), "engine bug");
+            board.valid_moves().into_iter().next()
+        });
+        let config = || {
+            SimulationConfig::builder()
+                .num_games(200)
+                .engines(RandomEngine::new(), fragile)
+                .on_engine_error(EngineErrorPolicy::SkipGame)
+                .seed(3)
+        };
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&calls);
+        let result = Simulator::new(config().build())
+            .with_progress(50, move |completed, _, _| log.lock().unwrap().push(completed))
+            .run_sequential();
+        assert!(result.skipped_games > 0);
+        assert_eq!(calls.lock().unwrap().last(), Some(&result.games_completed));
+
+        let stopped = config().stop_when(StopCondition::when(|result| result.skipped_games == 3)).build();
+        let stopped = Simulator::new(stopped).run_with_records(|_| {});
+        // A skipped game must not be replayed with the same seed until time runs out
+        let timed = Simulator::new(config().record_games(RecordPolicy::All).build())
+            .run_for(std::time::Duration::from_millis(20));
+        std::panic::set_hook(previous_hook);
+        assert_eq!(stopped.skipped_games, 3);
+        assert!(timed.skipped_games > 0 && timed.skipped_games < timed.games_completed);
+        let seeds: std::collections::HashSet<_> = timed.records.iter().map(|record| record.seed).collect();
+        assert_eq!(seeds.len(), timed.records.len());
+    }
+
+    #[test]
+    fn test_merge_simulation_results() {
+        use std::time::Duration;
+
+        let run = |x_wins, o_wins, draws, millis| {
+            let mut result = SimulationResult::new();
+            result.x_wins = x_wins;
+            result.o_wins = o_wins;
+            result.draws = draws;
+            result.games_completed = x_wins + o_wins + draws;
+            result.total_duration = Duration::from_millis(millis);
+            result.avg_game_duration = Duration::from_millis(millis) / result.games_completed as u32;
+            result.throughput = result.games_completed as f64 / (millis as f64 / 1000.0);
+            result
+        };
+        let a = run(60, 20, 20, 100);
+        let b = run(100, 50, 150, 100);
+
+        let sequential = a.clone() + b.clone();
+        assert_eq!((sequential.games_completed, sequential.x_wins, sequential.draws), (400, 160, 170));
+        assert_eq!(sequential.total_duration, Duration::from_millis(200));
+        assert!((sequential.throughput - 2000.0).abs() < 1e-6);
+        assert_eq!(sequential.avg_game_duration, Duration::from_micros(500));
+
+        let mut concurrent = a.clone();
+        concurrent.merge_concurrent(&b);
+        assert_eq!(concurrent.total_duration, Duration::from_millis(100));
+        assert!((concurrent.throughput - 4000.0).abs() < 1e-6);
+        assert_eq!(concurrent.avg_game_duration, sequential.avg_game_duration);
+
+        let mut total = SimulationResult::new();
+        total += &a;
+        total += b.clone();
+        assert_eq!(total, sequential);
+        assert_eq!([a.clone(), b.clone()].iter().sum::<SimulationResult>(), sequential);
+        assert_eq!(SimulationResult::new() + SimulationResult::new(), SimulationResult::new());
+    }
+
+    #[test]
+    fn test_parallel_simulator_merges_thread_results() {
+        use std::sync::{Arc, Mutex};
+
+        let config = ParallelConfig::builder()
+            .num_games(1_050)
+            .engines(FastEngine, MinimaxEngine::new())
+            .num_threads(4)
+            .chunk_size(100)
+            .build();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&reports);
+        let result = ParallelSimulator::new(config)
+            .with_progress(500, move |completed, total, partial| {
+                assert_eq!(partial.games_completed, completed);
+                log.lock().unwrap().push((completed, total));
+            })
+            .run_parallel();
+
+        assert_eq!(result.games_completed, 1_050);
+        assert_eq!(result.x_wins + result.o_wins + result.draws, 1_050);
+        assert_eq!(result.x_wins, 0);
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.last(), Some(&(1_050, 1_050)));
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_starting_player_policy() {
+        let alternate = StartingPlayerPolicy::Alternate;
+        assert_eq!(alternate.starting_player(4), Player::X);
+        assert_eq!(alternate.starting_player(5), Player::O);
+
+        let random = StartingPlayerPolicy::Random { seed: 9 };
+        let starters: Vec<Player> = (0..200).map(|game| random.starting_player(game)).collect();
+        assert_eq!(starters, (0..200).map(|game| random.starting_player(game)).collect::<Vec<_>>());
+        let x_starts = starters.iter().filter(|&&player| player == Player::X).count();
+        assert!(x_starts > 60 && x_starts < 140);
+
+        // The starter is not the top bit of the game seed drawn from the same seed
+        let matches = (0..200)
+            .filter(|&game| (starters[game] == Player::X) == (crate::simulation::config::derive_game_seed(9, game) >> 63 == 0))
+            .count();
+        assert!(matches > 60 && matches < 140);
+
+        let config = SimulationConfig::builder()
+            .num_games(10)
+            .engine(FastEngine)
+            .starting_player_policy(StartingPlayerPolicy::Alternate)
+            .build();
+        let mut starts = Vec::new();
+        Simulator::new(config).run_with_records(|record| starts.push(record.starting_player));
+        assert_eq!(starts[..4], [Player::X, Player::O, Player::X, Player::O]);
+
+        let parallel = ParallelConfig::builder()
+            .num_games(1_000)
+            .engines(FastEngine, RandomEngine::seeded(1))
+            .num_threads(3)
+            .chunk_size(64)
+            .starting_player_policy(StartingPlayerPolicy::Random { seed: 3 })
+            .build();
+        let result = ParallelSimulator::new(parallel).run_parallel();
+        assert_eq!(result.games_completed, 1_000);
+        assert!(result.x_wins > result.o_wins);
+    }
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        let run = |seed: u64| {
+            let config = SimulationConfig::builder()
+                .num_games(500)
+                .engines(RandomEngine::new(), NoisyEngine::new(FastEngine, 0.5))
+                .seed(seed)
+                .build();
+            Simulator::new(config).run_sequential()
+        };
+        let (a, b) = (run(11), run(11));
+        assert_eq!((a.x_wins, a.o_wins, a.draws), (b.x_wins, b.o_wins, b.draws));
+
+        let mut seeds = Vec::new();
+        let config = SimulationConfig::builder().num_games(3).seed(11).build();
+        Simulator::new(config).run_with_records(|record| seeds.push(record.seed));
+        assert!(seeds.iter().all(Option::is_some));
+        assert_ne!(seeds[0], seeds[1]);
+
+        let parallel = |threads: usize, chunk_size: usize| {
+            let config = ParallelConfig::builder()
+                .num_games(500)
+                .engine_sources(|| Box::new(RandomEngine::new()), || Box::new(NoisyEngine::new(FastEngine, 0.5)))
+                .num_threads(threads)
+                .chunk_size(chunk_size)
+                .seed(11)
+                .build();
+            ParallelSimulator::new(config).run_parallel()
+        };
+        for (threads, chunk_size) in [(1, 500), (4, 7)] {
+            let c = parallel(threads, chunk_size);
+            assert_eq!((a.x_wins, a.o_wins, a.draws), (c.x_wins, c.o_wins, c.draws));
+        }
+    }
+
+    #[test]
+    fn test_win_rate_confidence_interval() {
+        let mut result = SimulationResult::new();
+        assert_eq!(result.win_rate_ci(Player::X, 0.95), (0.0, 100.0));
+
+        result.games_completed = 1_000;
+        result.x_wins = 550;
+        result.o_wins = 450;
+        let (low, high) = result.win_rate_ci(Player::X, 0.95);
+        assert!((low - 51.903).abs() < 0.01 && (high - 58.058).abs() < 0.01);
+        let (wide_low, wide_high) = result.win_rate_ci(Player::X, 0.99);
+        assert!(wide_low < low && wide_high > high);
+        assert!((result.win_rate_ci(Player::O, 0.95).0 - (100.0 - high)).abs() < 1e-9);
+
+        let mut zero = result.clone();
+        zero.x_wins = 0;
+        zero.o_wins = 1_000;
+        let (low, high) = zero.win_rate_ci(Player::X, 0.95);
+        assert!(low < 1e-9);
+        assert!(high > 0.0 && high < 1.0);
+
+        assert!(result.is_significantly_better_than(&zero, Player::X, 0.99));
+        assert!(!zero.is_significantly_better_than(&result, Player::X, 0.99));
+        assert!(!result.is_significantly_better_than(&result, Player::X, 0.95));
+        assert!(!result.is_significantly_better_than(&SimulationResult::new(), Player::X, 0.95));
+    }
+
+    #[test]
+    fn test_record_policy_selects_games() {
+        let run = |policy: RecordPolicy| {
+            let config = SimulationConfig::builder()
+                .num_games(100)
+                .engines(RandomEngine::new(), RandomEngine::new())
+                .seed(5)
+                .record_games(policy)
+                .build();
+            Simulator::new(config).run_sequential()
+        };
+
+        assert!(run(RecordPolicy::None).records.is_empty());
+        let all = run(RecordPolicy::All);
+        assert_eq!(all.records.len(), 100);
+        assert!(all.records.iter().all(|record| record.to_game().unwrap().result() == record.result));
+
+        let sample = run(RecordPolicy::Sample(7));
+        assert_eq!(sample.records.len(), 7);
+        let all_games = untimed(&all.records);
+        assert!(untimed(&sample.records).iter().all(|record| all_games.contains(record)));
+
+        let losses = run(RecordPolicy::LossesOnly);
+        assert_eq!(losses.records.len(), losses.o_wins);
+        assert!(losses.records.iter().all(|record| record.result == GameResult::Win(Player::O)));
+
+        let parallel = ParallelConfig::builder()
+            .num_games(100)
+            .engine_sources(|| Box::new(RandomEngine::new()), || Box::new(RandomEngine::new()))
+            .num_threads(3)
+            .chunk_size(9)
+            .seed(5)
+            .record_games(RecordPolicy::Sample(7))
+            .build();
+        let mut records = ParallelSimulator::new(parallel).run_parallel().records;
+        records.sort_by_key(|record| record.seed);
+        let mut expected = sample.records.clone();
+        expected.sort_by_key(|record| record.seed);
+        assert_eq!(untimed(&records), untimed(&expected));
+
+        let mut simulator = Simulator::new(SimulationConfig::builder().record_games(RecordPolicy::Sample(3)).build());
+        assert_eq!(simulator.run_for(std::time::Duration::from_millis(5)).records.len(), 3);
+    }
+
+    #[test]
+    fn test_simulation_observer_sees_every_event() {
+        #[derive(Default)]
+        struct Log {
+            starts: usize,
+            moves: Vec<((usize, usize), Player)>,
+            ends: Vec<GameResult>,
+            last_board: Option<Board>,
+        }
+
+        impl SimulationObserver for Log {
+            fn on_game_start(&mut self, board: &Board) {
+                assert_eq!(board.valid_moves().len(), 9);
+                self.starts += 1;
+            }
+
+            fn on_move(&mut self, board: &Board, position: (usize, usize), player: Player) {
+                assert_eq!(board.get(position.0, position.1), Some(Cell::Occupied(player)));
+                self.moves.push((position, player));
+                self.last_board = Some(board.clone());
+            }
+
+            fn on_game_end(&mut self, result: GameResult) {
+                self.ends.push(result);
+            }
+        }
+
+        let mut log = Log::default();
+        let config = SimulationConfig::builder().num_games(3).starting_player(Player::O).build();
+        let result = Simulator::new(config).run_with_observer(&mut log);
+
+        assert_eq!(log.starts, 3);
+        assert_eq!(log.ends.len(), 3);
+        assert_eq!(log.moves[0], ((0, 0), Player::O));
+        assert_eq!(log.moves[1], ((0, 1), Player::X));
+        assert_eq!(log.last_board.unwrap().game_result(), log.ends[2]);
+        assert_eq!(result.wins(Player::O), log.ends.iter().filter(|&&r| r == GameResult::Win(Player::O)).count());
+    }
+
+    #[test]
+    fn test_stop_conditions_end_runs_early() {
+        let config = SimulationConfig::builder()
+            .num_games(100_000)
+            .engines(FastEngine, RandomEngine::seeded(3))
+            .stop_when(StopCondition::Wins(Player::O, 2))
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!(result.o_wins, 2);
+        assert!(result.games_completed < 100_000);
+
+        let config = SimulationConfig::builder()
+            .num_games(100)
+            .engine(MinimaxEngine::new())
+            .stop_when(StopCondition::DrawStreak(5))
+            .build();
+        assert_eq!(Simulator::new(config).run_sequential().games_completed, 5);
+
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = std::sync::Arc::clone(&reports);
+        let config = SimulationConfig::builder()
+            .num_games(100)
+            .stop_when(StopCondition::DrawStreak(1_000))
+            .stop_when(StopCondition::when(|partial| partial.games_completed == 42))
+            .build();
+        let result = Simulator::new(config)
+            .with_progress(10, move |completed, _, _| log.lock().unwrap().push(completed))
+            .run_sequential();
+        assert_eq!(result.games_completed, 42);
+        assert_eq!(*reports.lock().unwrap(), [10, 20, 30, 40, 42]);
+    }
+
+    #[test]
+    fn test_engine_error_policy_contains_bad_engines() {
+        let silent = FnEngine::new(|_: &Board, _: Player| None);
+        let panicking = FnEngine::new(|board: &Board, _: Player| -> Option<(usize, usize)> {
+            if board.valid_moves().len() < 8 {
+                panic!("engine bug");
+            }
+            board.valid_moves().into_iter().next()
+        });
+
+        let config = SimulationConfig::builder()
+            .num_games(10)
+            .engines(silent, FastEngine)
+            .on_engine_error(EngineErrorPolicy::ForfeitGame)
+            .record_games(RecordPolicy::All)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert_eq!((result.o_wins, result.forfeits, result.games_completed), (10, 10, 10));
+        assert!(result.records.iter().all(|record| record.moves.is_empty()));
+
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let config = SimulationConfig::builder()
+            .num_games(10)
+            .engines(FastEngine, panicking)
+            .on_engine_error(EngineErrorPolicy::SkipGame)
+            .build();
+        let mut output = Vec::new();
+        let result = Simulator::new(config).run_jsonl(&mut output).unwrap();
+        std::panic::set_hook(previous_hook);
+        assert_eq!((result.games_completed, result.skipped_games, result.forfeits), (0, 10, 0));
+        assert!(output.is_empty());
+
+        let outcome = std::panic::catch_unwind(|| {
+            let config = SimulationConfig::builder()
+                .num_games(1)
+                .engine(FnEngine::new(|_: &Board, _: Player| Some((5, 5))))
+                .build();
+            Simulator::new(config).run_sequential()
+        });
+        assert!(outcome.is_err());
+    }
+
+    #[test]
+    fn test_engine_pool_random_pairings() {
+        assert!(EnginePool::builder().add_engine("fast", FastEngine).build().is_err());
+
+        let run = || {
+            EnginePool::builder()
+                .add_engine("fast", FastEngine)
+                .add_engine("random", RandomEngine::new())
+                .add_engine("minimax", MinimaxEngine::new())
+                .add_engine("noisy", NoisyEngine::new(MinimaxEngine::new(), 0.3))
+                .num_games(400)
+                .seed(8)
+                .build()
+                .unwrap()
+                .run()
+        };
+        let result = run();
+        assert_eq!(result, PoolResult { total_duration: result.total_duration, ..run() });
+
+        assert_eq!(result.games_completed, 400);
+        assert_eq!(result.engines.iter().map(|stats| stats.games).sum::<usize>(), 800);
+        assert_eq!(result.pairings.len(), 6);
+        assert_eq!(result.pairings.iter().map(|pairing| pairing.games).sum::<usize>(), 400);
+        for stats in &result.engines {
+            assert_eq!(stats.games, stats.wins + stats.losses + stats.draws);
+        }
+        assert_eq!(result.engine("minimax").unwrap().losses, 0);
+        assert_eq!(result.ranking()[0].name, "minimax");
+
+        let forward = result.pairing("random", "minimax").unwrap();
+        let backward = result.pairing("minimax", "random").unwrap();
+        assert_eq!((forward.wins, forward.losses), (backward.losses, backward.wins));
+        assert_eq!(forward.engines, ("random".to_string(), "minimax".to_string()));
+        assert!(result.pairing("random", "nobody").is_none());
+    }
+
+    #[test]
+    fn test_bracket_single_and_double_elimination() {
+        let build = |format: BracketFormat, count: usize| {
+            let mut builder = Bracket::builder().format(format).best_of(3).seed(4);
+            builder = builder.add_engine("minimax", MinimaxEngine::new());
+            for i in 1..count {
+                builder = builder.add_engine(&format!("random{}", i), RandomEngine::new());
+            }
+            builder.build().unwrap()
+        };
+
+        let single = build(BracketFormat::SingleElimination, 5).run();
+        assert_eq!(single.champion, "minimax");
+        assert_eq!(single.series.len(), 4);
+        assert_eq!(single.series[0].seeds, (4, 5));
+        assert!(single.series.iter().all(|series| series.wins.0 + series.wins.1 + series.draws <= 3));
+        let text = single.to_string();
+        assert!(text.starts_with("Single elimination, best of 3\n\nRound 1\n  #4 random3 "));
+        assert!(text.ends_with(&format!("Champion: minimax (runner-up: {})", single.runner_up)));
+
+        let double = build(BracketFormat::DoubleElimination, 4).run();
+        assert_eq!(double.champion, "minimax");
+        let finals = double.series.iter().filter(|series| series.stage == BracketStage::GrandFinal).count();
+        assert_eq!(finals, 1);
+        assert!(double.series.len() == 6 || double.series.len() == 7);
+        for name in ["random1", "random2", "random3"] {
+            let losses = double
+                .series
+                .iter()
+                .filter(|series| (series.engines.0 == name || series.engines.1 == name) && series.winner != name)
+                .count();
+            assert!(losses == 2 || name == double.runner_up);
+        }
+        assert_eq!(double, build(BracketFormat::DoubleElimination, 4).run());
+
+        let mut even = Bracket::builder()
+            .add_engine("a", MinimaxEngine::new())
+            .add_engine("b", MinimaxEngine::new())
+            .best_of(4)
+            .build()
+            .unwrap();
+        let result = even.run();
+        assert_eq!((result.series[0].wins, result.series[0].draws), ((0, 0), 4));
+        assert_eq!(result.champion, "a");
+        assert!(Bracket::builder().add_engine("a", FastEngine).build().is_err());
+    }
+
+    #[test]
+    fn test_glicko2_matches_reference_example() {
+        // Worked example from Glickman's Glicko-2 paper
+        let mut ratings = Glicko2::new();
+        for (name, rating, deviation) in [("a", 1500.0, 200.0), ("b", 1400.0, 30.0), ("c", 1550.0, 100.0), ("d", 1700.0, 300.0)] {
+            ratings.set_rating(name, Glicko2Rating { rating, deviation, volatility: 0.06 });
+        }
+        ratings.update(&[("a", "b", 1.0), ("a", "c", 0.0), ("a", "d", 0.0)]);
+        let a = ratings.rating("a").unwrap();
+        assert!((a.rating - 1464.06).abs() < 0.01, "rating {}", a.rating);
+        assert!((a.deviation - 151.52).abs() < 0.01, "deviation {}", a.deviation);
+        assert!((a.volatility - 0.05999).abs() < 0.00001, "volatility {}", a.volatility);
+    }
+
+    #[test]
+    fn test_bracket_glicko2_rates_champion_highest() {
+        let result = Bracket::builder()
+            .add_engine("minimax", MinimaxEngine::new())
+            .add_engine("random", RandomEngine::new())
+            .add_engine("fast", FastEngine)
+            .best_of(5)
+            .seed(3)
+            .build()
+            .unwrap()
+            .run();
+        let ratings = result.glicko2();
+        assert_eq!(ratings.ratings()[0].0, "minimax");
+        assert_eq!(ratings.standings()[0].0, result.champion);
+    }
+
+    #[test]
+    fn test_tournament_cross_table_is_consistent() {
+        let result = Tournament::builder()
+            .add_engine("fast", FastEngine)
+            .add_engine("random", RandomEngine::new())
+            .add_engine("minimax", MinimaxEngine::new())
+            .games_per_matchup(10)
+            .seed(11)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(result.games_completed, 30);
+        for a in &result.engines {
+            for b in &result.engines {
+                let (ab, ba) = (result.matchup(a, b).unwrap(), result.matchup(b, a).unwrap());
+                assert_eq!((ab.wins, ab.draws, ab.losses), (ba.losses, ba.draws, ba.wins));
+                assert_eq!(ab.games(), if a == b { 0 } else { 10 });
+            }
+        }
+        let standings = result.standings();
+        assert_eq!(standings[0].name, "minimax");
+        assert_eq!(standings.iter().map(|s| s.points).sum::<f64>(), 30.0);
+        assert_eq!(result.totals("minimax").unwrap().losses, 0);
+
+        let mut csv = Vec::new();
+        result.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("engine,minimax,"));
+        assert!(lines[0].ends_with(",wins,draws,losses,points"));
+        assert!(lines[1].starts_with("minimax,,"));
+
+        let table = result.to_string();
+        assert!(table.contains("W-D-L"));
+        assert_eq!(table.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_bench_scenarios_have_unique_names_and_run() {
+        let mut scenarios = crate::bench::scenarios();
+        let mut names: Vec<&str> = scenarios.iter().map(|s| s.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), scenarios.len());
+        for scenario in scenarios.iter_mut().filter(|s| !s.name().starts_with("simulator/")) {
+            let report = scenario.measure(std::time::Duration::ZERO);
+            assert_eq!(report.iterations, 1);
+            assert_eq!(report.units, scenario.units_per_iteration());
+        }
+        assert!(crate::bench::scenario("engine/minimax").is_some());
+        assert!(crate::bench::scenario("engine/unknown").is_none());
+    }
+
+    #[test]
+    fn test_board_index_matches_get() {
+        let mut board = Board::new();
+        board.make_move(0, 2, Player::O).unwrap();
+        for row in 0..3 {
+            for col in 0..3 {
+                assert_eq!(Some(board[(row, col)]), board.get(row, col));
+            }
+        }
+        assert!(std::panic::catch_unwind(|| board[(3, 0)]).is_err());
+    }
+
+    #[test]
+    fn test_board_cells_iterate_row_major() {
+        let mut board = Board::new();
+        board.make_move(0, 1, Player::X).unwrap();
+        board.make_move(2, 2, Player::O).unwrap();
+        let cells: Vec<_> = (&board).into_iter().collect();
+        assert_eq!(cells.len(), 9);
+        assert_eq!(board.cells().len(), 9);
+        assert_eq!(cells[1], ((0, 1), Cell::Occupied(Player::X)));
+        assert_eq!(cells[8], ((2, 2), Cell::Occupied(Player::O)));
+        for (index, &((row, col), cell)) in cells.iter().enumerate() {
+            assert_eq!((row, col), (index / 3, index % 3));
+            assert_eq!(cell, board[(row, col)]);
+        }
+    }
+
+    #[test]
+    fn test_pos_and_move_round_trip_with_tuples() {
+        for (index, pos) in Pos::ALL.into_iter().enumerate() {
+            assert_eq!(Pos::from_index(index), Some(pos));
+            let (row, col) = pos.into();
+            assert_eq!(Pos::try_from((row, col)), Ok(pos));
+            assert_eq!(pos.to_string().parse::<Pos>(), Ok(pos));
+        }
+        assert_eq!(Pos::try_from((0, 3)), Err("Position out of bounds"));
+        assert!("d1".parse::<Pos>().is_err());
+
+        let mut board = Board::new();
+        let mv = MinimaxEngine::new().choose(&board, Player::X).unwrap();
+        board.apply_move(mv).unwrap();
+        assert_eq!(board[mv.pos], Cell::Occupied(Player::X));
+        assert_eq!(board.valid_positions().len(), 8);
+        assert_eq!(board.apply_move(mv), Err("Cell already occupied"));
+        let mut random = RandomEngine::seeded(3);
+        let reply = random.choose_mut(&board, Player::O).unwrap();
+        assert!(board.valid_positions().contains(&reply.pos));
+    }
+
+    #[test]
+    fn test_board_from_moves_reports_first_illegal_move() {
+        let board = Board::from_moves(&[(1, 1), (0, 0)]).unwrap();
+        assert_eq!(board[(1, 1)], Cell::Occupied(Player::X));
+        assert_eq!(board[(0, 0)], Cell::Occupied(Player::O));
+
+        let error = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (2, 2)]).unwrap_err();
+        assert_eq!(error, IllegalMove { index: 5, reason: "Game is already over" });
+        assert_eq!(error.to_string(), "move 6: Game is already over");
+        assert_eq!(Board::from_moves(&[(3, 0)]).unwrap_err().index, 0);
+
+        let at = |row, col, player| Move::new(Pos::new(row, col).unwrap(), player);
+        let board = Board::from_player_moves(&[at(0, 0, Player::O), at(1, 1, Player::O)]).unwrap();
+        assert_eq!(board[(0, 0)], Cell::Occupied(Player::O));
+        assert_eq!(board.valid_moves().len(), 7);
+    }
+
+    #[test]
+    fn test_board_array_conversion_round_trips() {
+        let board = Board::from_moves(&[(0, 0), (1, 1), (0, 1), (2, 2), (0, 2)]).unwrap();
+        let cells: [[Cell; 3]; 3] = (&board).into();
+        let rebuilt = Board::try_from(cells).unwrap();
+        assert_eq!(rebuilt, board);
+        assert_eq!(rebuilt.game_result(), GameResult::Win(Player::X));
+
+        let mut cells = [[Cell::Empty; 3]; 3];
+        cells[0][0] = Cell::Occupied(Player::O);
+        assert!(Board::try_from(cells).is_ok());
+        cells[0][1] = Cell::Occupied(Player::O);
+        assert_eq!(Board::try_from(cells), Err("Inconsistent piece counts"));
+    }
+
+    #[test]
+    fn test_board_validate_rejects_impossible_positions() {
+        let x = Cell::Occupied(Player::X);
+        let o = Cell::Occupied(Player::O);
+        let e = Cell::Empty;
+        let check = |cells| {
+            let mut board = Board::new();
+            board.cells = cells;
+            board.validate()
+        };
+
+        assert_eq!(check([[e; 3]; 3]), Ok(()));
+        assert_eq!(check([[x, x, x], [o, o, e], [e, e, e]]), Ok(()));
+        assert_eq!(check([[x, x, x], [o, o, o], [e, e, e]]), Err("Both players completed a line"));
+        assert_eq!(check([[x, x, e], [e, e, e], [e, e, e]]), Err("Inconsistent piece counts"));
+        assert_eq!(check([[o, o, o], [x, x, e], [x, x, e]]), Err("Moves were played after the game ended"));
+        // Two lines through the last move
+        assert_eq!(check([[x, x, x], [x, o, o], [x, o, o]]), Ok(()));
+
+        let mut played = Board::new();
+        for (row, col) in [(0, 0), (1, 1), (2, 2), (0, 2), (2, 0), (1, 0), (2, 1)] {
+            let player = played.current_player().unwrap();
+            played.make_move(row, col, player).unwrap();
+            assert_eq!(played.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_winning_and_blocking_moves() {
+        // X on a1, c3, a3 threatens a2 and b3; O on b2, b1 threatens b3
+        let board = Board::from_moves(&[(0, 0), (1, 1), (2, 2), (0, 1), (2, 0)]).unwrap();
+        assert_eq!(board.winning_moves(Player::X), vec![(1, 0), (2, 1)]);
+        assert_eq!(board.winning_moves(Player::O), vec![(2, 1)]);
+        assert_eq!(board.blocking_moves(Player::O), vec![(1, 0), (2, 1)]);
+        for (row, col) in board.winning_moves(Player::X) {
+            let mut next = board.clone();
+            next.make_move(row, col, Player::X).unwrap();
+            assert_eq!(next.game_result(), GameResult::Win(Player::X));
+        }
+
+        let mut misere = Board::with_rules(Rules::Misere);
+        for (row, col, player) in [(0, 0, Player::X), (1, 1, Player::O), (0, 1, Player::X)] {
+            misere.make_move(row, col, player).unwrap();
+        }
+        assert!(misere.winning_moves(Player::X).is_empty());
+        assert!(Board::new().winning_moves(Player::X).is_empty());
+    }
+
+    #[test]
+    fn test_threats_classify_positions() {
+        let empty = Threats::analyze(&Board::new(), Player::X);
+        assert_eq!(empty.status, TacticalStatus::Quiet);
+        assert_eq!((empty.open_lines, empty.opponent_open_lines), (8, 8));
+
+        // X on a1, b1; O on b2: X can win on c1
+        let board = Board::from_moves(&[(0, 0), (1, 1), (0, 1)]).unwrap();
+        let threats = Threats::analyze(&board, Player::X);
+        assert_eq!(threats.wins, vec![(0, 2)]);
+        assert_eq!(threats.status, TacticalStatus::CanWin);
+        assert_eq!(threats.open_lines, 4);
+        assert_eq!(threats::open_lines(&board, Player::O), 4);
+
+        // X on a1, c3; O on b2 with X to move again: a3 and c1 fork
+        let mut board = Board::from_moves(&[(0, 0), (1, 1), (2, 2)]).unwrap();
+        assert_eq!(Threats::analyze(&board, Player::X).status, TacticalStatus::CanFork);
+        assert_eq!(Threats::analyze(&board, Player::O).opponent_forks, vec![(0, 2), (2, 0)]);
+
+        board.make_move(0, 1, Player::O).unwrap();
+        board.make_move(2, 1, Player::X).unwrap();
+        board.make_move(2, 0, Player::O).unwrap();
+        board.make_move(1, 0, Player::X).unwrap();
+        board.make_move(0, 2, Player::O).unwrap();
+        assert_eq!(Threats::analyze(&board, Player::X).status, TacticalStatus::Finished);
+    }
+
+    #[test]
+    fn test_board_transforms_compose() {
+        let board = Board::from_moves(&[(0, 1), (1, 1), (2, 2)]).unwrap();
+        assert_eq!(board.rotate90().rotate90(), board.rotate180());
+        assert_eq!(board.rotate90().rotate180(), board.rotate270());
+        assert_eq!(board.rotate270().rotate90(), board);
+        assert_eq!(board.mirror_horizontal().mirror_horizontal(), board);
+        assert_eq!(board.mirror_horizontal().mirror_vertical(), board.rotate180());
+        assert_eq!(board.mirror_vertical()[(0, 2)], Cell::Occupied(Player::X));
+        for symmetry in Symmetry::ALL {
+            let image = board.transform(symmetry);
+            assert_eq!(image.transform(symmetry.inverse()), board);
+            assert_eq!(image.game_result(), board.game_result());
+            assert_eq!(image[symmetry.apply((0, 1))], board[(0, 1)]);
+        }
+    }
+
+    #[test]
+    fn test_symmetry_class_sizes_and_inverse_transforms() {
+        assert_eq!(Board::new().symmetry_class(), vec![(Board::new(), Symmetry::Identity)]);
+        assert_eq!(Board::from_moves(&[(0, 1)]).unwrap().symmetry_class().len(), 4);
+        let board = Board::from_moves(&[(0, 1), (0, 0)]).unwrap();
+        let class = board.symmetry_class();
+        assert_eq!(class.len(), 8);
+        assert_eq!(class[0].0, board);
+        for (image, back) in &class {
+            assert_eq!(image.transform(*back), board);
+            assert_eq!(image.canonical().0, board.canonical().0);
+        }
+    }
+
+    #[test]
+    fn test_enumerate_positions_counts() {
+        let all: Vec<Board> = enumerate_positions().collect();
+        assert_eq!(all.len(), 5478);
+        assert!(all.iter().all(|board| board.validate().is_ok()));
+        let per_ply: Vec<usize> = (0..=9).map(|ply| enumerate_positions().ply(ply).count()).collect();
+        assert_eq!(per_ply, vec![1, 9, 72, 252, 756, 1260, 1520, 1140, 390, 78]);
+        assert_eq!(enumerate_positions().to_move(Player::X).count(), per_ply.iter().step_by(2).sum::<usize>());
+        // 958 of the positions are finished games
+        assert_eq!(enumerate_positions().in_progress().count(), 5478 - 958);
+        let misere = enumerate_positions().rules(Rules::Misere).ply(5).next().unwrap();
+        assert_eq!(misere.rules(), Rules::Misere);
+    }
+
+    #[test]
+    fn test_is_reachable_matches_enumeration() {
+        let reachable: std::collections::HashSet<u16> = enumerate_positions().map(|board| board.encode()).collect();
+        for code in 0..19_683u16 {
+            let board = Board::decode(code, Rules::Standard);
+            assert_eq!(board.is_reachable(), reachable.contains(&code), "{}", board.cell_string());
+        }
+    }
+
+    #[test]
+    fn test_batch_simulator_matches_sequential_fast_engine() {
+        let batch = BatchSimulator::builder().num_games(300).batch_size(128).build().run();
+        let config = SimulationConfig::builder().num_games(300).engine(FastEngine).build();
+        let sequential = Simulator::new(config).run_sequential();
+        assert_eq!((batch.x_wins, batch.o_wins, batch.draws), (sequential.x_wins, sequential.o_wins, sequential.draws));
+
+        let misere = BatchSimulator::builder().num_games(10).rules(Rules::Misere).build().run();
+        assert_eq!(misere.o_wins, 10);
+
+        let random = |batch_size| {
+            BatchSimulator::builder()
+                .num_games(5000)
+                .batch_size(batch_size)
+                .policies(BatchPolicy::Random, BatchPolicy::FirstFree)
+                .starting_player(Player::O)
+                .seed(9)
+                .build()
+                .run()
+        };
+        let (a, b) = (random(64), random(1000));
+        assert_eq!((a.x_wins, a.o_wins, a.draws), (b.x_wins, b.o_wins, b.draws));
+        assert_eq!(a.games_completed, 5000);
+    }
+
+    #[test]
+    fn test_batch_win_detection_matches_board() {
+        // Every mask of one player's pieces, in an odd count so the SIMD path
+        // also leaves a scalar tail
+        let masks: Vec<u16> = (0..512u16).chain(0..3).collect();
+        let mut state = vec![0u8; masks.len()];
+        crate::simulation::batch::mark_wins(&masks, &mut state, 1);
+
+        for (&mask, &state) in masks.iter().zip(&state) {
+            let won = WIN_LINES
+                .iter()
+                .any(|line| line.iter().all(|&(row, col)| mask & (1 << (row * 3 + col)) != 0));
+            assert_eq!(state == 1, won, "mask {mask:09b}");
+        }
+
+        // Lanes that are already finished keep their state
+        let mut state = vec![3u8; 8];
+        crate::simulation::batch::mark_wins(&[0o007; 8], &mut state, 1);
+        assert_eq!(state, vec![3u8; 8]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_run_rayon_matches_threads() {
+        let config = || {
+            ParallelConfig::builder()
+                .num_games(5_000)
+                .engine(RandomEngine::new())
+                .chunk_size(300)
+                .seed(11)
+                .record_games(RecordPolicy::All)
+                .build()
+        };
+        let threaded = ParallelSimulator::new(config()).run_parallel();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        let pooled = pool.install(|| ParallelSimulator::new(config()).run_rayon());
+
+        assert_eq!(pooled.games_completed, 5_000);
+        assert_eq!((pooled.x_wins, pooled.o_wins, pooled.draws), (threaded.x_wins, threaded.o_wins, threaded.draws));
+
+        // Records come back in game order, the same games a sequential run plays
+        let sequential = Simulator::new(
+            SimulationConfig::builder()
+                .num_games(5_000)
+                .engine(RandomEngine::new())
+                .seed(11)
+                .record_games(RecordPolicy::All)
+                .build(),
+        )
+        .run_sequential();
+        assert_eq!(untimed(&pooled.records), untimed(&sequential.records));
+
+        // Unseeded, every rayon split still plays its own games
+        let unseeded = ParallelConfig::builder()
+            .num_games(8_000)
+            .engine(RandomEngine::new())
+            .chunk_size(1_000)
+            .record_games(RecordPolicy::All)
+            .build();
+        let records = pool.install(|| ParallelSimulator::new(unseeded).run_rayon()).records;
+        let distinct: std::collections::HashSet<_> = records.iter().map(|record| &record.moves).collect();
+        assert!(distinct.len() > 5_000, "only {} distinct games", distinct.len());
+    }
+
+    #[test]
+    fn test_parallel_config_default_chunk_size() {
+        let config = ParallelConfig::builder().num_games(100).num_threads(16).build();
+        assert_eq!(config.chunk_size(), 1);
+        let config = ParallelConfig::builder().num_games(0).build();
+        assert_eq!(config.chunk_size(), 1);
+        let config = ParallelConfig::builder().num_games(64_000).num_threads(2).chunk_size(10).build();
+        assert_eq!(config.chunk_size(), 10);
+        assert!(ParallelConfig::default().num_threads() >= 1);
+    }
+
+    #[cfg(feature = "affinity")]
+    #[test]
+    fn test_parallel_pinned_threads() {
+        let config = ParallelConfig::builder()
+            .num_games(2_000)
+            .engine(RandomEngine::new())
+            .num_threads(3)
+            .seed(5)
+            .pin_threads(true)
+            .build();
+        assert!(config.pin_threads());
+        let pinned = ParallelSimulator::new(config).run_parallel();
+
+        let config = ParallelConfig::builder().num_games(2_000).engine(RandomEngine::new()).seed(5).build();
+        let unpinned = ParallelSimulator::new(config).run_parallel();
+        assert_eq!((pinned.x_wins, pinned.o_wins, pinned.draws), (unpinned.x_wins, unpinned.o_wins, unpinned.draws));
+    }
+
+    #[test]
+    fn test_parallel_games_independent_of_threads() {
+        let run = |threads: usize, chunk_size: usize| {
+            let config = ParallelConfig::builder()
+                .num_games(300)
+                .engine(RandomEngine::new())
+                .num_threads(threads)
+                .chunk_size(chunk_size)
+                .seed(2024)
+                .record_games(RecordPolicy::All)
+                .build();
+            let seeds: Vec<_> = (0..300).map(|game| config.game_seed(game).unwrap()).collect();
+            let mut records = ParallelSimulator::new(config).run_parallel().records;
+            // Workers finish in any order; put the records back in game order
+            records.sort_by_key(|record| seeds.iter().position(|&seed| Some(seed) == record.seed));
+            untimed(&records)
+        };
+
+        let single = run(1, 300);
+        let sequential = SimulationConfig::builder().num_games(300).seed(2024).build();
+        for (game, record) in single.iter().enumerate() {
+            assert_eq!(record.seed, sequential.game_seed(game));
+        }
+        for (threads, chunk_size) in [(2, 1), (5, 13), (8, 64)] {
+            assert_eq!(run(threads, chunk_size), single);
+        }
+    }
+
+    #[test]
+    fn test_unseeded_parallel_workers_play_different_games() {
+        let config = ParallelConfig::builder()
+            .num_games(8_000)
+            .engine(RandomEngine::new())
+            .num_threads(8)
+            .chunk_size(1_000)
+            .record_games(RecordPolicy::All)
+            .build();
+        let records = untimed(&ParallelSimulator::new(config).run_parallel().records);
+        let distinct: std::collections::HashSet<_> = records.iter().map(|record| &record.moves).collect();
+        assert!(distinct.len() > 5_000, "only {} distinct games", distinct.len());
+        assert!(records.iter().all(|record| record.seed.is_none()));
+    }
+
+    #[test]
+    fn test_game_result_helpers() {
+        for player in [Player::X, Player::O] {
+            let result = GameResult::Win(player);
+            assert_eq!(result.winner(), Some(player));
+            assert_eq!(result.loser(), Some(player.opponent()));
+            assert!(result.is_terminal());
+            assert!(!result.is_draw());
+        }
+        assert_eq!((GameResult::Draw.winner(), GameResult::Draw.loser()), (None, None));
+        assert!(GameResult::Draw.is_terminal() && GameResult::Draw.is_draw());
+        assert_eq!(GameResult::InProgress.winner(), None);
+        assert!(!GameResult::InProgress.is_terminal() && !GameResult::InProgress.is_draw());
+
+        let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]).unwrap();
+        assert_eq!(board.game_result().winner(), Some(Player::X));
+    }
+
+    #[test]
+    fn test_formatter_highlight() {
+        let mut game = Game::new();
+        assert_eq!(game.last_move(), None);
+        assert_eq!(game.formatter().to_string(), game.board().to_string());
+
+        game.play(1, 1).unwrap();
+        game.play(2, 0).unwrap();
+        let text = game.formatter().grid(true).coordinates(true).to_string();
+        assert_eq!(text, "   a   b   c\n1  . | . | . \n  ---+---+---\n2  . | X | . \n  ---+---+---\n3 [O]| . | . ");
+
+        let colored = game.formatter().colors(true).to_string();
+        assert_eq!(colored.lines().count(), 3);
+        assert!(colored.contains("\x1b[7m"));
+        assert!(!colored.contains("[O]"));
+
+        game.undo();
+        assert_eq!(game.last_move(), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_simulation_result_display() {
+        let mut result = SimulationResult::new();
+        let empty = result.to_string();
+        assert!(empty.contains("0 (0.00%)"));
+        assert!(!empty.contains("Forfeits"));
+
+        result.games_completed = 4;
+        result.x_wins = 3;
+        result.draws = 1;
+        result.forfeits = 2;
+        let text = result.to_string();
+        let widths: Vec<usize> = text.lines().map(|line| line.chars().count()).collect();
+        assert!(widths.windows(2).all(|pair| pair[0] == pair[1]), "{text}");
+        assert!(text.contains("3 (75.00%)"));
+        assert!(text.contains("Forfeits"));
+        assert!(!text.contains("Skipped"));
+    }
+
+    #[test]
+    fn test_play_game_record() {
+        let record = play_game(&mut FastEngine, &mut FastEngine, Player::O);
+        assert_eq!(record.starting_player, Player::O);
+        assert_eq!(record.moves, vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (2, 0)]);
+        assert_eq!(record.result, GameResult::Win(Player::O));
+
+        let record = play_game_with_rules(&mut FastEngine, &mut FastEngine, Player::X, Rules::Misere);
+        assert_eq!(record.rules, Rules::Misere);
+        assert_eq!(record.result, GameResult::Win(Player::O));
+
+        // An engine without a move leaves the game in progress
+        let mut idle = FnEngine::new(|_: &Board, _| None);
+        let record = play_game(&mut FastEngine, &mut idle, Player::X);
+        assert_eq!(record.moves, vec![(0, 0)]);
+        assert_eq!(record.result, GameResult::InProgress);
+
+        let mut engines: Vec<BoxedEngine> = vec![Box::new(RandomEngine::seeded(1)), Box::new(MinimaxEngine::new())];
+        let (x, o) = engines.split_at_mut(1);
+        let record = play_game(x[0].as_mut(), o[0].as_mut(), Player::X);
+        assert_ne!(record.result, GameResult::Win(Player::X));
+    }
+
+    #[test]
+    fn test_match_series() {
+        assert!(Match::builder().add_engine("fast", FastEngine).build().is_err());
+
+        let mut series = Match::builder()
+            .add_engine("fast", FastEngine)
+            .add_engine("minimax", MinimaxEngine::new())
+            .best_of(9)
+            .stop_when_decided(true)
+            .build()
+            .unwrap();
+        let result = series.run();
+        assert_eq!(result.winner(), Some("minimax"));
+        assert!(result.decided_early());
+        assert_eq!(result.records.len(), result.score.games());
+        assert_eq!(result.score.wins, 0);
+        let (fast, minimax) = result.points();
+        assert!(minimax - fast > (9 - result.records.len()) as f64);
+        assert!(result.to_string().ends_with("minimax wins"));
+
+        // Two copies of the same deterministic engine split a full series
+        let mut mirror = Match::builder()
+            .add_engine("a", FastEngine)
+            .add_engine("b", FastEngine)
+            .best_of(4)
+            .build()
+            .unwrap();
+        let result = mirror.run();
+        assert_eq!(result.verdict, MatchVerdict::Tied);
+        assert_eq!(result.score, MatchupRecord { wins: 2, draws: 0, losses: 2 });
+        assert!(result.results().all(|outcome| outcome == GameResult::Win(Player::X)));
+    }
+
+    #[test]
+    fn test_engine_info() {
+        assert_eq!(FastEngine.name(), "fast");
+        assert_eq!(MinimaxEngine::new().name(), "minimax");
+        assert!(FastEngine.version().is_some());
+        assert!(FastEngine.description().is_some());
+
+        let boxed: BoxedEngine = Box::new(RandomEngine::seeded(1));
+        assert_eq!(boxed.name(), "random");
+
+        let config = SimulationConfig::builder()
+            .num_games(2)
+            .engines(FastEngine, MinimaxEngine::new())
+            .record_games(RecordPolicy::All)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        let record = &result.records[0];
+        assert_eq!(record.tag("X"), Some("fast"));
+        assert_eq!(record.tag("O"), Some("minimax"));
+
+        let tournament = Tournament::builder()
+            .add_by_info(FastEngine)
+            .add_by_info(MinimaxEngine::new())
+            .build()
+            .unwrap();
+        assert_eq!(tournament.names(), ["fast", "minimax"]);
+    }
+
+    #[test]
+    fn test_minimax_depth_heuristic() {
+        let shallow = MinimaxEngine::with_depth(1);
+        assert_eq!(shallow.choose_move(&Board::new(), Player::X), Some((1, 1)));
+
+        // X on a1 and b1, O on b2: one ply is enough to complete the line
+        let board = Board::from_moves(&[(0, 0), (1, 1), (0, 1)]).unwrap();
+        let search = shallow.search(&board, Player::O);
+        assert!(search.score.abs() < 10);
+        assert!(!search.complete);
+
+        // Two plies see the threat and block it
+        assert_eq!(MinimaxEngine::with_depth(2).choose_move(&board, Player::O), Some((0, 2)));
+
+        // Under misère rules an edge, which lies on the fewest lines, scores best
+        let misere = Board::with_rules(Rules::Misere);
+        assert_eq!(shallow.choose_move(&misere, Player::X), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_eval_evaluate() {
+        assert_eq!(eval::evaluate(&Board::new(), Player::X), 0);
+
+        let won = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]).unwrap();
+        assert_eq!(eval::evaluate(&won, Player::X), eval::WIN_EVAL);
+        assert_eq!(eval::evaluate(&won, Player::O), -eval::WIN_EVAL);
+
+        // X on opposite corners with O in the center has two forking squares
+        let board = Board::from_moves(&[(0, 0), (1, 1), (2, 2)]).unwrap();
+        let score = eval::evaluate(&board, Player::X);
+        assert!(score > 0 && score < eval::WIN_EVAL);
+        assert_eq!(eval::evaluate(&board, Player::O), -score);
+
+        let engine = EvalEngine::new(|board: &Board, player| eval::evaluate(board, player) as f64, 1);
+        assert_eq!(engine.choose_move(&Board::new(), Player::X), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_minimax_distance_to_mate() {
+        // X can win at once on the top row or later elsewhere; the score
+        // reflects the fastest win
+        let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1)]).unwrap();
+        let scores = MinimaxEngine::new().evaluate_moves(&board, Player::X);
+        assert_eq!(scores[0], ((0, 2), 9.0));
+        assert!(scores[1].1 < 9.0);
+
+        // Exact evaluations agree with the solved database
+        let db = SolvedDatabase::shared(Rules::Standard);
+        let engine = MinimaxEngine::new();
+        for (board, solution) in db.iter().filter(|(board, _)| board.game_result() == GameResult::InProgress).step_by(37) {
+            let player = board.current_player().unwrap();
+            assert_eq!(engine.search(&board, player).eval(), Some(Eval::from(solution)), "{:?}", board);
+        }
+
+        assert_eq!(Eval::from_score(Eval::LossIn(4).score()), Eval::LossIn(4));
+        assert_eq!(MinimaxEngine::with_depth(2).search(&Board::new(), Player::X).eval(), None);
+    }
+
+    #[test]
+    fn test_transposition_table_shared_across_threads() {
+        use std::sync::Arc;
+
+        let table = Arc::new(TranspositionTable::new());
+        let engine = CachedEngine::new(MinimaxEngine::new(), Arc::clone(&table));
+        assert_eq!(engine.name(), "minimax");
+
+        let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1)]).unwrap();
+        assert_eq!(engine.choose_move(&board, Player::X), Some((0, 2)));
+        assert_eq!(table.get(&board, Player::X), Some(Some((0, 2))));
+        assert_eq!(table.get(&board, Player::O), None);
+
+        let config = ParallelConfig::builder()
+            .num_games(200)
+            .num_threads(4)
+            .engines(engine.clone(), RandomEngine::seeded(5))
+            .seed(9)
+            .build();
+        let cached = ParallelSimulator::new(config).run_parallel();
+        let config = ParallelConfig::builder()
+            .num_games(200)
+            .num_threads(4)
+            .engines(MinimaxEngine::new(), RandomEngine::seeded(5))
+            .seed(9)
+            .build();
+        let plain = ParallelSimulator::new(config).run_parallel();
+        assert_eq!((cached.x_wins, cached.o_wins, cached.draws), (plain.x_wins, plain.o_wins, plain.draws));
+
+        let stats = table.stats();
+        assert!(stats.hits > stats.misses);
+        assert!(stats.entries <= stats.misses);
+        table.clear();
+        assert_eq!(table.stats(), TableStats::default());
+    }
+
+    #[test]
+    fn test_estimate_strength_ladder() {
+        let perfect = estimate_strength_with(PerfectLookupEngine, 20, 3);
+        assert_eq!(perfect.score, 1.0);
+        assert_eq!(perfect.ladder.len(), 3);
+        assert!(perfect.ladder.iter().all(|result| result.record.losses == 0 && result.record.games() == 20));
+
+        let random = estimate_strength_with(RandomEngine::new(), 20, 3);
+        assert!(random.score < perfect.score);
+        assert!(random.elo < perfect.elo);
+        assert_eq!(random, estimate_strength_with(RandomEngine::new(), 20, 3));
+
+        let shallow = estimate_strength_with(MinimaxEngine::with_depth(1), 20, 3);
+        assert!(shallow.elo > random.elo && shallow.elo < perfect.elo);
+        assert!(shallow.to_string().contains("vs heuristic"));
+    }
+
+    #[test]
+    fn test_exploitability_best_response() {
+        use crate::analysis::exploitability;
+
+        let perfect = exploitability(&mut PerfectLookupEngine, 1);
+        assert_eq!((perfect.as_x, perfect.as_o, perfect.value_x), (0.5, 0.5, 0.5));
+        assert_eq!(perfect.gain(), 0.0);
+
+        let fast = exploitability(&mut FastEngine, 1);
+        assert_eq!((fast.as_x, fast.as_o), (1.0, 1.0));
+        assert_eq!(fast.gain(), 0.5);
+
+        // A random engine is beatable but not every time
+        let random = exploitability(&mut RandomEngine::seeded(4), 50);
+        assert!(random.gain() > 0.0 && random.gain() < fast.gain());
+        assert!(random.positions > fast.positions);
+        assert!(random.to_string().starts_with("best response scores"));
+    }
+
+    #[test]
+    fn test_annotate_game() {
+        use crate::analysis::{annotate_game, MoveQuality};
+
+        // Perfect self-play has only best moves
+        let record = play_game(&mut PerfectLookupEngine, &mut PerfectLookupEngine, Player::X);
+        let annotations = annotate_game(&record).unwrap();
+        assert_eq!(annotations.len(), record.moves.len());
+        assert!(annotations.iter().all(|a| a.quality == MoveQuality::Best && a.swing() == 0.0));
+
+        // X forks instead of completing the top row: a slower win
+        let record = GameRecord::new(Rules::Standard, Player::X, &[(0, 0), (2, 0), (0, 1), (2, 2), (2, 1)]).unwrap();
+        let last = &annotate_game(&record).unwrap()[4];
+        assert_eq!(last.quality, MoveQuality::Inaccuracy);
+        assert_eq!(last.best_moves, vec![(0, 2)]);
+        assert_eq!(last.to_string(), "5. X b3: inaccuracy (win in 1 -> win in 3), best c1");
+
+        // With O first the same moves get the same labels, with colors swapped
+        let swapped = GameRecord::new(Rules::Standard, Player::O, &record.moves).unwrap();
+        let annotations = annotate_game(&swapped).unwrap();
+        assert_eq!(annotations[4].quality, MoveQuality::Inaccuracy);
+        assert_eq!(annotations[4].player, Player::O);
+
+        let mut invalid = record.clone();
+        invalid.moves[1] = (0, 0);
+        assert!(annotate_game(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_analyze_position() {
+        use crate::analysis::analyze;
+
+        let empty = analyze(&Board::new(), Player::X).unwrap();
+        assert_eq!(empty.value, Eval::Draw);
+        assert_eq!(empty.best_moves.len(), 9);
+        assert!(empty.to_string().starts_with("X to move: draw\n  a1 draw"));
+
+        // X to win at once on the top row; moves are sorted best first
+        let board = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1)]).unwrap();
+        let analysis = analyze(&board, Player::X).unwrap();
+        assert_eq!(analysis.value, Eval::WinIn(1));
+        assert_eq!(analysis.best_moves, vec![(0, 2)]);
+        assert_eq!(analysis.moves[0], ((0, 2), Eval::WinIn(1)));
+        assert!(analysis.moves.windows(2).all(|w| w[0].1.score() >= w[1].1.score()));
+
+        // The same position in a game O started, with O to move
+        let mut game = Game::with_starting_player(Player::O);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            game.play(row, col).unwrap();
+        }
+        assert_eq!(analyze(game.board(), Player::O).unwrap().moves, analysis.moves);
+
+        let won = Board::from_moves(&[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]).unwrap();
+        let finished = analyze(&won, Player::O).unwrap();
+        assert_eq!(finished.value, Eval::LossIn(0));
+        assert!(finished.moves.is_empty());
+    }
+
+    #[test]
+    fn test_opening_stats() {
+        let config = || SimulationConfig::builder().num_games(300).engine(RandomEngine::new()).seed(11).build();
+        let mut first = OpeningStats::new();
+        let result = Simulator::new(config()).run_with_observer(&mut first);
+        assert_eq!(first.games(), 300);
+        assert_eq!(first.iter().count(), 9);
+        let x_wins: usize = SquareKind::ALL.iter().map(|&kind| first.by_kind(kind).x_wins).sum();
+        assert_eq!(x_wins, result.x_wins);
+
+        let mut two = OpeningStats::with_plies(2);
+        Simulator::new(config()).run_with_observer(&mut two);
+        assert!(two.iter().all(|(opening, _)| opening.len() == 2));
+        assert_eq!(two.get(&[(1, 1)]), first.get(&[(1, 1)]));
+
+        let mut merged = first.clone();
+        merged.merge(&first);
+        assert_eq!(merged.get(&[]).games(), 600);
+        assert!(first.to_string().contains("corner openings:"));
+        assert_eq!(SquareKind::of((0, 1)), SquareKind::Edge);
+    }
+
+    #[test]
+    fn test_move_heatmap_by_ply_and_player() {
+        let config = SimulationConfig::builder()
+            .num_games(200)
+            .engines(RandomEngine::new(), FastEngine)
+            .starting_player_policy(StartingPlayerPolicy::Alternate)
+            .seed(2)
+            .build();
+        let mut heatmap = MoveHeatmap::new();
+        let result = Simulator::new(config).run_with_observer(&mut heatmap);
+
+        assert_eq!(heatmap.moves_at(0), result.games_completed);
+        // O opens half the games and FastEngine always opens in the corner
+        assert_eq!(heatmap.get(0, Player::O)[0][0], 100);
+        assert_eq!(heatmap.get(0, Player::O).iter().flatten().sum::<usize>(), 100);
+
+        let per_ply: usize = (0..9).map(|ply| heatmap.moves_at(ply)).sum();
+        assert_eq!(per_ply, heatmap.total().iter().flatten().sum::<usize>());
+        assert_eq!(heatmap.total(), {
+            let mut both = MoveHeatmap::new();
+            both.merge(&heatmap);
+            both.total()
+        });
+        assert!(heatmap.to_string().starts_with("move 1\n"));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_simulation_config_file_round_trip() {
+        let registry = EngineRegistry::with_builtins();
+        let config = || {
+            SimulationConfig::builder()
+                .num_games(200)
+                .seed(11)
+                .rules(Rules::Misere)
+                .starting_player_policy(StartingPlayerPolicy::Random { seed: 3 })
+                .record_games(RecordPolicy::Sample(5))
+                .stop_when(StopCondition::DrawStreak(50))
+                .engine_specs(&registry, "minimax:depth=2", "random:seed=9")
+                .unwrap()
+                .build()
+        };
+
+        let dir = std::env::temp_dir();
+        for name in ["zttt-config-test.toml", "zttt-config-test.json"] {
+            let path = dir.join(name);
+            config().to_file(&path).unwrap();
+            let loaded = SimulationConfig::from_file(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(loaded.rules(), Rules::Misere);
+            assert_eq!(loaded.record_policy(), RecordPolicy::Sample(5));
+            assert_eq!(loaded.engine_specs(), Some(("minimax:depth=2", Some("random:seed=9"))));
+            let expected = Simulator::new(config()).run_sequential();
+            let actual = Simulator::new(loaded).run_sequential();
+            assert_eq!((actual.x_wins, actual.o_wins, actual.draws), (expected.x_wins, expected.o_wins, expected.draws));
+            assert_eq!(untimed(&actual.records), untimed(&expected.records));
+        }
+
+        // Engines given as values have no spec to save
+        let config = SimulationConfig::builder().engine(FastEngine).build();
+        assert!(config.to_toml().is_err());
+        assert!(SimulationConfig::from_toml("engine_x = { name = \"fast\" }", &registry).is_err());
+        assert!(SimulationConfig::from_toml("[engine]\nname = \"unknown\"", &registry).is_err());
+        assert!(SimulationConfig::from_file("config.yaml").is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        /// Collects the names of spans and event messages
+        #[derive(Clone, Default)]
+        struct Names(Arc<Mutex<Vec<String>>>);
+
+        impl tracing::Subscriber for Names {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name().to_string());
+                Id::from_u64(names.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                self.0.lock().unwrap().push(format!("event {}", event.metadata().target()));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let names = Names::default();
+        tracing::subscriber::with_default(names.clone(), || {
+            let config = SimulationConfig::builder().num_games(3).engine(FastEngine).build();
+            Simulator::new(config).run_sequential();
+            Tournament::builder()
+                .add_engine("fast", FastEngine)
+                .add_engine("random", RandomEngine::seeded(1))
+                .games_per_matchup(2)
+                .build()
+                .unwrap()
+                .run();
+        });
+
+        let names = names.0.lock().unwrap();
+        let count = |name: &str| names.iter().filter(|n| *n == name).count();
+        assert_eq!(count("simulation"), 1);
+        assert_eq!(count("game"), 5);
+        // FastEngine against itself wins on the seventh move
+        assert_eq!(count("engine"), 3 * 7);
+        assert_eq!(count("tournament"), 1);
+        assert_eq!(count("matchup"), 1);
+        assert!(count("event zttt_rs::simulation::simulator") >= 4);
+    }
+
+    #[cfg(feature = "indicatif")]
+    #[test]
+    fn test_progress_bars() {
+        use crate::simulation::{ParallelConfig, ParallelSimulator, Simulator, SimulationConfig, Tournament};
+        use crate::backend::{FastEngine, RandomEngine};
+        use std::time::Duration;
+
+        let config = || SimulationConfig::builder().num_games(500).engine(FastEngine).build();
+        assert_eq!(Simulator::new(config()).with_progress_bar().run_sequential().games_completed, 500);
+        let timed = Simulator::new(config()).with_progress_bar().run_for(Duration::from_millis(20));
+        assert!(timed.games_completed > 0);
+
+        let parallel = ParallelConfig::builder().num_games(500).num_threads(2).engine(FastEngine).build();
+        assert_eq!(ParallelSimulator::new(parallel).with_progress_bar().run_parallel().games_completed, 500);
+
+        let result = Tournament::builder()
+            .add_engine("fast", FastEngine)
+            .add_engine("random", RandomEngine::new())
+            .games_per_matchup(10)
+            .progress_bar()
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(result.games_completed, 10);
+    }
+
+    #[test]
+    fn test_run_streaming() {
+        let config = || SimulationConfig::builder().num_games(1_000).engine(RandomEngine::seeded(3)).build();
+        let events: Vec<_> = Simulator::new(config()).run_streaming(250).into_iter().collect();
+        assert_eq!(events.len(), 5);
+        let completed: Vec<_> = events[..4]
+            .iter()
+            .map(|event| match event {
+                SimulationEvent::Progress(snapshot) => snapshot.completed,
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(completed, vec![250, 500, 750, 1_000]);
+
+        let mut x_wins = 0;
+        let mut finished = None;
+        for event in Simulator::new(config()).run_streaming_games(1_000) {
+            match event {
+                SimulationEvent::Game(result) => x_wins += usize::from(result == GameResult::Win(Player::X)),
+                SimulationEvent::Finished(result) => finished = Some(result),
+                SimulationEvent::Progress(snapshot) => assert_eq!(snapshot.fraction(), 1.0),
+            }
+        }
+        let finished = finished.unwrap();
+        assert_eq!(finished.games_completed, 1_000);
+        assert_eq!(finished.x_wins, x_wins);
+        assert!(matches!(events[4], SimulationEvent::Finished(ref result) if result.games_completed == 1_000));
+
+        let receiver = Simulator::new(SimulationConfig::builder().num_games(usize::MAX).build()).run_streaming_games(1);
+        assert!(receiver.recv().is_ok());
+        drop(receiver);
+    }
+
+    #[test]
+    fn test_iter_games() {
+        let config = || {
+            SimulationConfig::builder()
+                .num_games(40)
+                .engines(RandomEngine::new(), MinimaxEngine::new())
+                .starting_player_policy(StartingPlayerPolicy::Alternate)
+                .seed(11)
+                .build()
+        };
+        let expected = Simulator::new(config()).run_sequential();
+        let mut simulator = Simulator::new(config());
+        let mut games = simulator.iter_games();
+        let records: Vec<_> = games.by_ref().collect();
+        assert_eq!(records.len(), 40);
+        assert_eq!(games.result().o_wins, expected.o_wins);
+        assert_eq!(records[1].starting_player, Player::O);
+        assert_eq!(games.next(), None);
+
+        let mut simulator = Simulator::new(config());
+        assert_eq!(simulator.iter_games().take(5).count(), 5);
+        let first_x_win = simulator.iter_games().position(|game| game.result == GameResult::Win(Player::X));
+        assert_eq!(first_x_win, None);
+
+        let mut stopping = Simulator::new(
+            SimulationConfig::builder().num_games(100).stop_when(StopCondition::Wins(Player::X, 3)).build(),
+        );
+        assert_eq!(stopping.iter_games().count(), 3);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_games() {
+        use rayon::prelude::*;
+
+        let config = ParallelConfig::builder()
+            .num_games(2_000)
+            .engines(RandomEngine::new(), FastEngine)
+            .num_threads(3)
+            .seed(5)
+            .build();
+        let mut simulator = ParallelSimulator::new(config);
+        let expected = simulator.run_parallel();
+        let o_wins = simulator
+            .par_iter_games()
+            .filter(|game| game.result == GameResult::Win(Player::O))
+            .count();
+        assert_eq!(o_wins, expected.o_wins);
+        assert_eq!(simulator.par_iter_games().count(), 2_000);
+
+        // Without a seed each split reseeds its engines rather than sharing a stream
+        let unseeded = ParallelConfig::builder().num_games(8_000).engine(RandomEngine::new()).build();
+        let distinct: std::collections::HashSet<_> =
+            ParallelSimulator::new(unseeded).par_iter_games().map(|game| game.moves).collect();
+        assert!(distinct.len() > 5_000, "only {} distinct games", distinct.len());
+    }
+
+    #[test]
+    fn test_bounded_record_policies() {
+        let config = |policy| {
+            SimulationConfig::builder().num_games(1_000).engine(RandomEngine::new()).record_games(policy).seed(4).build()
+        };
+
+        let last = Simulator::new(config(RecordPolicy::Last(10))).run_sequential();
+        let all = Simulator::new(config(RecordPolicy::All)).run_sequential();
+        assert_eq!(untimed(&last.records), untimed(&all.records[990..]));
+
+        let mut timed = Simulator::new(config(RecordPolicy::Last(3)));
+        let timed = timed.run_for(std::time::Duration::from_millis(10));
+        assert_eq!(timed.records.len(), 3);
+
+        let reservoir = Simulator::new(config(RecordPolicy::Reservoir(25))).run_sequential();
+        assert_eq!(reservoir.records.len(), 25);
+        let all_games = untimed(&all.records);
+        assert!(untimed(&reservoir.records).iter().all(|record| all_games.contains(record)));
+
+        // The sample follows the seed rather than always keeping the same game numbers
+        let kept_games = |config: SimulationConfig| {
+            let seeds: Vec<_> = (0..1_000).map(|game| config.game_seed(game)).collect();
+            let result = Simulator::new(config).run_sequential();
+            result.records.iter().map(|record| seeds.iter().position(|&seed| seed == record.seed).unwrap()).collect::<Vec<_>>()
+        };
+        let reseeded = SimulationConfig::builder()
+            .num_games(1_000)
+            .engine(RandomEngine::new())
+            .record_games(RecordPolicy::Reservoir(25))
+            .seed(5)
+            .build();
+        assert_ne!(kept_games(config(RecordPolicy::Reservoir(25))), kept_games(reseeded));
+
+        let parallel = ParallelConfig::builder()
+            .num_games(1_000)
+            .engine(RandomEngine::new())
+            .num_threads(4)
+            .chunk_size(64)
+            .record_games(RecordPolicy::Reservoir(25))
+            .seed(4)
+            .build();
+        let mut parallel = ParallelSimulator::new(parallel);
+        assert_eq!(untimed(&parallel.run_parallel().records), untimed(&reservoir.records));
+        let parallel_last = ParallelConfig::builder()
+            .num_games(1_000)
+            .engine(RandomEngine::new())
+            .num_threads(3)
+            .record_games(RecordPolicy::Last(10))
+            .seed(4)
+            .build();
+        assert_eq!(untimed(&ParallelSimulator::new(parallel_last).run_parallel().records), untimed(&last.records));
+    }
+
+    #[test]
+    fn test_distinct_games() {
+        let mut distinct = DistinctGames::new();
+        let config = SimulationConfig::builder()
+            .num_games(100)
+            .engine(FastEngine)
+            .starting_player_policy(StartingPlayerPolicy::Alternate)
+            .build();
+        Simulator::new(config).run_with_observer(&mut distinct);
+        assert_eq!((distinct.games(), distinct.distinct(), distinct.duplicates()), (100, 2, 98));
+        assert_eq!(distinct.to_string(), "100 games, 2 distinct, 98 duplicates (98.0%)");
+
+        let config = || SimulationConfig::builder().num_games(300).engine(RandomEngine::new()).seed(2).build();
+        let moves: std::collections::HashSet<_> = Simulator::new(config()).iter_games().map(|game| game.moves).collect();
+        let mut random = DistinctGames::new();
+        Simulator::new(config()).run_with_observer(&mut random);
+        assert_eq!((random.games(), random.distinct()), (300, moves.len()));
+
+        let again = random.clone();
+        random.merge(&again);
+        assert_eq!((random.games(), random.distinct()), (600, moves.len()));
+    }
+
+    #[test]
+    fn test_run_manifest() {
+        let registry = EngineRegistry::with_builtins();
+        let config = || {
+            SimulationConfig::builder()
+                .engine_specs(&registry, "minimax:depth=1", "random")
+                .unwrap()
+                .num_games(50)
+                .seed(42)
+                .record_games(RecordPolicy::Last(5))
+                .stop_when(StopCondition::DrawStreak(100))
+                .manifest(true)
+                .build()
+        };
+        let result = Simulator::new(config()).run_sequential();
+        let manifest = result.manifest.clone().unwrap();
+        assert_eq!((manifest.seed, manifest.num_games), (Some(42), 50));
+        assert_eq!((manifest.engine_x.as_str(), manifest.engine_o.as_str()), ("minimax:depth=1", "random"));
+        assert_eq!(manifest.stop_when, ["draw_streak:100"]);
+        let text = result.to_string();
+        assert!(text.contains("seed: 42") && text.contains("record_games: last:5"));
+
+        let mut output = Vec::new();
+        Simulator::new(config()).run_jsonl(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let first = output.lines().next().unwrap();
+        assert!(first.starts_with(r#"{"manifest":{"zttt_version":""#));
+        assert!(first.contains(r#""engine_x":"minimax:depth=1""#));
+        assert_eq!(output.lines().count(), 51);
+
+        let plain = SimulationConfig::builder().num_games(10).build();
+        assert!(Simulator::new(plain).run_sequential().manifest.is_none());
+        let parallel = ParallelConfig::builder()
+            .num_games(100)
+            .engine(RandomEngine::new())
+            .num_threads(2)
+            .seed(7)
+            .manifest(true)
+            .build();
+        let manifest = ParallelSimulator::new(parallel).run_parallel().manifest.unwrap();
+        assert_eq!((manifest.seed, manifest.num_games), (Some(7), 100));
+    }
+
+    #[test]
+    fn test_move_durations() {
+        use std::time::Duration;
+
+        let config = SimulationConfig::builder()
+            .num_games(20)
+            .engines(FastEngine, MinimaxEngine::new())
+            .record_games(RecordPolicy::All)
+            .build();
+        let result = Simulator::new(config).run_sequential();
+        assert!(result.records.iter().all(|record| record.move_durations.len() == record.moves.len()));
+        let record = &result.records[0];
+        assert_eq!(record.time_used(Player::X) + record.time_used(Player::O), record.move_durations.iter().sum());
+
+        let text = record.to_notation();
+        assert!(text.contains("[MoveTimes \""));
+        assert_eq!(&GameRecord::from_notation(&text).unwrap(), record);
+
+        let mut timed = GameRecord::new(Rules::Standard, Player::O, &[(1, 1), (0, 0)]).unwrap();
+        assert_eq!(timed.time_used(Player::O), Duration::ZERO);
+        timed.move_durations = vec![Duration::from_nanos(1500), Duration::from_nanos(20)];
+        assert_eq!(timed.time_used(Player::O), Duration::from_nanos(1500));
+        let annotations = crate::analysis::annotate_game(&timed).unwrap();
+        assert_eq!(annotations[1].duration, Some(Duration::from_nanos(20)));
+
+        let mut sink = JsonlSink::new(Vec::new());
+        sink.write(&timed).unwrap();
+        let line = String::from_utf8(sink.finish().unwrap()).unwrap();
+        assert!(line.contains(r#""moves":[[1,1],[0,0]],"move_ns":[1500,20],"#));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_game_archive() {
+        use std::io::Cursor;
+
+        let config = || {
+            SimulationConfig::builder()
+                .num_games(5_000)
+                .engine(RandomEngine::new())
+                .starting_player_policy(StartingPlayerPolicy::Alternate)
+                .record_games(RecordPolicy::All)
+                .seed(8)
+                .manifest(true)
+                .build()
+        };
+        let expected = Simulator::new(config()).run_sequential();
+        let mut archive = Cursor::new(Vec::new());
+        Simulator::new(config()).run_archive(&mut archive).unwrap();
+        assert!(archive.get_ref().len() < 5_000 * 5);
+
+        let mut reader = ArchiveReader::open(archive).unwrap();
+        assert_eq!((reader.len(), reader.master_seed()), (5_000, Some(8)));
+        assert!(reader.manifest_json().unwrap().contains(r#""seed":"8""#));
+        let games: Vec<GameRecord> = reader.records().map(Result::unwrap).collect();
+        let tagless = |records: &[GameRecord]| -> Vec<GameRecord> {
+            untimed(records).into_iter().map(|record| GameRecord { tags: Vec::new(), ..record }).collect()
+        };
+        assert_eq!(games, tagless(&expected.records));
+        assert_eq!(reader.get(4_321).unwrap().as_ref(), Some(&games[4_321]));
+        assert_eq!(reader.get(17).unwrap().as_ref(), Some(&games[17]));
+        assert!(reader.get(5_000).unwrap().is_none());
+
+        let mut misere = GameRecord::new(Rules::Misere, Player::O, &[(2, 2), (0, 1)]).unwrap();
+        misere.result = GameResult::Win(Player::O);
+        let standard = GameRecord::new(Rules::Standard, Player::X, &[]).unwrap();
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap().block_size(2).compression_level(19);
+        for record in [&standard, &misere, &misere, &standard, &standard] {
+            writer.write(record).unwrap();
+        }
+        assert_eq!(writer.games_written(), 5);
+        let mut off_board = standard.clone();
+        off_board.moves.push((3, 0));
+        assert_eq!(writer.write(&off_board).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        let mut seeded = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap().master_seed(1);
+        assert_eq!(seeded.write(&standard).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        let mut reader = ArchiveReader::open(writer.finish().unwrap()).unwrap();
+        let games: Vec<GameRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(games, [&standard, &misere, &misere, &standard, &standard].map(Clone::clone));
+        assert!(reader.manifest_json().is_none());
+
+        // A block offset near u64::MAX must not wrap past the bounds check
+        let mut overflowing = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        overflowing.write(&standard).unwrap();
+        let mut bytes = overflowing.finish().unwrap().into_inner();
+        let footer = bytes.len() - 29;
+        let index = u64::from_le_bytes(bytes[footer..footer + 8].try_into().unwrap()) as usize;
+        bytes[index..index + 8].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+        let error = ArchiveReader::open(Cursor::new(bytes)).err().unwrap();
+        assert_eq!(error.to_string(), "Archive block lies outside the file");
+
+        let unfinished = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        let mut bytes = unfinished.finish().unwrap().into_inner();
+        assert!(ArchiveReader::open(Cursor::new(bytes.clone())).unwrap().is_empty());
+        let mut corrupt = bytes.clone();
+        let blocks = corrupt.len() - 29 + 8;
+        corrupt[blocks..blocks + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let error = ArchiveReader::open(Cursor::new(corrupt)).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Archive index does not match the file length");
+        bytes.truncate(bytes.len() - 1);
+        let error = ArchiveReader::open(Cursor::new(bytes)).err().unwrap();
+        assert_eq!(error.to_string(), "Archive is truncated or was not finished");
+        assert!(ArchiveReader::open(Cursor::new(b"PAR1 not an archive".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_scenario_suite_from_file() {
+        let board: Board = ". . O\n. X .\n. . .".parse().unwrap();
+        assert_eq!(board, Board::from_moves(&[(1, 1), (0, 2)]).unwrap());
+        assert_eq!("X.O/.X.".parse::<Board>(), Err("Board must have nine cells"));
+        assert_eq!("X.O/.X./...X".parse::<Board>(), Err("Board must have nine cells"));
+        assert_eq!("x.o/.../...".parse::<Board>(), Err("Board cells must be X, O, or ."));
+
+        let text = "# regression positions\n\
+                    \n\
+                    X.O/OX./... 1-0          # take the diagonal\n\
+                    O../.../... X 1/2-1/2\n\
+                    .../.X./... O\n";
+        let path = std::env::temp_dir().join(format!("zttt-scenarios-{}.txt", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let suite = ScenarioSuite::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(suite.len(), 3);
+        let names: Vec<&str> = suite.cases().iter().map(|case| case.name.as_str()).collect();
+        assert_eq!(names, ["take the diagonal", "line 4", "line 5"]);
+        assert_eq!(suite.cases()[0].to_move, Player::X);
+        assert_eq!(suite.cases()[1].expected, Some(GameResult::Draw));
+        assert_eq!((suite.cases()[2].to_move, suite.cases()[2].expected), (Player::O, None));
+
+        let error = |text: &str| text.parse::<ScenarioSuite>().unwrap_err().to_string();
+        assert_eq!(error("\n\nXXX/OO./... 1-0"), "line 3: Game is already over");
+        assert_eq!(error("O../.../..."), "line 1: Cannot tell whose move it is; add X or O after the board");
+        assert_eq!(error("X../.../... X"), "line 1: Side to move does not match the piece counts");
+        assert_eq!(error(".../.../... 1-0 X"), "line 1: Expected the side to move, then a result: 1-0, 0-1, or 1/2-1/2");
+        let missing = ScenarioSuite::from_file(std::env::temp_dir().join("zttt-no-such-suite.txt")).unwrap_err();
+        assert_eq!((missing.line, missing.to_string().as_str()), (0, "Could not read position file"));
+    }
+
+    #[test]
+    fn test_scenario_runner() {
+        let suite: ScenarioSuite = "
+            X.O/OX./... X 1-0      # take the diagonal
+            XX./OO./... O 0-1      # win instead of blocking
+            .../.../...   1/2-1/2  # empty board
+            .../.../...            # no expectation
+        "
+        .parse()
+        .unwrap();
+
+        let mut perfect = ScenarioRunner::builder().add_by_info(MinimaxEngine::new()).build().unwrap();
+        let report = perfect.run(&suite);
+        assert!(report.all_passed());
+        assert_eq!(report.outcomes[2].results, vec![GameResult::Draw]);
+
+        // FastEngine plays the first free square and misses both wins
+        let mut weak = ScenarioRunner::builder()
+            .add_engine("fast", FastEngine)
+            .add_engine("minimax", MinimaxEngine::new())
+            .games_per_case(3)
+            .build()
+            .unwrap();
+        let report = weak.run(&suite);
+        assert_eq!((report.passed(), report.failed()), (2, 2));
+        assert_eq!(report.failures().next().unwrap().misses(), 3);
+        assert!(report.to_string().contains("FAIL take the diagonal: expected 1-0, 3 of 3 games missed"));
+        assert!(report.to_string().ends_with("2 of 4 cases passed"));
+
+        // Seeded random play is reproducible
+        let run = || {
+            let mut runner = ScenarioRunner::builder().add_by_info(RandomEngine::new()).games_per_case(20).seed(7).build().unwrap();
+            runner.run(&suite).outcomes
+        };
+        assert_eq!(run(), run());
+
+        assert!(ScenarioRunner::builder().build().is_err());
+        assert!(ScenarioRunner::builder().add_by_info(FastEngine).games_per_case(0).build().is_err());
+    }
 }
@@ -0,0 +1,169 @@
+//! SVG rendering of boards and games
+//!
+//! Produces standalone SVG documents for reports and blog posts. A [`Game`]
+//! can be drawn with move numbers and its winning line highlighted.
+//!
+//! # Example
+//!
+//! ```
+//! use zttt_rs::backend::Game;
+//! use zttt_rs::render::{game_svg, SvgRenderer};
+//!
+//! let mut game = Game::new();
+//! for (row, col) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+//!     game.play(row, col).unwrap();
+//! }
+//! let svg = game_svg(&game);
+//! assert!(svg.starts_with("<svg"));
+//!
+//! let small = SvgRenderer::new().cell_size(40).move_numbers(false).game(&game);
+//! assert!(small.contains(r#"width="120""#));
+//! ```
+
+use std::fmt::Write;
+use crate::backend::{Board, Cell, Game, Player};
+
+const X_COLOR: &str = "#1f77b4";
+const O_COLOR: &str = "#d62728";
+const GRID_COLOR: &str = "#333333";
+const WIN_COLOR: &str = "#2ca02c";
+
+/// Renders boards and games as SVG strings
+#[derive(Debug, Clone, Copy)]
+pub struct SvgRenderer {
+    cell_size: u32,
+    move_numbers: bool,
+    highlight_win: bool,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            cell_size: 100,
+            move_numbers: true,
+            highlight_win: true,
+        }
+    }
+}
+
+impl SvgRenderer {
+    /// Creates a renderer with 100px cells, move numbers, and win highlighting
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width and height of each cell in pixels (at least 10)
+    pub fn cell_size(mut self, pixels: u32) -> Self {
+        self.cell_size = pixels.max(10);
+        self
+    }
+
+    /// Sets whether games are drawn with the move number in each cell
+    pub fn move_numbers(mut self, enabled: bool) -> Self {
+        self.move_numbers = enabled;
+        self
+    }
+
+    /// Sets whether a completed line is struck through
+    pub fn highlight_win(mut self, enabled: bool) -> Self {
+        self.highlight_win = enabled;
+        self
+    }
+
+    /// Renders a board position
+    pub fn board(&self, board: &Board) -> String {
+        self.render(board, &[None; 9])
+    }
+
+    /// Renders a game's final position, numbering cells by the move that filled them
+    pub fn game(&self, game: &Game) -> String {
+        let mut numbers = [None; 9];
+        if self.move_numbers {
+            for (index, &(row, col)) in game.moves().iter().enumerate() {
+                numbers[row * 3 + col] = Some(index + 1);
+            }
+        }
+        self.render(game.board(), &numbers)
+    }
+
+    fn render(&self, board: &Board, numbers: &[Option<usize>; 9]) -> String {
+        let cell = self.cell_size as f64;
+        let size = self.cell_size * 3;
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+        );
+        let _ = write!(svg, r#"<rect width="{size}" height="{size}" fill="white"/>"#);
+
+        let stroke = cell / 25.0;
+        for i in 1..3 {
+            let at = cell * i as f64;
+            let _ = write!(
+                svg,
+                r#"<line x1="{at}" y1="0" x2="{at}" y2="{size}" stroke="{GRID_COLOR}" stroke-width="{stroke}"/>"#
+            );
+            let _ = write!(
+                svg,
+                r#"<line x1="0" y1="{at}" x2="{size}" y2="{at}" stroke="{GRID_COLOR}" stroke-width="{stroke}"/>"#
+            );
+        }
+
+        let pad = cell * 0.22;
+        let mark = cell / 12.0;
+        for row in 0..3 {
+            for col in 0..3 {
+                let (x, y) = (col as f64 * cell, row as f64 * cell);
+                match board.get(row, col) {
+                    Some(Cell::Occupied(Player::X)) => {
+                        let (x0, y0, x1, y1) = (x + pad, y + pad, x + cell - pad, y + cell - pad);
+                        let _ = write!(
+                            svg,
+                            r#"<path d="M{x0} {y0}L{x1} {y1}M{x1} {y0}L{x0} {y1}" stroke="{X_COLOR}" stroke-width="{mark}" stroke-linecap="round"/>"#
+                        );
+                    }
+                    Some(Cell::Occupied(Player::O)) => {
+                        let (cx, cy, r) = (x + cell / 2.0, y + cell / 2.0, cell / 2.0 - pad);
+                        let _ = write!(
+                            svg,
+                            r#"<circle cx="{cx}" cy="{cy}" r="{r}" fill="none" stroke="{O_COLOR}" stroke-width="{mark}"/>"#
+                        );
+                    }
+                    _ => {}
+                }
+                if let Some(number) = numbers[row * 3 + col] {
+                    let (tx, ty, font) = (x + cell * 0.06, y + cell * 0.2, cell * 0.16);
+                    let _ = write!(
+                        svg,
+                        r#"<text x="{tx}" y="{ty}" font-family="sans-serif" font-size="{font}" fill="{GRID_COLOR}">{number}</text>"#
+                    );
+                }
+            }
+        }
+
+        if self.highlight_win {
+            if let Some([(r0, c0), _, (r2, c2)]) = board.winning_line() {
+                let center = |i: usize| i as f64 * cell + cell / 2.0;
+                let (x1, y1, x2, y2) = (center(c0), center(r0), center(c2), center(r2));
+                let width = cell / 8.0;
+                let _ = write!(
+                    svg,
+                    r#"<line class="win" x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{WIN_COLOR}" stroke-width="{width}" stroke-linecap="round" opacity="0.7"/>"#
+                );
+            }
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// Renders a board with the default [`SvgRenderer`]
+pub fn board_svg(board: &Board) -> String {
+    SvgRenderer::new().board(board)
+}
+
+/// Renders a game with the default [`SvgRenderer`]
+pub fn game_svg(game: &Game) -> String {
+    SvgRenderer::new().game(game)
+}
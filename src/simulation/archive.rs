@@ -0,0 +1,452 @@
+//! Compressed, seekable archives of game records (requires the `zstd` feature)
+//!
+//! A `.zttt` archive stores games in a compact binary encoding, compressed
+//! in blocks with zstd, and ends with an index of the blocks, so any game can
+//! be read without decompressing the ones before it:
+//!
+//! | Section | Contents |
+//! |---------|----------|
+//! | header | magic `ZTTT`, format version (u8) |
+//! | blocks | one zstd frame per block of up to [`block_size`](ArchiveWriter::block_size) games |
+//! | index | per block: file offset (u64), compressed length (u32), games (u32), rules (u8, 0 standard, 1 misère) |
+//! | manifest | the [`RunManifest`] as JSON, or nothing |
+//! | footer | index offset (u64), block count (u32), manifest length (u32), master seed (u64), whether there is a master seed (u8), magic `ZTTT` |
+//!
+//! Integers are little-endian. Each game starts with one byte holding its
+//! number of moves (bits 0-3), its result (bits 4-5: in progress, X, O, or
+//! draw), whether O moved first (bit 6), and whether a seed follows (bit 7).
+//! The seed, if any, follows as a u64, then the moves as cell indices
+//! `row * 3 + col`, two per byte with the first in the low nibble. A
+//! nine-move game takes six bytes before compression. Move durations and tags
+//! are not stored.
+//!
+//! Without a following seed, a game's seed is the one a
+//! [`Simulator`](crate::simulation::Simulator) seeded with the master seed
+//! gives the game at its position in the archive, or none if the archive has
+//! no master seed. Seeded runs therefore store no seeds at all.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::Cursor;
+//! use zttt_rs::simulation::{ArchiveReader, SimulationConfig, Simulator};
+//!
+//! let mut archive = Cursor::new(Vec::new());
+//! let config = SimulationConfig::builder().num_games(1_000).seed(3).build();
+//! Simulator::new(config).run_archive(&mut archive).unwrap();
+//!
+//! let mut reader = ArchiveReader::open(archive).unwrap();
+//! assert_eq!(reader.len(), 1_000);
+//! let game = reader.get(750).unwrap().unwrap();
+//! assert_eq!(game.to_game().unwrap().result(), game.result);
+//! ```
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use crate::backend::{GameRecord, GameResult, Player, Rules};
+use crate::simulation::config::derive_game_seed;
+use crate::simulation::manifest::RunManifest;
+
+const MAGIC: &[u8; 4] = b"ZTTT";
+const VERSION: u8 = 1;
+const HEADER_LEN: u64 = 5;
+const FOOTER_LEN: i64 = 29;
+const INDEX_ENTRY_LEN: usize = 17;
+
+/// Location and contents of one compressed block
+#[derive(Debug, Clone, Copy)]
+struct BlockEntry {
+    offset: u64,
+    length: u32,
+    games: u32,
+    rules: Rules,
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes game records to a `.zttt` archive
+///
+/// Games are buffered and compressed as a block every
+/// [`block_size`](Self::block_size) games, and whenever the rules change.
+/// The index is written by [`finish`](Self::finish); an archive that is not
+/// finished cannot be read.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use zttt_rs::backend::{GameRecord, Player, Rules};
+/// use zttt_rs::simulation::{ArchiveReader, ArchiveWriter};
+///
+/// let record = GameRecord::new(Rules::Standard, Player::X, &[(1, 1), (0, 0)]).unwrap();
+/// let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+/// writer.write(&record).unwrap();
+/// let archive = writer.finish().unwrap();
+///
+/// let mut reader = ArchiveReader::open(archive).unwrap();
+/// assert_eq!(reader.get(0).unwrap(), Some(record));
+/// ```
+pub struct ArchiveWriter<W: Write> {
+    writer: W,
+    position: u64,
+    block: Vec<u8>,
+    block_games: u32,
+    block_rules: Rules,
+    block_size: u32,
+    level: i32,
+    index: Vec<BlockEntry>,
+    manifest: Option<String>,
+    master_seed: Option<u64>,
+    written: u64,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Writes the archive header and creates a writer that compresses every
+    /// 65536 games at zstd's default level
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        Ok(ArchiveWriter {
+            writer,
+            position: HEADER_LEN,
+            block: Vec::new(),
+            block_games: 0,
+            block_rules: Rules::Standard,
+            block_size: 65_536,
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            index: Vec::new(),
+            manifest: None,
+            master_seed: None,
+            written: 0,
+        })
+    }
+
+    /// Sets how many games are compressed together (at least 1)
+    ///
+    /// Larger blocks compress better; smaller ones make reading a single
+    /// game faster.
+    pub fn block_size(mut self, games: u32) -> Self {
+        self.block_size = games.max(1);
+        self
+    }
+
+    /// Sets the zstd compression level, from 1 (fastest) to 22 (smallest)
+    pub fn compression_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the master seed of the run being archived, so games whose seed
+    /// derives from it take no space for the seed
+    ///
+    /// Every game must then have a seed.
+    pub fn master_seed(mut self, seed: u64) -> Self {
+        self.master_seed = Some(seed);
+        self
+    }
+
+    /// Returns the number of games written so far, including buffered ones
+    pub fn games_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Stores `manifest` in the archive, replacing any earlier one
+    pub fn write_manifest(&mut self, manifest: &RunManifest) {
+        self.manifest = Some(manifest.to_json());
+    }
+
+    /// Appends a game, compressing the block if it is full
+    ///
+    /// Returns an `InvalidInput` error if the record has more than nine
+    /// moves, a move off the board, or no seed although the archive has a
+    /// master seed.
+    pub fn write(&mut self, record: &GameRecord) -> io::Result<()> {
+        let moves = &record.moves;
+        if moves.len() > 9 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Game has more than nine moves"));
+        }
+        if moves.iter().any(|&(row, col)| row > 2 || col > 2) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Move is off the board"));
+        }
+        let derived = self.master_seed.map(|master| derive_game_seed(master, self.written as usize));
+        let stored_seed = match (record.seed, derived) {
+            (None, Some(_)) => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Game has no seed but the archive has a master seed"));
+            }
+            (seed, derived) if seed == derived => None,
+            (seed, _) => seed,
+        };
+        if self.block_games > 0 && record.rules != self.block_rules {
+            self.write_block()?;
+        }
+        self.block_rules = record.rules;
+
+        let result = match record.result {
+            GameResult::InProgress => 0,
+            GameResult::Win(Player::X) => 1,
+            GameResult::Win(Player::O) => 2,
+            GameResult::Draw => 3,
+        };
+        let starts_o = u8::from(record.starting_player == Player::O);
+        let has_seed = u8::from(stored_seed.is_some());
+        self.block.push(moves.len() as u8 | result << 4 | starts_o << 6 | has_seed << 7);
+        if let Some(seed) = stored_seed {
+            self.block.extend_from_slice(&seed.to_le_bytes());
+        }
+        for pair in moves.chunks(2) {
+            let cell = |&(row, col): &(usize, usize)| (row * 3 + col) as u8;
+            self.block.push(cell(&pair[0]) | pair.get(1).map_or(0, cell) << 4);
+        }
+
+        self.written += 1;
+        self.block_games += 1;
+        if self.block_games >= self.block_size {
+            self.write_block()?;
+        }
+        Ok(())
+    }
+
+    /// Compresses and writes any buffered games, then the index, manifest,
+    /// and footer, and returns the writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_block()?;
+        let mut tail = Vec::with_capacity(self.index.len() * INDEX_ENTRY_LEN);
+        for entry in &self.index {
+            tail.extend_from_slice(&entry.offset.to_le_bytes());
+            tail.extend_from_slice(&entry.length.to_le_bytes());
+            tail.extend_from_slice(&entry.games.to_le_bytes());
+            tail.push(u8::from(entry.rules == Rules::Misere));
+        }
+        let manifest = self.manifest.take().unwrap_or_default();
+        tail.extend_from_slice(manifest.as_bytes());
+        tail.extend_from_slice(&self.position.to_le_bytes());
+        tail.extend_from_slice(&(self.index.len() as u32).to_le_bytes());
+        tail.extend_from_slice(&(manifest.len() as u32).to_le_bytes());
+        tail.extend_from_slice(&self.master_seed.unwrap_or(0).to_le_bytes());
+        tail.push(u8::from(self.master_seed.is_some()));
+        tail.extend_from_slice(MAGIC);
+        self.writer.write_all(&tail)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_block(&mut self) -> io::Result<()> {
+        if self.block_games == 0 {
+            return Ok(());
+        }
+        let compressed = zstd::bulk::compress(&self.block, self.level)?;
+        self.writer.write_all(&compressed)?;
+        self.index.push(BlockEntry {
+            offset: self.position,
+            length: compressed.len() as u32,
+            games: self.block_games,
+            rules: self.block_rules,
+        });
+        self.position += compressed.len() as u64;
+        self.block.clear();
+        self.block_games = 0;
+        Ok(())
+    }
+}
+
+/// Reads game records from a `.zttt` archive, in order or by index
+///
+/// Opening an archive reads only its index. Reading a game decompresses its
+/// block, which stays cached, so reading games in order decompresses each
+/// block once.
+pub struct ArchiveReader<R: Read + Seek> {
+    reader: R,
+    index: Vec<BlockEntry>,
+    /// Number of the first game in each block
+    first_games: Vec<u64>,
+    len: u64,
+    manifest: Option<String>,
+    master_seed: Option<u64>,
+    /// The most recently read block and the start of each game in it
+    cached: Option<(usize, Vec<u8>, Vec<usize>)>,
+}
+
+impl<R: Read + Seek> ArchiveReader<R> {
+    /// Opens an archive, reading its header and index
+    ///
+    /// The archive must start at the beginning of `reader`. Returns an
+    /// `InvalidData` error if `reader` does not hold a finished archive.
+    pub fn open(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; HEADER_LEN as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("Not a zttt archive"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("Unsupported zttt archive version"));
+        }
+
+        let mut footer = [0; FOOTER_LEN as usize];
+        let footer_offset = reader.seek(SeekFrom::End(-FOOTER_LEN))?;
+        reader.read_exact(&mut footer)?;
+        if &footer[25..] != MAGIC {
+            return Err(invalid("Archive is truncated or was not finished"));
+        }
+        let index_offset = u64::from_le_bytes(footer[..8].try_into().unwrap());
+        let blocks = u32::from_le_bytes(footer[8..12].try_into().unwrap()) as usize;
+        let manifest_len = u32::from_le_bytes(footer[12..16].try_into().unwrap()) as usize;
+        let master_seed = u64::from_le_bytes(footer[16..24].try_into().unwrap());
+        let master_seed = (footer[24] != 0).then_some(master_seed);
+
+        // The index and manifest must fill the space before the footer exactly;
+        // checking first keeps a corrupt footer from requesting a huge buffer
+        let tail_len = (blocks as u64 * INDEX_ENTRY_LEN as u64) + manifest_len as u64;
+        if index_offset < HEADER_LEN || index_offset.checked_add(tail_len) != Some(footer_offset) {
+            return Err(invalid("Archive index does not match the file length"));
+        }
+
+        let mut tail = vec![0; tail_len as usize];
+        reader.seek(SeekFrom::Start(index_offset))?;
+        reader.read_exact(&mut tail)?;
+        let (entries, manifest) = tail.split_at(blocks * INDEX_ENTRY_LEN);
+
+        let mut index = Vec::with_capacity(blocks);
+        let mut first_games = Vec::with_capacity(blocks);
+        let mut len = 0;
+        for entry in entries.chunks_exact(INDEX_ENTRY_LEN) {
+            let entry = BlockEntry {
+                offset: u64::from_le_bytes(entry[..8].try_into().unwrap()),
+                length: u32::from_le_bytes(entry[8..12].try_into().unwrap()),
+                games: u32::from_le_bytes(entry[12..16].try_into().unwrap()),
+                rules: if entry[16] == 0 { Rules::Standard } else { Rules::Misere },
+            };
+            if entry.offset < HEADER_LEN
+                || entry.offset.checked_add(u64::from(entry.length)).is_none_or(|end| end > index_offset)
+            {
+                return Err(invalid("Archive block lies outside the file"));
+            }
+            first_games.push(len);
+            len += u64::from(entry.games);
+            index.push(entry);
+        }
+        let manifest = match manifest {
+            [] => None,
+            json => Some(String::from_utf8(json.to_vec()).map_err(|_| invalid("Archive manifest is not UTF-8"))?),
+        };
+
+        Ok(ArchiveReader { reader, index, first_games, len, manifest, master_seed, cached: None })
+    }
+
+    /// Returns the number of games in the archive
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the archive holds no games
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the archived [`RunManifest`] as JSON, if one was written
+    pub fn manifest_json(&self) -> Option<&str> {
+        self.manifest.as_deref()
+    }
+
+    /// Returns the master seed set with [`ArchiveWriter::master_seed`]
+    pub fn master_seed(&self) -> Option<u64> {
+        self.master_seed
+    }
+
+    /// Reads game number `game`, counting from zero, or returns `None` if
+    /// the archive has fewer games
+    pub fn get(&mut self, game: u64) -> io::Result<Option<GameRecord>> {
+        if game >= self.len {
+            return Ok(None);
+        }
+        let block = self.first_games.partition_point(|&first| first <= game) - 1;
+        if self.cached.as_ref().is_none_or(|(cached, _, _)| *cached != block) {
+            self.cached = Some(self.load_block(block)?);
+        }
+        let (_, bytes, starts) = self.cached.as_ref().expect("block was just loaded");
+        let start = starts[(game - self.first_games[block]) as usize];
+        let mut record = decode_game(&bytes[start..], self.index[block].rules)?;
+        if record.seed.is_none() {
+            record.seed = self.master_seed.map(|master| derive_game_seed(master, game as usize));
+        }
+        Ok(Some(record))
+    }
+
+    /// Returns an iterator reading every game in order
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self, next_game: 0 }
+    }
+
+    /// Decompresses block number `block` and finds where each game starts
+    fn load_block(&mut self, block: usize) -> io::Result<(usize, Vec<u8>, Vec<usize>)> {
+        let entry = self.index[block];
+        let mut compressed = vec![0; entry.length as usize];
+        self.reader.seek(SeekFrom::Start(entry.offset))?;
+        self.reader.read_exact(&mut compressed)?;
+        let bytes = zstd::stream::decode_all(compressed.as_slice())?;
+
+        let mut starts = Vec::with_capacity(entry.games as usize);
+        let mut position = 0;
+        while position < bytes.len() {
+            starts.push(position);
+            let flags = bytes[position];
+            position += 1 + usize::from(flags >> 7) * 8 + usize::from(flags & 0x0f).div_ceil(2);
+        }
+        if position != bytes.len() || starts.len() != entry.games as usize {
+            return Err(invalid("Archive block does not match its index"));
+        }
+        Ok((block, bytes, starts))
+    }
+}
+
+/// Decodes the game at the start of `bytes`, checking that its moves are legal
+fn decode_game(bytes: &[u8], rules: Rules) -> io::Result<GameRecord> {
+    let flags = bytes[0];
+    let length = usize::from(flags & 0x0f);
+    let starting_player = if flags & 0x40 == 0 { Player::X } else { Player::O };
+    let (seed, packed) = if flags & 0x80 == 0 {
+        (None, &bytes[1..])
+    } else {
+        (Some(u64::from_le_bytes(bytes[1..9].try_into().unwrap())), &bytes[9..])
+    };
+    let moves: Vec<(usize, usize)> = packed
+        .iter()
+        .flat_map(|&pair| [pair & 0x0f, pair >> 4])
+        .take(length)
+        .map(|cell| (usize::from(cell / 3), usize::from(cell % 3)))
+        .collect();
+
+    let mut record = GameRecord::new(rules, starting_player, &moves).map_err(invalid)?;
+    // Forfeits end before the board is decided, so the result is stored
+    // rather than recomputed
+    record.result = match (flags >> 4) & 0x03 {
+        0 => GameResult::InProgress,
+        1 => GameResult::Win(Player::X),
+        2 => GameResult::Win(Player::O),
+        _ => GameResult::Draw,
+    };
+    record.seed = seed;
+    Ok(record)
+}
+
+/// Iterator over the games of an archive, returned by [`ArchiveReader::records`]
+pub struct Records<'a, R: Read + Seek> {
+    reader: &'a mut ArchiveReader<R>,
+    next_game: u64,
+}
+
+impl<R: Read + Seek> Iterator for Records<'_, R> {
+    type Item = io::Result<GameRecord>;
+
+    fn next(&mut self) -> Option<io::Result<GameRecord>> {
+        let game = self.next_game;
+        self.next_game += 1;
+        self.reader.get(game).transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.reader.len().saturating_sub(self.next_game) as usize;
+        (remaining, Some(remaining))
+    }
+}
@@ -0,0 +1,260 @@
+//! Arrow and Parquet export of game records (requires the `arrow` feature)
+//!
+//! Games are exported as two tables that join on the `game` column:
+//!
+//! | Table | Columns |
+//! |-------|---------|
+//! | games | `game` u64, `starting_player` utf8, `rules` utf8, `result` utf8 (`X`, `O`, `draw`, or null), `length` u8, `seed` u64 (nullable), `moves` list<u8> of cell indices `row * 3 + col` |
+//! | moves | `game` u64, `ply` u8 (starting at 1), `player` utf8, `row` u8, `col` u8, `duration_ns` u64 (null if the game was not timed) |
+//!
+//! # Example
+//!
+//! ```
+//! use zttt_rs::simulation::{Simulator, SimulationConfig};
+//!
+//! let (mut games, mut moves) = (Vec::new(), Vec::new());
+//! let mut simulator = Simulator::new(SimulationConfig::builder().num_games(100).build());
+//! let result = simulator.run_parquet(&mut games, &mut moves).unwrap();
+//! assert_eq!(result.games_completed, 100);
+//! assert!(games.starts_with(b"PAR1"));
+//! ```
+
+use std::io::Write;
+use std::sync::{Arc, OnceLock};
+use arrow_array::builder::{ArrayBuilder, ListBuilder, StringBuilder, UInt64Builder, UInt8Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::KeyValue;
+use crate::backend::{GameRecord, GameResult};
+use crate::simulation::manifest::RunManifest;
+
+/// Returns the schema of the per-game table
+pub fn games_schema() -> SchemaRef {
+    static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            Arc::new(Schema::new(vec![
+                Field::new("game", DataType::UInt64, false),
+                Field::new("starting_player", DataType::Utf8, false),
+                Field::new("rules", DataType::Utf8, false),
+                Field::new("result", DataType::Utf8, true),
+                Field::new("length", DataType::UInt8, false),
+                Field::new("seed", DataType::UInt64, true),
+                Field::new("moves", DataType::List(Arc::new(Field::new("item", DataType::UInt8, true))), false),
+            ]))
+        })
+        .clone()
+}
+
+/// Returns the schema of the per-move table
+pub fn moves_schema() -> SchemaRef {
+    static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            Arc::new(Schema::new(vec![
+                Field::new("game", DataType::UInt64, false),
+                Field::new("ply", DataType::UInt8, false),
+                Field::new("player", DataType::Utf8, false),
+                Field::new("row", DataType::UInt8, false),
+                Field::new("col", DataType::UInt8, false),
+                Field::new("duration_ns", DataType::UInt64, true),
+            ]))
+        })
+        .clone()
+}
+
+/// Accumulates game records into Arrow record batches
+///
+/// Games are numbered in the order they are pushed, continuing across batches.
+pub struct RecordBatchBuilder {
+    next_game: u64,
+    games: GameColumns,
+    moves: MoveColumns,
+}
+
+struct GameColumns {
+    game: UInt64Builder,
+    starting_player: StringBuilder,
+    rules: StringBuilder,
+    result: StringBuilder,
+    length: UInt8Builder,
+    seed: UInt64Builder,
+    moves: ListBuilder<UInt8Builder>,
+}
+
+struct MoveColumns {
+    game: UInt64Builder,
+    ply: UInt8Builder,
+    player: StringBuilder,
+    row: UInt8Builder,
+    col: UInt8Builder,
+    duration_ns: UInt64Builder,
+}
+
+impl RecordBatchBuilder {
+    /// Creates an empty builder
+    pub fn new() -> Self {
+        RecordBatchBuilder {
+            next_game: 0,
+            games: GameColumns {
+                game: UInt64Builder::new(),
+                starting_player: StringBuilder::new(),
+                rules: StringBuilder::new(),
+                result: StringBuilder::new(),
+                length: UInt8Builder::new(),
+                seed: UInt64Builder::new(),
+                moves: ListBuilder::new(UInt8Builder::new()),
+            },
+            moves: MoveColumns {
+                game: UInt64Builder::new(),
+                ply: UInt8Builder::new(),
+                player: StringBuilder::new(),
+                row: UInt8Builder::new(),
+                col: UInt8Builder::new(),
+                duration_ns: UInt64Builder::new(),
+            },
+        }
+    }
+
+    /// Returns the number of games buffered since the last batch
+    pub fn len(&self) -> usize {
+        self.games.game.len()
+    }
+
+    /// Returns true if no games are buffered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a game to both tables
+    pub fn push(&mut self, record: &GameRecord) {
+        let game = self.next_game;
+        self.next_game += 1;
+
+        let columns = &mut self.games;
+        columns.game.append_value(game);
+        columns.starting_player.append_value(record.starting_player.to_string());
+        columns.rules.append_value(record.rules.to_string());
+        match record.result {
+            GameResult::Win(player) => columns.result.append_value(player.to_string()),
+            GameResult::Draw => columns.result.append_value("draw"),
+            GameResult::InProgress => columns.result.append_null(),
+        }
+        columns.length.append_value(record.moves.len() as u8);
+        columns.seed.append_option(record.seed);
+        for &(row, col) in &record.moves {
+            columns.moves.values().append_value((row * 3 + col) as u8);
+        }
+        columns.moves.append(true);
+
+        let mut player = record.starting_player;
+        for (index, &(row, col)) in record.moves.iter().enumerate() {
+            self.moves.game.append_value(game);
+            self.moves.ply.append_value(index as u8 + 1);
+            self.moves.player.append_value(player.to_string());
+            self.moves.row.append_value(row as u8);
+            self.moves.col.append_value(col as u8);
+            let duration = record.move_durations.get(index);
+            self.moves.duration_ns.append_option(duration.map(|duration| duration.as_nanos() as u64));
+            player = player.opponent();
+        }
+    }
+
+    /// Returns the buffered games as a per-game and a per-move batch, and clears the buffer
+    pub fn finish(&mut self) -> Result<(RecordBatch, RecordBatch), ArrowError> {
+        let games = &mut self.games;
+        let game_columns: Vec<ArrayRef> = vec![
+            Arc::new(games.game.finish()),
+            Arc::new(games.starting_player.finish()),
+            Arc::new(games.rules.finish()),
+            Arc::new(games.result.finish()),
+            Arc::new(games.length.finish()),
+            Arc::new(games.seed.finish()),
+            Arc::new(games.moves.finish()),
+        ];
+        let moves = &mut self.moves;
+        let move_columns: Vec<ArrayRef> = vec![
+            Arc::new(moves.game.finish()),
+            Arc::new(moves.ply.finish()),
+            Arc::new(moves.player.finish()),
+            Arc::new(moves.row.finish()),
+            Arc::new(moves.col.finish()),
+            Arc::new(moves.duration_ns.finish()),
+        ];
+        Ok((
+            RecordBatch::try_new(games_schema(), game_columns)?,
+            RecordBatch::try_new(moves_schema(), move_columns)?,
+        ))
+    }
+}
+
+impl Default for RecordBatchBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Streams game records into a pair of Parquet files
+///
+/// Records are buffered and written as a row group every
+/// [`batch_size`](Self::batch_size) games.
+pub struct ParquetSink<G: Write + Send, M: Write + Send> {
+    builder: RecordBatchBuilder,
+    batch_size: usize,
+    games: ArrowWriter<G>,
+    moves: ArrowWriter<M>,
+}
+
+impl<G: Write + Send, M: Write + Send> ParquetSink<G, M> {
+    /// Creates a sink writing the games table to `games` and the moves table to `moves`
+    pub fn new(games: G, moves: M) -> Result<Self, ParquetError> {
+        Ok(ParquetSink {
+            builder: RecordBatchBuilder::new(),
+            batch_size: 65_536,
+            games: ArrowWriter::try_new(games, games_schema(), None)?,
+            moves: ArrowWriter::try_new(moves, moves_schema(), None)?,
+        })
+    }
+
+    /// Sets how many games are buffered before writing (at least 1)
+    pub fn batch_size(mut self, games: usize) -> Self {
+        self.batch_size = games.max(1);
+        self
+    }
+
+    /// Stores `manifest` in both files' key-value metadata, each entry under
+    /// its key prefixed with `zttt.`
+    pub fn write_manifest(&mut self, manifest: &RunManifest) {
+        for (key, value) in manifest.entries() {
+            let entry = KeyValue::new(format!("zttt.{}", key), value);
+            self.games.append_key_value_metadata(entry.clone());
+            self.moves.append_key_value_metadata(entry);
+        }
+    }
+
+    /// Appends a game, writing the batch if it is full
+    pub fn write(&mut self, record: &GameRecord) -> Result<(), ParquetError> {
+        self.builder.push(record);
+        if self.builder.len() >= self.batch_size {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Writes any buffered games, closes both files, and returns the writers
+    pub fn finish(mut self) -> Result<(G, M), ParquetError> {
+        if !self.builder.is_empty() {
+            self.write_batch()?;
+        }
+        Ok((self.games.into_inner()?, self.moves.into_inner()?))
+    }
+
+    fn write_batch(&mut self) -> Result<(), ParquetError> {
+        let (games, moves) = self.builder.finish()?;
+        self.games.write(&games)?;
+        self.moves.write(&moves)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,426 @@
+//! Knockout bracket tournaments
+//!
+//! Engines are seeded in the order they are added and placed in a standard
+//! bracket (1 vs 8, 4 vs 5, 2 vs 7, 3 vs 6 for eight engines), with byes for
+//! the top seeds when the field is not a power of two. Each pairing plays a
+//! best-of-N series with alternating colors.
+
+use std::fmt;
+use crate::backend::{BoxedEngine, GameResult, Player, Rules, StatefulEngine};
+use crate::simulation::duel::play_pair;
+use crate::simulation::rating::Glicko2;
+
+/// Knockout format of a [`Bracket`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BracketFormat {
+    /// One series loss eliminates an engine
+    #[default]
+    SingleElimination,
+    /// Engines drop to a losers bracket after their first series loss and are
+    /// eliminated after their second
+    DoubleElimination,
+}
+
+/// Part of the bracket a series was played in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketStage {
+    /// The main bracket (the only one in single elimination)
+    Winners,
+    /// The double-elimination losers bracket
+    Losers,
+    /// Winners-bracket champion against losers-bracket champion
+    GrandFinal,
+    /// Deciding series, played if the losers-bracket champion wins the grand final
+    GrandFinalReset,
+}
+
+/// A knockout tournament between named engines
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{Bracket, BracketFormat};
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine, RandomEngine};
+///
+/// let mut bracket = Bracket::builder()
+///     .add_engine("minimax", MinimaxEngine::new())
+///     .add_engine("fast", FastEngine)
+///     .add_engine("random", RandomEngine::new())
+///     .format(BracketFormat::DoubleElimination)
+///     .best_of(3)
+///     .seed(1)
+///     .build()
+///     .unwrap();
+///
+/// let result = bracket.run();
+/// assert_eq!(result.champion, "minimax");
+/// println!("{}", result);
+/// ```
+pub struct Bracket {
+    names: Vec<String>,
+    engines: Vec<BoxedEngine>,
+    format: BracketFormat,
+    best_of: usize,
+    rules: Rules,
+    seed: Option<u64>,
+}
+
+/// Builder for [`Bracket`]
+pub struct BracketBuilder {
+    bracket: Bracket,
+}
+
+/// One best-of-N series in a [`BracketResult`]
+///
+/// `engines`, `seeds`, and `wins` list the higher seed first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketSeries {
+    pub stage: BracketStage,
+    /// Round within the stage, starting at 1
+    pub round: usize,
+    pub engines: (String, String),
+    pub seeds: (usize, usize),
+    pub wins: (usize, usize),
+    pub draws: usize,
+    pub winner: String,
+}
+
+/// Outcome of a [`Bracket`] run
+///
+/// `Display` draws the bracket round by round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketResult {
+    pub format: BracketFormat,
+    pub best_of: usize,
+    /// Every series in the order it was played
+    pub series: Vec<BracketSeries>,
+    pub champion: String,
+    pub runner_up: String,
+}
+
+impl Bracket {
+    /// Creates a builder with no engines
+    ///
+    /// Defaults: single elimination, best of 1, standard rules, no seed.
+    pub fn builder() -> BracketBuilder {
+        BracketBuilder {
+            bracket: Bracket {
+                names: Vec::new(),
+                engines: Vec::new(),
+                format: BracketFormat::SingleElimination,
+                best_of: 1,
+                rules: Rules::Standard,
+                seed: None,
+            },
+        }
+    }
+
+    /// Plays the whole bracket
+    ///
+    /// A series ends once one engine has more wins than the other could
+    /// still reach. Draws count for neither side, and a series that ends
+    /// level goes to the higher seed. The higher seed plays X in the first
+    /// game of each series.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    pub fn run(&mut self) -> BracketResult {
+        let mut run = Run {
+            bracket: self,
+            games: 0,
+            series: Vec::new(),
+        };
+        let (champion, runner_up) = run.play();
+        let series = run.series;
+        BracketResult {
+            format: self.format,
+            best_of: self.best_of,
+            series,
+            champion: self.names[champion].clone(),
+            runner_up: self.names[runner_up].clone(),
+        }
+    }
+}
+
+impl BracketBuilder {
+    /// Adds an engine as the next seed
+    pub fn add_engine(self, name: &str, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.add_boxed(name, Box::new(engine))
+    }
+
+    /// Adds a boxed engine as the next seed
+    pub fn add_boxed(mut self, name: &str, engine: BoxedEngine) -> Self {
+        self.bracket.names.push(name.to_string());
+        self.bracket.engines.push(engine);
+        self
+    }
+
+    /// Sets the knockout format
+    pub fn format(mut self, format: BracketFormat) -> Self {
+        self.bracket.format = format;
+        self
+    }
+
+    /// Sets the maximum number of games per series (at least 1)
+    pub fn best_of(mut self, games: usize) -> Self {
+        self.bracket.best_of = games.max(1);
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.bracket.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible engine randomness
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.bracket.seed = Some(seed);
+        self
+    }
+
+    /// Builds the bracket
+    ///
+    /// Returns an error if fewer than two engines were added.
+    pub fn build(self) -> Result<Bracket, &'static str> {
+        if self.bracket.engines.len() < 2 {
+            return Err("Bracket needs at least two engines");
+        }
+        Ok(self.bracket)
+    }
+}
+
+/// State of one bracket run; engines are identified by seed index
+struct Run<'a> {
+    bracket: &'a mut Bracket,
+    games: usize,
+    series: Vec<BracketSeries>,
+}
+
+impl Run<'_> {
+    /// Plays every round and returns the champion and runner-up
+    fn play(&mut self) -> (usize, usize) {
+        let double = self.bracket.format == BracketFormat::DoubleElimination;
+        let mut winners: Vec<Option<usize>> = placement(self.bracket.names.len());
+        let mut losers: Vec<usize> = Vec::new();
+        let mut last_loser = 0;
+        let (mut round, mut losers_round) = (0, 0);
+
+        while winners.len() > 1 {
+            round += 1;
+            let (next, mut dropped) = self.winners_round(&winners, round);
+            winners = next;
+            if let Some(&loser) = dropped.last() {
+                last_loser = loser;
+            }
+            if !double {
+                continue;
+            }
+
+            if losers.is_empty() {
+                losers = dropped;
+            } else if !dropped.is_empty() {
+                // Reversed so engines rarely meet the same opponent twice
+                dropped.reverse();
+                losers_round += 1;
+                losers = self.drop_in_round(&losers, &dropped, losers_round);
+            }
+            let remaining = winners.iter().flatten().count();
+            while losers.len() > (remaining / 2).max(1) {
+                losers_round += 1;
+                losers = self.losers_round(&losers, losers_round);
+            }
+        }
+
+        let champion = winners[0].expect("bracket has at least two engines");
+        match losers.first() {
+            Some(&challenger) if double => {
+                let (winner, loser) = self.series(champion, challenger, BracketStage::GrandFinal, 1);
+                if winner == champion {
+                    (winner, loser)
+                } else {
+                    self.series(champion, challenger, BracketStage::GrandFinalReset, 1)
+                }
+            }
+            _ => (champion, last_loser),
+        }
+    }
+
+    /// Plays adjacent slots against each other; empty slots are byes
+    fn winners_round(&mut self, slots: &[Option<usize>], round: usize) -> (Vec<Option<usize>>, Vec<usize>) {
+        let mut advancing = Vec::with_capacity(slots.len() / 2);
+        let mut eliminated = Vec::new();
+        for pair in slots.chunks(2) {
+            match (pair[0], pair[1]) {
+                (Some(a), Some(b)) => {
+                    let (winner, loser) = self.series(a, b, BracketStage::Winners, round);
+                    advancing.push(Some(winner));
+                    eliminated.push(loser);
+                }
+                (a, b) => advancing.push(a.or(b)),
+            }
+        }
+        (advancing, eliminated)
+    }
+
+    /// Plays losers-bracket survivors against engines dropping from the winners bracket
+    fn drop_in_round(&mut self, survivors: &[usize], dropped: &[usize], round: usize) -> Vec<usize> {
+        let mut advancing = Vec::with_capacity(survivors.len().max(dropped.len()));
+        for i in 0..survivors.len().max(dropped.len()) {
+            match (survivors.get(i), dropped.get(i)) {
+                (Some(&a), Some(&b)) => advancing.push(self.series(a, b, BracketStage::Losers, round).0),
+                (a, b) => advancing.extend(a.or(b)),
+            }
+        }
+        advancing
+    }
+
+    /// Plays losers-bracket engines against each other; an odd one out gets a bye
+    fn losers_round(&mut self, engines: &[usize], round: usize) -> Vec<usize> {
+        let mut advancing = Vec::with_capacity(engines.len().div_ceil(2));
+        for pair in engines.chunks(2) {
+            match *pair {
+                [a, b] => advancing.push(self.series(a, b, BracketStage::Losers, round).0),
+                [a] => advancing.push(a),
+                _ => unreachable!(),
+            }
+        }
+        advancing
+    }
+
+    /// Plays a best-of-N series and returns the winner and loser
+    fn series(&mut self, a: usize, b: usize, stage: BracketStage, round: usize) -> (usize, usize) {
+        let (high, low) = (a.min(b), a.max(b));
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("series", ?stage, round, high = %self.bracket.names[high], low = %self.bracket.names[low]).entered();
+        let best_of = self.bracket.best_of;
+        let (mut wins, mut draws) = ((0, 0), 0);
+
+        for game in 0..best_of {
+            let (x, o) = if game.is_multiple_of(2) { (high, low) } else { (low, high) };
+            let bracket = &mut *self.bracket;
+            let outcome = play_pair(&mut bracket.engines, x, o, bracket.rules, bracket.seed, self.games);
+            self.games += 1;
+            match outcome {
+                GameResult::Win(Player::X) if x == high => wins.0 += 1,
+                GameResult::Win(Player::O) if o == high => wins.0 += 1,
+                GameResult::Win(_) => wins.1 += 1,
+                _ => draws += 1,
+            }
+            let remaining = best_of - game - 1;
+            if wins.0 > wins.1 + remaining || wins.1 > wins.0 + remaining {
+                break;
+            }
+        }
+
+        let (winner, loser) = if wins.1 > wins.0 { (low, high) } else { (high, low) };
+        let names = &self.bracket.names;
+        #[cfg(feature = "tracing")]
+        tracing::info!(winner = %names[winner], wins = ?wins, draws, "series finished");
+        self.series.push(BracketSeries {
+            stage,
+            round,
+            engines: (names[high].clone(), names[low].clone()),
+            seeds: (high + 1, low + 1),
+            wins,
+            draws,
+            winner: names[winner].clone(),
+        });
+        (winner, loser)
+    }
+}
+
+/// Returns the first-round slots for `count` seeds, with `None` for byes
+fn placement(count: usize) -> Vec<Option<usize>> {
+    let size = count.next_power_of_two();
+    let mut order = vec![1];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|&seed| [seed, n + 1 - seed]).collect();
+    }
+    order.into_iter().map(|seed| (seed <= count).then(|| seed - 1)).collect()
+}
+
+impl fmt::Display for BracketStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BracketStage::Winners => write!(f, "Winners"),
+            BracketStage::Losers => write!(f, "Losers"),
+            BracketStage::GrandFinal => write!(f, "Grand final"),
+            BracketStage::GrandFinalReset => write!(f, "Grand final reset"),
+        }
+    }
+}
+
+impl fmt::Display for BracketSeries {
+    /// Writes e.g. `#1 minimax 2-0 #4 fast (1 draw) -> minimax`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} {}-{} #{} {}",
+            self.seeds.0, self.engines.0, self.wins.0, self.wins.1, self.seeds.1, self.engines.1
+        )?;
+        match self.draws {
+            0 => {}
+            1 => write!(f, " (1 draw)")?,
+            n => write!(f, " ({} draws)", n)?,
+        }
+        write!(f, " -> {}", self.winner)
+    }
+}
+
+impl BracketResult {
+    /// Returns Glicko-2 ratings treating the whole bracket as one rating period
+    ///
+    /// Engines are listed in seed order.
+    pub fn glicko2(&self) -> Glicko2 {
+        let mut ratings = Glicko2::new();
+        let mut seeded: Vec<(usize, &str)> = Vec::new();
+        let mut games = Vec::new();
+        for series in &self.series {
+            let (high, low) = (series.engines.0.as_str(), series.engines.1.as_str());
+            seeded.push((series.seeds.0, high));
+            seeded.push((series.seeds.1, low));
+            games.extend(std::iter::repeat_n((high, low, 1.0), series.wins.0));
+            games.extend(std::iter::repeat_n((high, low, 0.0), series.wins.1));
+            games.extend(std::iter::repeat_n((high, low, 0.5), series.draws));
+        }
+        seeded.sort_unstable();
+        for (_, name) in seeded {
+            ratings.add_player(name);
+        }
+        ratings.update(&games);
+        ratings
+    }
+}
+
+impl fmt::Display for BracketResult {
+    /// Writes each round's series under a heading, then the champion
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self.format {
+            BracketFormat::SingleElimination => "Single elimination",
+            BracketFormat::DoubleElimination => "Double elimination",
+        };
+        writeln!(f, "{}, best of {}", format, self.best_of)?;
+
+        let mut heading = None;
+        for series in &self.series {
+            if heading != Some((series.stage, series.round)) {
+                heading = Some((series.stage, series.round));
+                match series.stage {
+                    BracketStage::Winners if self.format == BracketFormat::SingleElimination => {
+                        writeln!(f, "\nRound {}", series.round)?
+                    }
+                    BracketStage::Winners | BracketStage::Losers => {
+                        writeln!(f, "\n{} round {}", series.stage, series.round)?
+                    }
+                    _ => writeln!(f, "\n{}", series.stage)?,
+                }
+            }
+            writeln!(f, "  {}", series)?;
+        }
+        write!(f, "\nChampion: {} (runner-up: {})", self.champion, self.runner_up)
+    }
+}
@@ -0,0 +1,629 @@
+//! Simulation configuration and builder
+
+use std::fmt;
+use crate::backend::{BoxedEngine, EngineRegistry, StatefulEngine, FastEngine, GameResult, Player, Rng, Rules};
+use crate::simulation::result::SimulationResult;
+
+/// Largest simulation a single request to the bundled servers may launch
+pub const MAX_SIMULATION_GAMES: usize = 1_000_000;
+
+/// Decides which player moves first in each game of a run
+///
+/// Engines keep their sides (the X engine always plays X); only the first
+/// mover changes. Alternating or randomizing the starter removes the
+/// first-move advantage from engine comparisons.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::StartingPlayerPolicy;
+/// use zttt_rs::backend::Player;
+///
+/// let policy = StartingPlayerPolicy::Alternate;
+/// assert_eq!(policy.starting_player(0), Player::X);
+/// assert_eq!(policy.starting_player(1), Player::O);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartingPlayerPolicy {
+    /// The same player starts every game
+    Fixed(Player),
+    /// X starts even-numbered games and O starts odd-numbered ones
+    Alternate,
+    /// Each game's starter is drawn from a generator seeded with `seed`
+    /// and the game's index, so runs are reproducible
+    ///
+    /// The draw uses its own stream, so reusing the run's seed here leaves
+    /// the starter independent of the engines' per-game seeds.
+    Random { seed: u64 },
+}
+
+/// Stream index bit for starter draws; game indices never reach it, so the
+/// starter is independent of the games' own seeds
+const STARTER_STREAM: u64 = 1 << 63;
+
+impl StartingPlayerPolicy {
+    /// Returns the player who starts game number `game` (counting from 0)
+    pub fn starting_player(&self, game: usize) -> Player {
+        match *self {
+            StartingPlayerPolicy::Fixed(player) => player,
+            StartingPlayerPolicy::Alternate if game.is_multiple_of(2) => Player::X,
+            StartingPlayerPolicy::Alternate => Player::O,
+            StartingPlayerPolicy::Random { seed } => {
+                if Rng::stream(seed, game as u64 | STARTER_STREAM).next_u64() >> 63 == 0 {
+                    Player::X
+                } else {
+                    Player::O
+                }
+            }
+        }
+    }
+}
+
+impl Default for StartingPlayerPolicy {
+    fn default() -> Self {
+        StartingPlayerPolicy::Fixed(Player::X)
+    }
+}
+
+impl fmt::Display for StartingPlayerPolicy {
+    /// Writes `X`, `O`, `alternate`, or `random:<seed>`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartingPlayerPolicy::Fixed(player) => write!(f, "{}", player),
+            StartingPlayerPolicy::Alternate => write!(f, "alternate"),
+            StartingPlayerPolicy::Random { seed } => write!(f, "random:{}", seed),
+        }
+    }
+}
+
+/// Selects which games a run keeps as full [`GameRecord`](crate::backend::GameRecord)s
+///
+/// Kept games end up in [`SimulationResult::records`](crate::simulation::SimulationResult::records).
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{RecordPolicy, SimulationConfig, Simulator};
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine};
+///
+/// let config = SimulationConfig::builder()
+///     .num_games(10)
+///     .engines(FastEngine, MinimaxEngine::new())
+///     .record_games(RecordPolicy::LossesOnly)
+///     .build();
+/// let result = Simulator::new(config).run_sequential();
+/// assert_eq!(result.records.len(), result.o_wins);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordPolicy {
+    /// Keep no games
+    #[default]
+    None,
+    /// Keep every game
+    All,
+    /// Keep `n` games spread evenly over the run (the first `n` when the
+    /// number of games is not known in advance, as in
+    /// [`run_for`](crate::simulation::Simulator::run_for))
+    Sample(usize),
+    /// Keep the games the X engine lost
+    LossesOnly,
+    /// Keep the last `n` games, so at most `n` records are held however
+    /// long the run
+    Last(usize),
+    /// Keep a uniform random sample of `n` games, chosen without knowing the
+    /// number of games in advance and holding at most `2n` records at a time
+    ///
+    /// Which games are picked depends only on their game numbers and the
+    /// seed, so parallel runs keep the same games for any thread count.
+    /// Unseeded runs pick a different sample each time.
+    Reservoir(usize),
+}
+
+impl RecordPolicy {
+    /// Returns true if game number `game` of `total` (0 if unknown) should be kept
+    pub(crate) fn keeps(&self, game: usize, total: usize, result: GameResult) -> bool {
+        match *self {
+            RecordPolicy::None => false,
+            RecordPolicy::All => true,
+            RecordPolicy::Sample(n) if total == 0 || n >= total => game < n,
+            RecordPolicy::Sample(n) => {
+                let (game, n, total) = (game as u128, n as u128, total as u128);
+                (game + 1) * n / total > game * n / total
+            }
+            RecordPolicy::LossesOnly => result == GameResult::Win(Player::O),
+            RecordPolicy::Last(n) | RecordPolicy::Reservoir(n) => n > 0,
+        }
+    }
+
+    /// Returns the most records a bounded policy keeps
+    pub(crate) fn limit(&self) -> Option<usize> {
+        match *self {
+            RecordPolicy::Last(n) | RecordPolicy::Reservoir(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the rank of game number `game` in a run with the given
+    /// [`record_salt`](SimulationConfig::record_salt); bounded policies keep
+    /// the highest-ranked games
+    pub(crate) fn rank(&self, game: usize, salt: u64) -> u64 {
+        match self {
+            RecordPolicy::Reservoir(_) => derive_game_seed(salt, game),
+            _ => game as u64,
+        }
+    }
+}
+
+impl fmt::Display for RecordPolicy {
+    /// Writes `none`, `all`, `losses`, or the policy and its count, e.g. `sample:100`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordPolicy::None => write!(f, "none"),
+            RecordPolicy::All => write!(f, "all"),
+            RecordPolicy::LossesOnly => write!(f, "losses"),
+            RecordPolicy::Sample(n) => write!(f, "sample:{}", n),
+            RecordPolicy::Last(n) => write!(f, "last:{}", n),
+            RecordPolicy::Reservoir(n) => write!(f, "reservoir:{}", n),
+        }
+    }
+}
+
+/// What the simulator does when an engine misbehaves
+///
+/// An engine misbehaves by returning an invalid move, returning no move while
+/// the game is in progress, or (except under `Panic`) panicking.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{EngineErrorPolicy, SimulationConfig, Simulator};
+/// use zttt_rs::backend::{Board, FastEngine, FnEngine, Player};
+///
+/// let broken = FnEngine::new(|_: &Board, _: Player| Some((9, 9)));
+/// let config = SimulationConfig::builder()
+///     .num_games(10)
+///     .engines(FastEngine, broken)
+///     .on_engine_error(EngineErrorPolicy::ForfeitGame)
+///     .build();
+/// let result = Simulator::new(config).run_sequential();
+/// assert_eq!((result.x_wins, result.forfeits), (10, 10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EngineErrorPolicy {
+    /// Panic, ending the run
+    #[default]
+    Panic,
+    /// End the game as a win for the other side and count a forfeit
+    ForfeitGame,
+    /// Discard the game and count it as skipped
+    SkipGame,
+}
+
+impl fmt::Display for EngineErrorPolicy {
+    /// Writes `panic`, `forfeit`, or `skip`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineErrorPolicy::Panic => write!(f, "panic"),
+            EngineErrorPolicy::ForfeitGame => write!(f, "forfeit"),
+            EngineErrorPolicy::SkipGame => write!(f, "skip"),
+        }
+    }
+}
+
+/// Ends a run before all configured games have been played
+///
+/// Conditions are checked after every game; the run stops as soon as any of
+/// them holds, and the result covers the games played so far.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{SimulationConfig, Simulator, StopCondition};
+/// use zttt_rs::backend::{FastEngine, Player, RandomEngine};
+///
+/// // How many games until FastEngine loses once?
+/// let config = SimulationConfig::builder()
+///     .num_games(1_000_000)
+///     .engines(FastEngine, RandomEngine::seeded(1))
+///     .stop_when(StopCondition::Wins(Player::O, 1))
+///     .build();
+/// let result = Simulator::new(config).run_sequential();
+/// assert_eq!(result.o_wins, 1);
+/// println!("FastEngine lost game {}", result.games_completed);
+/// ```
+pub enum StopCondition {
+    /// Stop once the player has won this many games
+    Wins(Player, usize),
+    /// Stop after this many draws in a row
+    DrawStreak(usize),
+    /// Stop when the predicate holds for the results so far
+    When(Box<dyn Fn(&SimulationResult) -> bool + Send>),
+}
+
+impl StopCondition {
+    /// Stops when `predicate` holds for the results so far
+    pub fn when(predicate: impl Fn(&SimulationResult) -> bool + Send + 'static) -> Self {
+        StopCondition::When(Box::new(predicate))
+    }
+
+    fn is_met(&self, result: &SimulationResult, draw_streak: usize) -> bool {
+        match self {
+            StopCondition::Wins(player, count) => result.wins(*player) >= *count,
+            StopCondition::DrawStreak(count) => draw_streak >= *count,
+            StopCondition::When(predicate) => predicate(result),
+        }
+    }
+}
+
+/// Tracks the state stop conditions depend on during a run
+#[derive(Default)]
+pub(crate) struct StopTracker {
+    draw_streak: usize,
+}
+
+impl StopTracker {
+    /// Notes a finished or skipped (`InProgress`) game and returns true if the
+    /// run should stop
+    ///
+    /// Skipped games leave the draw streak as it was.
+    pub(crate) fn should_stop(&mut self, config: &SimulationConfig, outcome: GameResult, result: &SimulationResult) -> bool {
+        if config.stop_conditions.is_empty() {
+            return false;
+        }
+        match outcome {
+            GameResult::Draw => self.draw_streak += 1,
+            GameResult::InProgress => {}
+            GameResult::Win(_) => self.draw_streak = 0,
+        }
+        config.stop_conditions.iter().any(|condition| condition.is_met(result, self.draw_streak))
+    }
+}
+
+/// Configuration for a simulation run
+///
+/// Built with [`SimulationConfig::builder`]. By default a single engine plays
+/// both sides; use [`SimulationConfigBuilder::engines`] to pit two different
+/// engines against each other. Engines may be plain [`Engine`](crate::backend::Engine)s
+/// or [`StatefulEngine`]s that keep state between moves and games.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::SimulationConfig;
+/// use zttt_rs::backend::{FastEngine, Player};
+///
+/// let config = SimulationConfig::builder()
+///     .num_games(10_000)
+///     .engine(FastEngine)
+///     .starting_player(Player::X)
+///     .build();
+/// assert_eq!(config.num_games(), 10_000);
+/// ```
+pub struct SimulationConfig {
+    pub(crate) num_games: usize,
+    pub(crate) engine_x: BoxedEngine,
+    pub(crate) engine_o: Option<BoxedEngine>,
+    pub(crate) starting: StartingPlayerPolicy,
+    pub(crate) rules: Rules,
+    pub(crate) seed: Option<u64>,
+    pub(crate) record_policy: RecordPolicy,
+    pub(crate) stop_conditions: Vec<StopCondition>,
+    pub(crate) error_policy: EngineErrorPolicy,
+    /// Registry specs the engines were created from, for X and (if separate) O
+    pub(crate) engine_specs: Option<(String, Option<String>)>,
+    pub(crate) manifest: bool,
+    /// Random value standing in for the seed when ranking records of an
+    /// unseeded run
+    pub(crate) entropy: u64,
+    /// Reseeds the engines from `entropy` before each game of an unseeded
+    /// run; set for parallel workers, which start from clones of one engine
+    pub(crate) reseed_unseeded: bool,
+}
+
+impl SimulationConfig {
+    /// Creates a builder with default settings
+    ///
+    /// Defaults: 1000 games, `FastEngine` for both sides, X starts every game, standard
+    /// rules, no seed.
+    pub fn builder() -> SimulationConfigBuilder {
+        SimulationConfigBuilder::default()
+    }
+
+    /// Returns the number of games to simulate
+    pub fn num_games(&self) -> usize {
+        self.num_games
+    }
+
+    /// Returns the player who moves first in the first game
+    pub fn starting_player(&self) -> Player {
+        self.starting.starting_player(0)
+    }
+
+    /// Returns the policy choosing the first mover of each game
+    pub fn starting_player_policy(&self) -> StartingPlayerPolicy {
+        self.starting
+    }
+
+    /// Returns the rules games are played under
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Returns the seed, if one was set
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns the registry specs of the X engine and, if O has its own
+    /// engine, of the O engine
+    ///
+    /// Returns `None` if an engine was given as a value rather than a spec.
+    /// The default engine has the spec `"fast"`.
+    pub fn engine_specs(&self) -> Option<(&str, Option<&str>)> {
+        self.engine_specs.as_ref().map(|(x, o)| (x.as_str(), o.as_deref()))
+    }
+
+    /// Returns which games are kept as full records
+    pub fn record_policy(&self) -> RecordPolicy {
+        self.record_policy
+    }
+
+    /// Returns what happens when an engine misbehaves
+    pub fn engine_error_policy(&self) -> EngineErrorPolicy {
+        self.error_policy
+    }
+
+    /// Returns true if results carry a [`RunManifest`](crate::simulation::RunManifest)
+    pub fn manifest(&self) -> bool {
+        self.manifest
+    }
+
+    /// Returns the seed game number `game` is played with, derived only from
+    /// the master seed and the game's index
+    ///
+    /// This is the seed stored in the game's [`GameRecord`](crate::backend::GameRecord),
+    /// and it is the same whether the game is played here or by a
+    /// [`ParallelSimulator`](crate::simulation::ParallelSimulator) with any
+    /// thread count. Returns `None` if no seed was set.
+    pub fn game_seed(&self, game: usize) -> Option<u64> {
+        self.seed.map(|seed| derive_game_seed(seed, game))
+    }
+
+    /// Returns the seed the engines are reseeded with before game number
+    /// `game`, if any
+    ///
+    /// This is the game's seed or, for a parallel worker in an unseeded run,
+    /// one drawn from `entropy`, so workers do not replay each other's games.
+    pub(crate) fn engine_seed(&self, game: usize) -> Option<u64> {
+        self.game_seed(game)
+            .or_else(|| self.reseed_unseeded.then(|| Rng::stream(self.entropy, game as u64 | ENTROPY_ENGINE_STREAM).next_u64()))
+    }
+
+    /// Returns the salt that ranks records under [`RecordPolicy::Reservoir`]
+    pub(crate) fn record_salt(&self) -> u64 {
+        record_salt(self.seed, self.entropy)
+    }
+
+    /// Reseeds the engines for a game played with `seed`
+    ///
+    /// When two engines are configured, O gets a seed derived from X's so the
+    /// sides do not mirror each other's random choices.
+    pub(crate) fn reseed_engines(&mut self, seed: u64) {
+        self.engine_x.reseed(seed);
+        if let Some(engine) = &mut self.engine_o {
+            engine.reseed(Rng::stream(seed, 1).next_u64());
+        }
+    }
+
+    /// Returns the name of the engine playing `player`
+    pub(crate) fn engine_name(&self, player: Player) -> &str {
+        match (player, &self.engine_o) {
+            (Player::O, Some(engine)) => engine.name(),
+            _ => self.engine_x.name(),
+        }
+    }
+
+    /// Returns the engine playing the given side
+    pub(crate) fn engine_for(&mut self, player: Player) -> &mut (dyn StatefulEngine + Send) {
+        match (player, &mut self.engine_o) {
+            (Player::O, Some(engine)) => engine.as_mut(),
+            _ => self.engine_x.as_mut(),
+        }
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Builder for [`SimulationConfig`]
+pub struct SimulationConfigBuilder {
+    config: SimulationConfig,
+}
+
+impl Default for SimulationConfigBuilder {
+    fn default() -> Self {
+        SimulationConfigBuilder {
+            config: SimulationConfig {
+                num_games: 1000,
+                engine_x: Box::new(FastEngine),
+                engine_o: None,
+                starting: StartingPlayerPolicy::default(),
+                rules: Rules::Standard,
+                seed: None,
+                record_policy: RecordPolicy::None,
+                stop_conditions: Vec::new(),
+                error_policy: EngineErrorPolicy::Panic,
+                engine_specs: Some(("fast".to_string(), None)),
+                manifest: false,
+                entropy: Rng::from_entropy().next_u64(),
+                reseed_unseeded: false,
+            },
+        }
+    }
+}
+
+impl SimulationConfigBuilder {
+    /// Sets the number of games to simulate
+    pub fn num_games(mut self, num_games: usize) -> Self {
+        self.config.num_games = num_games;
+        self
+    }
+
+    /// Sets a single engine that plays both sides
+    pub fn engine(mut self, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.config.engine_x = Box::new(engine);
+        self.config.engine_o = None;
+        self.config.engine_specs = None;
+        self
+    }
+
+    /// Sets separate engines for X and O
+    pub fn engines(
+        mut self,
+        engine_x: impl StatefulEngine + Send + 'static,
+        engine_o: impl StatefulEngine + Send + 'static,
+    ) -> Self {
+        self.config.engine_x = Box::new(engine_x);
+        self.config.engine_o = Some(Box::new(engine_o));
+        self.config.engine_specs = None;
+        self
+    }
+
+    /// Sets a single boxed engine, e.g. one built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry), that plays both sides
+    pub fn boxed_engine(mut self, engine: BoxedEngine) -> Self {
+        self.config.engine_x = engine;
+        self.config.engine_o = None;
+        self.config.engine_specs = None;
+        self
+    }
+
+    /// Sets separate boxed engines for X and O
+    pub fn boxed_engines(mut self, engine_x: BoxedEngine, engine_o: BoxedEngine) -> Self {
+        self.config.engine_x = engine_x;
+        self.config.engine_o = Some(engine_o);
+        self.config.engine_specs = None;
+        self
+    }
+
+    /// Sets a single engine, created by `registry` from `spec`, that plays both sides
+    ///
+    /// Unlike [`boxed_engine`](Self::boxed_engine), the spec is remembered so
+    /// the configuration can be saved with
+    /// [`SimulationConfig::to_file`](crate::simulation::SimulationConfig::to_file).
+    /// Returns an error if the registry cannot create the engine.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::EngineRegistry;
+    /// use zttt_rs::simulation::SimulationConfig;
+    ///
+    /// let config = SimulationConfig::builder()
+    ///     .engine_spec(&EngineRegistry::with_builtins(), "minimax:depth=3")
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(config.engine_specs(), Some(("minimax:depth=3", None)));
+    /// ```
+    pub fn engine_spec(mut self, registry: &EngineRegistry, spec: &str) -> Result<Self, &'static str> {
+        self.config.engine_x = registry.create(spec)?;
+        self.config.engine_o = None;
+        self.config.engine_specs = Some((spec.to_string(), None));
+        Ok(self)
+    }
+
+    /// Sets separate engines for X and O, created by `registry` from specs
+    ///
+    /// Like [`engine_spec`](Self::engine_spec), the specs are remembered.
+    pub fn engine_specs(mut self, registry: &EngineRegistry, spec_x: &str, spec_o: &str) -> Result<Self, &'static str> {
+        self.config.engine_x = registry.create(spec_x)?;
+        self.config.engine_o = Some(registry.create(spec_o)?);
+        self.config.engine_specs = Some((spec_x.to_string(), Some(spec_o.to_string())));
+        Ok(self)
+    }
+
+    /// Sets the player who moves first in each game
+    ///
+    /// Shorthand for `starting_player_policy(StartingPlayerPolicy::Fixed(player))`.
+    pub fn starting_player(self, player: Player) -> Self {
+        self.starting_player_policy(StartingPlayerPolicy::Fixed(player))
+    }
+
+    /// Sets how the first mover is chosen for each game
+    pub fn starting_player_policy(mut self, policy: StartingPlayerPolicy) -> Self {
+        self.config.starting = policy;
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.config.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible runs
+    ///
+    /// Each game gets its own seed, derived from this one and the game's
+    /// index, which is passed to the engines through
+    /// [`StatefulEngine::reseed`] before the game starts. Identical
+    /// configurations then produce identical results.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Sets which games are kept as full records
+    pub fn record_games(mut self, policy: RecordPolicy) -> Self {
+        self.config.record_policy = policy;
+        self
+    }
+
+    /// Sets what happens when an engine misbehaves
+    pub fn on_engine_error(mut self, policy: EngineErrorPolicy) -> Self {
+        self.config.error_policy = policy;
+        self
+    }
+
+    /// Adds a condition that ends the run early
+    ///
+    /// May be called several times; the run stops when any condition holds.
+    /// Conditions apply to [`Simulator`](crate::simulation::Simulator) runs
+    /// and are checked after each game.
+    pub fn stop_when(mut self, condition: StopCondition) -> Self {
+        self.config.stop_conditions.push(condition);
+        self
+    }
+
+    /// Attaches a [`RunManifest`](crate::simulation::RunManifest) describing
+    /// the run to every result, and writes it to JSON Lines and Parquet exports
+    pub fn manifest(mut self, enabled: bool) -> Self {
+        self.config.manifest = enabled;
+        self
+    }
+
+    /// Builds the configuration
+    pub fn build(self) -> SimulationConfig {
+        self.config
+    }
+}
+
+/// Derives the record-ranking salt from the master seed, or returns
+/// `entropy` for unseeded runs
+///
+/// The salt uses a stream no game index reaches, so ranks are independent of
+/// the games' own seeds.
+pub(crate) fn record_salt(seed: Option<u64>, entropy: u64) -> u64 {
+    seed.map_or(entropy, |seed| Rng::stream(seed, u64::MAX).next_u64())
+}
+
+/// Stream index bit for engine seeds drawn from `entropy`; game indices
+/// never reach it, so these seeds are independent of the record ranks
+const ENTROPY_ENGINE_STREAM: u64 = 1 << 62;
+
+/// Derives the seed of game number `game` from the master seed
+pub(crate) fn derive_game_seed(seed: u64, game: usize) -> u64 {
+    Rng::stream(seed, game as u64).next_u64()
+}
@@ -0,0 +1,384 @@
+//! Loading and saving simulation configurations as TOML or JSON
+//!
+//! A config file names its engines by [`EngineRegistry`] spec, split into a
+//! name and a table of parameters, so a run can be described, shared, and
+//! repeated without writing code:
+//!
+//! ```toml
+//! num_games = 10000
+//! rules = "standard"              # or "misere"
+//! seed = 42
+//! starting_player = "alternate"   # "X", "O", "alternate", or "random:<seed>"
+//! record_games = "losses"         # "none", "all", "losses", "sample:<n>", "last:<n>", or "reservoir:<n>"
+//! on_engine_error = "forfeit"     # "panic", "forfeit", or "skip"
+//! manifest = true                 # attach a RunManifest to the result
+//!
+//! [engine_x]
+//! name = "minimax"
+//! params = { depth = 3 }
+//!
+//! [engine_o]
+//! name = "random"
+//!
+//! [stop_when]
+//! o_wins = 1
+//! ```
+//!
+//! Every key is optional. A single `[engine]` table sets one engine for both
+//! sides instead of `[engine_x]` and `[engine_o]`. Custom
+//! [`StopCondition::When`] predicates cannot be written to a file.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::backend::{EngineParams, EngineRegistry, Player};
+use crate::simulation::config::{EngineErrorPolicy, RecordPolicy, SimulationConfig, StartingPlayerPolicy, StopCondition};
+
+/// On-disk form of a [`SimulationConfig`]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_games: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rules: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    starting_player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    record_games: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on_engine_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine: Option<EngineEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine_x: Option<EngineEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine_o: Option<EngineEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_when: Option<StopEntry>,
+}
+
+/// An engine spec as a name and a table of parameters
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EngineEntry {
+    name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<String, ParamValue>,
+}
+
+/// An engine parameter, written unquoted when it is a number or boolean
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ParamValue {
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+}
+
+/// Conditions ending a run early; the run stops when any of them holds
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StopEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x_wins: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    o_wins: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    draw_streak: Option<usize>,
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Integer(value) => write!(f, "{}", value),
+            ParamValue::Float(value) => write!(f, "{}", value),
+            ParamValue::Bool(value) => write!(f, "{}", value),
+            ParamValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl ParamValue {
+    fn parse(value: &str) -> ParamValue {
+        if let Ok(value) = value.parse() {
+            ParamValue::Integer(value)
+        } else if let Ok(value) = value.parse() {
+            ParamValue::Float(value)
+        } else if let Ok(value) = value.parse() {
+            ParamValue::Bool(value)
+        } else {
+            ParamValue::Text(value.to_string())
+        }
+    }
+}
+
+impl EngineEntry {
+    fn from_spec(spec: &str) -> Result<Self, &'static str> {
+        let (name, params) = spec.split_once(':').unwrap_or((spec, ""));
+        let params = EngineParams::parse(params)?
+            .iter()
+            .map(|(key, value)| (key.to_string(), ParamValue::parse(value)))
+            .collect();
+        Ok(EngineEntry { name: name.trim().to_string(), params })
+    }
+
+    fn to_spec(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+        if params.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}:{}", self.name, params.join(","))
+        }
+    }
+}
+
+/// Config file formats, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Format, &'static str> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(Format::Toml),
+            Some("json") => Ok(Format::Json),
+            _ => Err("Config file must end in .toml or .json"),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Loads a configuration from a `.toml` or `.json` file, creating engines
+    /// with the built-in [`EngineRegistry`]
+    ///
+    /// See the [module documentation](crate::simulation::config_file) for the
+    /// format. Returns an error if the file cannot be read or parsed, or if an
+    /// engine cannot be created.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<SimulationConfig, &'static str> {
+        Self::from_file_with(path, &EngineRegistry::with_builtins())
+    }
+
+    /// Loads a configuration from a file, creating engines with `registry`
+    pub fn from_file_with(path: impl AsRef<Path>, registry: &EngineRegistry) -> Result<SimulationConfig, &'static str> {
+        let path = path.as_ref();
+        let format = Format::of(path)?;
+        let text = std::fs::read_to_string(path).map_err(|_| "Could not read config file")?;
+        match format {
+            Format::Toml => Self::from_toml(&text, registry),
+            Format::Json => Self::from_json(&text, registry),
+        }
+    }
+
+    /// Parses a configuration in TOML, creating engines with `registry`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::EngineRegistry;
+    /// use zttt_rs::simulation::{SimulationConfig, Simulator};
+    ///
+    /// let config = SimulationConfig::from_toml(r#"
+    ///     num_games = 100
+    ///     seed = 7
+    ///
+    ///     [engine_x]
+    ///     name = "minimax"
+    ///     params = { depth = 2 }
+    ///
+    ///     [engine_o]
+    ///     name = "random"
+    /// "#, &EngineRegistry::with_builtins()).unwrap();
+    ///
+    /// assert_eq!(config.engine_specs(), Some(("minimax:depth=2", Some("random"))));
+    /// let result = Simulator::new(config).run_sequential();
+    /// assert_eq!(result.games_completed, 100);
+    /// ```
+    pub fn from_toml(text: &str, registry: &EngineRegistry) -> Result<SimulationConfig, &'static str> {
+        let file: ConfigFile = toml::from_str(text).map_err(|_| "Invalid TOML config")?;
+        file.build(registry)
+    }
+
+    /// Parses a configuration in JSON, creating engines with `registry`
+    pub fn from_json(text: &str, registry: &EngineRegistry) -> Result<SimulationConfig, &'static str> {
+        let file: ConfigFile = serde_json::from_str(text).map_err(|_| "Invalid JSON config")?;
+        file.build(registry)
+    }
+
+    /// Saves the configuration to a `.toml` or `.json` file
+    ///
+    /// Loading the file again with [`from_file`](Self::from_file) gives a
+    /// configuration that plays the same games. Returns an error if an engine
+    /// was not created from a registry spec, if a stop condition is a custom
+    /// predicate, or if the file cannot be written.
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), &'static str> {
+        let path = path.as_ref();
+        let text = match Format::of(path)? {
+            Format::Toml => self.to_toml()?,
+            Format::Json => self.to_json()?,
+        };
+        std::fs::write(path, text).map_err(|_| "Could not write config file")
+    }
+
+    /// Writes the configuration as TOML, like [`to_file`](Self::to_file)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::EngineRegistry;
+    /// use zttt_rs::simulation::SimulationConfig;
+    ///
+    /// let registry = EngineRegistry::with_builtins();
+    /// let config = SimulationConfig::builder()
+    ///     .num_games(500)
+    ///     .seed(1)
+    ///     .engine_specs(&registry, "minimax:depth=3", "random")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let text = config.to_toml().unwrap();
+    /// let loaded = SimulationConfig::from_toml(&text, &registry).unwrap();
+    /// assert_eq!(loaded.num_games(), 500);
+    /// assert_eq!(loaded.engine_specs(), config.engine_specs());
+    /// ```
+    pub fn to_toml(&self) -> Result<String, &'static str> {
+        toml::to_string(&ConfigFile::from_config(self)?).map_err(|_| "Could not write TOML config")
+    }
+
+    /// Writes the configuration as pretty-printed JSON, like [`to_file`](Self::to_file)
+    pub fn to_json(&self) -> Result<String, &'static str> {
+        serde_json::to_string_pretty(&ConfigFile::from_config(self)?).map_err(|_| "Could not write JSON config")
+    }
+}
+
+impl ConfigFile {
+    fn from_config(config: &SimulationConfig) -> Result<Self, &'static str> {
+        let (spec_x, spec_o) = config.engine_specs().ok_or("Engines were not created from registry specs")?;
+        let (engine, engine_x, engine_o) = match spec_o {
+            None => (Some(EngineEntry::from_spec(spec_x)?), None, None),
+            Some(spec_o) => (None, Some(EngineEntry::from_spec(spec_x)?), Some(EngineEntry::from_spec(spec_o)?)),
+        };
+
+        let mut stop = StopEntry::default();
+        for condition in &config.stop_conditions {
+            // Several conditions of one kind stop at the lowest count
+            let (slot, count) = match *condition {
+                StopCondition::Wins(Player::X, count) => (&mut stop.x_wins, count),
+                StopCondition::Wins(Player::O, count) => (&mut stop.o_wins, count),
+                StopCondition::DrawStreak(count) => (&mut stop.draw_streak, count),
+                StopCondition::When(_) => return Err("Custom stop conditions cannot be saved"),
+            };
+            *slot = Some(slot.map_or(count, |current| current.min(count)));
+        }
+
+        Ok(ConfigFile {
+            num_games: Some(config.num_games),
+            rules: Some(config.rules.to_string()),
+            seed: config.seed,
+            starting_player: Some(config.starting.to_string()),
+            record_games: Some(config.record_policy.to_string()),
+            on_engine_error: Some(config.error_policy.to_string()),
+            manifest: config.manifest.then_some(true),
+            engine,
+            engine_x,
+            engine_o,
+            stop_when: if config.stop_conditions.is_empty() { None } else { Some(stop) },
+        })
+    }
+
+    fn build(self, registry: &EngineRegistry) -> Result<SimulationConfig, &'static str> {
+        let mut builder = SimulationConfig::builder();
+        if let Some(num_games) = self.num_games {
+            builder = builder.num_games(num_games);
+        }
+        if let Some(rules) = self.rules {
+            builder = builder.rules(rules.parse()?);
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(starting) = self.starting_player {
+            builder = builder.starting_player_policy(parse_starting(&starting)?);
+        }
+        if let Some(record) = self.record_games {
+            builder = builder.record_games(parse_record(&record)?);
+        }
+        if let Some(policy) = self.on_engine_error {
+            builder = builder.on_engine_error(parse_error_policy(&policy)?);
+        }
+        if let Some(manifest) = self.manifest {
+            builder = builder.manifest(manifest);
+        }
+
+        builder = match (self.engine, self.engine_x, self.engine_o) {
+            (None, None, None) => builder,
+            (Some(engine), None, None) => builder.engine_spec(registry, &engine.to_spec())?,
+            (None, Some(x), Some(o)) => builder.engine_specs(registry, &x.to_spec(), &o.to_spec())?,
+            (Some(_), _, _) => return Err("Config sets both engine and engine_x/engine_o"),
+            _ => return Err("Config must set both engine_x and engine_o"),
+        };
+
+        if let Some(stop) = self.stop_when {
+            if let Some(count) = stop.x_wins {
+                builder = builder.stop_when(StopCondition::Wins(Player::X, count));
+            }
+            if let Some(count) = stop.o_wins {
+                builder = builder.stop_when(StopCondition::Wins(Player::O, count));
+            }
+            if let Some(count) = stop.draw_streak {
+                builder = builder.stop_when(StopCondition::DrawStreak(count));
+            }
+        }
+        Ok(builder.build())
+    }
+}
+
+fn parse_starting(value: &str) -> Result<StartingPlayerPolicy, &'static str> {
+    const ERROR: &str = "starting_player must be \"X\", \"O\", \"alternate\", or \"random:<seed>\"";
+    match value {
+        "X" => Ok(StartingPlayerPolicy::Fixed(Player::X)),
+        "O" => Ok(StartingPlayerPolicy::Fixed(Player::O)),
+        "alternate" => Ok(StartingPlayerPolicy::Alternate),
+        _ => {
+            let seed = value.strip_prefix("random:").ok_or(ERROR)?;
+            Ok(StartingPlayerPolicy::Random { seed: seed.parse().map_err(|_| ERROR)? })
+        }
+    }
+}
+
+fn parse_record(value: &str) -> Result<RecordPolicy, &'static str> {
+    const ERROR: &str =
+        "record_games must be \"none\", \"all\", \"losses\", \"sample:<n>\", \"last:<n>\", or \"reservoir:<n>\"";
+    match value {
+        "none" => Ok(RecordPolicy::None),
+        "all" => Ok(RecordPolicy::All),
+        "losses" => Ok(RecordPolicy::LossesOnly),
+        _ => {
+            let (kind, n) = value.split_once(':').ok_or(ERROR)?;
+            let n = n.parse().map_err(|_| ERROR)?;
+            match kind {
+                "sample" => Ok(RecordPolicy::Sample(n)),
+                "last" => Ok(RecordPolicy::Last(n)),
+                "reservoir" => Ok(RecordPolicy::Reservoir(n)),
+                _ => Err(ERROR),
+            }
+        }
+    }
+}
+
+fn parse_error_policy(value: &str) -> Result<EngineErrorPolicy, &'static str> {
+    match value {
+        "panic" => Ok(EngineErrorPolicy::Panic),
+        "forfeit" => Ok(EngineErrorPolicy::ForfeitGame),
+        "skip" => Ok(EngineErrorPolicy::SkipGame),
+        _ => Err("on_engine_error must be \"panic\", \"forfeit\", or \"skip\""),
+    }
+}
@@ -0,0 +1,122 @@
+//! Duplicate game detection
+//!
+//! [`DistinctGames`] is a [`SimulationObserver`] that identifies every game
+//! by its moves and counts how many different games a run actually played.
+//! Deterministic engines replay the same game over and over, so a
+//! million-game run of them may explore only a handful of lines.
+
+use std::collections::HashSet;
+use std::fmt;
+use crate::backend::{Board, GameResult, Player};
+use crate::simulation::observer::SimulationObserver;
+
+/// Counts of distinct and repeated games
+///
+/// Games are identified by their starting player and moves; two games are
+/// duplicates if they were played move for move the same. Unfinished games
+/// are not counted. There are at most about half a million distinct games,
+/// so memory stays small however long the run.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{FastEngine, RandomEngine};
+/// use zttt_rs::simulation::{DistinctGames, SimulationConfig, Simulator};
+///
+/// let run = |config| {
+///     let mut distinct = DistinctGames::new();
+///     Simulator::new(config).run_with_observer(&mut distinct);
+///     distinct
+/// };
+///
+/// let fast = run(SimulationConfig::builder().num_games(1000).engine(FastEngine).build());
+/// assert_eq!((fast.games(), fast.distinct()), (1000, 1));
+///
+/// let random = run(SimulationConfig::builder().num_games(1000).engine(RandomEngine::new()).build());
+/// assert!(random.distinct() > 500);
+/// println!("{}", random);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DistinctGames {
+    games: usize,
+    seen: HashSet<u64>,
+    current: u64,
+}
+
+impl DistinctGames {
+    /// Creates an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of games counted
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Returns the number of different games among them
+    pub fn distinct(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns the number of games that repeated an earlier game
+    pub fn duplicates(&self) -> usize {
+        self.games - self.seen.len()
+    }
+
+    /// Returns the percentage of games that repeated an earlier game
+    pub fn duplicate_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.duplicates() as f64 * 100.0 / self.games as f64
+        }
+    }
+
+    /// Adds the games of another collector, e.g. from another thread
+    ///
+    /// A game seen by both counts as a duplicate.
+    pub fn merge(&mut self, other: &DistinctGames) {
+        self.games += other.games;
+        self.seen.extend(&other.seen);
+    }
+}
+
+impl SimulationObserver for DistinctGames {
+    fn on_game_start(&mut self, _board: &Board) {
+        self.current = 0;
+    }
+
+    /// Extends the game's key by one move
+    ///
+    /// Each move is a base-10 digit (1 to 9) and the first move also records
+    /// the starting player, which decides who plays every later move. Nine
+    /// digits fit comfortably in a `u64`, so keys never collide.
+    fn on_move(&mut self, _board: &Board, (row, col): (usize, usize), player: Player) {
+        if self.current == 0 {
+            self.current = if player == Player::X { 1 } else { 2 };
+        }
+        self.current = self.current * 10 + (row * 3 + col + 1) as u64;
+    }
+
+    fn on_game_end(&mut self, result: GameResult) {
+        if result != GameResult::InProgress {
+            self.games += 1;
+            self.seen.insert(self.current);
+        }
+    }
+}
+
+impl fmt::Display for DistinctGames {
+    /// Writes e.g. `1000 games, 1 distinct, 999 duplicates (99.9%)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} games, {} distinct, {} duplicates ({:.1}%)",
+            self.games,
+            self.distinct(),
+            self.duplicates(),
+            self.duplicate_rate()
+        )
+    }
+}
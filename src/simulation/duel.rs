@@ -0,0 +1,90 @@
+//! Games and series between two engines drawn from a list, shared by the
+//! match and tournament formats
+
+use crate::backend::{play_game_with_rules, BoxedEngine, GameRecord, GameResult, Player, Rng, Rules};
+
+/// Plays game number `game` between `engines[x]` as X and `engines[o]` as O,
+/// with X moving first
+///
+/// With a seed, both engines are reseeded from it and the game number first,
+/// as in a seeded [`Simulator`](crate::simulation::Simulator) run.
+///
+/// # Panics
+///
+/// Panics if `x == o`, or if an engine returns an invalid move or no move
+/// while the game is still in progress.
+pub(crate) fn play_pair(
+    engines: &mut [BoxedEngine],
+    x: usize,
+    o: usize,
+    rules: Rules,
+    seed: Option<u64>,
+    game: usize,
+) -> GameResult {
+    play_pair_record(engines, x, o, rules, seed, game).result
+}
+
+/// Plays a game like [`play_pair`] and returns its full record, including
+/// the per-game seed
+pub(crate) fn play_pair_record(
+    engines: &mut [BoxedEngine],
+    x: usize,
+    o: usize,
+    rules: Rules,
+    seed: Option<u64>,
+    game: usize,
+) -> GameRecord {
+    let game_seed = seed.map(|seed| Rng::stream(seed, game as u64).next_u64());
+    if let Some(game_seed) = game_seed {
+        engines[x].reseed(game_seed);
+        engines[o].reseed(Rng::stream(game_seed, 1).next_u64());
+    }
+    let (engine_x, engine_o) = pair_mut(engines, x, o);
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("game", game, x = engine_x.name(), o = engine_o.name()).entered();
+
+    let mut record = play_game_with_rules(engine_x.as_mut(), engine_o.as_mut(), Player::X, rules);
+    assert!(record.result != GameResult::InProgress, "engine returned no move for a game in progress");
+    record.seed = game_seed;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(result = ?record.result, "game finished");
+    record
+}
+
+/// Plays up to `games` games between `engines[a]` and `engines[b]`, swapping
+/// colors after every game with `a` as X first
+///
+/// Games are numbered from `first_game` for seeding. `on_game` receives the
+/// indices of X and O and the game's record, and returns `false` to end the
+/// series early.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn play_series(
+    engines: &mut [BoxedEngine],
+    a: usize,
+    b: usize,
+    games: usize,
+    rules: Rules,
+    seed: Option<u64>,
+    first_game: usize,
+    mut on_game: impl FnMut(usize, usize, GameRecord) -> bool,
+) {
+    for game in 0..games {
+        let (x, o) = if game.is_multiple_of(2) { (a, b) } else { (b, a) };
+        let record = play_pair_record(engines, x, o, rules, seed, first_game + game);
+        if !on_game(x, o, record) {
+            break;
+        }
+    }
+}
+
+/// Returns mutable references to two different elements
+fn pair_mut<T>(items: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    assert!(a != b, "an engine cannot play itself");
+    if a < b {
+        let (left, right) = items.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = items.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
@@ -0,0 +1,111 @@
+//! Simulating games on demand
+//!
+//! [`Simulator::iter_games`] plays one game per call to `next`, so the
+//! standard iterator adapters decide how many games are played and what is
+//! kept of them.
+
+use std::iter::FusedIterator;
+use crate::backend::{GameRecord, GameResult};
+use crate::simulation::config::StopTracker;
+use crate::simulation::result::SimulationResult;
+use crate::simulation::simulator::Simulator;
+
+/// Iterator over simulated games, returned by [`Simulator::iter_games`]
+pub struct Games<'a> {
+    simulator: &'a mut Simulator,
+    next_game: usize,
+    result: SimulationResult,
+    stop: StopTracker,
+    stopped: bool,
+}
+
+impl Simulator {
+    /// Returns an iterator that plays the configured games one at a time,
+    /// yielding a [`GameRecord`] of each
+    ///
+    /// Nothing is played until the iterator is advanced, so `take`, `find`,
+    /// and friends stop the simulation as soon as they have what they need.
+    /// Games skipped by the [`EngineErrorPolicy`](crate::simulation::EngineErrorPolicy)
+    /// are not yielded, and the iterator ends early once a stop condition is
+    /// met. The record policy and progress callback are not used.
+    ///
+    /// # Panics
+    ///
+    /// Advancing the iterator panics if an engine returns an invalid move or
+    /// no move while the game is still in progress, unless the configured
+    /// error policy says otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::{GameResult, Player, RandomEngine};
+    /// use zttt_rs::simulation::{SimulationConfig, Simulator};
+    ///
+    /// let config = SimulationConfig::builder().engine(RandomEngine::seeded(7)).build();
+    /// let mut simulator = Simulator::new(config);
+    ///
+    /// let quick_wins: Vec<_> = simulator
+    ///     .iter_games()
+    ///     .filter(|game| game.result == GameResult::Win(Player::X) && game.moves.len() == 5)
+    ///     .take(3)
+    ///     .collect();
+    /// assert_eq!(quick_wins.len(), 3);
+    /// ```
+    pub fn iter_games(&mut self) -> Games<'_> {
+        Games {
+            simulator: self,
+            next_game: 0,
+            result: SimulationResult::new(),
+            stop: StopTracker::default(),
+            stopped: false,
+        }
+    }
+}
+
+impl Simulator {
+    /// Plays game number `game` into a new record, returning it with how the
+    /// game ended as in [`play_recorded`](Simulator::play_recorded)
+    pub(crate) fn play_new_record(&mut self, game: usize) -> (GameRecord, Result<GameResult, GameResult>) {
+        let mut record = self.new_record();
+        let played = self.play_recorded(game, &mut record, &mut ());
+        (record, played)
+    }
+}
+
+impl Games<'_> {
+    /// Returns the counts of the games yielded so far
+    ///
+    /// Timing fields are left at zero.
+    pub fn result(&self) -> &SimulationResult {
+        &self.result
+    }
+}
+
+impl Iterator for Games<'_> {
+    type Item = GameRecord;
+
+    fn next(&mut self) -> Option<GameRecord> {
+        let total = self.simulator.config().num_games();
+        while !self.stopped && self.next_game < total {
+            let game = self.next_game;
+            self.next_game += 1;
+
+            let (record, played) = self.simulator.play_new_record(game);
+            let outcome = self.result.record_played(played);
+            self.stopped = self.stop.should_stop(self.simulator.config(), outcome, &self.result);
+            if outcome != GameResult::InProgress {
+                return Some(record);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.stopped {
+            return (0, Some(0));
+        }
+        (0, Some(self.simulator.config().num_games() - self.next_game))
+    }
+}
+
+impl FusedIterator for Games<'_> {}
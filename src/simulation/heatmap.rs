@@ -0,0 +1,130 @@
+//! Move heatmaps by move number and player
+//!
+//! [`MoveHeatmap`] is a [`SimulationObserver`] counting how often each square
+//! is played. Besides the flat total it keeps separate counts for every ply
+//! and each player, showing how a strategy unfolds: which squares are taken
+//! on the first move, which replies follow, and so on.
+
+use std::fmt;
+use crate::backend::{Board, Player};
+use crate::simulation::observer::SimulationObserver;
+
+/// Square counts in `[row][col]` order
+pub type Grid = [[usize; 3]; 3];
+
+/// Counts of played squares by ply and player
+///
+/// Plies are numbered from zero: ply 0 is the first move of a game.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{FastEngine, Player};
+/// use zttt_rs::simulation::{MoveHeatmap, SimulationConfig, Simulator};
+///
+/// let mut heatmap = MoveHeatmap::new();
+/// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(10).engine(FastEngine).build());
+/// simulator.run_with_observer(&mut heatmap);
+///
+/// // FastEngine always fills the first free square
+/// assert_eq!(heatmap.ply(0)[0][0], 10);
+/// assert_eq!(heatmap.ply(1)[0][1], 10);
+/// assert_eq!(heatmap.player(Player::O)[0][1], 10);
+/// assert_eq!(heatmap.total()[0][0], 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MoveHeatmap {
+    /// `counts[player][ply][row][col]`, with X at index 0
+    counts: [[Grid; 9]; 2],
+}
+
+impl MoveHeatmap {
+    /// Creates an empty heatmap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the squares played by `player` on `ply`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ply` is 9 or more.
+    pub fn get(&self, ply: usize, player: Player) -> Grid {
+        self.counts[index(player)][ply]
+    }
+
+    /// Returns the squares played on `ply` by either player
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ply` is 9 or more.
+    pub fn ply(&self, ply: usize) -> Grid {
+        add(self.get(ply, Player::X), self.get(ply, Player::O))
+    }
+
+    /// Returns the squares played by `player` over the whole game
+    pub fn player(&self, player: Player) -> Grid {
+        self.counts[index(player)].iter().fold(Grid::default(), |total, &grid| add(total, grid))
+    }
+
+    /// Returns the squares played by either player over the whole game
+    pub fn total(&self) -> Grid {
+        add(self.player(Player::X), self.player(Player::O))
+    }
+
+    /// Returns the number of moves counted on `ply`
+    pub fn moves_at(&self, ply: usize) -> usize {
+        self.ply(ply).iter().flatten().sum()
+    }
+
+    /// Adds the counts of another heatmap, e.g. from another thread
+    pub fn merge(&mut self, other: &MoveHeatmap) {
+        for (mine, theirs) in self.counts.iter_mut().flatten().zip(other.counts.iter().flatten()) {
+            *mine = add(*mine, *theirs);
+        }
+    }
+}
+
+fn index(player: Player) -> usize {
+    match player {
+        Player::X => 0,
+        Player::O => 1,
+    }
+}
+
+fn add(a: Grid, b: Grid) -> Grid {
+    let mut sum = a;
+    for (row, other) in sum.iter_mut().zip(b) {
+        for (cell, count) in row.iter_mut().zip(other) {
+            *cell += count;
+        }
+    }
+    sum
+}
+
+impl SimulationObserver for MoveHeatmap {
+    fn on_move(&mut self, board: &Board, (row, col): (usize, usize), player: Player) {
+        let ply = board.count_pieces(Player::X) + board.count_pieces(Player::O) - 1;
+        self.counts[index(player)][ply][row][col] += 1;
+    }
+}
+
+impl fmt::Display for MoveHeatmap {
+    /// Writes a grid of percentages for each move number that has moves,
+    /// starting with `move 1` for ply 0
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for ply in (0..9).filter(|&ply| self.moves_at(ply) > 0) {
+            let total = self.moves_at(ply) as f64;
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "move {}", ply + 1)?;
+            for row in self.ply(ply) {
+                write!(f, "\n{:>5.1} {:>5.1} {:>5.1}", row[0] as f64 * 100.0 / total, row[1] as f64 * 100.0 / total, row[2] as f64 * 100.0 / total)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! JSON Lines output of per-game records
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use crate::backend::{GameRecord, GameResult};
+use crate::simulation::manifest::RunManifest;
+
+/// Writes one JSON object per game to a writer, flushing in batches
+///
+/// Each line has the game's index, its moves as `[row, col]` pairs, each
+/// move's duration in nanoseconds (empty if the game was not timed), the
+/// result (`"X"`, `"O"`, or `"draw"`), the number of moves, and the game's seed:
+///
+/// ```text
+/// {"game":0,"moves":[[0,0],[1,1],[0,1]],"move_ns":[950,4210,610],"result":"draw","length":9,"seed":null}
+/// ```
+///
+/// Lines are buffered in memory and written every [`batch_size`](Self::batch_size)
+/// games and on [`finish`](Self::finish).
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{GameRecord, Player, Rules};
+/// use zttt_rs::simulation::JsonlSink;
+///
+/// let mut sink = JsonlSink::new(Vec::new());
+/// sink.write(&GameRecord::new(Rules::Standard, Player::X, &[(1, 1)]).unwrap()).unwrap();
+/// let output = String::from_utf8(sink.finish().unwrap()).unwrap();
+/// assert_eq!(output, "{\"game\":0,\"moves\":[[1,1]],\"move_ns\":[],\"result\":null,\"length\":1,\"seed\":null}\n");
+/// ```
+pub struct JsonlSink<W: Write> {
+    writer: W,
+    buffer: String,
+    batch_size: usize,
+    pending: usize,
+    written: u64,
+}
+
+impl<W: Write> JsonlSink<W> {
+    /// Creates a sink that flushes every 1024 games
+    pub fn new(writer: W) -> Self {
+        JsonlSink {
+            writer,
+            buffer: String::new(),
+            batch_size: 1024,
+            pending: 0,
+            written: 0,
+        }
+    }
+
+    /// Sets how many games are buffered before writing (at least 1)
+    pub fn batch_size(mut self, games: usize) -> Self {
+        self.batch_size = games.max(1);
+        self
+    }
+
+    /// Returns the number of games written so far, including buffered ones
+    pub fn games_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Appends a game, writing the batch if it is full
+    pub fn write(&mut self, record: &GameRecord) -> io::Result<()> {
+        let result = match record.result {
+            GameResult::Win(player) => format!("\"{}\"", player),
+            GameResult::Draw => "\"draw\"".to_string(),
+            GameResult::InProgress => "null".to_string(),
+        };
+        let _ = write!(self.buffer, "{{\"game\":{},\"moves\":[", self.written);
+        for (index, (row, col)) in record.moves.iter().enumerate() {
+            let separator = if index == 0 { "" } else { "," };
+            let _ = write!(self.buffer, "{}[{},{}]", separator, row, col);
+        }
+        self.buffer.push_str("],\"move_ns\":[");
+        for (index, duration) in record.move_durations.iter().enumerate() {
+            let separator = if index == 0 { "" } else { "," };
+            let _ = write!(self.buffer, "{}{}", separator, duration.as_nanos());
+        }
+        let _ = write!(self.buffer, "],\"result\":{},\"length\":{},\"seed\":", result, record.moves.len());
+        match record.seed {
+            Some(seed) => {
+                let _ = writeln!(self.buffer, "{}}}", seed);
+            }
+            None => self.buffer.push_str("null}\n"),
+        }
+
+        self.written += 1;
+        self.pending += 1;
+        if self.pending >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Buffers a line holding `manifest`, as `{"manifest":{...}}`
+    ///
+    /// Call it before the first game so the manifest heads the file. The
+    /// line is not counted as a game.
+    pub fn write_manifest(&mut self, manifest: &RunManifest) {
+        let _ = writeln!(self.buffer, "{{\"manifest\":{}}}", manifest.to_json());
+    }
+
+    /// Writes buffered games and flushes the writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.write_all(self.buffer.as_bytes())?;
+        self.buffer.clear();
+        self.pending = 0;
+        self.writer.flush()
+    }
+
+    /// Writes any buffered games and returns the writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
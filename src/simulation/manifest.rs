@@ -0,0 +1,152 @@
+//! Reproducibility manifests
+//!
+//! A [`RunManifest`] records everything needed to rerun a simulation: the
+//! crate version, the configuration, the master seed, the engines, and when
+//! the run started. Enable it with
+//! [`SimulationConfigBuilder::manifest`](crate::simulation::SimulationConfigBuilder::manifest)
+//! (or the [`ParallelConfigBuilder`](crate::simulation::ParallelConfigBuilder)
+//! equivalent); the result then carries it, and JSON Lines and Parquet
+//! exports include it.
+
+use std::fmt::{self, Write as _};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::backend::{Player, Rules};
+use crate::simulation::config::{EngineErrorPolicy, RecordPolicy, SimulationConfig, StartingPlayerPolicy, StopCondition};
+
+/// Settings and provenance of a simulation run
+///
+/// `Display` and [`entries`](Self::entries) list the fields as `key: value`
+/// pairs, with values in the format of config files.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::EngineRegistry;
+/// use zttt_rs::simulation::{SimulationConfig, Simulator};
+///
+/// let config = SimulationConfig::builder()
+///     .engine_specs(&EngineRegistry::with_builtins(), "minimax:depth=2", "random")
+///     .unwrap()
+///     .num_games(100)
+///     .seed(42)
+///     .manifest(true)
+///     .build();
+/// let result = Simulator::new(config).run_sequential();
+///
+/// let manifest = result.manifest.unwrap();
+/// assert_eq!(manifest.seed, Some(42));
+/// assert_eq!(manifest.engine_x, "minimax:depth=2");
+/// assert!(manifest.to_string().contains("zttt_version: "));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunManifest {
+    /// Version of this crate
+    pub crate_version: String,
+    /// Start of the run, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Master seed, if the run was seeded
+    pub seed: Option<u64>,
+    pub num_games: usize,
+    pub rules: Rules,
+    pub starting_player: StartingPlayerPolicy,
+    pub record_policy: RecordPolicy,
+    pub error_policy: EngineErrorPolicy,
+    /// Stop conditions, e.g. `x_wins:10` or `draw_streak:5`; custom
+    /// conditions are listed as `custom`
+    pub stop_when: Vec<String>,
+    /// Registry spec of the X engine, with its parameters, or its name if it
+    /// was not built from a spec
+    pub engine_x: String,
+    /// Registry spec or name of the O engine
+    pub engine_o: String,
+}
+
+impl RunManifest {
+    /// Describes a run of `config` starting now
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        let (engine_x, engine_o) = match config.engine_specs() {
+            Some((x, o)) => (x.to_string(), o.unwrap_or(x).to_string()),
+            None => (config.engine_name(Player::X).to_string(), config.engine_name(Player::O).to_string()),
+        };
+        RunManifest {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+            seed: config.seed(),
+            num_games: config.num_games(),
+            rules: config.rules(),
+            starting_player: config.starting_player_policy(),
+            record_policy: config.record_policy(),
+            error_policy: config.engine_error_policy(),
+            stop_when: config.stop_conditions.iter().map(stop_name).collect(),
+            engine_x,
+            engine_o,
+        }
+    }
+
+    /// Returns the fields as `(key, value)` pairs
+    ///
+    /// The seed is `none` for unseeded runs and stop conditions are joined
+    /// with commas.
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("zttt_version", self.crate_version.clone()),
+            ("timestamp", self.timestamp.to_string()),
+            ("seed", self.seed.map_or("none".to_string(), |seed| seed.to_string())),
+            ("num_games", self.num_games.to_string()),
+            ("rules", self.rules.to_string()),
+            ("starting_player", self.starting_player.to_string()),
+            ("record_games", self.record_policy.to_string()),
+            ("on_engine_error", self.error_policy.to_string()),
+            ("stop_when", self.stop_when.join(",")),
+            ("engine_x", self.engine_x.clone()),
+            ("engine_o", self.engine_o.clone()),
+        ]
+    }
+
+    /// Returns the entries as a JSON object with string values
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        for (index, (key, value)) in self.entries().into_iter().enumerate() {
+            let separator = if index == 0 { "" } else { "," };
+            let _ = write!(json, "{}\"{}\":\"", separator, key);
+            for c in value.chars() {
+                match c {
+                    '"' | '\\' => {
+                        json.push('\\');
+                        json.push(c);
+                    }
+                    c if c.is_control() => {
+                        let _ = write!(json, "\\u{:04x}", c as u32);
+                    }
+                    c => json.push(c),
+                }
+            }
+            json.push('"');
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Names a stop condition, e.g. `x_wins:10`
+fn stop_name(condition: &StopCondition) -> String {
+    match condition {
+        StopCondition::Wins(Player::X, count) => format!("x_wins:{}", count),
+        StopCondition::Wins(Player::O, count) => format!("o_wins:{}", count),
+        StopCondition::DrawStreak(count) => format!("draw_streak:{}", count),
+        StopCondition::When(_) => "custom".to_string(),
+    }
+}
+
+impl fmt::Display for RunManifest {
+    /// Writes one `key: value` line per entry
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.entries().into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
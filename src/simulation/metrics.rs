@@ -0,0 +1,174 @@
+//! Prometheus metrics for long-running simulations (`metrics` feature)
+//!
+//! [`SimulationMetrics`] is a [`SimulationObserver`] that counts games and
+//! results as they finish and keeps a live throughput gauge, so a server
+//! running simulations for hours can be scraped instead of only reporting a
+//! final summary. Clones share the same metrics, so one instance can observe
+//! many runs, including runs on different threads.
+//!
+//! | Metric | Type | Meaning |
+//! |--------|------|---------|
+//! | `zttt_games_completed_total` | counter | Finished games |
+//! | `zttt_game_results_total{result}` | counter | Games by `result`: `x_win`, `o_win`, or `draw` |
+//! | `zttt_throughput_games_per_second` | gauge | Games finished per second, over roughly the last second |
+//! | `zttt_queue_depth` | gauge | Simulations waiting or running, maintained by the server |
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use prometheus::{Encoder, Gauge, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use crate::backend::{GameResult, Player};
+use crate::simulation::observer::SimulationObserver;
+
+/// Shortest interval the throughput gauge is averaged over
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Games between checks of the throughput window
+const THROUGHPUT_CHECK_INTERVAL: u64 = 64;
+
+/// Counters and gauges describing simulation progress
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{FastEngine, Player};
+/// use zttt_rs::simulation::{SimulationConfig, SimulationMetrics, Simulator};
+///
+/// let metrics = SimulationMetrics::new();
+/// let config = SimulationConfig::builder().num_games(100).engine(FastEngine).build();
+/// Simulator::new(config).run_with_observer(&mut metrics.clone());
+///
+/// assert_eq!(metrics.games_completed(), 100);
+/// assert_eq!(metrics.wins(Player::X), 100);
+/// assert!(metrics.encode().contains("zttt_games_completed_total 100"));
+/// ```
+#[derive(Clone)]
+pub struct SimulationMetrics {
+    registry: Registry,
+    games: IntCounter,
+    results: IntCounterVec,
+    throughput: Gauge,
+    queue_depth: IntGauge,
+    /// Start of the current throughput window and the game count at that time
+    window: Arc<Mutex<(Instant, u64)>>,
+}
+
+impl SimulationMetrics {
+    /// Creates the metrics in a registry of their own
+    pub fn new() -> Self {
+        Self::with_registry(Registry::new()).expect("a new registry has no conflicting metrics")
+    }
+
+    /// Creates the metrics and registers them in `registry`, e.g. one that
+    /// already holds a service's other metrics
+    ///
+    /// Returns an error if the registry already has metrics with these names.
+    pub fn with_registry(registry: Registry) -> Result<Self, &'static str> {
+        let games = IntCounter::new("zttt_games_completed_total", "Finished games").map_err(|_| "Invalid metric")?;
+        let results = IntCounterVec::new(Opts::new("zttt_game_results_total", "Finished games by result"), &["result"])
+            .map_err(|_| "Invalid metric")?;
+        let throughput = Gauge::new("zttt_throughput_games_per_second", "Games finished per second")
+            .map_err(|_| "Invalid metric")?;
+        let queue_depth = IntGauge::new("zttt_queue_depth", "Simulations waiting or running").map_err(|_| "Invalid metric")?;
+
+        registry.register(Box::new(games.clone())).map_err(|_| "Metric is already registered")?;
+        registry.register(Box::new(results.clone())).map_err(|_| "Metric is already registered")?;
+        registry.register(Box::new(throughput.clone())).map_err(|_| "Metric is already registered")?;
+        registry.register(Box::new(queue_depth.clone())).map_err(|_| "Metric is already registered")?;
+
+        Ok(SimulationMetrics {
+            registry,
+            games,
+            results,
+            throughput,
+            queue_depth,
+            window: Arc::new(Mutex::new((Instant::now(), 0))),
+        })
+    }
+
+    /// Returns the registry the metrics live in
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Counts a finished game; unfinished (skipped) games are ignored
+    pub fn record_game(&self, result: GameResult) {
+        let label = match result {
+            GameResult::Win(Player::X) => "x_win",
+            GameResult::Win(Player::O) => "o_win",
+            GameResult::Draw => "draw",
+            GameResult::InProgress => return,
+        };
+        self.results.with_label_values(&[label]).inc();
+        self.games.inc();
+        if self.games.get().is_multiple_of(THROUGHPUT_CHECK_INTERVAL) {
+            self.update_throughput();
+        }
+    }
+
+    /// Recomputes the throughput gauge if the current window has passed
+    fn update_throughput(&self) {
+        let Ok(mut window) = self.window.try_lock() else {
+            return;
+        };
+        let (start, games_at_start) = *window;
+        let elapsed = start.elapsed();
+        if elapsed >= THROUGHPUT_WINDOW {
+            let games = self.games.get();
+            self.throughput.set((games - games_at_start) as f64 / elapsed.as_secs_f64());
+            *window = (Instant::now(), games);
+        }
+    }
+
+    /// Returns the number of finished games
+    pub fn games_completed(&self) -> u64 {
+        self.games.get()
+    }
+
+    /// Returns the number of games won by `player`
+    pub fn wins(&self, player: Player) -> u64 {
+        let label = match player {
+            Player::X => "x_win",
+            Player::O => "o_win",
+        };
+        self.results.with_label_values(&[label]).get()
+    }
+
+    /// Returns the number of drawn games
+    pub fn draws(&self) -> u64 {
+        self.results.with_label_values(&["draw"]).get()
+    }
+
+    /// Returns the last measured throughput in games per second
+    pub fn throughput(&self) -> f64 {
+        self.throughput.get()
+    }
+
+    /// Returns the gauge of simulations waiting or running
+    ///
+    /// Servers increment it when a simulation is submitted and decrement it
+    /// when the simulation finishes.
+    pub fn queue_depth(&self) -> &IntGauge {
+        &self.queue_depth
+    }
+
+    /// Renders every metric in the registry in the Prometheus text format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("metrics encode to memory");
+        String::from_utf8(buffer).expect("text format is UTF-8")
+    }
+}
+
+impl Default for SimulationMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationObserver for SimulationMetrics {
+    fn on_game_end(&mut self, result: GameResult) {
+        self.record_game(result);
+    }
+}
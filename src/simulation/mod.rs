@@ -13,6 +13,25 @@
 //! - **Provide flexibility**: Support various simulation configurations and scenarios
 //! - **Collect insights**: Gather statistics and metrics from simulation runs
 //!
+//! ## Quick Start
+//!
+//! ```
+//! use zttt_rs::simulation::{Simulator, SimulationConfig};
+//! use zttt_rs::backend::{FastEngine, Player};
+//!
+//! let config = SimulationConfig::builder()
+//!     .num_games(10_000)
+//!     .engine(FastEngine)
+//!     .starting_player(Player::X)
+//!     .build();
+//!
+//! let result = Simulator::new(config).run_sequential();
+//! println!("Win rate: {:.2}%", result.win_rate(Player::X));
+//! ```
+//!
+//! Board variants such as [`BoardN`](crate::backend::BoardN) are simulated
+//! with [`simulate_variant`], which shares the same game loop.
+//!
 //! ## Planned Features
 //!
 //! ### Core Simulation Engine
@@ -41,14 +60,16 @@
 //! - Custom game state initializers
 //! - Streaming results to avoid memory overhead
 
-// TODO: Phase 1 - Core Simulation Runner
-// - [ ] Create `SimulationConfig` struct
+// Phase 1 - Core Simulation Runner (DONE)
+// - [x] Create `SimulationConfig` struct
 //   - num_games: usize
-//   - engine: Box<dyn Engine>
-//   - starting_player: Player
-//   - seed: Option<u64> (for reproducibility)
+//   - engine: Box<dyn Engine> (engine_x / engine_o)
+//   - starting: StartingPlayerPolicy (Fixed / Alternate / Random { seed })
+//   - seed: Option<u64> (derive_game_seed(seed, game) reseeds both engines
+//     before each game and is kept as GameRecord::seed; also salts the
+//     reservoir sample. The Random starter policy carries its own seed)
 //
-// - [ ] Create `SimulationResult` struct
+// - [x] Create `SimulationResult` struct
 //   - games_completed: usize
 //   - x_wins: usize
 //   - o_wins: usize
@@ -57,55 +78,72 @@
 //   - avg_game_duration: Duration
 //   - throughput: f64 (games/sec)
 //
-// - [ ] Implement `Simulator` struct
+// - [x] Implement `Simulator` struct
 //   - run_sequential() -> SimulationResult
 //   - run_with_callback(callback: impl Fn(GameResult)) -> SimulationResult
+//   - run_with_observer(&mut impl SimulationObserver) -> SimulationResult
+//   - run_for(budget: Duration) -> SimulationResult
+//   - with_progress(every_n_games, |completed, total, partial| ..) -> Simulator
+//   - run_with_records(callback: impl FnMut(&GameRecord)) -> SimulationResult
+//   - run_jsonl(writer) -> io::Result<SimulationResult> (JsonlSink)
+//   - run_parquet(games, moves) (`arrow` feature, ParquetSink)
+//   - run_archive(writer) (`zstd` feature, seekable .zttt archive via ArchiveWriter / ArchiveReader)
+//   - run_streaming(every_n_games) / run_streaming_games(..) -> Receiver<SimulationEvent>
+//   - iter_games() -> Games (lazy Iterator<Item = GameRecord>)
 
-// TODO: Phase 2 - Multi-threaded Simulation
-// - [ ] Create `ParallelConfig` struct
-//   - extends SimulationConfig
+// Phase 2 - Multi-threaded Simulation (done)
+// - [x] `ParallelConfig` struct (builder, engine sources cloned per thread)
 //   - num_threads: usize
 //   - chunk_size: usize (games per thread batch)
 //
-// - [ ] Implement `ParallelSimulator` struct
+// - [x] `ParallelSimulator` struct
 //   - run_parallel() -> SimulationResult
-//   - Uses std::thread or rayon for parallelism
-//   - Work-stealing queue for load balancing
-//   - Lock-free statistics aggregation where possible
-//
-// - [ ] Thread safety considerations
-//   - Engine implementations must be Send + Sync
-//   - Consider Arc<dyn Engine> for shared engines
-//   - Use atomic counters for statistics
+//   - Uses std::thread::scope; workers claim chunks from an atomic counter
+//   - Per-thread SimulationResult merged at the end, relaxed atomics for progress
+//   - run_rayon() -> SimulationResult (`rayon` feature, par_iter over chunks + reduce)
+//   - par_iter_games() -> impl ParallelIterator<Item = GameRecord> (`rayon` feature)
+//
+// - [x] Thread safety considerations
+//   - Each worker builds its own engines, so engines need not be Sync
+//   - with_progress callbacks run on the calling thread
+//
+// - [x] `BatchSimulator`: lockstep games over structure-of-arrays boards
+//   - One array per cell across the batch; built-in BatchPolicy move selection
 
 // TODO: Phase 3 - Statistics & Analysis
 // - [ ] Create `Statistics` struct
 //   - Detailed win/loss/draw breakdown
-//   - Move frequency heatmap
+//   - [x] Move frequency heatmap (MoveHeatmap, per ply and per player)
 //   - Game length distribution
 //   - Performance percentiles (p50, p95, p99)
 //
-// - [ ] Implement `StatisticsCollector` trait
-//   - on_game_start()
-//   - on_move_made()
-//   - on_game_end()
-//   - finalize() -> Statistics
+// - [x] Lifecycle hooks: `SimulationObserver` trait
+//   - on_game_start(), on_move(), on_game_end() with no-op defaults
+//   - Simulator::run_with_observer(&mut observer)
 //
 // - [ ] Built-in collectors
+//   - [x] OpeningStats: outcomes by first move(s) and square kind
 //   - BasicStatistics: win/loss/draw only
 //   - DetailedStatistics: includes move analysis
 //   - PerformanceStatistics: timing and throughput
 
 // TODO: Phase 4 - Advanced Features
 // - [ ] Tournament system
-//   - Round-robin engine matchups
-//   - Elimination brackets
+//   - [x] Engine pool with seeded random pairings (EnginePool)
+//   - [x] Best-of-N series between two engines with per-game records (Match)
+//   - [x] Round-robin engine matchups with cross-table (Tournament, built on Match's series loop)
+//   - [x] Single and double elimination brackets (Bracket)
 //   - ELO rating calculation
+//   - [x] Strength estimate against a fixed Random/Heuristic/Perfect ladder (estimate_strength)
+//   - [x] Glicko-2 ratings (Glicko2) from pool, bracket, and tournament results
+//
+// - [x] Config files: SimulationConfig::from_file / to_file (TOML, JSON)
+//   - Engines named by registry spec so saved runs can be repeated
 //
-// - [ ] Custom initializers
-//   - Start from specific board states
-//   - Test specific scenarios
-//   - Load positions from file
+// - [x] Custom initializers
+//   - [x] Start from specific board states (ScenarioCase)
+//   - [x] Test specific scenarios (ScenarioRunner, `zttt check`)
+//   - [x] Load positions from file (ScenarioSuite::from_file)
 //
 // - [ ] Result streaming
 //   - Stream to file (CSV, JSON)
@@ -193,12 +231,64 @@
 // - Custom engine integration guide
 // - Migration guide from current examples
 
+pub mod config;
+#[cfg(feature = "config")]
+pub mod config_file;
+pub mod result;
+pub mod manifest;
+pub mod simulator;
+pub mod stream;
+pub mod games;
+pub mod jsonl;
+pub mod parallel;
+pub mod batch;
+pub mod observer;
+pub mod openings;
+pub mod heatmap;
+pub mod distinct;
+pub mod scenario;
+pub mod pool;
+pub mod bracket;
+pub mod series;
+pub mod tournament;
+pub mod rating;
+pub mod strength;
+mod duel;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "zstd")]
+pub mod archive;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "indicatif")]
+mod progress_bar;
+
+pub use config::{EngineErrorPolicy, RecordPolicy, SimulationConfig, SimulationConfigBuilder, StartingPlayerPolicy, StopCondition, MAX_SIMULATION_GAMES};
+pub use result::SimulationResult;
+pub use manifest::RunManifest;
+pub use simulator::{Simulator, simulate_variant};
+pub use stream::{ProgressSnapshot, SimulationEvent};
+pub use games::Games;
+pub use jsonl::JsonlSink;
+pub use observer::SimulationObserver;
+pub use openings::{OpeningRecord, OpeningStats, SquareKind};
+pub use heatmap::{Grid, MoveHeatmap};
+pub use distinct::DistinctGames;
+pub use scenario::{ScenarioCase, ScenarioOutcome, ScenarioParseError, ScenarioReport, ScenarioRunner, ScenarioRunnerBuilder, ScenarioSuite};
+pub use bracket::{Bracket, BracketBuilder, BracketFormat, BracketResult, BracketSeries, BracketStage};
+pub use series::{Match, MatchBuilder, MatchResult, MatchVerdict};
+pub use tournament::{MatchupRecord, Tournament, TournamentBuilder, TournamentResult, TournamentStanding};
+pub use rating::{Glicko2, Glicko2Rating};
+pub use strength::{estimate_strength, estimate_strength_with, LadderResult, StrengthEstimate, DEFAULT_GAMES_PER_OPPONENT};
+pub use pool::{EnginePool, EnginePoolBuilder, EngineStats, PairingStats, PoolResult};
+pub use batch::{BatchPolicy, BatchSimulator, BatchSimulatorBuilder};
+pub use parallel::{ParallelConfig, ParallelConfigBuilder, ParallelSimulator, EngineSource};
+#[cfg(feature = "arrow")]
+pub use arrow::{RecordBatchBuilder, ParquetSink};
+#[cfg(feature = "zstd")]
+pub use archive::{ArchiveReader, ArchiveWriter};
+#[cfg(feature = "metrics")]
+pub use metrics::SimulationMetrics;
+
 // Placeholder exports (will be implemented in phases)
-// pub struct SimulationConfig;
-// pub struct SimulationResult;
-// pub struct Simulator;
-// pub struct ParallelConfig;
-// pub struct ParallelSimulator;
 // pub struct Statistics;
-// pub trait StatisticsCollector;
-// pub struct Tournament;
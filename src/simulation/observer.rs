@@ -0,0 +1,54 @@
+//! Instrumentation hooks for simulation runs
+
+use crate::backend::{Board, GameResult, Player};
+
+/// Receives events as the simulator plays games
+///
+/// Every method has a no-op default, so observers implement only the events
+/// they care about. Pass an observer to
+/// [`Simulator::run_with_observer`](crate::simulation::Simulator::run_with_observer).
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Board, Player};
+/// use zttt_rs::simulation::{SimulationConfig, SimulationObserver, Simulator};
+///
+/// /// Counts how often each cell is played
+/// #[derive(Default)]
+/// struct Heatmap([[usize; 3]; 3]);
+///
+/// impl SimulationObserver for Heatmap {
+///     fn on_move(&mut self, _board: &Board, (row, col): (usize, usize), _player: Player) {
+///         self.0[row][col] += 1;
+///     }
+/// }
+///
+/// let mut heatmap = Heatmap::default();
+/// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(100).build());
+/// simulator.run_with_observer(&mut heatmap);
+/// assert_eq!(heatmap.0[0][0], 100);
+/// ```
+pub trait SimulationObserver {
+    /// Called before the first move of each game with the empty board
+    fn on_game_start(&mut self, _board: &Board) {}
+
+    /// Called after each move with the board after the move
+    fn on_move(&mut self, _board: &Board, _position: (usize, usize), _player: Player) {}
+
+    /// Called after each game with its result
+    fn on_game_end(&mut self, _result: GameResult) {}
+}
+
+/// The observer that ignores every event
+impl SimulationObserver for () {}
+
+/// Adapts a per-game callback to an observer
+pub(crate) struct OnGameEnd<F>(pub(crate) F);
+
+impl<F: FnMut(GameResult)> SimulationObserver for OnGameEnd<F> {
+    #[inline]
+    fn on_game_end(&mut self, result: GameResult) {
+        (self.0)(result)
+    }
+}
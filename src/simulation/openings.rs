@@ -0,0 +1,254 @@
+//! Outcome statistics by opening
+//!
+//! [`OpeningStats`] is a [`SimulationObserver`] that files every game under
+//! its first move, or its first few moves, and counts the results, answering
+//! questions like "how often does X win after a corner opening?".
+
+use std::collections::BTreeMap;
+use std::fmt;
+use crate::backend::format::square_name;
+use crate::backend::{Board, GameResult, Player};
+use crate::simulation::observer::SimulationObserver;
+
+/// Kind of square, which decides a first move up to symmetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SquareKind {
+    Center,
+    Corner,
+    Edge,
+}
+
+impl SquareKind {
+    /// All kinds, center first
+    pub const ALL: [SquareKind; 3] = [SquareKind::Center, SquareKind::Corner, SquareKind::Edge];
+
+    /// Returns the kind of the square (row, col)
+    pub fn of((row, col): (usize, usize)) -> SquareKind {
+        match (row == 1, col == 1) {
+            (true, true) => SquareKind::Center,
+            (false, false) => SquareKind::Corner,
+            _ => SquareKind::Edge,
+        }
+    }
+}
+
+impl fmt::Display for SquareKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SquareKind::Center => write!(f, "center"),
+            SquareKind::Corner => write!(f, "corner"),
+            SquareKind::Edge => write!(f, "edge"),
+        }
+    }
+}
+
+/// Results of the games that began with one opening
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OpeningRecord {
+    pub x_wins: usize,
+    pub o_wins: usize,
+    pub draws: usize,
+}
+
+impl OpeningRecord {
+    /// Returns the number of games
+    pub fn games(&self) -> usize {
+        self.x_wins + self.o_wins + self.draws
+    }
+
+    /// Returns the percentage of games won by `player`
+    pub fn win_rate(&self, player: Player) -> f64 {
+        let wins = match player {
+            Player::X => self.x_wins,
+            Player::O => self.o_wins,
+        };
+        percentage(wins, self.games())
+    }
+
+    /// Returns the percentage of games drawn
+    pub fn draw_rate(&self) -> f64 {
+        percentage(self.draws, self.games())
+    }
+
+    fn add(&mut self, result: GameResult) {
+        match result {
+            GameResult::Win(Player::X) => self.x_wins += 1,
+            GameResult::Win(Player::O) => self.o_wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::InProgress => {}
+        }
+    }
+}
+
+impl std::ops::AddAssign for OpeningRecord {
+    fn add_assign(&mut self, other: Self) {
+        self.x_wins += other.x_wins;
+        self.o_wins += other.o_wins;
+        self.draws += other.draws;
+    }
+}
+
+fn percentage(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+/// Game outcomes tabulated by their opening moves
+///
+/// Games that end before `plies` moves are filed under the moves they had,
+/// and unfinished games are not counted. `Display` renders a summary by
+/// first-move square kind followed by a table of every opening.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{Player, RandomEngine};
+/// use zttt_rs::simulation::{OpeningStats, SimulationConfig, Simulator, SquareKind};
+///
+/// let config = SimulationConfig::builder()
+///     .num_games(1000)
+///     .engine(RandomEngine::new())
+///     .seed(1)
+///     .build();
+/// let mut openings = OpeningStats::new();
+/// Simulator::new(config).run_with_observer(&mut openings);
+///
+/// assert_eq!(openings.games(), 1000);
+/// let center = openings.by_kind(SquareKind::Center);
+/// let edge = openings.by_kind(SquareKind::Edge);
+/// assert!(center.win_rate(Player::X) > edge.win_rate(Player::X));
+/// println!("{}", openings);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningStats {
+    plies: usize,
+    openings: BTreeMap<Vec<(usize, usize)>, OpeningRecord>,
+    current: Vec<(usize, usize)>,
+}
+
+impl OpeningStats {
+    /// Creates a collector keyed by the first move
+    pub fn new() -> Self {
+        Self::with_plies(1)
+    }
+
+    /// Creates a collector keyed by the first `plies` moves (at least one)
+    pub fn with_plies(plies: usize) -> Self {
+        OpeningStats {
+            plies: plies.max(1),
+            openings: BTreeMap::new(),
+            current: Vec::with_capacity(plies),
+        }
+    }
+
+    /// Returns the number of moves each opening is keyed by
+    pub fn plies(&self) -> usize {
+        self.plies
+    }
+
+    /// Returns the number of games counted
+    pub fn games(&self) -> usize {
+        self.openings.values().map(OpeningRecord::games).sum()
+    }
+
+    /// Returns the results of games that began with `moves`
+    ///
+    /// `moves` may be shorter than [`plies`](Self::plies), in which case the
+    /// results of every opening starting with it are added up.
+    pub fn get(&self, moves: &[(usize, usize)]) -> OpeningRecord {
+        let mut total = OpeningRecord::default();
+        for (_, &record) in self.openings.iter().filter(|(opening, _)| opening.starts_with(moves)) {
+            total += record;
+        }
+        total
+    }
+
+    /// Returns the results of games whose first move was on a square of `kind`
+    pub fn by_kind(&self, kind: SquareKind) -> OpeningRecord {
+        let mut total = OpeningRecord::default();
+        for (_, &record) in self.openings.iter().filter(|(opening, _)| opening.first().map(|&mv| SquareKind::of(mv)) == Some(kind)) {
+            total += record;
+        }
+        total
+    }
+
+    /// Iterates over openings and their results in row-major move order
+    pub fn iter(&self) -> impl Iterator<Item = (&[(usize, usize)], OpeningRecord)> + '_ {
+        self.openings.iter().map(|(opening, &record)| (opening.as_slice(), record))
+    }
+
+    /// Adds the counts of another collector, e.g. from another thread
+    ///
+    /// # Panics
+    ///
+    /// Panics if the collectors key openings by different numbers of plies.
+    pub fn merge(&mut self, other: &OpeningStats) {
+        assert_eq!(self.plies, other.plies, "openings are keyed by different numbers of plies");
+        for (opening, &record) in &other.openings {
+            *self.openings.entry(opening.clone()).or_default() += record;
+        }
+    }
+}
+
+impl Default for OpeningStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationObserver for OpeningStats {
+    fn on_game_start(&mut self, _board: &Board) {
+        self.current.clear();
+    }
+
+    fn on_move(&mut self, _board: &Board, position: (usize, usize), _player: Player) {
+        if self.current.len() < self.plies {
+            self.current.push(position);
+        }
+    }
+
+    fn on_game_end(&mut self, result: GameResult) {
+        if result != GameResult::InProgress {
+            self.openings.entry(self.current.clone()).or_default().add(result);
+        }
+    }
+}
+
+impl fmt::Display for OpeningStats {
+    /// Writes e.g. `corner openings: 58.9% X wins, 29.1% O wins, 12.0% draws`
+    /// per square kind, then one row per opening
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for kind in SquareKind::ALL {
+            let record = self.by_kind(kind);
+            if record.games() > 0 {
+                writeln!(
+                    f,
+                    "{} openings: {:.1}% X wins, {:.1}% O wins, {:.1}% draws",
+                    kind,
+                    record.win_rate(Player::X),
+                    record.win_rate(Player::O),
+                    record.draw_rate()
+                )?;
+            }
+        }
+
+        let width = self.plies * 3;
+        write!(f, "\n{:<width$} {:>7} {:>7} {:>7} {:>7}", "opening", "games", "X wins", "O wins", "draws")?;
+        for (opening, record) in self.iter() {
+            let names: Vec<String> = opening.iter().map(|&(row, col)| square_name(row, col)).collect();
+            write!(
+                f,
+                "\n{:<width$} {:>7} {:>6.1}% {:>6.1}% {:>6.1}%",
+                names.join(" "),
+                record.games(),
+                record.win_rate(Player::X),
+                record.win_rate(Player::O),
+                record.draw_rate()
+            )?;
+        }
+        Ok(())
+    }
+}
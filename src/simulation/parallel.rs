@@ -0,0 +1,675 @@
+//! Multi-threaded simulation runner
+//!
+//! Games are split into chunks that worker threads claim from a shared atomic
+//! counter. Each worker owns its engines and accumulates its own
+//! [`SimulationResult`]; the per-thread results are merged once at the end,
+//! so no lock is taken while games are played.
+//!
+//! Seeded runs are deterministic: game `i` is played with a seed derived
+//! from the master seed and `i` alone (see [`ParallelConfig::game_seed`]),
+//! and games are numbered across the whole run. Which thread plays a game,
+//! and when, does not affect its outcome, so the aggregate result is the
+//! same for any thread count, chunk size, or schedule.
+//!
+//! With the `rayon` feature, [`ParallelSimulator::run_rayon`] plays the same
+//! chunks on the current rayon thread pool instead of spawning its own threads,
+//! and [`ParallelSimulator::par_iter_games`] hands the games to rayon adapters.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::backend::{BoxedEngine, Player, Rng, Rules, StatefulEngine};
+#[cfg(feature = "rayon")]
+use crate::backend::{GameRecord, GameResult};
+use crate::simulation::config::{derive_game_seed, record_salt, EngineErrorPolicy, RecordPolicy, SimulationConfig, StartingPlayerPolicy};
+use crate::simulation::manifest::RunManifest;
+use crate::simulation::result::SimulationResult;
+use crate::simulation::simulator::{ProgressCallback, Simulator};
+#[cfg(feature = "indicatif")]
+use crate::simulation::progress_bar::{bar_callback, BAR_INTERVAL};
+
+/// Creates a fresh engine for each worker thread
+pub type EngineSource = Arc<dyn Fn() -> BoxedEngine + Send + Sync>;
+
+/// How often the aggregator checks worker progress
+const PROGRESS_POLL: Duration = Duration::from_millis(5);
+
+/// Largest chunk size chosen when none is set
+const MAX_DEFAULT_CHUNK: usize = 1000;
+
+/// Chunks per thread aimed for when no chunk size is set, so threads that
+/// finish early can pick up more work
+const CHUNKS_PER_THREAD: usize = 8;
+
+/// Configuration for a [`ParallelSimulator`] run
+///
+/// Engines are given as sources so every worker thread gets its own instance.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::ParallelConfig;
+/// use zttt_rs::backend::FastEngine;
+///
+/// let config = ParallelConfig::builder()
+///     .num_games(100_000)
+///     .engine(FastEngine)
+///     .num_threads(4)
+///     .chunk_size(1_000)
+///     .build();
+/// assert_eq!(config.num_threads(), 4);
+/// ```
+pub struct ParallelConfig {
+    num_games: usize,
+    num_threads: usize,
+    chunk_size: Option<usize>,
+    #[cfg(feature = "affinity")]
+    pin_threads: bool,
+    engine_x: EngineSource,
+    engine_o: Option<EngineSource>,
+    starting: StartingPlayerPolicy,
+    rules: Rules,
+    seed: Option<u64>,
+    record_policy: RecordPolicy,
+    error_policy: EngineErrorPolicy,
+    manifest: bool,
+    /// Random value shared by the workers of an unseeded run
+    entropy: u64,
+}
+
+impl ParallelConfig {
+    /// Creates a builder with default settings
+    ///
+    /// Defaults: 1000 games, one thread per available core (from
+    /// [`std::thread::available_parallelism`]), a chunk size derived from the
+    /// game and thread counts, `FastEngine` for both sides, X starts every
+    /// game, standard rules, no seed.
+    pub fn builder() -> ParallelConfigBuilder {
+        ParallelConfigBuilder::default()
+    }
+
+    /// Returns the number of games to simulate
+    pub fn num_games(&self) -> usize {
+        self.num_games
+    }
+
+    /// Returns the number of worker threads
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Returns the number of games a worker claims at a time
+    ///
+    /// Unless set explicitly, this gives each thread about eight chunks, and
+    /// at most 1000 games per chunk.
+    ///
+    /// ```
+    /// use zttt_rs::simulation::ParallelConfig;
+    ///
+    /// let config = ParallelConfig::builder().num_games(8_000).num_threads(4).build();
+    /// assert_eq!(config.chunk_size(), 250);
+    ///
+    /// let config = ParallelConfig::builder().num_games(1_000_000).num_threads(4).build();
+    /// assert_eq!(config.chunk_size(), 1000);
+    /// ```
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size.unwrap_or_else(|| {
+            (self.num_games / (self.num_threads * CHUNKS_PER_THREAD)).clamp(1, MAX_DEFAULT_CHUNK)
+        })
+    }
+
+    /// Returns whether worker threads are pinned to cores
+    #[cfg(feature = "affinity")]
+    pub fn pin_threads(&self) -> bool {
+        self.pin_threads
+    }
+
+    /// Returns the policy choosing the first mover of each game
+    pub fn starting_player_policy(&self) -> StartingPlayerPolicy {
+        self.starting
+    }
+
+    /// Returns the rules games are played under
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Returns the seed, if one was set
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns which games are kept as full records
+    pub fn record_policy(&self) -> RecordPolicy {
+        self.record_policy
+    }
+
+    /// Returns the seed game number `game` is played with
+    ///
+    /// See [`SimulationConfig::game_seed`]; the seed does not depend on the
+    /// thread count or chunk size.
+    pub fn game_seed(&self, game: usize) -> Option<u64> {
+        self.seed.map(|seed| derive_game_seed(seed, game))
+    }
+
+    /// Returns what happens when an engine misbehaves
+    pub fn engine_error_policy(&self) -> EngineErrorPolicy {
+        self.error_policy
+    }
+
+    /// Returns true if results carry a [`RunManifest`]
+    pub fn manifest(&self) -> bool {
+        self.manifest
+    }
+
+    /// Returns the salt that ranks records under [`RecordPolicy::Reservoir`],
+    /// the same for every worker
+    fn record_salt(&self) -> u64 {
+        record_salt(self.seed, self.entropy)
+    }
+
+    /// Describes a run of this configuration starting now, naming the
+    /// engines the sources build
+    fn run_manifest(&self) -> RunManifest {
+        RunManifest::from_config(&self.worker_config())
+    }
+
+    /// Builds the single-threaded configuration a worker runs with
+    fn worker_config(&self) -> SimulationConfig {
+        SimulationConfig {
+            num_games: self.num_games,
+            engine_x: (self.engine_x)(),
+            engine_o: self.engine_o.as_ref().map(|source| source()),
+            starting: self.starting,
+            rules: self.rules,
+            seed: self.seed,
+            record_policy: self.record_policy,
+            stop_conditions: Vec::new(),
+            error_policy: self.error_policy,
+            engine_specs: None,
+            manifest: false,
+            entropy: self.entropy,
+            reseed_unseeded: true,
+        }
+    }
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Builder for [`ParallelConfig`]
+pub struct ParallelConfigBuilder {
+    config: ParallelConfig,
+}
+
+impl Default for ParallelConfigBuilder {
+    fn default() -> Self {
+        ParallelConfigBuilder {
+            config: ParallelConfig {
+                num_games: 1000,
+                num_threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+                chunk_size: None,
+                #[cfg(feature = "affinity")]
+                pin_threads: false,
+                engine_x: Arc::new(|| Box::new(crate::backend::FastEngine)),
+                engine_o: None,
+                starting: StartingPlayerPolicy::default(),
+                rules: Rules::Standard,
+                seed: None,
+                record_policy: RecordPolicy::None,
+                error_policy: EngineErrorPolicy::Panic,
+                manifest: false,
+                entropy: Rng::from_entropy().next_u64(),
+            },
+        }
+    }
+}
+
+impl ParallelConfigBuilder {
+    /// Sets the number of games to simulate
+    pub fn num_games(mut self, num_games: usize) -> Self {
+        self.config.num_games = num_games;
+        self
+    }
+
+    /// Sets the number of worker threads (at least 1)
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.config.num_threads = num_threads.max(1);
+        self
+    }
+
+    /// Sets how many games a worker claims at a time (at least 1)
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.config.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+
+    /// Pins worker `i` to core `i` (wrapping around if there are more
+    /// workers than cores)
+    ///
+    /// Pinning keeps each worker's engines and caches on one core, which makes
+    /// benchmark timings steadier. If the platform does not report its cores,
+    /// workers run unpinned.
+    #[cfg(feature = "affinity")]
+    pub fn pin_threads(mut self, pin: bool) -> Self {
+        self.config.pin_threads = pin;
+        self
+    }
+
+    /// Sets a single engine, cloned for each worker, that plays both sides
+    ///
+    /// Engines are reseeded before every game, from random entropy when no
+    /// seed is set, so the clones do not replay the same games.
+    pub fn engine<E>(self, engine: E) -> Self
+    where
+        E: StatefulEngine + Clone + Send + Sync + 'static,
+    {
+        self.engine_source(move || Box::new(engine.clone()))
+    }
+
+    /// Sets separate engines for X and O, cloned for each worker
+    pub fn engines<X, O>(self, engine_x: X, engine_o: O) -> Self
+    where
+        X: StatefulEngine + Clone + Send + Sync + 'static,
+        O: StatefulEngine + Clone + Send + Sync + 'static,
+    {
+        self.engine_sources(move || Box::new(engine_x.clone()), move || Box::new(engine_o.clone()))
+    }
+
+    /// Sets a function creating the engine that plays both sides
+    ///
+    /// Useful for engines that are not `Clone`, such as those built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry).
+    pub fn engine_source(mut self, source: impl Fn() -> BoxedEngine + Send + Sync + 'static) -> Self {
+        self.config.engine_x = Arc::new(source);
+        self.config.engine_o = None;
+        self
+    }
+
+    /// Sets functions creating the engines for X and O
+    pub fn engine_sources(
+        mut self,
+        source_x: impl Fn() -> BoxedEngine + Send + Sync + 'static,
+        source_o: impl Fn() -> BoxedEngine + Send + Sync + 'static,
+    ) -> Self {
+        self.config.engine_x = Arc::new(source_x);
+        self.config.engine_o = Some(Arc::new(source_o));
+        self
+    }
+
+    /// Sets the player who moves first in each game
+    pub fn starting_player(self, player: Player) -> Self {
+        self.starting_player_policy(StartingPlayerPolicy::Fixed(player))
+    }
+
+    /// Sets how the first mover is chosen for each game
+    ///
+    /// Games are numbered across the whole run, so the outcome does not depend
+    /// on how chunks are spread over threads.
+    pub fn starting_player_policy(mut self, policy: StartingPlayerPolicy) -> Self {
+        self.config.starting = policy;
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.config.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible runs
+    ///
+    /// Per-game seeds depend only on this seed and the game's index, so the
+    /// aggregate result is the same for any thread count or chunk size.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Sets which games are kept as full records
+    ///
+    /// Games are numbered across the whole run, so the same games are kept for
+    /// any thread count, but they are collected in no particular order.
+    pub fn record_games(mut self, policy: RecordPolicy) -> Self {
+        self.config.record_policy = policy;
+        self
+    }
+
+    /// Sets what happens when an engine misbehaves
+    pub fn on_engine_error(mut self, policy: EngineErrorPolicy) -> Self {
+        self.config.error_policy = policy;
+        self
+    }
+
+    /// Attaches a [`RunManifest`] describing the run to every result
+    pub fn manifest(mut self, enabled: bool) -> Self {
+        self.config.manifest = enabled;
+        self
+    }
+
+    /// Builds the configuration
+    pub fn build(self) -> ParallelConfig {
+        self.config
+    }
+}
+
+/// Runs games on several threads according to a [`ParallelConfig`]
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{ParallelConfig, ParallelSimulator};
+/// use zttt_rs::backend::FastEngine;
+///
+/// let config = ParallelConfig::builder()
+///     .num_games(20_000)
+///     .engine(FastEngine)
+///     .num_threads(4)
+///     .build();
+///
+/// let result = ParallelSimulator::new(config)
+///     .with_progress(5_000, |completed, total, _| println!("{}/{}", completed, total))
+///     .run_parallel();
+/// assert_eq!(result.games_completed, 20_000);
+/// ```
+pub struct ParallelSimulator {
+    config: ParallelConfig,
+    progress: Option<Progress>,
+}
+
+/// Progress callback and how often it is called
+struct Progress {
+    every: usize,
+    callback: ProgressCallback,
+}
+
+/// Totals shared by the workers, updated once per chunk
+#[derive(Default)]
+struct Counters {
+    x_wins: AtomicUsize,
+    o_wins: AtomicUsize,
+    draws: AtomicUsize,
+    finished_workers: AtomicUsize,
+}
+
+impl Counters {
+    fn add(&self, result: &SimulationResult) {
+        self.x_wins.fetch_add(result.x_wins, Ordering::Relaxed);
+        self.o_wins.fetch_add(result.o_wins, Ordering::Relaxed);
+        self.draws.fetch_add(result.draws, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, elapsed: Duration) -> SimulationResult {
+        let mut result = SimulationResult::new();
+        result.x_wins = self.x_wins.load(Ordering::Relaxed);
+        result.o_wins = self.o_wins.load(Ordering::Relaxed);
+        result.draws = self.draws.load(Ordering::Relaxed);
+        result.games_completed = result.x_wins + result.o_wins + result.draws;
+        result.finish(elapsed);
+        result
+    }
+}
+
+/// Marks a worker as finished even if it panics
+struct FinishGuard<'a>(&'a AtomicUsize);
+
+impl Drop for FinishGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl ParallelSimulator {
+    /// Creates a simulator for the given configuration
+    pub fn new(config: ParallelConfig) -> Self {
+        ParallelSimulator { config, progress: None }
+    }
+
+    /// Returns the configuration
+    pub fn config(&self) -> &ParallelConfig {
+        &self.config
+    }
+
+    /// Calls `callback(completed, total, partial)` roughly every `every_n_games`
+    /// games and once after the last game
+    ///
+    /// The callback runs on the thread that called
+    /// [`run_parallel`](Self::run_parallel), which aggregates the workers'
+    /// counts. Workers report once per chunk, so reports are as fine-grained as
+    /// the chunk size allows.
+    pub fn with_progress(
+        mut self,
+        every_n_games: usize,
+        callback: impl FnMut(usize, usize, &SimulationResult) + Send + 'static,
+    ) -> Self {
+        self.progress = Some(Progress {
+            every: every_n_games.max(1),
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    /// Shows a progress bar with ETA and live throughput on stderr
+    ///
+    /// Replaces any callback set with [`with_progress`](Self::with_progress).
+    /// Nothing is drawn when stderr is not a terminal.
+    #[cfg(feature = "indicatif")]
+    pub fn with_progress_bar(self) -> Self {
+        self.with_progress(BAR_INTERVAL, bar_callback())
+    }
+
+    /// Runs all games on the configured number of threads
+    ///
+    /// The returned `total_duration` and `throughput` are wall-clock values;
+    /// `avg_game_duration` is the time one thread spent per game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress, unless the configured [`EngineErrorPolicy`] says
+    /// otherwise.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "parallel_simulation", skip_all, fields(games = self.config.num_games, threads = self.config.num_threads)))]
+    pub fn run_parallel(&mut self) -> SimulationResult {
+        let config = &self.config;
+        let chunk_size = config.chunk_size();
+        let chunks = config.num_games.div_ceil(chunk_size);
+        let workers = config.num_threads.min(chunks).max(1);
+        let next_chunk = AtomicUsize::new(0);
+        let counters = Counters::default();
+        let start = Instant::now();
+
+        #[cfg(feature = "affinity")]
+        let cores = config.pin_threads.then(core_affinity::get_core_ids).flatten().unwrap_or_default();
+
+        let results: Vec<SimulationResult> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_worker| {
+                    #[cfg(feature = "affinity")]
+                    let core = (!cores.is_empty()).then(|| cores[_worker % cores.len()]);
+                    let (next_chunk, counters) = (&next_chunk, &counters);
+                    #[cfg(feature = "tracing")]
+                    let span = tracing::Span::current();
+                    scope.spawn(move || {
+                        #[cfg(feature = "tracing")]
+                        let _span = span.enter();
+                        #[cfg(feature = "affinity")]
+                        if let Some(core) = core {
+                            core_affinity::set_for_current(core);
+                        }
+                        run_worker(config, chunk_size, next_chunk, counters)
+                    })
+                })
+                .collect();
+
+            if let Some(progress) = &mut self.progress {
+                let total = config.num_games;
+                let mut reported = None;
+                loop {
+                    let done = counters.finished_workers.load(Ordering::Acquire) == workers;
+                    let partial = counters.snapshot(start.elapsed());
+                    let completed = partial.games_completed;
+                    // The last call comes once every worker is done, even if
+                    // skipped games kept the count short of the total
+                    let due = completed / progress.every > reported.unwrap_or(0) / progress.every;
+                    if (due || done) && reported != Some(completed) {
+                        (progress.callback)(completed, total, &partial);
+                        reported = Some(completed);
+                    }
+                    if done {
+                        break;
+                    }
+                    thread::sleep(PROGRESS_POLL);
+                }
+            }
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+
+        let mut result = SimulationResult::new();
+        result.manifest = config.manifest.then(|| config.run_manifest());
+        for worker in &results {
+            result.merge_concurrent(worker);
+        }
+        result.trim_records(config.record_policy, config.record_salt());
+        result.set_wall_time(start.elapsed());
+        result
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelSimulator {
+    /// Runs all games on the current rayon thread pool
+    ///
+    /// Chunks of [`chunk_size`](ParallelConfig::chunk_size) games are played
+    /// with `par_iter` and merged with `reduce`, so results match
+    /// [`run_parallel`](Self::run_parallel) for the same seed. Unseeded runs
+    /// reseed each game's engines from random entropy, as `run_parallel`
+    /// does, so rayon's splits do not repeat each other. The thread
+    /// count comes from the pool rather than
+    /// [`num_threads`](ParallelConfig::num_threads); use
+    /// `ThreadPool::install` to run on a specific pool. The progress callback
+    /// is not called, and threads are not pinned. Records are returned in game order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{ParallelConfig, ParallelSimulator};
+    /// use zttt_rs::backend::RandomEngine;
+    ///
+    /// let config = ParallelConfig::builder()
+    ///     .num_games(10_000)
+    ///     .engine(RandomEngine::new())
+    ///     .seed(7)
+    ///     .build();
+    ///
+    /// let mut simulator = ParallelSimulator::new(config);
+    /// let result = simulator.run_rayon();
+    /// assert_eq!(result.games_completed, 10_000);
+    /// assert_eq!(result.x_wins, simulator.run_parallel().x_wins);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`run_parallel`](Self::run_parallel).
+    pub fn run_rayon(&self) -> SimulationResult {
+        use rayon::prelude::*;
+
+        let config = &self.config;
+        let chunk_size = config.chunk_size();
+        let chunks = config.num_games.div_ceil(chunk_size);
+        let start = Instant::now();
+
+        let manifest = config.manifest.then(|| config.run_manifest());
+        let mut result = (0..chunks)
+            .into_par_iter()
+            .map_init(
+                || Simulator::new(config.worker_config()),
+                |simulator, chunk| {
+                    let first = chunk * chunk_size;
+                    simulator.run_chunk(first..config.num_games.min(first + chunk_size))
+                },
+            )
+            .reduce(SimulationResult::new, |mut total, chunk| {
+                total.merge(&chunk);
+                total.trim_records(config.record_policy, config.record_salt());
+                total
+            });
+        result.manifest = manifest;
+        result.set_wall_time(start.elapsed());
+        result
+    }
+
+    /// Returns a parallel iterator over the configured games, yielding a
+    /// [`GameRecord`] of each
+    ///
+    /// Games are generated on the current rayon pool, each worker building its
+    /// engines from the configured sources, so custom statistics can be
+    /// computed with the usual `map`/`fold`/`reduce` adapters. Seeded runs
+    /// yield the same games as [`run_parallel`](Self::run_parallel), though not
+    /// in order; unseeded runs reseed each game's engines from random entropy,
+    /// so splits do not yield copies of each other's games. Games skipped by
+    /// the error policy are not yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use zttt_rs::simulation::{ParallelConfig, ParallelSimulator};
+    /// use zttt_rs::backend::RandomEngine;
+    ///
+    /// let config = ParallelConfig::builder().num_games(10_000).engine(RandomEngine::new()).build();
+    /// let simulator = ParallelSimulator::new(config);
+    ///
+    /// let (games, moves) = simulator
+    ///     .par_iter_games()
+    ///     .map(|game| (1, game.moves.len()))
+    ///     .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+    /// assert_eq!(games, 10_000);
+    /// println!("{:.2} moves per game", moves as f64 / games as f64);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`run_parallel`](Self::run_parallel).
+    pub fn par_iter_games(&self) -> impl rayon::iter::ParallelIterator<Item = GameRecord> + '_ {
+        use rayon::prelude::*;
+
+        let config = &self.config;
+        (0..config.num_games)
+            .into_par_iter()
+            .map_init(|| Simulator::new(config.worker_config()), |simulator, game| simulator.play_new_record(game).0)
+            .filter(|record| record.result != GameResult::InProgress)
+    }
+}
+
+/// Plays chunks until none are left, returning this worker's totals
+fn run_worker(
+    config: &ParallelConfig,
+    chunk_size: usize,
+    next_chunk: &AtomicUsize,
+    counters: &Counters,
+) -> SimulationResult {
+    let _finished = FinishGuard(&counters.finished_workers);
+    let mut simulator = Simulator::new(config.worker_config());
+    let mut local = SimulationResult::new();
+
+    loop {
+        let first = next_chunk.fetch_add(1, Ordering::Relaxed) * chunk_size;
+        if first >= config.num_games {
+            break;
+        }
+        let chunk = simulator.run_chunk(first..config.num_games.min(first + chunk_size));
+        counters.add(&chunk);
+        local.merge(&chunk);
+        local.trim_records(config.record_policy, config.record_salt());
+    }
+    local
+}
@@ -0,0 +1,314 @@
+//! Engine pool simulations with random pairings
+//!
+//! A pool holds several named engines. Each game picks two different engines
+//! at random and randomly assigns them to X and O, so a single pass produces
+//! per-engine and per-pairing statistics without a fixed schedule.
+
+use std::time::{Duration, Instant};
+use crate::backend::{BoxedEngine, GameResult, Player, Rng, Rules, StatefulEngine};
+use crate::simulation::duel::play_pair;
+use crate::simulation::rating::Glicko2;
+
+/// A set of named engines playing randomly paired games
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::EnginePool;
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine, RandomEngine};
+///
+/// let mut pool = EnginePool::builder()
+///     .add_engine("fast", FastEngine)
+///     .add_engine("random", RandomEngine::new())
+///     .add_engine("minimax", MinimaxEngine::new())
+///     .num_games(300)
+///     .seed(42)
+///     .build()
+///     .unwrap();
+///
+/// let result = pool.run();
+/// assert_eq!(result.ranking()[0].name, "minimax");
+/// assert_eq!(result.engine("minimax").unwrap().losses, 0);
+/// ```
+pub struct EnginePool {
+    names: Vec<String>,
+    engines: Vec<BoxedEngine>,
+    num_games: usize,
+    rules: Rules,
+    seed: Option<u64>,
+}
+
+/// Builder for [`EnginePool`]
+pub struct EnginePoolBuilder {
+    pool: EnginePool,
+}
+
+/// Results for one engine across all its games in a pool run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineStats {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+}
+
+/// Results for one pair of engines in a pool run
+///
+/// Counts are from the point of view of `engines.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingStats {
+    pub engines: (String, String),
+    pub games: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+}
+
+/// Per-engine and per-pairing results of an [`EnginePool`] run
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolResult {
+    /// One entry per engine, in the order they were added
+    pub engines: Vec<EngineStats>,
+    /// One entry per pair of engines that met, ordered by the engines' positions
+    pub pairings: Vec<PairingStats>,
+    pub games_completed: usize,
+    pub total_duration: Duration,
+}
+
+impl EnginePool {
+    /// Creates a builder with no engines
+    ///
+    /// Defaults: 1000 games, standard rules, no seed.
+    pub fn builder() -> EnginePoolBuilder {
+        EnginePoolBuilder {
+            pool: EnginePool {
+                names: Vec::new(),
+                engines: Vec::new(),
+                num_games: 1000,
+                rules: Rules::Standard,
+                seed: None,
+            },
+        }
+    }
+
+    /// Returns the engine names in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Plays the configured number of games between random pairs of engines
+    ///
+    /// Pairings and colors are drawn from the seed if one was set, and the
+    /// engines are reseeded before each game as in a seeded
+    /// [`Simulator`](crate::simulation::Simulator) run, so seeded runs are
+    /// reproducible. X always moves first; the random color assignment
+    /// balances the first-move advantage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    pub fn run(&mut self) -> PoolResult {
+        let start = Instant::now();
+        let count = self.engines.len();
+        let mut rng = self.seed.map_or_else(Rng::from_entropy, Rng::new);
+        let mut engines: Vec<EngineStats> = self
+            .names
+            .iter()
+            .map(|name| EngineStats { name: name.clone(), games: 0, wins: 0, losses: 0, draws: 0 })
+            .collect();
+        let mut pairings = vec![(0, 0, 0); count * count];
+
+        for game in 0..self.num_games {
+            let first = rng.below(count);
+            let second = (first + 1 + rng.below(count - 1)) % count;
+            let (x, o) = if rng.next_u64() >> 63 == 0 { (first, second) } else { (second, first) };
+            let (score_x, score_o) = match play_pair(&mut self.engines, x, o, self.rules, self.seed, game) {
+                GameResult::Win(Player::X) => (Outcome::Win, Outcome::Loss),
+                GameResult::Win(Player::O) => (Outcome::Loss, Outcome::Win),
+                _ => (Outcome::Draw, Outcome::Draw),
+            };
+            score_x.add_to(&mut engines[x]);
+            score_o.add_to(&mut engines[o]);
+
+            // Pairings are stored once, from the lower index's point of view
+            let (low, outcome) = if x < o { ((x, o), score_x) } else { ((o, x), score_o) };
+            let pairing = &mut pairings[low.0 * count + low.1];
+            match outcome {
+                Outcome::Win => pairing.0 += 1,
+                Outcome::Loss => pairing.1 += 1,
+                Outcome::Draw => pairing.2 += 1,
+            }
+        }
+
+        let mut pairing_stats = Vec::new();
+        for a in 0..count {
+            for b in a + 1..count {
+                let (wins, losses, draws) = pairings[a * count + b];
+                if wins + losses + draws > 0 {
+                    pairing_stats.push(PairingStats {
+                        engines: (self.names[a].clone(), self.names[b].clone()),
+                        games: wins + losses + draws,
+                        wins,
+                        losses,
+                        draws,
+                    });
+                }
+            }
+        }
+
+        PoolResult {
+            engines,
+            pairings: pairing_stats,
+            games_completed: self.num_games,
+            total_duration: start.elapsed(),
+        }
+    }
+}
+
+impl EnginePoolBuilder {
+    /// Adds an engine under `name`
+    pub fn add_engine(self, name: &str, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.add_boxed(name, Box::new(engine))
+    }
+
+    /// Adds a boxed engine, e.g. one built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry)
+    pub fn add_boxed(mut self, name: &str, engine: BoxedEngine) -> Self {
+        self.pool.names.push(name.to_string());
+        self.pool.engines.push(engine);
+        self
+    }
+
+    /// Sets the number of games to play
+    pub fn num_games(mut self, num_games: usize) -> Self {
+        self.pool.num_games = num_games;
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.pool.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible pairings and engine randomness
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.pool.seed = Some(seed);
+        self
+    }
+
+    /// Builds the pool
+    ///
+    /// Returns an error if fewer than two engines were added.
+    pub fn build(self) -> Result<EnginePool, &'static str> {
+        if self.pool.engines.len() < 2 {
+            return Err("Engine pool needs at least two engines");
+        }
+        Ok(self.pool)
+    }
+}
+
+impl EngineStats {
+    /// Returns the score as a fraction in `0.0..=1.0`, counting draws as half a win
+    pub fn score(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            (self.wins as f64 + self.draws as f64 / 2.0) / self.games as f64
+        }
+    }
+
+    /// Returns the win rate as a percentage
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64 * 100.0
+        }
+    }
+}
+
+impl PoolResult {
+    /// Returns Glicko-2 ratings treating the whole run as one rating period
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::EnginePool;
+    /// use zttt_rs::backend::{MinimaxEngine, RandomEngine};
+    ///
+    /// let mut pool = EnginePool::builder()
+    ///     .add_engine("minimax", MinimaxEngine::new())
+    ///     .add_engine("random", RandomEngine::new())
+    ///     .num_games(50)
+    ///     .build()
+    ///     .unwrap();
+    /// let ratings = pool.run().glicko2();
+    /// assert_eq!(ratings.standings()[0].0, "minimax");
+    /// ```
+    pub fn glicko2(&self) -> Glicko2 {
+        let mut games = Vec::new();
+        for pairing in &self.pairings {
+            let (a, b) = (pairing.engines.0.as_str(), pairing.engines.1.as_str());
+            games.extend(std::iter::repeat_n((a, b, 1.0), pairing.wins));
+            games.extend(std::iter::repeat_n((a, b, 0.0), pairing.losses));
+            games.extend(std::iter::repeat_n((a, b, 0.5), pairing.draws));
+        }
+        let mut ratings = Glicko2::new();
+        for stats in &self.engines {
+            ratings.add_player(&stats.name);
+        }
+        ratings.update(&games);
+        ratings
+    }
+
+    /// Returns the stats of the engine named `name`
+    pub fn engine(&self, name: &str) -> Option<&EngineStats> {
+        self.engines.iter().find(|stats| stats.name == name)
+    }
+
+    /// Returns the stats for games between `a` and `b`, from `a`'s point of view
+    pub fn pairing(&self, a: &str, b: &str) -> Option<PairingStats> {
+        self.pairings.iter().find_map(|pairing| {
+            if pairing.engines.0 == a && pairing.engines.1 == b {
+                Some(pairing.clone())
+            } else if pairing.engines.0 == b && pairing.engines.1 == a {
+                Some(PairingStats {
+                    engines: (a.to_string(), b.to_string()),
+                    wins: pairing.losses,
+                    losses: pairing.wins,
+                    ..pairing.clone()
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the engines sorted by score, best first
+    pub fn ranking(&self) -> Vec<&EngineStats> {
+        let mut ranking: Vec<&EngineStats> = self.engines.iter().collect();
+        ranking.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        ranking
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    fn add_to(self, stats: &mut EngineStats) {
+        stats.games += 1;
+        match self {
+            Outcome::Win => stats.wins += 1,
+            Outcome::Loss => stats.losses += 1,
+            Outcome::Draw => stats.draws += 1,
+        }
+    }
+}
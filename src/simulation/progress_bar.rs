@@ -0,0 +1,43 @@
+//! Terminal progress bars for long runs (`indicatif` feature)
+//!
+//! Bars are drawn to stderr and stay hidden when stderr is not a terminal, so
+//! enabling them is harmless in scripts and tests.
+
+use std::fmt;
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use crate::simulation::result::SimulationResult;
+
+/// Games between progress bar updates from a simulator's progress callback
+pub(crate) const BAR_INTERVAL: usize = 100;
+
+/// Creates a bar counting `total` games, or a spinner if `total` is 0
+pub(crate) fn games_bar(total: usize) -> ProgressBar {
+    let (bar, template) = if total == 0 {
+        (ProgressBar::new_spinner(), "{spinner} [{elapsed_precise}] {human_pos} games, {throughput} {msg}")
+    } else {
+        (
+            ProgressBar::new(total as u64),
+            "[{elapsed_precise}] {wide_bar} {human_pos}/{human_len} games, {throughput}, ETA {eta} {msg}",
+        )
+    };
+    let style = ProgressStyle::with_template(template)
+        .expect("valid template")
+        .with_key("throughput", |state: &ProgressState, w: &mut dyn fmt::Write| {
+            let _ = write!(w, "{:.0} games/s", state.per_sec());
+        });
+    bar.set_style(style);
+    bar
+}
+
+/// Returns a progress callback that draws a [`games_bar`], created on the
+/// first report once the total is known
+pub(crate) fn bar_callback() -> impl FnMut(usize, usize, &SimulationResult) + Send + 'static {
+    let mut bar: Option<ProgressBar> = None;
+    move |completed, total, _partial| {
+        let bar = bar.get_or_insert_with(|| games_bar(total));
+        bar.set_position(completed as u64);
+        if total != 0 && completed == total {
+            bar.finish();
+        }
+    }
+}
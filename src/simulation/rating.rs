@@ -0,0 +1,218 @@
+//! Glicko-2 ratings for tournament results
+//!
+//! Glicko-2 tracks a rating, a rating deviation (how uncertain the rating is),
+//! and a volatility (how erratic the engine's results are). With few games the
+//! deviation stays large, which a single rating number would hide.
+
+use std::f64::consts::PI;
+
+/// Converts between the Glicko and Glicko-2 scales
+const SCALE: f64 = 173.7178;
+
+/// Convergence tolerance of the volatility iteration
+const EPSILON: f64 = 1e-6;
+
+/// A Glicko-2 rating
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glicko2Rating {
+    /// Rating on the familiar Elo-like scale, starting at 1500
+    pub rating: f64,
+    /// Rating deviation: one standard deviation of the rating's uncertainty
+    pub deviation: f64,
+    /// Expected fluctuation of the rating
+    pub volatility: f64,
+}
+
+impl Glicko2Rating {
+    /// Returns the interval that contains the true rating with about 95% confidence
+    pub fn interval(&self) -> (f64, f64) {
+        (self.rating - 1.96 * self.deviation, self.rating + 1.96 * self.deviation)
+    }
+}
+
+impl Default for Glicko2Rating {
+    /// An unrated player: 1500 ± 350, volatility 0.06
+    fn default() -> Self {
+        Glicko2Rating {
+            rating: 1500.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+}
+
+/// Glicko-2 ratings for a set of named players
+///
+/// Results are applied one rating period at a time with [`update`](Self::update).
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::Glicko2;
+///
+/// let mut ratings = Glicko2::new();
+/// ratings.update(&[("minimax", "random", 1.0), ("minimax", "random", 0.5)]);
+///
+/// let minimax = ratings.rating("minimax").unwrap();
+/// let random = ratings.rating("random").unwrap();
+/// assert!(minimax.rating > random.rating);
+/// assert!(minimax.deviation < 350.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glicko2 {
+    tau: f64,
+    players: Vec<(String, Glicko2Rating)>,
+}
+
+impl Glicko2 {
+    /// Creates an empty rating list with the system constant τ = 0.5
+    pub fn new() -> Self {
+        Glicko2 { tau: 0.5, players: Vec::new() }
+    }
+
+    /// Sets the system constant τ, which limits how fast volatility changes
+    ///
+    /// Glickman suggests values between 0.3 and 1.2.
+    pub fn tau(mut self, tau: f64) -> Self {
+        self.tau = tau;
+        self
+    }
+
+    /// Adds a player at the default rating, unless already present
+    pub fn add_player(&mut self, name: &str) {
+        self.index(name);
+    }
+
+    /// Sets the rating of `name`, adding the player if needed
+    pub fn set_rating(&mut self, name: &str, rating: Glicko2Rating) {
+        let index = self.index(name);
+        self.players[index].1 = rating;
+    }
+
+    /// Returns the rating of `name`, if it is known
+    pub fn rating(&self, name: &str) -> Option<Glicko2Rating> {
+        self.players.iter().find(|(player, _)| player == name).map(|&(_, rating)| rating)
+    }
+
+    /// Returns every player's rating in the order they first appeared
+    pub fn ratings(&self) -> &[(String, Glicko2Rating)] {
+        &self.players
+    }
+
+    /// Returns the players sorted by rating, best first
+    pub fn standings(&self) -> Vec<(&str, Glicko2Rating)> {
+        let mut standings: Vec<(&str, Glicko2Rating)> =
+            self.players.iter().map(|(name, rating)| (name.as_str(), *rating)).collect();
+        standings.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating));
+        standings
+    }
+
+    /// Applies one rating period of games
+    ///
+    /// Each game is `(player, opponent, score)`, where the score is from
+    /// `player`'s point of view: 1.0 for a win, 0.5 for a draw, 0.0 for a
+    /// loss. Unknown players start at the default rating. Players who did not
+    /// play in the period become less certain, as Glicko-2 prescribes.
+    pub fn update(&mut self, games: &[(&str, &str, f64)]) {
+        for &(player, opponent, _) in games {
+            self.index(player);
+            self.index(opponent);
+        }
+
+        // (opponent index, score) per player, both sides of every game
+        let mut results: Vec<Vec<(usize, f64)>> = vec![Vec::new(); self.players.len()];
+        for &(player, opponent, score) in games {
+            let (a, b) = (self.index(player), self.index(opponent));
+            results[a].push((b, score));
+            results[b].push((a, 1.0 - score));
+        }
+
+        let before: Vec<Glicko2Rating> = self.players.iter().map(|&(_, rating)| rating).collect();
+        for (index, games) in results.iter().enumerate() {
+            self.players[index].1 = updated(before[index], games, &before, self.tau);
+        }
+    }
+
+    fn index(&mut self, name: &str) -> usize {
+        match self.players.iter().position(|(player, _)| player == name) {
+            Some(index) => index,
+            None => {
+                self.players.push((name.to_string(), Glicko2Rating::default()));
+                self.players.len() - 1
+            }
+        }
+    }
+}
+
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns `rating` after one period with the given `(opponent, score)` results
+fn updated(rating: Glicko2Rating, games: &[(usize, f64)], ratings: &[Glicko2Rating], tau: f64) -> Glicko2Rating {
+    let mu = (rating.rating - 1500.0) / SCALE;
+    let phi = rating.deviation / SCALE;
+    let sigma = rating.volatility;
+
+    if games.is_empty() {
+        return Glicko2Rating {
+            deviation: (phi * phi + sigma * sigma).sqrt() * SCALE,
+            ..rating
+        };
+    }
+
+    let mut inverse_variance = 0.0;
+    let mut improvement = 0.0;
+    for &(opponent, score) in games {
+        let mu_j = (ratings[opponent].rating - 1500.0) / SCALE;
+        let phi_j = ratings[opponent].deviation / SCALE;
+        let g = 1.0 / (1.0 + 3.0 * phi_j * phi_j / (PI * PI)).sqrt();
+        let expected = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+        inverse_variance += g * g * expected * (1.0 - expected);
+        improvement += g * (score - expected);
+    }
+    let v = 1.0 / inverse_variance;
+    let delta = v * improvement;
+
+    // New volatility by the Illinois algorithm (Glickman, step 5)
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2)) - (x - a) / (tau * tau)
+    };
+    let mut lower = a;
+    let mut upper = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        a - k * tau
+    };
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    while (upper - lower).abs() > EPSILON {
+        let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_c = f(c);
+        if f_c * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = c;
+        f_upper = f_c;
+    }
+    let volatility = (lower / 2.0).exp();
+
+    let phi_star = (phi * phi + volatility * volatility).sqrt();
+    let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let new_mu = mu + new_phi * new_phi * improvement;
+    Glicko2Rating {
+        rating: new_mu * SCALE + 1500.0,
+        deviation: new_phi * SCALE,
+        volatility,
+    }
+}
@@ -0,0 +1,436 @@
+//! Aggregated simulation results
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::time::Duration;
+use crate::backend::{GameRecord, GameResult, Player};
+use crate::simulation::config::RecordPolicy;
+use crate::simulation::manifest::RunManifest;
+
+/// Results and statistics from a completed simulation run
+///
+/// Rates are returned as percentages in the range `0.0..=100.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub games_completed: usize,
+    pub x_wins: usize,
+    pub o_wins: usize,
+    pub draws: usize,
+    pub total_duration: Duration,
+    pub avg_game_duration: Duration,
+    /// Games per second
+    pub throughput: f64,
+    /// Games an engine forfeited by misbehaving, counted as wins for its
+    /// opponent (see [`EngineErrorPolicy`](crate::simulation::EngineErrorPolicy))
+    pub forfeits: usize,
+    /// Games discarded because an engine misbehaved; not counted in
+    /// `games_completed`
+    pub skipped_games: usize,
+    /// Games kept according to the configured [`RecordPolicy`](crate::simulation::RecordPolicy)
+    pub records: Vec<GameRecord>,
+    /// Settings and provenance of the run, if the configuration asked for
+    /// them with `manifest(true)`
+    pub manifest: Option<RunManifest>,
+    /// Game number of each record, tracked only for bounded record policies
+    record_games: Vec<usize>,
+}
+
+impl SimulationResult {
+    /// Creates an empty result with no games recorded
+    pub fn new() -> Self {
+        SimulationResult {
+            games_completed: 0,
+            x_wins: 0,
+            o_wins: 0,
+            draws: 0,
+            total_duration: Duration::ZERO,
+            avg_game_duration: Duration::ZERO,
+            throughput: 0.0,
+            forfeits: 0,
+            skipped_games: 0,
+            records: Vec::new(),
+            manifest: None,
+            record_games: Vec::new(),
+        }
+    }
+
+    /// Keeps a copy of game number `game`, which `policy` selected, ranking it
+    /// with `salt`
+    pub(crate) fn keep_record(&mut self, record: &GameRecord, game: usize, policy: RecordPolicy, salt: u64) {
+        self.records.push(record.clone());
+        if let Some(limit) = policy.limit() {
+            self.record_games.push(game);
+            if self.records.len() >= limit.saturating_mul(2) {
+                self.trim_records(policy, salt);
+            }
+        }
+    }
+
+    /// Drops all but the records a bounded `policy` keeps, leaving the rest
+    /// in game order
+    pub(crate) fn trim_records(&mut self, policy: RecordPolicy, salt: u64) {
+        let Some(limit) = policy.limit() else {
+            return;
+        };
+        if self.records.len() <= limit {
+            return;
+        }
+        let mut kept: Vec<_> = self.record_games.iter().copied().zip(self.records.drain(..)).collect();
+        kept.select_nth_unstable_by_key(limit, |&(game, _)| std::cmp::Reverse(policy.rank(game, salt)));
+        kept.truncate(limit);
+        kept.sort_unstable_by_key(|&(game, _)| game);
+        (self.record_games, self.records) = kept.into_iter().unzip();
+    }
+
+    /// Counts a finished game
+    pub(crate) fn record(&mut self, result: GameResult) {
+        match result {
+            GameResult::Win(Player::X) => self.x_wins += 1,
+            GameResult::Win(Player::O) => self.o_wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::InProgress => return,
+        }
+        self.games_completed += 1;
+    }
+
+    /// Counts a game that ended normally (`Ok`) or through an engine error
+    /// (`Err` holding the forfeit result, or `InProgress` if it was skipped)
+    pub(crate) fn record_played(&mut self, played: Result<GameResult, GameResult>) -> GameResult {
+        match played {
+            Ok(outcome) => {
+                self.record(outcome);
+                outcome
+            }
+            Err(GameResult::InProgress) => {
+                self.skipped_games += 1;
+                GameResult::InProgress
+            }
+            Err(outcome) => {
+                self.forfeits += 1;
+                self.record(outcome);
+                outcome
+            }
+        }
+    }
+
+    /// Sets the total duration and derives the timing fields from it
+    pub(crate) fn finish(&mut self, total_duration: Duration) {
+        self.total_duration = total_duration;
+        if self.games_completed > 0 {
+            self.avg_game_duration = Duration::from_secs_f64(total_duration.as_secs_f64() / self.games_completed as f64);
+        }
+        let secs = total_duration.as_secs_f64();
+        self.throughput = if secs > 0.0 { self.games_completed as f64 / secs } else { 0.0 };
+    }
+
+    /// Returns the number of wins for the given player
+    pub fn wins(&self, player: Player) -> usize {
+        match player {
+            Player::X => self.x_wins,
+            Player::O => self.o_wins,
+        }
+    }
+
+    /// Returns the win rate of the given player as a percentage
+    pub fn win_rate(&self, player: Player) -> f64 {
+        self.percentage(self.wins(player))
+    }
+
+    /// Returns the draw rate as a percentage
+    pub fn draw_rate(&self) -> f64 {
+        self.percentage(self.draws)
+    }
+
+    /// Returns a Wilson score interval for the win rate of `player`, as
+    /// percentages
+    ///
+    /// `confidence` is the coverage probability, e.g. `0.95` for a 95%
+    /// interval. The interval stays within `0.0..=100.0` and is `(0.0, 100.0)`
+    /// when no games have been played.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `confidence` is not strictly between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Player;
+    /// use zttt_rs::simulation::SimulationResult;
+    ///
+    /// let mut result = SimulationResult::new();
+    /// result.games_completed = 1_000;
+    /// result.x_wins = 550;
+    /// result.o_wins = 450;
+    ///
+    /// let (low, high) = result.win_rate_ci(Player::X, 0.95);
+    /// assert!(low > 51.0 && high < 58.2);
+    /// ```
+    pub fn win_rate_ci(&self, player: Player, confidence: f64) -> (f64, f64) {
+        let z = z_score(confidence, 2.0);
+        let n = self.games_completed as f64;
+        if self.games_completed == 0 {
+            return (0.0, 100.0);
+        }
+        let p = self.wins(player) as f64 / n;
+        let denominator = 1.0 + z * z / n;
+        let center = (p + z * z / (2.0 * n)) / denominator;
+        let margin = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+        (((center - margin) * 100.0).max(0.0), ((center + margin) * 100.0).min(100.0))
+    }
+
+    /// Returns true if `player` wins significantly more often here than in `other`
+    ///
+    /// Uses a one-sided two-proportion z-test at the given `confidence`
+    /// (e.g. `0.95`). Returns false if either result has no games.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `confidence` is not strictly between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::backend::Player;
+    /// use zttt_rs::simulation::SimulationResult;
+    ///
+    /// let with_wins = |games, x_wins| {
+    ///     let mut result = SimulationResult::new();
+    ///     result.games_completed = games;
+    ///     result.x_wins = x_wins;
+    ///     result.draws = games - x_wins;
+    ///     result
+    /// };
+    ///
+    /// // 55% vs 50% is noise over 100 games but not over 10,000
+    /// assert!(!with_wins(100, 55).is_significantly_better_than(&with_wins(100, 50), Player::X, 0.95));
+    /// assert!(with_wins(10_000, 5_500).is_significantly_better_than(&with_wins(10_000, 5_000), Player::X, 0.95));
+    /// ```
+    pub fn is_significantly_better_than(&self, other: &SimulationResult, player: Player, confidence: f64) -> bool {
+        let z_critical = z_score(confidence, 1.0);
+        if self.games_completed == 0 || other.games_completed == 0 {
+            return false;
+        }
+        let (n1, n2) = (self.games_completed as f64, other.games_completed as f64);
+        let (w1, w2) = (self.wins(player) as f64, other.wins(player) as f64);
+        let pooled = (w1 + w2) / (n1 + n2);
+        let error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+        let difference = w1 / n1 - w2 / n2;
+        if error == 0.0 {
+            return difference > 0.0;
+        }
+        difference / error > z_critical
+    }
+
+    /// Adds the results of a run that happened after this one
+    ///
+    /// Durations add up, as for chunks run one after another on a single
+    /// thread. This is what `+` and `+=` do.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig, SimulationResult};
+    ///
+    /// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(500).build());
+    /// let total: SimulationResult = (0..4).map(|_| simulator.run_sequential()).sum();
+    /// assert_eq!(total.games_completed, 2_000);
+    /// ```
+    pub fn merge(&mut self, other: &SimulationResult) {
+        let wall = self.total_duration + other.total_duration;
+        self.combine(other, wall);
+    }
+
+    /// Adds the results of a run that happened at the same time as this one
+    ///
+    /// Use this for runs on separate threads or machines: the total duration
+    /// is the longer of the two, so throughput reflects wall-clock time,
+    /// while the average game duration is still per game.
+    pub fn merge_concurrent(&mut self, other: &SimulationResult) {
+        let wall = self.total_duration.max(other.total_duration);
+        self.combine(other, wall);
+    }
+
+    /// Replaces the total duration with a measured wall-clock time
+    pub(crate) fn set_wall_time(&mut self, wall: Duration) {
+        self.total_duration = wall;
+        let secs = wall.as_secs_f64();
+        self.throughput = if secs > 0.0 { self.games_completed as f64 / secs } else { 0.0 };
+    }
+
+    fn combine(&mut self, other: &SimulationResult, wall: Duration) {
+        let games = self.games_completed + other.games_completed;
+        let game_time = self.avg_game_duration.as_secs_f64() * self.games_completed as f64
+            + other.avg_game_duration.as_secs_f64() * other.games_completed as f64;
+
+        self.games_completed = games;
+        self.x_wins += other.x_wins;
+        self.o_wins += other.o_wins;
+        self.draws += other.draws;
+        self.forfeits += other.forfeits;
+        self.skipped_games += other.skipped_games;
+        self.records.extend_from_slice(&other.records);
+        self.record_games.extend_from_slice(&other.record_games);
+        if self.manifest.is_none() {
+            self.manifest.clone_from(&other.manifest);
+        }
+        self.total_duration = wall;
+        self.avg_game_duration = if games > 0 {
+            Duration::from_secs_f64(game_time / games as f64)
+        } else {
+            Duration::ZERO
+        };
+        let secs = wall.as_secs_f64();
+        self.throughput = if secs > 0.0 { games as f64 / secs } else { 0.0 };
+    }
+
+    fn percentage(&self, count: usize) -> f64 {
+        if self.games_completed == 0 {
+            0.0
+        } else {
+            count as f64 / self.games_completed as f64 * 100.0
+        }
+    }
+}
+
+impl Default for SimulationResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SimulationResult {
+    /// Writes a boxed summary of outcomes and timing, followed by the
+    /// manifest if there is one
+    ///
+    /// Forfeits and skipped games are listed only when there were any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{SimulationConfig, Simulator};
+    /// use zttt_rs::backend::FastEngine;
+    ///
+    /// let config = SimulationConfig::builder().num_games(100).engine(FastEngine).build();
+    /// let report = Simulator::new(config).run_sequential().to_string();
+    /// assert!(report.starts_with("┌"));
+    /// assert!(report.lines().any(|line| line.starts_with("│ X wins") && line.contains(" 100 (100.00%)")));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = |count: usize| format!("{} ({:.2}%)", count, self.percentage(count));
+        let mut rows = vec![
+            ("Games", self.games_completed.to_string()),
+            ("X wins", outcome(self.x_wins)),
+            ("O wins", outcome(self.o_wins)),
+            ("Draws", outcome(self.draws)),
+        ];
+        if self.forfeits > 0 {
+            rows.push(("Forfeits", self.forfeits.to_string()));
+        }
+        if self.skipped_games > 0 {
+            rows.push(("Skipped", self.skipped_games.to_string()));
+        }
+        rows.push(("Duration", format!("{:.3?}", self.total_duration)));
+        rows.push(("Per game", format!("{:.1?}", self.avg_game_duration)));
+        rows.push(("Throughput", format!("{:.0} games/s", self.throughput)));
+
+        let title = "Simulation results";
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let value_width = rows.iter().map(|(_, value)| value.chars().count()).max().unwrap_or(0);
+        let width = (label_width + 2 + value_width).max(title.len());
+        let rule = "─".repeat(width + 2);
+
+        writeln!(f, "┌{rule}┐")?;
+        writeln!(f, "│ {title:width$} │")?;
+        writeln!(f, "├{rule}┤")?;
+        for (label, value) in &rows {
+            let value_width = width - label_width - 2;
+            writeln!(f, "│ {label:label_width$}  {value:>value_width$} │")?;
+        }
+        write!(f, "└{rule}┘")?;
+        if let Some(manifest) = &self.manifest {
+            write!(f, "\n{}", manifest)?;
+        }
+        Ok(())
+    }
+}
+
+impl AddAssign<&SimulationResult> for SimulationResult {
+    fn add_assign(&mut self, other: &SimulationResult) {
+        self.merge(other);
+    }
+}
+
+impl AddAssign for SimulationResult {
+    fn add_assign(&mut self, other: SimulationResult) {
+        self.merge(&other);
+    }
+}
+
+impl Add for SimulationResult {
+    type Output = SimulationResult;
+
+    fn add(mut self, other: SimulationResult) -> SimulationResult {
+        self.merge(&other);
+        self
+    }
+}
+
+impl Sum for SimulationResult {
+    fn sum<I: Iterator<Item = SimulationResult>>(iter: I) -> Self {
+        iter.fold(SimulationResult::new(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a SimulationResult> for SimulationResult {
+    fn sum<I: Iterator<Item = &'a SimulationResult>>(iter: I) -> Self {
+        iter.fold(SimulationResult::new(), |mut total, result| {
+            total.merge(result);
+            total
+        })
+    }
+}
+
+/// Returns the standard normal critical value for a one- (`tails = 1.0`) or
+/// two-sided (`tails = 2.0`) test at `confidence`
+fn z_score(confidence: f64, tails: f64) -> f64 {
+    assert!(confidence > 0.0 && confidence < 1.0, "confidence must be between 0 and 1");
+    normal_quantile(1.0 - (1.0 - confidence) / tails)
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation,
+/// relative error below 1.2e-9)
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
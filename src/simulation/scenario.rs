@@ -0,0 +1,473 @@
+//! Suites of starting positions for engine regression tests
+//!
+//! A position file lists one position per line, with optional annotations:
+//!
+//! ```text
+//! # X can win by completing the diagonal
+//! X.O/OX./... X 1-0   # take the diagonal
+//! XO./.X./..O         # X to move, no expected result
+//! .../.O./X.. X 1/2-1/2
+//! ```
+//!
+//! Each line holds the board in the [`Board`] `FromStr` format (without
+//! spaces), then optionally the side to move (`X` or `O`), the expected
+//! result (`1-0` if X wins, `0-1` if O wins, `1/2-1/2` for a draw), and a
+//! `#` comment naming the case. The side to move defaults to the one
+//! implied by the piece counts with X moving first. Blank lines and lines
+//! starting with `#` are skipped.
+//!
+//! A [`ScenarioRunner`] plays every case of a suite with your engines and
+//! reports which cases ended with their expected result, so a change that
+//! weakens an engine shows up as a failing case.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use crate::backend::{Board, BoxedEngine, GameResult, Player, Rng, StatefulEngine};
+
+/// A starting position, the side to move, and the result expected from it
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{GameResult, Player};
+/// use zttt_rs::simulation::ScenarioCase;
+///
+/// let case = ScenarioCase::new("XX./OO./...".parse().unwrap(), Player::X)
+///     .unwrap()
+///     .expect(GameResult::Win(Player::X))
+///     .named("complete the top row");
+/// assert_eq!(case.board.valid_moves().len(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioCase {
+    /// Label for reports: the line's comment, or `line N` if it has none
+    pub name: String,
+    pub board: Board,
+    pub to_move: Player,
+    /// Result the game must end with, if any
+    pub expected: Option<GameResult>,
+}
+
+impl ScenarioCase {
+    /// Creates an unnamed case with no expected result
+    ///
+    /// Returns an error if the game is already over or `to_move` cannot be
+    /// the side to move given the piece counts.
+    pub fn new(board: Board, to_move: Player) -> Result<Self, &'static str> {
+        if board.game_result() != GameResult::InProgress {
+            return Err("Game is already over");
+        }
+        // The side to move has as many pieces as the other, or one fewer
+        let mover = board.count_pieces(to_move);
+        let other = board.count_pieces(to_move.opponent());
+        if mover != other && mover + 1 != other {
+            return Err("Side to move does not match the piece counts");
+        }
+        Ok(ScenarioCase {
+            name: String::new(),
+            board,
+            to_move,
+            expected: None,
+        })
+    }
+
+    /// Sets the result the game must end with
+    pub fn expect(mut self, result: GameResult) -> Self {
+        self.expected = Some(result);
+        self
+    }
+
+    /// Sets the case's label
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+/// An ordered collection of [`ScenarioCase`]s, usually loaded from a
+/// position file
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{GameResult, Player};
+/// use zttt_rs::simulation::ScenarioSuite;
+///
+/// let suite: ScenarioSuite = "
+///     .../.../...          # empty board
+///     X.O/OX./... X 1-0    # take the diagonal
+/// "
+/// .parse()
+/// .unwrap();
+/// assert_eq!(suite.len(), 2);
+/// assert_eq!(suite.cases()[1].expected, Some(GameResult::Win(Player::X)));
+/// assert_eq!(suite.cases()[1].name, "take the diagonal");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScenarioSuite {
+    cases: Vec<ScenarioCase>,
+}
+
+/// Why a position file could not be loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScenarioParseError {
+    /// Line of the file, starting at 1, or 0 if the file could not be read
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ScenarioParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "line {}: {}", self.line, self.reason)
+        }
+    }
+}
+
+impl std::error::Error for ScenarioParseError {}
+
+impl ScenarioSuite {
+    /// Creates an empty suite
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a position file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ScenarioParseError> {
+        let text = std::fs::read_to_string(path).map_err(|_| ScenarioParseError {
+            line: 0,
+            reason: "Could not read position file",
+        })?;
+        text.parse()
+    }
+
+    /// Appends a case
+    pub fn push(&mut self, case: ScenarioCase) {
+        self.cases.push(case);
+    }
+
+    /// Returns the cases in file order
+    pub fn cases(&self) -> &[ScenarioCase] {
+        &self.cases
+    }
+
+    /// Returns the number of cases
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    /// Returns true if the suite has no cases
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+}
+
+impl FromStr for ScenarioSuite {
+    type Err = ScenarioParseError;
+
+    /// Parses the contents of a position file
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut suite = ScenarioSuite::new();
+        for (index, line) in s.lines().enumerate() {
+            let (position, comment) = line.split_once('#').unwrap_or((line, ""));
+            if position.trim().is_empty() {
+                continue;
+            }
+            let case = parse_case(position).map_err(|reason| ScenarioParseError { line: index + 1, reason })?;
+            let name = match comment.trim() {
+                "" => format!("line {}", index + 1),
+                comment => comment.to_string(),
+            };
+            suite.push(case.named(name));
+        }
+        Ok(suite)
+    }
+}
+
+/// Parses a line without its comment
+fn parse_case(position: &str) -> Result<ScenarioCase, &'static str> {
+    let mut tokens = position.split_whitespace();
+    let board: Board = tokens.next().ok_or("Missing board")?.parse()?;
+    let mut to_move = None;
+    let mut expected = None;
+    for token in tokens {
+        match token {
+            "X" if to_move.is_none() && expected.is_none() => to_move = Some(Player::X),
+            "O" if to_move.is_none() && expected.is_none() => to_move = Some(Player::O),
+            "1-0" if expected.is_none() => expected = Some(GameResult::Win(Player::X)),
+            "0-1" if expected.is_none() => expected = Some(GameResult::Win(Player::O)),
+            "1/2-1/2" if expected.is_none() => expected = Some(GameResult::Draw),
+            _ => return Err("Expected the side to move, then a result: 1-0, 0-1, or 1/2-1/2"),
+        }
+    }
+    let to_move = to_move
+        .or_else(|| board.current_player())
+        .ok_or("Cannot tell whose move it is; add X or O after the board")?;
+    let case = ScenarioCase::new(board, to_move)?;
+    Ok(match expected {
+        Some(result) => case.expect(result),
+        None => case,
+    })
+}
+
+/// Plays every case of a [`ScenarioSuite`] and checks the results
+///
+/// With one engine, it plays both sides. With two, the engine added first
+/// plays the side to move in every case and the second plays its opponent,
+/// so expectations such as "X must win" test the first engine against the
+/// second.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine};
+/// use zttt_rs::simulation::{ScenarioRunner, ScenarioSuite};
+///
+/// let suite: ScenarioSuite = "
+///     X.O/OX./... X 1-0    # take the diagonal
+///     XX./OO./... O 0-1    # win instead of blocking
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// let mut runner = ScenarioRunner::builder()
+///     .add_engine("minimax", MinimaxEngine::new())
+///     .add_engine("fast", FastEngine)
+///     .build()
+///     .unwrap();
+/// let report = runner.run(&suite);
+/// assert!(report.all_passed());
+/// println!("{}", report);
+/// ```
+pub struct ScenarioRunner {
+    names: Vec<String>,
+    engines: Vec<BoxedEngine>,
+    games_per_case: usize,
+    seed: Option<u64>,
+}
+
+/// Builder for [`ScenarioRunner`]
+pub struct ScenarioRunnerBuilder {
+    runner: ScenarioRunner,
+}
+
+/// Results of one [`ScenarioCase`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioOutcome {
+    pub name: String,
+    /// Result the case expected, if any
+    pub expected: Option<GameResult>,
+    /// Result of each game played from the position
+    pub results: Vec<GameResult>,
+}
+
+/// Outcomes of a [`ScenarioRunner`] run, in suite order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioReport {
+    pub outcomes: Vec<ScenarioOutcome>,
+    pub total_duration: Duration,
+}
+
+impl ScenarioRunner {
+    /// Creates a builder with no engines
+    ///
+    /// Defaults: one game per case, no seed.
+    pub fn builder() -> ScenarioRunnerBuilder {
+        ScenarioRunnerBuilder {
+            runner: ScenarioRunner {
+                names: Vec::new(),
+                engines: Vec::new(),
+                games_per_case: 1,
+                seed: None,
+            },
+        }
+    }
+
+    /// Returns the engine names in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Plays every case of `suite`
+    ///
+    /// With a seed, engines are reseeded before each game, numbering games
+    /// across the whole suite, so runs are reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    pub fn run(&mut self, suite: &ScenarioSuite) -> ScenarioReport {
+        let start = Instant::now();
+        let mut outcomes = Vec::with_capacity(suite.len());
+        for (index, case) in suite.cases().iter().enumerate() {
+            let results = (0..self.games_per_case)
+                .map(|game| self.play(case, index * self.games_per_case + game))
+                .collect();
+            outcomes.push(ScenarioOutcome {
+                name: case.name.clone(),
+                expected: case.expected,
+                results,
+            });
+        }
+        ScenarioReport {
+            outcomes,
+            total_duration: start.elapsed(),
+        }
+    }
+
+    /// Plays game number `game` from the case's position
+    fn play(&mut self, case: &ScenarioCase, game: usize) -> GameResult {
+        if let Some(seed) = self.seed {
+            let game_seed = Rng::stream(seed, game as u64).next_u64();
+            self.engines[0].reseed(game_seed);
+            if let Some(opponent) = self.engines.get_mut(1) {
+                opponent.reseed(Rng::stream(game_seed, 1).next_u64());
+            }
+        }
+        let mut board = case.board.clone();
+        let mut player = case.to_move;
+        while board.game_result() == GameResult::InProgress {
+            let engine = if player == case.to_move { 0 } else { self.engines.len() - 1 };
+            let (row, col) = self.engines[engine]
+                .choose_move_mut(&board, player)
+                .expect("engine returned no move for a game in progress");
+            board.make_move(row, col, player).expect("engine returned an invalid move");
+            player = player.opponent();
+        }
+        board.game_result()
+    }
+}
+
+impl ScenarioRunnerBuilder {
+    /// Adds an engine under `name`
+    pub fn add_engine(self, name: &str, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.add_boxed(name, Box::new(engine))
+    }
+
+    /// Adds an engine under its [`StatefulEngine::name`]
+    pub fn add_by_info(self, engine: impl StatefulEngine + Send + 'static) -> Self {
+        let name = engine.name().to_string();
+        self.add_engine(&name, engine)
+    }
+
+    /// Adds a boxed engine, e.g. one built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry)
+    pub fn add_boxed(mut self, name: &str, engine: BoxedEngine) -> Self {
+        self.runner.names.push(name.to_string());
+        self.runner.engines.push(engine);
+        self
+    }
+
+    /// Sets the number of games played from each position
+    ///
+    /// More than one game is only useful with engines that choose randomly;
+    /// a case passes only if every game ends as expected.
+    pub fn games_per_case(mut self, games: usize) -> Self {
+        self.runner.games_per_case = games;
+        self
+    }
+
+    /// Sets the seed for reproducible engine randomness
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.runner.seed = Some(seed);
+        self
+    }
+
+    /// Builds the runner
+    ///
+    /// Returns an error unless one or two engines were added, or if
+    /// `games_per_case` is zero.
+    pub fn build(self) -> Result<ScenarioRunner, &'static str> {
+        if !(1..=2).contains(&self.runner.engines.len()) {
+            return Err("Scenario runner needs one or two engines");
+        }
+        if self.runner.games_per_case == 0 {
+            return Err("Scenario runner must play at least one game per case");
+        }
+        Ok(self.runner)
+    }
+}
+
+impl ScenarioOutcome {
+    /// Returns true if every game ended with the expected result, or the case
+    /// expected none
+    pub fn passed(&self) -> bool {
+        match self.expected {
+            Some(expected) => self.results.iter().all(|&result| result == expected),
+            None => true,
+        }
+    }
+
+    /// Returns the number of games that did not end with the expected result
+    pub fn misses(&self) -> usize {
+        match self.expected {
+            Some(expected) => self.results.iter().filter(|&&result| result != expected).count(),
+            None => 0,
+        }
+    }
+}
+
+impl ScenarioReport {
+    /// Returns the number of cases that passed
+    pub fn passed(&self) -> usize {
+        self.outcomes.iter().filter(|outcome| outcome.passed()).count()
+    }
+
+    /// Returns the number of cases that failed
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.passed()
+    }
+
+    /// Returns true if no case failed
+    pub fn all_passed(&self) -> bool {
+        self.outcomes.iter().all(ScenarioOutcome::passed)
+    }
+
+    /// Returns the cases that failed
+    pub fn failures(&self) -> impl Iterator<Item = &ScenarioOutcome> + '_ {
+        self.outcomes.iter().filter(|outcome| !outcome.passed())
+    }
+}
+
+/// Writes a result as in position files and game notation
+fn result_token(result: GameResult) -> &'static str {
+    match result {
+        GameResult::Win(Player::X) => "1-0",
+        GameResult::Win(Player::O) => "0-1",
+        GameResult::Draw => "1/2-1/2",
+        GameResult::InProgress => "*",
+    }
+}
+
+impl fmt::Display for ScenarioOutcome {
+    /// Writes e.g. `FAIL take the diagonal: expected 1-0, 2 of 10 games missed`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", if self.passed() { "PASS" } else { "FAIL" }, self.name)?;
+        match self.expected {
+            Some(expected) if self.misses() > 0 => write!(
+                f,
+                ": expected {}, {} of {} games missed",
+                result_token(expected),
+                self.misses(),
+                self.results.len()
+            ),
+            Some(expected) => write!(f, ": {}", result_token(expected)),
+            None => write!(f, ": no expected result"),
+        }
+    }
+}
+
+impl fmt::Display for ScenarioReport {
+    /// Writes one line per case, then e.g. `9 of 10 cases passed`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outcome in &self.outcomes {
+            writeln!(f, "{}", outcome)?;
+        }
+        write!(f, "{} of {} cases passed", self.passed(), self.outcomes.len())
+    }
+}
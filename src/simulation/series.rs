@@ -0,0 +1,249 @@
+//! Best-of-N match series between two engines
+//!
+//! A [`Match`] plays a fixed number of games between two engines, swapping
+//! colors after every game, and keeps the record of each game. The series can
+//! optionally stop as soon as the trailing engine can no longer catch up.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::backend::{BoxedEngine, GameRecord, GameResult, Player, Rules, StatefulEngine};
+use crate::simulation::duel::play_series;
+use crate::simulation::tournament::MatchupRecord;
+
+/// A series of games between two named engines
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{Match, MatchVerdict};
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine};
+///
+/// let mut series = Match::builder()
+///     .add_engine("minimax", MinimaxEngine::new())
+///     .add_engine("fast", FastEngine)
+///     .best_of(7)
+///     .build()
+///     .unwrap();
+///
+/// let result = series.run();
+/// assert_eq!(result.verdict, MatchVerdict::Won("minimax".to_string()));
+/// assert_eq!(result.records.len(), 7);
+/// assert_eq!(result.records[0].tag("X"), Some("minimax"));
+/// assert_eq!(result.records[1].tag("X"), Some("fast"));
+/// ```
+pub struct Match {
+    names: Vec<String>,
+    engines: Vec<BoxedEngine>,
+    games: usize,
+    stop_when_decided: bool,
+    rules: Rules,
+    seed: Option<u64>,
+}
+
+/// Builder for [`Match`]
+pub struct MatchBuilder {
+    series: Match,
+}
+
+/// Outcome of a [`Match`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchVerdict {
+    /// The named engine scored more points
+    Won(String),
+    /// Both engines scored the same number of points
+    Tied,
+}
+
+/// Games and verdict of a [`Match`] run
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchResult {
+    /// Engine names, the first of which played X in the first game
+    pub engines: [String; 2],
+    /// Number of games the series was scheduled for
+    pub games_scheduled: usize,
+    /// Every game played, in order, tagged with the engines' names under `X`
+    /// and `O`
+    pub records: Vec<GameRecord>,
+    /// The first engine's wins, draws, and losses
+    pub score: MatchupRecord,
+    pub verdict: MatchVerdict,
+    pub total_duration: Duration,
+}
+
+impl Match {
+    /// Creates a builder with no engines
+    ///
+    /// Defaults: best of 10 games, played to the end, standard rules, no seed.
+    pub fn builder() -> MatchBuilder {
+        MatchBuilder {
+            series: Match {
+                names: Vec::new(),
+                engines: Vec::new(),
+                games: 10,
+                stop_when_decided: false,
+                rules: Rules::Standard,
+                seed: None,
+            },
+        }
+    }
+
+    /// Returns the engine names in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Plays the series
+    ///
+    /// The engine added first plays X in the first game, and the engines swap
+    /// colors after every game. With a seed, engines are reseeded before each
+    /// game as in a seeded [`Simulator`](crate::simulation::Simulator) run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    pub fn run(&mut self) -> MatchResult {
+        let start = Instant::now();
+        let games = self.games;
+        let stop_when_decided = self.stop_when_decided;
+        let names = &self.names;
+        let mut records = Vec::with_capacity(games);
+        let mut score = MatchupRecord::default();
+
+        play_series(&mut self.engines, 0, 1, games, self.rules, self.seed, 0, |x, o, mut record| {
+            match record.result.winner() {
+                Some(winner) if (winner == Player::X) == (x == 0) => score.wins += 1,
+                Some(_) => score.losses += 1,
+                None => score.draws += 1,
+            }
+            record.tags = vec![("X".to_string(), names[x].clone()), ("O".to_string(), names[o].clone())];
+            records.push(record);
+            !(stop_when_decided && is_decided(score, games))
+        });
+
+        let (first, second) = points(score);
+        let verdict = if first > second {
+            MatchVerdict::Won(self.names[0].clone())
+        } else if second > first {
+            MatchVerdict::Won(self.names[1].clone())
+        } else {
+            MatchVerdict::Tied
+        };
+
+        MatchResult {
+            engines: [self.names[0].clone(), self.names[1].clone()],
+            games_scheduled: games,
+            records,
+            score,
+            verdict,
+            total_duration: start.elapsed(),
+        }
+    }
+}
+
+/// Returns true if neither engine can change the verdict in the remaining games
+fn is_decided(score: MatchupRecord, games: usize) -> bool {
+    let remaining = (games - score.games()) as f64;
+    let (first, second) = points(score);
+    (first - second).abs() > remaining
+}
+
+/// Returns the points of both engines given the first engine's score
+fn points(score: MatchupRecord) -> (f64, f64) {
+    (score.points(), score.losses as f64 + score.draws as f64 / 2.0)
+}
+
+impl MatchBuilder {
+    /// Adds an engine under `name`
+    pub fn add_engine(self, name: &str, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.add_boxed(name, Box::new(engine))
+    }
+
+    /// Adds an engine under its [`StatefulEngine::name`]
+    ///
+    /// Engines of the same type get the same name, so give them distinct
+    /// names with [`add_engine`](Self::add_engine) instead.
+    pub fn add_by_info(self, engine: impl StatefulEngine + Send + 'static) -> Self {
+        let name = engine.name().to_string();
+        self.add_engine(&name, engine)
+    }
+
+    /// Adds a boxed engine, e.g. one built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry)
+    pub fn add_boxed(mut self, name: &str, engine: BoxedEngine) -> Self {
+        self.series.names.push(name.to_string());
+        self.series.engines.push(engine);
+        self
+    }
+
+    /// Sets the number of games in the series
+    pub fn best_of(mut self, games: usize) -> Self {
+        self.series.games = games;
+        self
+    }
+
+    /// Ends the series once the trailing engine can no longer catch up
+    pub fn stop_when_decided(mut self, stop: bool) -> Self {
+        self.series.stop_when_decided = stop;
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.series.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible engine randomness
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.series.seed = Some(seed);
+        self
+    }
+
+    /// Builds the match
+    ///
+    /// Returns an error unless exactly two engines were added.
+    pub fn build(self) -> Result<Match, &'static str> {
+        if self.series.engines.len() != 2 {
+            return Err("Match needs exactly two engines");
+        }
+        Ok(self.series)
+    }
+}
+
+impl MatchResult {
+    /// Returns the points of both engines, one per win and half per draw
+    pub fn points(&self) -> (f64, f64) {
+        points(self.score)
+    }
+
+    /// Returns the winning engine's name, or `None` if the match was tied
+    pub fn winner(&self) -> Option<&str> {
+        match &self.verdict {
+            MatchVerdict::Won(name) => Some(name),
+            MatchVerdict::Tied => None,
+        }
+    }
+
+    /// Returns true if the series ended before all scheduled games were played
+    pub fn decided_early(&self) -> bool {
+        self.records.len() < self.games_scheduled
+    }
+
+    /// Returns the result of every game, in order
+    pub fn results(&self) -> impl Iterator<Item = GameResult> + '_ {
+        self.records.iter().map(|record| record.result)
+    }
+}
+
+impl fmt::Display for MatchResult {
+    /// Writes e.g. `minimax 6.5 - 0.5 fast (6-1-0), minimax wins`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.points();
+        write!(f, "{} {} - {} {} ({})", self.engines[0], first, second, self.engines[1], self.score)?;
+        match &self.verdict {
+            MatchVerdict::Won(name) => write!(f, ", {} wins", name),
+            MatchVerdict::Tied => write!(f, ", tied"),
+        }
+    }
+}
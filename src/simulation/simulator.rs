@@ -0,0 +1,671 @@
+//! Sequential simulation runner
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use crate::backend::{play_out, Board, GameBoard, GameRecord, GameResult, Player, VariantEngine};
+#[cfg(feature = "tokio")]
+use crate::backend::AsyncEngine;
+use crate::simulation::config::{EngineErrorPolicy, RecordPolicy, SimulationConfig, StopTracker};
+use crate::simulation::jsonl::JsonlSink;
+use crate::simulation::manifest::RunManifest;
+#[cfg(feature = "arrow")]
+use crate::simulation::arrow::ParquetSink;
+#[cfg(feature = "zstd")]
+use crate::simulation::archive::ArchiveWriter;
+use crate::simulation::observer::{OnGameEnd, SimulationObserver};
+#[cfg(feature = "indicatif")]
+use crate::simulation::progress_bar::{bar_callback, BAR_INTERVAL};
+use crate::simulation::result::SimulationResult;
+
+/// Games played between clock checks in [`Simulator::run_for`]
+const CLOCK_INTERVAL: usize = 8;
+
+/// Runs batches of games according to a [`SimulationConfig`]
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::{Simulator, SimulationConfig};
+/// use zttt_rs::backend::{FastEngine, Player};
+///
+/// let config = SimulationConfig::builder()
+///     .num_games(1_000)
+///     .engine(FastEngine)
+///     .build();
+///
+/// let result = Simulator::new(config).run_sequential();
+/// assert_eq!(result.games_completed, 1_000);
+/// println!("Win rate: {:.2}%", result.win_rate(Player::X));
+/// ```
+pub struct Simulator {
+    config: SimulationConfig,
+    progress: Option<Progress>,
+}
+
+/// Callback receiving `(completed, total, partial)` progress reports
+pub(crate) type ProgressCallback = Box<dyn FnMut(usize, usize, &SimulationResult) + Send>;
+
+/// Progress callback and how often it is called
+struct Progress {
+    every: usize,
+    callback: ProgressCallback,
+    /// Completed and skipped games at the last call of the current run
+    reported: Option<(usize, usize)>,
+}
+
+impl Simulator {
+    /// Creates a simulator for the given configuration
+    pub fn new(config: SimulationConfig) -> Self {
+        Simulator { config, progress: None }
+    }
+
+    /// Calls `callback(completed, total, partial)` every `every_n_games` games
+    /// and after the last game
+    ///
+    /// `partial` holds the results so far, with timing fields measured up to
+    /// the call. `total` is the configured number of games, or 0 for
+    /// [`run_for`](Self::run_for) where it is not known in advance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    ///
+    /// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(10_000).build())
+    ///     .with_progress(2_500, |completed, total, partial| {
+    ///         println!("{}/{} games, {:.0} games/sec", completed, total, partial.throughput);
+    ///     });
+    /// simulator.run_sequential();
+    /// ```
+    pub fn with_progress(
+        mut self,
+        every_n_games: usize,
+        callback: impl FnMut(usize, usize, &SimulationResult) + Send + 'static,
+    ) -> Self {
+        self.progress = Some(Progress {
+            every: every_n_games.max(1),
+            callback: Box::new(callback),
+            reported: None,
+        });
+        self
+    }
+
+    /// Shows a progress bar with ETA and live throughput on stderr
+    ///
+    /// Replaces any callback set with [`with_progress`](Self::with_progress).
+    /// [`run_for`](Self::run_for) shows a spinner, since its game count is not
+    /// known in advance. Nothing is drawn when stderr is not a terminal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    ///
+    /// let result = Simulator::new(SimulationConfig::builder().num_games(10_000).build())
+    ///     .with_progress_bar()
+    ///     .run_sequential();
+    /// assert_eq!(result.games_completed, 10_000);
+    /// ```
+    #[cfg(feature = "indicatif")]
+    pub fn with_progress_bar(self) -> Self {
+        self.with_progress(BAR_INTERVAL, bar_callback())
+    }
+
+    /// Returns the configuration
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Runs all games on the current thread
+    pub fn run_sequential(&mut self) -> SimulationResult {
+        self.run_with_callback(|_| {})
+    }
+
+    /// Runs all games on the current thread, calling `callback` after each game
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress, unless the configured [`EngineErrorPolicy`] says
+    /// otherwise.
+    pub fn run_with_callback(&mut self, callback: impl FnMut(GameResult)) -> SimulationResult {
+        self.run_games(0..self.config.num_games, &mut OnGameEnd(callback))
+    }
+
+    /// Runs all games on the current thread, reporting each game's start,
+    /// moves, and end to `observer`
+    ///
+    /// See [`SimulationObserver`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress, unless the configured [`EngineErrorPolicy`] says
+    /// otherwise.
+    pub fn run_with_observer<O: SimulationObserver + ?Sized>(&mut self, observer: &mut O) -> SimulationResult {
+        self.run_games(0..self.config.num_games, observer)
+    }
+
+    /// Plays the games numbered `games`, reporting them to `observer`
+    fn run_games<O: SimulationObserver + ?Sized>(&mut self, games: Range<usize>, observer: &mut O) -> SimulationResult {
+        self.run_games_until(games, observer, || false)
+    }
+
+    /// Plays the games numbered `games`, reporting them to `observer`, and
+    /// stops early once `cancelled` returns true after a game
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "simulation", skip_all, fields(first = games.start, games = games.len())))]
+    pub(crate) fn run_games_until<O: SimulationObserver + ?Sized>(
+        &mut self,
+        games: Range<usize>,
+        observer: &mut O,
+        cancelled: impl Fn() -> bool,
+    ) -> SimulationResult {
+        let mut result = self.new_result();
+        let start = Instant::now();
+
+        let mut record = self.new_record();
+        let mut stop = StopTracker::default();
+        let total = games.len();
+        for game in games {
+            let outcome = self.play_counted(game, self.config.num_games, &mut record, &mut result, observer);
+            self.report_progress(&mut result, start, total);
+            if stop.should_stop(&self.config, outcome, &result) || cancelled() {
+                break;
+            }
+        }
+        self.report_final(&mut result, start, total);
+
+        result.trim_records(self.config.record_policy, self.config.record_salt());
+        result.finish(start.elapsed());
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            games = result.games_completed,
+            x_wins = result.x_wins,
+            o_wins = result.o_wins,
+            draws = result.draws,
+            throughput = result.throughput,
+            "simulation finished"
+        );
+        result
+    }
+
+    /// Plays as many games as fit in a wall-clock budget, ignoring `num_games`
+    ///
+    /// The clock is checked every few games, so the run may overshoot the
+    /// budget by the duration of a handful of games.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress, unless the configured [`EngineErrorPolicy`] says
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    ///
+    /// let mut simulator = Simulator::new(SimulationConfig::default());
+    /// let result = simulator.run_for(Duration::from_millis(20));
+    /// assert!(result.games_completed > 0);
+    /// println!("{:.0} games/sec", result.throughput);
+    /// ```
+    pub fn run_for(&mut self, budget: Duration) -> SimulationResult {
+        let mut result = self.new_result();
+        let start = Instant::now();
+        let mut record = self.new_record();
+        let mut stop = StopTracker::default();
+        // Counts skipped games too, so each attempt gets its own seed and starter
+        let mut game = 0;
+
+        'run: while start.elapsed() < budget {
+            for _ in 0..CLOCK_INTERVAL {
+                let outcome = self.play_counted(game, 0, &mut record, &mut result, &mut ());
+                game += 1;
+                self.report_progress(&mut result, start, 0);
+                if stop.should_stop(&self.config, outcome, &result) {
+                    break 'run;
+                }
+            }
+        }
+        self.report_final(&mut result, start, 0);
+
+        result.trim_records(self.config.record_policy, self.config.record_salt());
+        result.finish(start.elapsed());
+        result
+    }
+
+    /// Runs all games on the current thread, passing a [`GameRecord`] of each
+    /// game to `callback`
+    ///
+    /// Recording the moves makes this slightly slower than
+    /// [`run_with_callback`](Self::run_with_callback).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    ///
+    /// let mut longest = 0;
+    /// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(100).build());
+    /// simulator.run_with_records(|record| longest = longest.max(record.moves.len()));
+    /// assert!(longest >= 5);
+    /// ```
+    pub fn run_with_records(&mut self, mut callback: impl FnMut(&GameRecord)) -> SimulationResult {
+        self.run_records(|record| {
+            callback(record);
+            true
+        })
+    }
+
+    /// Runs all games, writing each one to `writer` as a line of JSON
+    ///
+    /// See [`JsonlSink`] for the line format. With a manifest configured, it
+    /// is written as the first line. The run stops at the first write error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    ///
+    /// let mut output = Vec::new();
+    /// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(10).build());
+    /// let result = simulator.run_jsonl(&mut output).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap().lines().count(), result.games_completed);
+    /// ```
+    pub fn run_jsonl<W: Write>(&mut self, writer: W) -> io::Result<SimulationResult> {
+        let mut sink = JsonlSink::new(writer);
+        if self.config.manifest {
+            sink.write_manifest(&RunManifest::from_config(&self.config));
+        }
+        let mut error = None;
+        let result = self.run_records(|record| match sink.write(record) {
+            Ok(()) => true,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        });
+        match error {
+            Some(e) => Err(e),
+            None => sink.finish().map(|_| result),
+        }
+    }
+
+    /// Runs all games, writing them to a games and a moves Parquet file
+    /// (requires the `arrow` feature)
+    ///
+    /// See the [`arrow`](crate::simulation::arrow) module for the table
+    /// layouts. With a manifest configured, it is stored in both files'
+    /// key-value metadata. The run stops at the first write error.
+    #[cfg(feature = "arrow")]
+    pub fn run_parquet<G, M>(&mut self, games: G, moves: M) -> Result<SimulationResult, parquet::errors::ParquetError>
+    where
+        G: Write + Send,
+        M: Write + Send,
+    {
+        let mut sink = ParquetSink::new(games, moves)?;
+        if self.config.manifest {
+            sink.write_manifest(&RunManifest::from_config(&self.config));
+        }
+        let mut error = None;
+        let result = self.run_records(|record| match sink.write(record) {
+            Ok(()) => true,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        });
+        match error {
+            Some(e) => Err(e),
+            None => sink.finish().map(|_| result),
+        }
+    }
+
+    /// Runs all games, writing them to a compressed `.zttt` archive (requires
+    /// the `zstd` feature)
+    ///
+    /// See the [`archive`](crate::simulation::archive) module for the format.
+    /// With a manifest configured, it is stored in the archive. The run stops
+    /// at the first write error.
+    #[cfg(feature = "zstd")]
+    pub fn run_archive<W: Write>(&mut self, writer: W) -> io::Result<SimulationResult> {
+        let mut archive = ArchiveWriter::new(writer)?;
+        if let Some(seed) = self.config.seed {
+            archive = archive.master_seed(seed);
+        }
+        if self.config.manifest {
+            archive.write_manifest(&RunManifest::from_config(&self.config));
+        }
+        let mut error = None;
+        let result = self.run_records(|record| match archive.write(record) {
+            Ok(()) => true,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        });
+        match error {
+            Some(e) => Err(e),
+            None => archive.finish().map(|_| result),
+        }
+    }
+
+    /// Plays games with move recording until they run out or `per_game` returns false
+    fn run_records(&mut self, mut per_game: impl FnMut(&GameRecord) -> bool) -> SimulationResult {
+        let mut result = self.new_result();
+        let start = Instant::now();
+        let mut record = self.new_record();
+        let mut stop = StopTracker::default();
+
+        let total = self.config.num_games;
+        for game in 0..total {
+            let played = self.play_recorded(game, &mut record, &mut ());
+            self.count(game, total, played, &record, &mut result);
+            // Skipped games are not passed on, but still count towards stop conditions
+            if record.result != GameResult::InProgress && !per_game(&record) {
+                break;
+            }
+            self.report_progress(&mut result, start, total);
+            if stop.should_stop(&self.config, record.result, &result) {
+                break;
+            }
+        }
+        self.report_final(&mut result, start, total);
+
+        result.trim_records(self.config.record_policy, self.config.record_salt());
+        result.finish(start.elapsed());
+        result
+    }
+
+    /// Runs all games with asynchronous engines (requires the `tokio` feature)
+    ///
+    /// The game count, starting player, and rules come from the configuration;
+    /// its synchronous engines are not used. The task yields to the runtime
+    /// between games so long runs do not starve other tasks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{Simulator, SimulationConfig};
+    /// use zttt_rs::backend::{BlockingEngine, FastEngine};
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let mut simulator = Simulator::new(SimulationConfig::builder().num_games(10).build());
+    /// let mut x = BlockingEngine::new(FastEngine);
+    /// let mut o = BlockingEngine::new(FastEngine);
+    ///
+    /// let result = runtime.block_on(simulator.run_async(&mut x, &mut o));
+    /// assert_eq!(result.games_completed, 10);
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn run_async<X, O>(&mut self, engine_x: &mut X, engine_o: &mut O) -> SimulationResult
+    where
+        X: AsyncEngine,
+        O: AsyncEngine,
+    {
+        let mut result = SimulationResult::new();
+        let start = Instant::now();
+
+        for game in 0..self.config.num_games {
+            let mut board = Board::with_rules(self.config.rules);
+            let mut current = self.config.starting.starting_player(game);
+
+            while board.game_result() == GameResult::InProgress {
+                let chosen = match current {
+                    Player::X => engine_x.choose_move(&board, current).await,
+                    Player::O => engine_o.choose_move(&board, current).await,
+                };
+                let Some((row, col)) = chosen else {
+                    break;
+                };
+                board.make_move(row, col, current).expect("engine returned an invalid move");
+                current = current.opponent();
+            }
+
+            let outcome = board.game_result();
+            assert!(outcome != GameResult::InProgress, "engine returned no move for a game in progress");
+            result.record(outcome);
+            tokio::task::yield_now().await;
+        }
+
+        result.finish(start.elapsed());
+        result
+    }
+
+    /// Plays the games numbered `games`, ignoring the configured count and
+    /// progress callback
+    pub(crate) fn run_chunk(&mut self, games: Range<usize>) -> SimulationResult {
+        let progress = self.progress.take();
+        let result = self.run_games(games, &mut ());
+        self.progress = progress;
+        result
+    }
+
+    /// Calls the progress callback if another `every` games have completed
+    /// since the last call
+    fn report_progress(&mut self, result: &mut SimulationResult, start: Instant, total: usize) {
+        if let Some(progress) = &mut self.progress {
+            let completed = result.games_completed;
+            let last = progress.reported.map(|(completed, _)| completed);
+            if completed.is_multiple_of(progress.every) && last != Some(completed) {
+                progress.reported = Some((completed, result.skipped_games));
+                result.finish(start.elapsed());
+                (progress.callback)(completed, total, result);
+            }
+        }
+    }
+
+    /// Calls the progress callback once a run has ended, however it ended,
+    /// unless the last call already saw the final counts
+    fn report_final(&mut self, result: &mut SimulationResult, start: Instant, total: usize) {
+        if let Some(progress) = &mut self.progress {
+            let completed = result.games_completed;
+            if progress.reported.take() != Some((completed, result.skipped_games)) {
+                result.finish(start.elapsed());
+                (progress.callback)(completed, total, result);
+            }
+        }
+    }
+
+    /// Returns an empty result, carrying a manifest if the configuration
+    /// asks for one
+    fn new_result(&self) -> SimulationResult {
+        let mut result = SimulationResult::new();
+        if self.config.manifest {
+            result.manifest = Some(RunManifest::from_config(&self.config));
+        }
+        result
+    }
+
+    /// Returns an empty record to be filled by [`play_recorded`](Self::play_recorded),
+    /// tagged with the engines' names under `X` and `O`
+    pub(crate) fn new_record(&self) -> GameRecord {
+        GameRecord {
+            rules: self.config.rules,
+            starting_player: self.config.starting_player(),
+            moves: Vec::with_capacity(9),
+            result: GameResult::InProgress,
+            move_durations: Vec::with_capacity(9),
+            seed: None,
+            tags: vec![
+                ("X".to_string(), self.config.engine_name(Player::X).to_string()),
+                ("O".to_string(), self.config.engine_name(Player::O).to_string()),
+            ],
+        }
+    }
+
+    /// Plays game number `game` of `total` and counts it, recording the moves
+    /// in `record` only if the record policy needs them
+    fn play_counted<O: SimulationObserver + ?Sized>(
+        &mut self,
+        game: usize,
+        total: usize,
+        record: &mut GameRecord,
+        result: &mut SimulationResult,
+        observer: &mut O,
+    ) -> GameResult {
+        if self.config.record_policy == RecordPolicy::None {
+            result.record_played(self.play_game(game, None, observer))
+        } else {
+            let played = self.play_recorded(game, record, observer);
+            self.count(game, total, played, record, result);
+            record.result
+        }
+    }
+
+    /// Plays game number `game`, filling `record` with its moves, their
+    /// durations, and the result
+    pub(crate) fn play_recorded<O: SimulationObserver + ?Sized>(
+        &mut self,
+        game: usize,
+        record: &mut GameRecord,
+        observer: &mut O,
+    ) -> Result<GameResult, GameResult> {
+        record.starting_player = self.config.starting.starting_player(game);
+        record.seed = self.config.game_seed(game);
+        record.moves.clear();
+        record.move_durations.clear();
+        let played = self.play_game(game, Some(record), observer);
+        record.result = played.unwrap_or_else(|outcome| outcome);
+        played
+    }
+
+    /// Counts a recorded game, keeping a copy if the record policy selects it
+    ///
+    /// Skipped games are never kept.
+    fn count(
+        &self,
+        game: usize,
+        total: usize,
+        played: Result<GameResult, GameResult>,
+        record: &GameRecord,
+        result: &mut SimulationResult,
+    ) {
+        let outcome = result.record_played(played);
+        if outcome != GameResult::InProgress && self.config.record_policy.keeps(game, total, outcome) {
+            result.keep_record(record, game, self.config.record_policy, self.config.record_salt());
+        }
+    }
+
+    /// Plays game number `game`, appending its moves and how long each engine
+    /// call took to `record` if given
+    ///
+    /// Returns `Err` if an engine misbehaved and the error policy ended the
+    /// game: the forfeit result, or `InProgress` if the game is skipped.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "game", level = "debug", skip(self, record, observer)))]
+    fn play_game<O: SimulationObserver + ?Sized>(
+        &mut self,
+        game: usize,
+        mut record: Option<&mut GameRecord>,
+        observer: &mut O,
+    ) -> Result<GameResult, GameResult> {
+        let mut board = Board::with_rules(self.config.rules);
+        let mut current = self.config.starting.starting_player(game);
+        if let Some(seed) = self.config.engine_seed(game) {
+            self.config.reseed_engines(seed);
+        }
+        observer.on_game_start(&board);
+
+        while board.game_result() == GameResult::InProgress {
+            // Only recorded games pay for the clock reads
+            let started = record.is_some().then(Instant::now);
+            let (row, col) = match self.choose_move(&board, current) {
+                Some((row, col)) if board.make_move(row, col, current).is_ok() => (row, col),
+                chosen => {
+                    let outcome = self.engine_error(chosen, current);
+                    observer.on_game_end(outcome);
+                    return Err(outcome);
+                }
+            };
+            if let (Some(record), Some(started)) = (record.as_deref_mut(), started) {
+                record.moves.push((row, col));
+                record.move_durations.push(started.elapsed());
+            }
+            observer.on_move(&board, (row, col), current);
+            current = current.opponent();
+        }
+
+        let outcome = board.game_result();
+        observer.on_game_end(outcome);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(result = ?outcome, "game finished");
+        Ok(outcome)
+    }
+
+    /// Asks the engine for `player`'s move, treating a panic as no move unless
+    /// the error policy is `Panic`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "engine", level = "trace", skip(self, board), fields(engine = self.config.engine_name(player)))
+    )]
+    fn choose_move(&mut self, board: &Board, player: Player) -> Option<(usize, usize)> {
+        let policy = self.config.error_policy;
+        let engine = self.config.engine_for(player);
+        if policy == EngineErrorPolicy::Panic {
+            return engine.choose_move_mut(board, player);
+        }
+        panic::catch_unwind(AssertUnwindSafe(|| engine.choose_move_mut(board, player))).unwrap_or(None)
+    }
+
+    /// Applies the error policy after `player`'s engine chose `chosen`, which
+    /// was invalid or missing, and returns the game's result
+    fn engine_error(&self, chosen: Option<(usize, usize)>, player: Player) -> GameResult {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(engine = self.config.engine_name(player), %player, ?chosen, policy = ?self.config.error_policy, "engine misbehaved");
+        match self.config.error_policy {
+            EngineErrorPolicy::Panic if chosen.is_some() => panic!("engine returned an invalid move"),
+            EngineErrorPolicy::Panic => panic!("engine returned no move for a game in progress"),
+            EngineErrorPolicy::ForfeitGame => GameResult::Win(player.opponent()),
+            EngineErrorPolicy::SkipGame => GameResult::InProgress,
+        }
+    }
+}
+
+/// Simulates games on any board variant
+///
+/// Each game starts from a clone of `board` and is played by `engine` for
+/// both sides using [`play_out`]. None of the [`SimulationConfig`] policies
+/// apply: there is no error policy, observer, or reseeding, so results are
+/// comparable across board shapes but not necessarily with a [`Simulator`]
+/// run.
+///
+/// # Panics
+///
+/// Panics if the engine returns an invalid move or no move while the game is
+/// still in progress.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::simulate_variant;
+/// use zttt_rs::backend::{BoardN, FastEngine, Player};
+///
+/// let result = simulate_variant(&BoardN::<4>::new(3), &FastEngine, 100, Player::X);
+/// assert_eq!(result.games_completed, 100);
+/// ```
+pub fn simulate_variant<B, E>(board: &B, engine: &E, num_games: usize, starting_player: Player) -> SimulationResult
+where
+    B: GameBoard,
+    E: VariantEngine<B> + ?Sized,
+{
+    let mut result = SimulationResult::new();
+    let start = Instant::now();
+
+    for _ in 0..num_games {
+        let outcome = play_out(board.clone(), engine, engine, starting_player);
+        assert!(outcome != GameResult::InProgress, "engine returned no move for a game in progress");
+        result.record(outcome);
+    }
+
+    result.finish(start.elapsed());
+    result
+}
@@ -0,0 +1,161 @@
+//! Observing a simulation from another thread
+//!
+//! [`Simulator::run_streaming`] moves the simulator to a background thread
+//! and returns a channel of [`SimulationEvent`]s: periodic progress
+//! snapshots, optionally every game's result, and finally the full
+//! [`SimulationResult`]. Dropping the receiver stops the run after the game in
+//! progress.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crate::backend::GameResult;
+use crate::simulation::observer::SimulationObserver;
+use crate::simulation::result::SimulationResult;
+use crate::simulation::simulator::Simulator;
+
+/// Events buffered before the simulation waits for the receiver
+const STREAM_BUFFER: usize = 1024;
+
+/// Counts of a run in progress, sent by [`Simulator::run_streaming`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressSnapshot {
+    pub completed: usize,
+    /// Configured number of games
+    pub total: usize,
+    pub x_wins: usize,
+    pub o_wins: usize,
+    pub draws: usize,
+    /// Time since the run started
+    pub elapsed: Duration,
+    /// Games per second so far
+    pub throughput: f64,
+}
+
+impl ProgressSnapshot {
+    fn new(completed: usize, total: usize, partial: &SimulationResult) -> Self {
+        ProgressSnapshot {
+            completed,
+            total,
+            x_wins: partial.x_wins,
+            o_wins: partial.o_wins,
+            draws: partial.draws,
+            elapsed: partial.total_duration,
+            throughput: partial.throughput,
+        }
+    }
+
+    /// Returns the share of the configured games completed, from 0.0 to 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.completed as f64 / self.total as f64
+    }
+}
+
+/// Message received from a [`Simulator::run_streaming`] run
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationEvent {
+    /// Sent every `every_n_games` games and after the last game
+    Progress(ProgressSnapshot),
+    /// Result of one game, sent only by
+    /// [`run_streaming_games`](Simulator::run_streaming_games)
+    Game(GameResult),
+    /// The run's full result; always the last event of a run that did not
+    /// panic
+    Finished(Box<SimulationResult>),
+}
+
+/// Sends each game's result, noting when the receiver is gone
+struct GameSender {
+    sender: SyncSender<SimulationEvent>,
+    closed: Arc<AtomicBool>,
+}
+
+impl SimulationObserver for GameSender {
+    fn on_game_end(&mut self, result: GameResult) {
+        if self.sender.send(SimulationEvent::Game(result)).is_err() {
+            self.closed.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Simulator {
+    /// Runs all games on a background thread, sending a
+    /// [`SimulationEvent::Progress`] every `every_n_games` games and a
+    /// [`SimulationEvent::Finished`] at the end
+    ///
+    /// Replaces any callback set with
+    /// [`with_progress`](Simulator::with_progress). The run stops early if the
+    /// receiver is dropped. If an engine panics, the channel closes without a
+    /// `Finished` event.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{SimulationConfig, SimulationEvent, Simulator};
+    ///
+    /// let config = SimulationConfig::builder().num_games(10_000).build();
+    /// for event in Simulator::new(config).run_streaming(2_500) {
+    ///     match event {
+    ///         SimulationEvent::Progress(snapshot) => println!("{:.0}% done", snapshot.fraction() * 100.0),
+    ///         SimulationEvent::Finished(result) => assert_eq!(result.games_completed, 10_000),
+    ///         SimulationEvent::Game(_) => unreachable!(),
+    ///     }
+    /// }
+    /// ```
+    pub fn run_streaming(self, every_n_games: usize) -> Receiver<SimulationEvent> {
+        self.stream(every_n_games, false)
+    }
+
+    /// Like [`run_streaming`](Simulator::run_streaming), but also sends a
+    /// [`SimulationEvent::Game`] after every finished game
+    ///
+    /// The channel holds a limited number of events, so a receiver that falls
+    /// behind slows the simulation down instead of letting events pile up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zttt_rs::simulation::{SimulationConfig, SimulationEvent, Simulator};
+    ///
+    /// let config = SimulationConfig::builder().num_games(100).build();
+    /// let games = Simulator::new(config)
+    ///     .run_streaming_games(50)
+    ///     .into_iter()
+    ///     .filter(|event| matches!(event, SimulationEvent::Game(_)))
+    ///     .count();
+    /// assert_eq!(games, 100);
+    /// ```
+    pub fn run_streaming_games(self, every_n_games: usize) -> Receiver<SimulationEvent> {
+        self.stream(every_n_games, true)
+    }
+
+    fn stream(self, every_n_games: usize, per_game: bool) -> Receiver<SimulationEvent> {
+        let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER);
+        let closed = Arc::new(AtomicBool::new(false));
+        let progress = (sender.clone(), Arc::clone(&closed));
+        let mut simulator = self.with_progress(every_n_games, move |completed, total, partial| {
+            let snapshot = ProgressSnapshot::new(completed, total, partial);
+            if progress.0.send(SimulationEvent::Progress(snapshot)).is_err() {
+                progress.1.store(true, Ordering::Relaxed);
+            }
+        });
+
+        thread::spawn(move || {
+            let games = 0..simulator.config().num_games();
+            let cancelled = || closed.load(Ordering::Relaxed);
+            let result = if per_game {
+                let mut observer = GameSender { sender: sender.clone(), closed: Arc::clone(&closed) };
+                simulator.run_games_until(games, &mut observer, cancelled)
+            } else {
+                simulator.run_games_until(games, &mut (), cancelled)
+            };
+            let _ = sender.send(SimulationEvent::Finished(Box::new(result)));
+        });
+        receiver
+    }
+}
@@ -0,0 +1,172 @@
+//! Engine strength estimation against a fixed ladder of opponents
+//!
+//! [`estimate_strength`] plays an engine against three reference opponents
+//! of increasing strength and condenses the results into a normalized score
+//! and an approximate Elo rating, giving a standard answer to "how good is my
+//! engine?" that does not depend on which other engines happen to be around.
+//!
+//! The ladder is:
+//!
+//! | Opponent | Engine | Anchor Elo |
+//! |----------|--------|------------|
+//! | `random` | [`RandomEngine`] | 1000 |
+//! | `heuristic` | [`MinimaxEngine::with_depth`]`(1)` | 1600 |
+//! | `perfect` | [`PerfectLookupEngine`] | 2000 |
+//!
+//! The normalized score is calibrated by running [`PerfectLookupEngine`]
+//! through the same schedule: perfect play scores `1.0`. The random and
+//! heuristic opponents measure close to their anchors; perfect play rates
+//! about 1900, since it can only draw the perfect opponent and cannot beat
+//! the random one every time.
+
+use std::fmt;
+use crate::backend::{BoxedEngine, MinimaxEngine, PerfectLookupEngine, Player, RandomEngine, Rules, StatefulEngine};
+use crate::simulation::duel::play_series;
+use crate::simulation::tournament::MatchupRecord;
+
+/// Games played against each opponent by [`estimate_strength`]
+pub const DEFAULT_GAMES_PER_OPPONENT: usize = 100;
+/// Seed used by [`estimate_strength`]
+const DEFAULT_SEED: u64 = 0x5eed;
+/// Ratings searched for the performance rating
+const ELO_RANGE: (f64, f64) = (0.0, 3000.0);
+
+/// Name and anchor rating of each reference opponent
+const LADDER: [(&str, f64); 3] = [("random", 1000.0), ("heuristic", 1600.0), ("perfect", 2000.0)];
+
+/// Results against one reference opponent
+#[derive(Debug, Clone, PartialEq)]
+pub struct LadderResult {
+    pub opponent: &'static str,
+    /// Elo rating assigned to the opponent
+    pub anchor: f64,
+    /// The estimated engine's wins, draws, and losses
+    pub record: MatchupRecord,
+}
+
+/// Outcome of [`estimate_strength`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthEstimate {
+    /// Results against each opponent, weakest first
+    pub ladder: Vec<LadderResult>,
+    /// Points against each opponent as a fraction of the points perfect play
+    /// scores in the same schedule, capped at one and averaged: `0.0` for
+    /// losing every game, `1.0` for playing as well as a perfect engine
+    pub score: f64,
+    /// Performance rating: the rating at which the ladder's expected score
+    /// matches the actual one
+    pub elo: f64,
+}
+
+/// Estimates the strength of `engine` with the default schedule
+///
+/// Plays [`DEFAULT_GAMES_PER_OPPONENT`] games against each opponent of the
+/// ladder with a fixed seed, so deterministic engines always get the same
+/// estimate.
+///
+/// # Panics
+///
+/// Panics if an engine returns an invalid move or no move while the game is
+/// still in progress.
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::backend::{FastEngine, PerfectLookupEngine};
+/// use zttt_rs::simulation::estimate_strength;
+///
+/// let fast = estimate_strength(FastEngine);
+/// let perfect = estimate_strength(PerfectLookupEngine);
+/// assert!(perfect.elo > fast.elo);
+/// assert_eq!(perfect.score, 1.0);
+/// assert!(fast.score < 0.5);
+/// println!("{}", fast);
+/// ```
+pub fn estimate_strength(engine: impl StatefulEngine + Send + 'static) -> StrengthEstimate {
+    estimate_strength_with(engine, DEFAULT_GAMES_PER_OPPONENT, DEFAULT_SEED)
+}
+
+/// Estimates the strength of `engine` with `games` games per opponent
+///
+/// Colors alternate, with `engine` playing X in the first game against each
+/// opponent. Engines are reseeded from `seed` before every game.
+pub fn estimate_strength_with(engine: impl StatefulEngine + Send + 'static, games: usize, seed: u64) -> StrengthEstimate {
+    let ladder = play_ladder(Box::new(engine), games, seed);
+    let reference = play_ladder(Box::new(PerfectLookupEngine), games, seed);
+
+    let score = ladder
+        .iter()
+        .zip(&reference)
+        .map(|(result, perfect)| {
+            let best = perfect.record.points();
+            if best > 0.0 { (result.record.points() / best).min(1.0) } else { 1.0 }
+        })
+        .sum::<f64>()
+        / LADDER.len() as f64;
+    let elo = performance_rating(&ladder);
+    StrengthEstimate { ladder, score, elo }
+}
+
+/// Plays `games` games between `engine` and each opponent of the ladder
+fn play_ladder(engine: BoxedEngine, games: usize, seed: u64) -> Vec<LadderResult> {
+    let mut engines: Vec<BoxedEngine> = vec![
+        engine,
+        Box::new(RandomEngine::new()),
+        Box::new(MinimaxEngine::with_depth(1)),
+        Box::new(PerfectLookupEngine),
+    ];
+
+    let mut ladder = Vec::with_capacity(LADDER.len());
+    for (index, &(opponent, anchor)) in LADDER.iter().enumerate() {
+        let mut record = MatchupRecord::default();
+        let first_game = index * games;
+        play_series(&mut engines, 0, index + 1, games, Rules::Standard, Some(seed), first_game, |x, _, game| {
+            match game.result.winner() {
+                Some(winner) if (winner == Player::X) == (x == 0) => record.wins += 1,
+                Some(_) => record.losses += 1,
+                None => record.draws += 1,
+            }
+            true
+        });
+        ladder.push(LadderResult { opponent, anchor, record });
+    }
+    ladder
+}
+
+/// Returns the expected score against an opponent rated `anchor`
+fn expected_score(rating: f64, anchor: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((anchor - rating) / 400.0))
+}
+
+/// Finds the rating whose expected score over the ladder equals the actual
+/// score, by bisection within [`ELO_RANGE`]
+fn performance_rating(ladder: &[LadderResult]) -> f64 {
+    let actual: f64 = ladder.iter().map(|result| result.record.points()).sum();
+    let expected = |rating: f64| -> f64 {
+        ladder
+            .iter()
+            .map(|result| result.record.games() as f64 * expected_score(rating, result.anchor))
+            .sum()
+    };
+
+    let (mut low, mut high) = ELO_RANGE;
+    for _ in 0..50 {
+        let mid = (low + high) / 2.0;
+        if expected(mid) < actual {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+impl fmt::Display for StrengthEstimate {
+    /// Writes one line per opponent followed by the score and rating
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.ladder {
+            writeln!(f, "vs {:<9} ({:>4.0}): {}", result.opponent, result.anchor, result.record)?;
+        }
+        write!(f, "score {:.2}, elo ~{:.0}", self.score, self.elo)
+    }
+}
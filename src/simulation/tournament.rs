@@ -0,0 +1,418 @@
+//! Round-robin tournaments
+//!
+//! Every engine plays every other engine the same number of games, alternating
+//! colors, and the results are collected in a cross-table: one row and one
+//! column per engine, each cell holding the row engine's wins, draws, and
+//! losses against the column engine. Each matchup is played as a series like
+//! a [`Match`](crate::simulation::Match).
+
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use crate::backend::{BoxedEngine, GameRecord, GameResult, Player, Rules, StatefulEngine};
+use crate::simulation::duel::play_series;
+use crate::simulation::rating::Glicko2;
+#[cfg(feature = "indicatif")]
+use crate::simulation::progress_bar::games_bar;
+
+/// A round-robin tournament between named engines
+///
+/// # Example
+///
+/// ```
+/// use zttt_rs::simulation::Tournament;
+/// use zttt_rs::backend::{FastEngine, MinimaxEngine, RandomEngine};
+///
+/// let mut tournament = Tournament::builder()
+///     .add_engine("fast", FastEngine)
+///     .add_engine("random", RandomEngine::new())
+///     .add_engine("minimax", MinimaxEngine::new())
+///     .games_per_matchup(20)
+///     .seed(7)
+///     .build()
+///     .unwrap();
+///
+/// let result = tournament.run();
+/// assert_eq!(result.standings()[0].name, "minimax");
+/// assert_eq!(result.matchup("minimax", "random").unwrap().losses, 0);
+/// println!("{}", result);
+/// ```
+pub struct Tournament {
+    names: Vec<String>,
+    engines: Vec<BoxedEngine>,
+    games_per_matchup: usize,
+    rules: Rules,
+    seed: Option<u64>,
+    #[cfg(feature = "indicatif")]
+    progress_bar: bool,
+}
+
+/// Builder for [`Tournament`]
+pub struct TournamentBuilder {
+    tournament: Tournament,
+}
+
+/// Wins, draws, and losses of one engine, against one opponent or overall
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchupRecord {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// One engine's line in the [`TournamentResult::standings`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentStanding {
+    pub name: String,
+    pub record: MatchupRecord,
+    /// One point per win and half a point per draw
+    pub points: f64,
+}
+
+/// Cross-table of a [`Tournament`] run
+///
+/// `Display` renders the cross-table in standings order, with totals and
+/// points; [`write_csv`](Self::write_csv) exports the same table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentResult {
+    /// Engine names in the order they were added
+    pub engines: Vec<String>,
+    pub games_per_matchup: usize,
+    /// `cross_table[row][col]` is engine `row`'s record against engine `col`;
+    /// the diagonal is empty
+    pub cross_table: Vec<Vec<MatchupRecord>>,
+    pub games_completed: usize,
+    pub total_duration: Duration,
+}
+
+impl Tournament {
+    /// Creates a builder with no engines
+    ///
+    /// Defaults: 100 games per matchup, standard rules, no seed.
+    pub fn builder() -> TournamentBuilder {
+        TournamentBuilder {
+            tournament: Tournament {
+                names: Vec::new(),
+                engines: Vec::new(),
+                games_per_matchup: 100,
+                rules: Rules::Standard,
+                seed: None,
+                #[cfg(feature = "indicatif")]
+                progress_bar: false,
+            },
+        }
+    }
+
+    /// Returns the engine names in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Plays every matchup
+    ///
+    /// Engines swap colors after every game of a matchup, starting with the
+    /// engine added first as X. With a seed, engines are reseeded before each
+    /// game as in a seeded [`Simulator`](crate::simulation::Simulator) run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an engine returns an invalid move or no move while the game
+    /// is still in progress.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "tournament", skip_all, fields(engines = self.engines.len(), games_per_matchup = self.games_per_matchup)))]
+    pub fn run(&mut self) -> TournamentResult {
+        let start = Instant::now();
+        let count = self.engines.len();
+        let mut cross_table = vec![vec![MatchupRecord::default(); count]; count];
+        let mut games = 0;
+        #[cfg(feature = "indicatif")]
+        let bar = self.progress_bar.then(|| games_bar(count * (count - 1) / 2 * self.games_per_matchup));
+
+        for a in 0..count {
+            for b in a + 1..count {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::info_span!("matchup", a = %self.names[a], b = %self.names[b]).entered();
+                #[cfg(feature = "indicatif")]
+                if let Some(bar) = &bar {
+                    bar.set_message(format!("{} vs {}", self.names[a], self.names[b]));
+                }
+                let series = |x, o, game: GameRecord| {
+                    #[cfg(feature = "indicatif")]
+                    if let Some(bar) = &bar {
+                        bar.inc(1);
+                    }
+                    match game.result {
+                        GameResult::Win(Player::X) => record(&mut cross_table, x, o),
+                        GameResult::Win(Player::O) => record(&mut cross_table, o, x),
+                        _ => {
+                            cross_table[a][b].draws += 1;
+                            cross_table[b][a].draws += 1;
+                        }
+                    }
+                    true
+                };
+                play_series(&mut self.engines, a, b, self.games_per_matchup, self.rules, self.seed, games, series);
+                games += self.games_per_matchup;
+                #[cfg(feature = "tracing")]
+                tracing::info!(record = %cross_table[a][b], "matchup finished");
+            }
+        }
+        #[cfg(feature = "indicatif")]
+        if let Some(bar) = bar {
+            bar.finish_with_message("done");
+        }
+
+        TournamentResult {
+            engines: self.names.clone(),
+            games_per_matchup: self.games_per_matchup,
+            cross_table,
+            games_completed: games,
+            total_duration: start.elapsed(),
+        }
+    }
+}
+
+/// Records a win for `winner` over `loser`
+fn record(cross_table: &mut [Vec<MatchupRecord>], winner: usize, loser: usize) {
+    cross_table[winner][loser].wins += 1;
+    cross_table[loser][winner].losses += 1;
+}
+
+impl TournamentBuilder {
+    /// Adds an engine under `name`
+    pub fn add_engine(self, name: &str, engine: impl StatefulEngine + Send + 'static) -> Self {
+        self.add_boxed(name, Box::new(engine))
+    }
+
+    /// Adds an engine under its [`StatefulEngine::name`]
+    ///
+    /// Engines of the same type get the same name, so give them distinct
+    /// names with [`add_engine`](Self::add_engine) instead.
+    pub fn add_by_info(self, engine: impl StatefulEngine + Send + 'static) -> Self {
+        let name = engine.name().to_string();
+        self.add_engine(&name, engine)
+    }
+
+    /// Adds a boxed engine, e.g. one built by an
+    /// [`EngineRegistry`](crate::backend::EngineRegistry)
+    pub fn add_boxed(mut self, name: &str, engine: BoxedEngine) -> Self {
+        self.tournament.names.push(name.to_string());
+        self.tournament.engines.push(engine);
+        self
+    }
+
+    /// Sets the number of games each pair of engines plays
+    pub fn games_per_matchup(mut self, games: usize) -> Self {
+        self.tournament.games_per_matchup = games;
+        self
+    }
+
+    /// Sets the rules games are played under
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.tournament.rules = rules;
+        self
+    }
+
+    /// Sets the seed for reproducible engine randomness
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.tournament.seed = Some(seed);
+        self
+    }
+
+    /// Shows a progress bar with ETA, live throughput, and the matchup being
+    /// played on stderr while the tournament runs
+    ///
+    /// Nothing is drawn when stderr is not a terminal.
+    #[cfg(feature = "indicatif")]
+    pub fn progress_bar(mut self) -> Self {
+        self.tournament.progress_bar = true;
+        self
+    }
+
+    /// Builds the tournament
+    ///
+    /// Returns an error if fewer than two engines were added.
+    pub fn build(self) -> Result<Tournament, &'static str> {
+        if self.tournament.engines.len() < 2 {
+            return Err("Tournament needs at least two engines");
+        }
+        Ok(self.tournament)
+    }
+}
+
+impl MatchupRecord {
+    /// Returns the number of games played
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Returns one point per win and half a point per draw
+    pub fn points(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.0
+    }
+}
+
+impl std::ops::AddAssign for MatchupRecord {
+    fn add_assign(&mut self, other: Self) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+    }
+}
+
+impl fmt::Display for MatchupRecord {
+    /// Writes `wins-draws-losses`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.draws, self.losses)
+    }
+}
+
+impl TournamentResult {
+    /// Returns `a`'s record against `b`
+    pub fn matchup(&self, a: &str, b: &str) -> Option<MatchupRecord> {
+        let a = self.engines.iter().position(|name| name == a)?;
+        let b = self.engines.iter().position(|name| name == b)?;
+        Some(self.cross_table[a][b])
+    }
+
+    /// Returns `name`'s record against all opponents
+    pub fn totals(&self, name: &str) -> Option<MatchupRecord> {
+        let index = self.engines.iter().position(|engine| engine == name)?;
+        Some(self.total(index))
+    }
+
+    /// Returns the engines sorted by points, best first
+    ///
+    /// Ties are broken by wins, then by the order the engines were added.
+    pub fn standings(&self) -> Vec<TournamentStanding> {
+        self.order()
+            .into_iter()
+            .map(|index| {
+                let record = self.total(index);
+                TournamentStanding {
+                    name: self.engines[index].clone(),
+                    record,
+                    points: record.points(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns Glicko-2 ratings treating the whole tournament as one rating period
+    pub fn glicko2(&self) -> Glicko2 {
+        let mut ratings = Glicko2::new();
+        let mut games = Vec::new();
+        for (a, name) in self.engines.iter().enumerate() {
+            ratings.add_player(name);
+            for b in a + 1..self.engines.len() {
+                let (a_name, b_name) = (name.as_str(), self.engines[b].as_str());
+                let record = self.cross_table[a][b];
+                games.extend(std::iter::repeat_n((a_name, b_name, 1.0), record.wins));
+                games.extend(std::iter::repeat_n((a_name, b_name, 0.5), record.draws));
+                games.extend(std::iter::repeat_n((a_name, b_name, 0.0), record.losses));
+            }
+        }
+        ratings.update(&games);
+        ratings
+    }
+
+    /// Writes the cross-table as CSV, in standings order
+    ///
+    /// The header is `engine`, one column per opponent, then `wins`, `draws`,
+    /// `losses`, and `points`. Cells are `wins-draws-losses`; the diagonal is
+    /// left empty.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let order = self.order();
+        write!(writer, "engine")?;
+        for &col in &order {
+            write!(writer, ",{}", csv_field(&self.engines[col]))?;
+        }
+        writeln!(writer, ",wins,draws,losses,points")?;
+
+        for &row in &order {
+            write!(writer, "{}", csv_field(&self.engines[row]))?;
+            for &col in &order {
+                if row == col {
+                    write!(writer, ",")?;
+                } else {
+                    write!(writer, ",{}", self.cross_table[row][col])?;
+                }
+            }
+            let total = self.total(row);
+            writeln!(writer, ",{},{},{},{}", total.wins, total.draws, total.losses, total.points())?;
+        }
+        Ok(())
+    }
+
+    fn total(&self, index: usize) -> MatchupRecord {
+        let mut total = MatchupRecord::default();
+        for &record in &self.cross_table[index] {
+            total += record;
+        }
+        total
+    }
+
+    /// Engine indices in standings order
+    fn order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.engines.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (self.total(a), self.total(b));
+            b.points().total_cmp(&a.points()).then(b.wins.cmp(&a.wins))
+        });
+        order
+    }
+}
+
+/// Quotes a CSV field if it contains a comma, quote, or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl fmt::Display for TournamentResult {
+    /// Writes the cross-table in standings order, with totals and points
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = self.order();
+        let cells: Vec<Vec<String>> = order
+            .iter()
+            .map(|&row| {
+                order
+                    .iter()
+                    .map(|&col| if row == col { "-".to_string() } else { self.cross_table[row][col].to_string() })
+                    .collect()
+            })
+            .collect();
+        let totals: Vec<MatchupRecord> = order.iter().map(|&index| self.total(index)).collect();
+
+        let rank_width = order.len().to_string().len();
+        let name_width = self.engines.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+        let column_widths: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .map(|(col, &engine)| {
+                let widest = cells.iter().map(|row| row[col].len()).max().unwrap_or(0);
+                widest.max(self.engines[engine].chars().count())
+            })
+            .collect();
+        let total_width = totals.iter().map(|total| total.to_string().len()).max().unwrap_or(0).max(5);
+
+        writeln!(f, "Round robin, {} games per matchup", self.games_per_matchup)?;
+        write!(f, "\n{:rank_width$}  {:name_width$}", "", "")?;
+        for (&engine, width) in order.iter().zip(&column_widths) {
+            write!(f, "  {:>width$}", self.engines[engine])?;
+        }
+        write!(f, "  {:>total_width$}  Points", "W-D-L")?;
+
+        for (rank, (&engine, row)) in order.iter().zip(&cells).enumerate() {
+            write!(f, "\n{:>rank_width$}  {:name_width$}", rank + 1, self.engines[engine])?;
+            for (cell, width) in row.iter().zip(&column_widths) {
+                write!(f, "  {:>width$}", cell)?;
+            }
+            let total = totals[rank];
+            write!(f, "  {:>total_width$}  {:>6.1}", total.to_string(), total.points())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! WebAssembly bindings (requires the `wasm` feature)
+//!
+//! Exposes the same board and engines used in simulations to JavaScript, so
+//! a web frontend and a batch simulation agree on every rule. Players are
+//! passed as the strings `"X"` and `"O"`, and cells as indices `row * 3 + col`.
+//!
+//! The library builds as an `rlib` only, so build the WebAssembly module as a
+//! `cdylib` explicitly and generate the JavaScript glue with `wasm-bindgen`:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/zttt_rs.wasm
+//! ```
+//!
+//! ```js
+//! import { Board, Engine } from "zttt-rs";
+//!
+//! const board = new Board(false);
+//! board.makeMove(1, 1, "X");
+//! const cell = new Engine("minimax").chooseMove(board, "O");
+//! board.makeMove(Math.floor(cell / 3), cell % 3, "O");
+//! console.log(board.gameResult()); // "in_progress"
+//! ```
+
+use wasm_bindgen::prelude::*;
+use crate::backend::{Board, BoxedEngine, Cell, EngineRegistry, GameResult, Player, Rules};
+
+/// A 3x3 board
+#[wasm_bindgen(js_name = Board)]
+pub struct WasmBoard {
+    board: Board,
+}
+
+#[wasm_bindgen(js_class = Board)]
+impl WasmBoard {
+    /// Creates an empty board, optionally under misère rules
+    #[wasm_bindgen(constructor)]
+    pub fn new(misere: bool) -> WasmBoard {
+        let rules = if misere { Rules::Misere } else { Rules::Standard };
+        WasmBoard {
+            board: Board::with_rules(rules),
+        }
+    }
+
+    /// Places `player`'s piece at (row, col)
+    #[wasm_bindgen(js_name = makeMove)]
+    pub fn make_move(&mut self, row: usize, col: usize, player: &str) -> Result<(), JsError> {
+        let player = parse_player(player)?;
+        self.board.make_move(row, col, player).map_err(JsError::new)
+    }
+
+    /// Returns `"X"`, `"O"`, `"draw"`, or `"in_progress"`
+    #[wasm_bindgen(js_name = gameResult)]
+    pub fn game_result(&self) -> String {
+        match self.board.game_result() {
+            GameResult::Win(player) => player.to_string(),
+            GameResult::Draw => "draw".to_string(),
+            GameResult::InProgress => "in_progress".to_string(),
+        }
+    }
+
+    /// Returns `"X"`, `"O"`, or `""` for the cell at (row, col)
+    pub fn get(&self, row: usize, col: usize) -> Option<String> {
+        self.board.get(row, col).map(|cell| match cell {
+            Cell::Occupied(player) => player.to_string(),
+            Cell::Empty => String::new(),
+        })
+    }
+
+    /// Returns the indices of the empty cells
+    #[wasm_bindgen(js_name = validMoves)]
+    pub fn valid_moves(&self) -> Vec<u8> {
+        self.board
+            .valid_moves()
+            .into_iter()
+            .map(|(row, col)| (row * 3 + col) as u8)
+            .collect()
+    }
+
+    /// Clears the board, keeping its rules
+    pub fn reset(&mut self) {
+        self.board.reset();
+    }
+
+    /// Renders the board as text
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.board.to_string()
+    }
+}
+
+/// A built-in engine created from a registry spec such as `"minimax:depth=3"`
+///
+/// See [`EngineRegistry::with_builtins`] for the available specs.
+#[wasm_bindgen(js_name = Engine)]
+pub struct WasmEngine {
+    engine: BoxedEngine,
+}
+
+#[wasm_bindgen(js_class = Engine)]
+impl WasmEngine {
+    /// Creates an engine from a spec
+    #[wasm_bindgen(constructor)]
+    pub fn new(spec: &str) -> Result<WasmEngine, JsError> {
+        let engine = EngineRegistry::with_builtins().create(spec).map_err(JsError::new)?;
+        Ok(WasmEngine { engine })
+    }
+
+    /// Returns the index of the chosen cell, or `undefined` if there is no move
+    #[wasm_bindgen(js_name = chooseMove)]
+    pub fn choose_move(&mut self, board: &WasmBoard, player: &str) -> Result<Option<u8>, JsError> {
+        let player = parse_player(player)?;
+        Ok(self
+            .engine
+            .choose_move_mut(&board.board, player)
+            .map(|(row, col)| (row * 3 + col) as u8))
+    }
+}
+
+fn parse_player(player: &str) -> Result<Player, JsError> {
+    match player {
+        "X" => Ok(Player::X),
+        "O" => Ok(Player::O),
+        _ => Err(JsError::new("Player must be \"X\" or \"O\"")),
+    }
+}